@@ -22,6 +22,9 @@ use tracing::debug;
 pub struct Config {
     pub server: ServerConfig,
     pub metrics_retain_idle: Duration,
+    /// Limits the number of distinct label sets per metric family for metrics
+    /// whose labels are derived from traffic. Unbounded when `None`.
+    pub metrics_max_series: Option<usize>,
     #[cfg(feature = "pprof")]
     pub enable_profiling: bool,
     pub enable_shutdown: bool,
@@ -117,6 +120,7 @@ impl Config {
             .push_map_target(|(permit, http)| Permitted { permit, http })
             .push(inbound::policy::NewHttpPolicy::layer(
                 metrics.http_authz.clone(),
+                None,
             ))
             .push(Rescue::layer())
             .push_on_service(http::BoxResponse::layer())
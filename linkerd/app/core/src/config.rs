@@ -2,13 +2,14 @@ pub use crate::exp_backoff::ExponentialBackoff;
 use crate::{
     proxy::http::{h1, h2},
     svc::{queue, ExtractParam, Param},
-    transport::{DualListenAddr, Keepalive, ListenAddr, UserTimeout},
+    transport::{AcceptShards, DualListenAddr, Keepalive, ListenAddr, UserTimeout},
 };
 use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub struct ServerConfig {
     pub addr: DualListenAddr,
+    pub accept_shards: AcceptShards,
     pub keepalive: Keepalive,
     pub user_timeout: UserTimeout,
     pub http2: h2::ServerParams,
@@ -73,6 +74,12 @@ impl Param<ListenAddr> for ServerConfig {
     }
 }
 
+impl Param<AcceptShards> for ServerConfig {
+    fn param(&self) -> AcceptShards {
+        self.accept_shards
+    }
+}
+
 impl Param<Keepalive> for ServerConfig {
     fn param(&self) -> Keepalive {
         self.keepalive
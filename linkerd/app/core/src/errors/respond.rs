@@ -2,7 +2,7 @@ use super::{
     body::ResponseBody,
     header::{GRPC_CONTENT_TYPE, GRPC_MESSAGE, GRPC_STATUS, L5D_PROXY_CONNECTION, L5D_PROXY_ERROR},
 };
-use crate::svc;
+use crate::{http_tracing::SpanAttributes, svc};
 use http::header::{HeaderValue, LOCATION};
 use linkerd_error::{Error, Result};
 use linkerd_error_respond as respond;
@@ -54,6 +54,7 @@ pub struct Respond<R> {
     is_grpc: bool,
     is_orig_proto_upgrade: bool,
     client: Option<ClientHandle>,
+    span: Option<SpanAttributes>,
     emit_headers: bool,
 }
 
@@ -316,6 +317,7 @@ where
     fn new_respond(&self, req: &http::Request<B>) -> Self::Respond {
         let client = req.extensions().get::<ClientHandle>().cloned();
         debug_assert!(client.is_some(), "Missing client handle");
+        let span = SpanAttributes::get(req).cloned();
 
         let rescue = self.rescue.clone();
         let emit_headers = self.emit_headers;
@@ -337,6 +339,7 @@ where
                     .unwrap_or(false);
                 Respond {
                     client,
+                    span,
                     rescue,
                     is_grpc,
                     is_orig_proto_upgrade: false,
@@ -348,6 +351,7 @@ where
                 let is_h2_upgrade = req.extensions().get::<orig_proto::WasUpgrade>().is_some();
                 Respond {
                     client,
+                    span,
                     rescue,
                     version,
                     is_grpc: false,
@@ -406,6 +410,11 @@ where
             self.rescue.rescue(error)
         })?;
 
+        // Record the reason for the synthesized response on the request's span.
+        if let Some(span) = self.span.as_ref() {
+            span.insert("linkerd.proxy_error", &rsp.message);
+        }
+
         if rsp.close_connection {
             if let Some(ClientHandle { close, .. }) = self.client.as_ref() {
                 close.close();
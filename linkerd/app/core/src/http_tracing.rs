@@ -1,12 +1,23 @@
 use linkerd_error::Error;
-use linkerd_stack::layer;
+use linkerd_http_retry::Attempt;
+use linkerd_stack::{layer, ExtractParam, NewService, Service};
 use linkerd_trace_context::{
     self as trace_context,
     export::{ExportSpan, SpanKind, SpanLabels},
     Span, TraceContext,
 };
-use std::{str::FromStr, sync::Arc};
+pub use linkerd_trace_context::{
+    Format, Formats, SampledSpan, Sampler, SpanAttributes, UnknownFormat,
+};
+use std::{
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
 use tokio::sync::mpsc;
+use tracing::{info_span, instrument::Instrumented, Instrument};
+
+const X_REQUEST_ID: &str = "x-request-id";
 
 #[derive(Debug, Copy, Clone, Default)]
 pub enum CollectorProtocol {
@@ -31,26 +42,70 @@ impl FromStr for CollectorProtocol {
 
 pub type SpanSink = mpsc::Sender<ExportSpan>;
 
+/// Instruments server-side requests. Requests without a trace context may
+/// start a new trace, as determined by the `sampler`.
 pub fn server<S>(
     sink: Option<SpanSink>,
+    formats: Formats,
+    sampler: Sampler,
     labels: impl Into<SpanLabels>,
 ) -> impl layer::Layer<S, Service = TraceContext<Option<SpanConverter>, S>> + Clone {
-    TraceContext::layer(sink.map(move |sink| SpanConverter {
-        kind: SpanKind::Server,
-        sink,
-        labels: labels.into(),
-    }))
+    TraceContext::layer_with(
+        sink.map(move |sink| SpanConverter {
+            kind: SpanKind::Server,
+            sink,
+            labels: labels.into(),
+        }),
+        formats,
+        sampler,
+    )
 }
 
+/// Instruments client-side requests.
+///
+/// Each attempt of a retried request is recorded as its own client span,
+/// which records the attempt's number as `linkerd.retry.attempt`.
 pub fn client<S>(
     sink: Option<SpanSink>,
+    formats: Formats,
     labels: impl Into<SpanLabels>,
-) -> impl layer::Layer<S, Service = TraceContext<Option<SpanConverter>, S>> + Clone {
-    TraceContext::layer(sink.map(move |sink| SpanConverter {
-        kind: SpanKind::Client,
-        sink,
-        labels: labels.into(),
-    }))
+) -> impl layer::Layer<S, Service = TraceContext<Option<SpanConverter>, RecordAttempt<S>>> + Clone {
+    let trace = TraceContext::layer_with(
+        sink.map(move |sink| SpanConverter {
+            kind: SpanKind::Client,
+            sink,
+            labels: labels.into(),
+        }),
+        formats,
+        Sampler::default(),
+    );
+    layer::mk(move |inner| layer::Layer::layer(&trace, RecordAttempt { inner }))
+}
+
+/// Processes each request in a `request` span that records the request's
+/// trace ID, span ID, and `x-request-id`, so that log lines emitted while
+/// handling the request can be correlated with its distributed trace.
+///
+/// IDs are read in the same `formats` as [`server`]. When used with
+/// [`server`], this layer should be inner to it so that the span ID is that of
+/// the proxy's span.
+pub fn correlate<S>(formats: Formats) -> impl layer::Layer<S, Service = Correlate<S>> + Clone {
+    layer::mk(move |inner| Correlate {
+        inner,
+        formats: formats.clone(),
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct Correlate<S> {
+    inner: S,
+    formats: Formats,
+}
+
+/// Records the attempt number of a retried request on its client span.
+#[derive(Clone, Debug)]
+pub struct RecordAttempt<S> {
+    inner: S,
 }
 
 #[derive(Clone)]
@@ -74,3 +129,247 @@ impl trace_context::SpanSink for SpanConverter {
         Ok(())
     }
 }
+
+// === impl Correlate ===
+
+impl<B, S> Service<http::Request<B>> for Correlate<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let ids = trace_context::trace_ids(&req, &self.formats);
+        let request_id = req
+            .headers()
+            .get(X_REQUEST_ID)
+            .and_then(|v| v.to_str().ok());
+        let span = if ids.is_some() || request_id.is_some() {
+            let (trace_id, span_id) = ids.unzip();
+            info_span!(
+                "request",
+                trace_id = trace_id.map(tracing::field::display),
+                span_id = span_id.map(tracing::field::display),
+                request_id,
+            )
+        } else {
+            tracing::Span::none()
+        };
+
+        let _enter = span.enter();
+        self.inner.call(req).instrument(span.clone())
+    }
+}
+
+/// Attributes describing a stack target (e.g. a route, backend, or endpoint).
+#[derive(Clone, Debug, Default)]
+pub struct TargetAttributes(Arc<[(&'static str, String)]>);
+
+/// Adds a target's [`TargetAttributes`] to the span of each request that it
+/// handles.
+///
+/// Route and backend attributes are recorded on the span of the original
+/// request, which covers all of its attempts; the span therefore describes the
+/// backend of the last attempt. Per-attempt detail, including the attempt
+/// number, is recorded on each attempt's endpoint client span (see [`client`]).
+#[derive(Clone, Debug)]
+pub struct NewSpanAttributes<X, N> {
+    extract: X,
+    inner: N,
+}
+
+#[derive(Clone, Debug)]
+pub struct SetSpanAttributes<S> {
+    attributes: TargetAttributes,
+    inner: S,
+}
+
+// === impl TargetAttributes ===
+
+impl FromIterator<(&'static str, String)> for TargetAttributes {
+    fn from_iter<I: IntoIterator<Item = (&'static str, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+// === impl NewSpanAttributes ===
+
+impl<X: Clone, N> NewSpanAttributes<X, N> {
+    pub fn layer_via(extract: X) -> impl layer::Layer<N, Service = Self> + Clone {
+        layer::mk(move |inner| Self {
+            extract: extract.clone(),
+            inner,
+        })
+    }
+}
+
+impl<T, X, N> NewService<T> for NewSpanAttributes<X, N>
+where
+    X: ExtractParam<TargetAttributes, T>,
+    N: NewService<T>,
+{
+    type Service = SetSpanAttributes<N::Service>;
+
+    fn new_service(&self, target: T) -> Self::Service {
+        let attributes = self.extract.extract_param(&target);
+        let inner = self.inner.new_service(target);
+        SetSpanAttributes { attributes, inner }
+    }
+}
+
+// === impl SetSpanAttributes ===
+
+impl<B, S> Service<http::Request<B>> for SetSpanAttributes<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if let Some(span) = SpanAttributes::get(&req) {
+            for (key, value) in self.attributes.0.iter() {
+                span.insert(key, value);
+            }
+        }
+        self.inner.call(req)
+    }
+}
+
+// === impl RecordAttempt ===
+
+impl<B, S> Service<http::Request<B>> for RecordAttempt<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if let (Some(span), Some(Attempt(n))) =
+            (SpanAttributes::get(&req), req.extensions().get::<Attempt>())
+        {
+            span.insert("linkerd.retry.attempt", n);
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_stack::{layer::Layer, service_fn};
+
+    #[test]
+    fn correlates_requests() {
+        let (spans, _trace) =
+            linkerd_tracing::test::CapturedSpans::init("linkerd_app_core::http_tracing");
+
+        let mut svc = correlate(Formats::default()).layer(service_fn(|_: http::Request<()>| {
+            let name = tracing::Span::current().metadata().map(|m| m.name());
+            assert_eq!(name, Some("request"));
+            futures::future::ready(Ok::<_, Error>(()))
+        }));
+        let req = http::Request::builder()
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .header(X_REQUEST_ID, "abc")
+            .body(())
+            .unwrap();
+        drop(svc.call(req));
+
+        let mut svc = correlate(Formats::default()).layer(service_fn(|_: http::Request<()>| {
+            assert!(tracing::Span::current().is_none());
+            futures::future::ready(Ok::<_, Error>(()))
+        }));
+        drop(svc.call(http::Request::new(())));
+
+        let formats = Formats::new([Format::Datadog, Format::W3C], None);
+        let mut svc = correlate(formats).layer(service_fn(|_: http::Request<()>| {
+            futures::future::ready(Ok::<_, Error>(()))
+        }));
+        let req = http::Request::builder()
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .header("x-datadog-trace-id", "1234")
+            .header("x-datadog-parent-id", "5678")
+            .body(())
+            .unwrap();
+        drop(svc.call(req));
+
+        let closed = spans.closed();
+        assert_eq!(closed.len(), 2, "only correlated requests are instrumented");
+        assert_eq!(
+            closed[1].get("trace_id").map(String::as_str),
+            Some("000000000000000000000000000004d2"),
+            "IDs must be read in the configured formats"
+        );
+        assert_eq!(
+            closed[1].get("span_id").map(String::as_str),
+            Some("000000000000162e")
+        );
+        let fields = &closed[0];
+        assert_eq!(
+            fields.get("trace_id").map(String::as_str),
+            Some("94d7f6ec6b95f3e916179cb6cfd01390")
+        );
+        assert_eq!(
+            fields.get("span_id").map(String::as_str),
+            Some("55ccfce77f972614")
+        );
+        assert_eq!(fields.get("request_id").map(String::as_str), Some("abc"));
+    }
+
+    #[tokio::test]
+    async fn records_attempts_on_client_spans() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let mut svc = client(Some(tx), Formats::default(), SpanLabels::default()).layer(
+            service_fn(|_: http::Request<()>| {
+                futures::future::ready(Ok::<_, Error>(http::Response::new(())))
+            }),
+        );
+
+        for n in 1..=2 {
+            let mut req = http::Request::builder()
+                .header(
+                    "traceparent",
+                    "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+                )
+                .body(())
+                .unwrap();
+            req.extensions_mut()
+                .insert(Attempt(std::num::NonZeroU16::new(n).unwrap()));
+            svc.call(req).await.unwrap();
+
+            let ExportSpan { span, kind, .. } = rx.try_recv().expect("span must be recorded");
+            assert_eq!(kind, SpanKind::Client);
+            assert_eq!(
+                span.labels.get("linkerd.retry.attempt"),
+                Some(&n.to_string())
+            );
+        }
+    }
+}
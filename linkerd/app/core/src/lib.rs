@@ -40,6 +40,7 @@ pub use linkerd_idle_cache as idle_cache;
 pub use linkerd_io as io;
 pub use linkerd_opencensus as opencensus;
 pub use linkerd_opentelemetry as opentelemetry;
+pub use linkerd_proxy_protocol as proxy_protocol;
 pub use linkerd_service_profiles as profiles;
 pub use linkerd_stack_metrics as stack_metrics;
 pub use linkerd_stack_tracing as stack_tracing;
@@ -49,6 +49,9 @@ pub struct Proxy {
     pub http_endpoint: HttpEndpoint,
     pub transport: transport::Metrics,
     pub stack: Stack,
+    /// Limits the number of label sets in metric families whose labels are
+    /// derived from traffic.
+    pub cardinality: CardinalityLimits,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -166,7 +169,10 @@ where
 // === impl Metrics ===
 
 impl Metrics {
-    pub fn new(retain_idle: Duration) -> (Self, impl FmtMetrics + Clone + Send + 'static) {
+    pub fn new(
+        retain_idle: Duration,
+        cardinality: CardinalityLimits,
+    ) -> (Self, impl FmtMetrics + Clone + Send + 'static) {
         let (control, control_report) = {
             let m = http_metrics::Requests::<ControlLabels, Class>::default();
             let r = m.clone().into_report(retain_idle).with_prefix("control");
@@ -174,13 +180,17 @@ impl Metrics {
         };
 
         let (http_endpoint, endpoint_report) = {
-            let m = http_metrics::Requests::<EndpointLabels, Class>::default();
+            let m = http_metrics::Requests::<EndpointLabels, Class>::with_limit(
+                cardinality.limit("request_total"),
+            );
             let r = m.clone().into_report(retain_idle);
             (m, r)
         };
 
         let (http_profile_route, profile_route_report) = {
-            let m = http_metrics::Requests::<ProfileRouteLabels, Class>::default();
+            let m = http_metrics::Requests::<ProfileRouteLabels, Class>::with_limit(
+                cardinality.limit("route_request_total"),
+            );
             let r = m.clone().into_report(retain_idle).with_prefix("route");
             (m, r)
         };
@@ -192,7 +202,9 @@ impl Metrics {
         };
 
         let (http_profile_route_actual, actual_report) = {
-            let m = http_metrics::Requests::<ProfileRouteLabels, Class>::default();
+            let m = http_metrics::Requests::<ProfileRouteLabels, Class>::with_limit(
+                cardinality.limit("route_actual_request_total"),
+            );
             let r = m
                 .clone()
                 .into_report(retain_idle)
@@ -211,6 +223,7 @@ impl Metrics {
             http_profile_route_actual,
             stack: stack.clone(),
             transport,
+            cardinality,
         };
 
         let (opencensus, opencensus_report) = opencensus::metrics::new();
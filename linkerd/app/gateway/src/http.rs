@@ -1,6 +1,7 @@
 use super::Gateway;
 use inbound::{GatewayAddr, GatewayDomainInvalid};
 use linkerd_app_core::{
+    identity,
     metrics::ServerLabel,
     profiles,
     proxy::{
@@ -14,7 +15,7 @@ use linkerd_app_core::{
 };
 use linkerd_app_inbound as inbound;
 use linkerd_app_outbound as outbound;
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 use tokio::sync::watch;
 
 mod gateway;
@@ -71,6 +72,7 @@ impl Gateway {
         T: svc::Param<Remote<ClientAddr>>,
         T: svc::Param<ServerLabel>,
         T: svc::Param<tls::ConditionalServerTls>,
+        T: svc::Param<Option<Arc<identity::PeerCert>>>,
         T: svc::Param<tls::ClientId>,
         T: svc::Param<inbound::policy::AllowPolicy>,
         T: svc::Param<Option<watch::Receiver<profiles::Profile>>>,
@@ -89,6 +91,8 @@ impl Gateway {
             .into_stack()
             // Discard `T` and its associated client-specific metadata.
             .push_map_target(Target::discard_parent)
+            // Describe the authenticated client to outbound access logs.
+            .push_http_insert_target::<tls::ClientId>()
             .push(svc::ArcNewService::layer())
             // Add headers to prevent loops.
             .push(NewHttpGateway::layer(
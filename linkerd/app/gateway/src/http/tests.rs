@@ -81,6 +81,12 @@ async fn upgraded_request_remains_relative_form() {
         }
     }
 
+    impl svc::Param<Option<Arc<identity::PeerCert>>> for Target {
+        fn param(&self) -> Option<Arc<identity::PeerCert>> {
+            None
+        }
+    }
+
     impl svc::Param<Option<profiles::Receiver>> for Target {
         fn param(&self) -> Option<profiles::Receiver> {
             Some(linkerd_app_test::profile::only(profiles::Profile {
@@ -1,6 +1,8 @@
 use super::{server::Opaq, Gateway};
 use inbound::{GatewayAddr, GatewayDomainInvalid};
-use linkerd_app_core::{io, svc, tls, transport::addrs::*, Error};
+use linkerd_app_core::{
+    io, svc, tls, transport::addrs::*, transport_header::OriginalClient, Error,
+};
 use linkerd_app_inbound as inbound;
 use linkerd_app_outbound as outbound;
 use tokio::sync::watch;
@@ -9,6 +11,10 @@ use tokio::sync::watch;
 pub struct Target {
     addr: GatewayAddr,
     routes: watch::Receiver<outbound::opaq::Routes>,
+    /// The client on whose behalf the connection is forwarded. Only the
+    /// client's identity is described so that stacks are shared by all of a
+    /// client's connections.
+    client_id: tls::ClientId,
 }
 
 impl Gateway {
@@ -25,7 +31,7 @@ impl Gateway {
         T: svc::Param<inbound::policy::AllowPolicy>,
         T: Clone + Send + Sync + Unpin + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + Send + Unpin + 'static,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + Send + Unpin + 'static,
         // Opaq outbound stack.
         N: svc::NewService<Target, Service = NSvc> + Clone + Send + Sync + Unpin + 'static,
         NSvc: svc::Service<I, Response = (), Error = Error>,
@@ -50,6 +56,7 @@ impl Gateway {
 impl<T> TryFrom<Opaq<T>> for Target
 where
     T: svc::Param<GatewayAddr>,
+    T: svc::Param<tls::ClientId>,
 {
     type Error = GatewayDomainInvalid;
 
@@ -67,7 +74,11 @@ where
             (*opaq).param(),
         );
 
-        Ok(Target { addr, routes })
+        Ok(Target {
+            addr,
+            routes,
+            client_id: (**opaq).param(),
+        })
     }
 }
 
@@ -77,9 +88,20 @@ impl svc::Param<watch::Receiver<outbound::opaq::Routes>> for Target {
     }
 }
 
+impl svc::Param<Option<OriginalClient>> for Target {
+    fn param(&self) -> Option<OriginalClient> {
+        Some(OriginalClient {
+            // Targets are shared by all of a client's connections, so the
+            // client's address is not described.
+            addr: None,
+            identity: Some(self.client_id.0.clone()),
+        })
+    }
+}
+
 impl PartialEq for Target {
     fn eq(&self, other: &Self) -> bool {
-        self.addr == other.addr
+        self.addr == other.addr && self.client_id == other.client_id
     }
 }
 
@@ -88,5 +110,6 @@ impl Eq for Target {}
 impl std::hash::Hash for Target {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.addr.hash(state);
+        self.client_id.hash(state);
     }
 }
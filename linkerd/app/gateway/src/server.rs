@@ -1,11 +1,11 @@
 use crate::Gateway;
 use linkerd_app_core::{
-    io, profiles, proxy::http, svc, tls, transport::addrs::*, transport_header::SessionProtocol,
-    Addr, Error,
+    identity, io, profiles, proxy::http, svc, tls, transport::addrs::*,
+    transport_header::SessionProtocol, Addr, Error,
 };
 use linkerd_app_inbound::{self as inbound, GatewayAddr, GatewayDomainInvalid};
 use linkerd_app_outbound::{self as outbound};
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 use tokio::sync::watch;
 
 /// Target for HTTP stacks.
@@ -41,7 +41,7 @@ impl Gateway {
         T: svc::Param<Option<SessionProtocol>>,
         T: Clone + Send + Sync + Unpin + 'static,
         // Server-side socket
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         // Opaq outbound stack
         O: svc::NewService<Opaq<T>, Service = OSvc>,
@@ -226,6 +226,13 @@ where
     }
 }
 
+impl<T> svc::Param<Option<Arc<identity::PeerCert>>> for Http<T> {
+    fn param(&self) -> Option<Arc<identity::PeerCert>> {
+        // Client certificates are not forwarded through the gateway.
+        None
+    }
+}
+
 impl<T> svc::Param<tls::ClientId> for Http<T>
 where
     T: svc::Param<tls::ClientId>,
@@ -32,7 +32,7 @@ impl<N> Inbound<N> {
     where
         T: svc::Param<Remote<ClientAddr>> + svc::Param<OrigDstAddr>,
         T: Clone + Send + 'static,
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         N: svc::NewService<Accept, Service = NSvc> + Clone + Send + Sync + Unpin + 'static,
         NSvc: svc::Service<I, Response = ()>,
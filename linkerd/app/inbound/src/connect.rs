@@ -0,0 +1,123 @@
+use linkerd_app_core::{
+    io, svc,
+    transport::{ClientAddr, ConnectTcp, ConnectUnix, Local, Remote, ServerAddr, UnixPath},
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::net::{TcpStream, UnixStream};
+
+/// Establishes connections to the local application.
+///
+/// Connections to ports that are mapped to a Unix domain socket are made over
+/// that socket. All other connections are made over TCP.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectLocal {
+    tcp: ConnectTcp,
+    unix: ConnectUnix,
+    unix_sockets: Arc<HashMap<u16, UnixPath>>,
+}
+
+pub(crate) type Connection = io::EitherIo<io::ScopedIo<TcpStream>, io::ScopedIo<UnixStream>>;
+
+/// Describes the local end of a connection. Unix domain socket connections
+/// have no local address.
+pub(crate) type Metadata = Option<Local<ClientAddr>>;
+
+// === impl ConnectLocal ===
+
+impl ConnectLocal {
+    pub(crate) fn new(tcp: ConnectTcp, unix_sockets: Arc<HashMap<u16, UnixPath>>) -> Self {
+        Self {
+            tcp,
+            unix: ConnectUnix::default(),
+            unix_sockets,
+        }
+    }
+}
+
+impl svc::Service<Remote<ServerAddr>> for ConnectLocal {
+    type Response = (Connection, Metadata);
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send + Sync + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, addr: Remote<ServerAddr>) -> Self::Future {
+        if let Some(path) = self.unix_sockets.get(&addr.port()) {
+            let connect = self.unix.call(path.clone());
+            return Box::pin(async move {
+                let (io, _) = connect.await?;
+                Ok((io::EitherIo::Right(io), None))
+            });
+        }
+
+        let connect = self.tcp.call(addr);
+        Box::pin(async move {
+            let (io, local) = connect.await?;
+            Ok((io::EitherIo::Left(io), Some(local)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_app_core::transport::{Keepalive, UserTimeout};
+    use svc::ServiceExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn connect(unix_sockets: impl IntoIterator<Item = (u16, UnixPath)>) -> ConnectLocal {
+        ConnectLocal::new(
+            ConnectTcp::new(Keepalive(None), UserTimeout(None)),
+            Arc::new(unix_sockets.into_iter().collect()),
+        )
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn connects_mapped_port_over_unix_socket() {
+        let path =
+            std::env::temp_dir().join(format!("linkerd-inbound-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).expect("must bind");
+        let server = tokio::spawn(async move {
+            let (mut io, _) = listener.accept().await.expect("must accept");
+            io.write_all(b"hello").await.expect("must write");
+        });
+
+        let addr = Remote(ServerAddr(([127, 0, 0, 1], 8080).into()));
+        let (mut io, local) = connect([(8080, UnixPath(path.clone().into()))])
+            .oneshot(addr)
+            .await
+            .expect("must connect");
+        assert_eq!(local, None);
+        assert!(matches!(io, io::EitherIo::Right(_)));
+
+        let mut buf = Vec::new();
+        io.read_to_end(&mut buf).await.expect("must read");
+        assert_eq!(buf, b"hello");
+        server.await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn connects_other_ports_over_tcp() {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("must bind");
+        let addr = Remote(ServerAddr(listener.local_addr().unwrap()));
+        let unmapped = UnixPath(std::path::Path::new("/nonexistent.sock").into());
+        let (io, local) = connect([(addr.port().wrapping_add(1), unmapped)])
+            .oneshot(addr)
+            .await
+            .expect("must connect");
+        assert!(local.is_some());
+        assert!(matches!(io, io::EitherIo::Left(_)));
+    }
+}
@@ -14,9 +14,10 @@ use linkerd_app_core::{
     },
     Error, Infallible,
 };
-use std::{fmt::Debug, time};
+use std::{fmt::Debug, sync::Arc, time};
 use tracing::info;
 
+mod client_cert;
 #[cfg(test)]
 mod tests;
 
@@ -43,6 +44,9 @@ struct Tls {
     client_addr: Remote<ClientAddr>,
     orig_dst_addr: OrigDstAddr,
     status: tls::ConditionalServerTls,
+    /// Describes the client's certificate when it is to be forwarded to the
+    /// application.
+    client_cert: Option<Arc<identity::PeerCert>>,
     policy: AllowPolicy,
 }
 
@@ -73,7 +77,7 @@ impl Inbound<svc::ArcNewTcp<Http, io::BoxedIo>> {
     where
         T: svc::Param<OrigDstAddr> + svc::Param<Remote<ClientAddr>> + svc::Param<AllowPolicy>,
         T: Clone + Send + 'static,
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         F: svc::NewService<Forward, Service = FSvc> + Clone + Send + Sync + Unpin + 'static,
         FSvc: svc::Service<io::BoxedIo, Response = ()> + Send + 'static,
@@ -93,7 +97,7 @@ impl Inbound<svc::ArcNewTcp<Http, io::BoxedIo>> {
         forward: F,
     ) -> Inbound<svc::ArcNewTcp<Tls, I>>
     where
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         F: svc::NewService<Forward, Service = FSvc> + Clone + Send + Sync + Unpin + 'static,
         FSvc: svc::Service<io::BoxedIo, Response = ()> + Send + 'static,
@@ -221,7 +225,7 @@ impl<I> Inbound<svc::ArcNewTcp<Tls, TlsIo<I>>> {
     where
         T: svc::Param<OrigDstAddr> + svc::Param<Remote<ClientAddr>> + svc::Param<AllowPolicy>,
         T: Clone + Send + 'static,
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         F: svc::NewService<Forward, Service = FSvc> + Clone + Send + Sync + Unpin + 'static,
         FSvc: svc::Service<io::BoxedIo, Response = ()> + Send + 'static,
@@ -243,13 +247,14 @@ impl<I> Inbound<svc::ArcNewTcp<Tls, TlsIo<I>>> {
                 .push_switch(
                     // Ensure that the connection is authorized before proceeding with protocol
                     // detection.
-                    |(status, t): (tls::ConditionalServerTls, T)| -> Result<_, Infallible> {
+                    |(client_cert, (status, t)): (_, (_, T))| -> Result<_, Infallible> {
                         let policy: AllowPolicy = t.param();
                         let protocol = policy.protocol();
                         let tls = Tls {
                             client_addr: t.param(),
                             orig_dst_addr: t.param(),
                             status,
+                            client_cert,
                             policy,
                         };
 
@@ -268,6 +273,7 @@ impl<I> Inbound<svc::ArcNewTcp<Tls, TlsIo<I>>> {
                         .push_on_service(svc::MapTargetLayer::new(io::BoxedIo::new))
                         .into_inner(),
                 )
+                .push(client_cert::NewClientCert::layer(cfg.forward_client_cert))
                 .arc_new_tcp()
                 .push(tls::NewDetectTls::<identity::Server, _, _>::layer(
                     TlsParams {
@@ -289,6 +295,7 @@ impl<I> Inbound<svc::ArcNewTcp<Tls, TlsIo<I>>> {
                                 client_addr: t.param(),
                                 orig_dst_addr: t.param(),
                                 status: TLS_PORT_SKIPPED,
+                                client_cert: None,
                                 policy,
                             }));
                         }
@@ -390,6 +397,12 @@ impl svc::Param<tls::ConditionalServerTls> for Http {
     }
 }
 
+impl svc::Param<Option<Arc<identity::PeerCert>>> for Http {
+    fn param(&self) -> Option<Arc<identity::PeerCert>> {
+        self.tls.client_cert.clone()
+    }
+}
+
 impl svc::Param<http::normalize_uri::DefaultAuthority> for Http {
     fn param(&self) -> http::normalize_uri::DefaultAuthority {
         http::normalize_uri::DefaultAuthority(Some(
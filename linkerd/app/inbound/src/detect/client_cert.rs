@@ -0,0 +1,75 @@
+use linkerd_app_core::{
+    identity, io,
+    svc::{self, ServiceExt},
+    tls,
+};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Describes the client's certificate from a connection's TLS session when
+/// the certificate is to be forwarded to the application.
+///
+/// The certificate is read from the connection, rather than being recorded in
+/// the connection's `ServerTls`, so that it is only parsed when it is needed.
+#[derive(Clone, Debug)]
+pub(super) struct NewClientCert<N> {
+    enabled: bool,
+    inner: N,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct ClientCert<T, N> {
+    target: T,
+    enabled: bool,
+    inner: N,
+}
+
+// === impl NewClientCert ===
+
+impl<N> NewClientCert<N> {
+    pub(super) fn layer(enabled: bool) -> impl svc::Layer<N, Service = Self> + Clone {
+        svc::layer::mk(move |inner| Self { enabled, inner })
+    }
+}
+
+impl<T, N: Clone> svc::NewService<T> for NewClientCert<N> {
+    type Service = ClientCert<T, N>;
+
+    fn new_service(&self, target: T) -> Self::Service {
+        ClientCert {
+            target,
+            enabled: self.enabled,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+// === impl ClientCert ===
+
+impl<T, L, I, N, S> svc::Service<tls::server::Io<identity::ServerIo<L>, I>> for ClientCert<T, N>
+where
+    T: Clone,
+    N: svc::NewService<(Option<Arc<identity::PeerCert>>, T), Service = S>,
+    S: svc::Service<tls::server::Io<identity::ServerIo<L>, I>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = svc::Oneshot<S, tls::server::Io<identity::ServerIo<L>, I>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, io: tls::server::Io<identity::ServerIo<L>, I>) -> Self::Future {
+        let client_cert = match io {
+            io::EitherIo::Left(ref tls) if self.enabled => tls.client_cert().map(Arc::new),
+            _ => None,
+        };
+        self.inner
+            .new_service((client_cert, self.target.clone()))
+            .oneshot(io)
+    }
+}
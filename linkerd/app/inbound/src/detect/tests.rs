@@ -102,6 +102,7 @@ async fn detect_http_non_http() {
             client_id: Some(client_id()),
             negotiated_protocol: None,
         }),
+        client_cert: None,
         policy: allow(Protocol::Detect {
             timeout: std::time::Duration::from_secs(10),
             http: Arc::new([linkerd_proxy_server_policy::http::default(authzs())]),
@@ -140,6 +141,7 @@ async fn detect_http() {
             client_id: Some(client_id()),
             negotiated_protocol: None,
         }),
+        client_cert: None,
         policy: allow(Protocol::Detect {
             timeout: std::time::Duration::from_secs(10),
             http: Arc::new([linkerd_proxy_server_policy::http::default(authzs())]),
@@ -180,6 +182,7 @@ async fn hinted_http1() {
             client_id: Some(client_id()),
             negotiated_protocol: None,
         }),
+        client_cert: None,
         policy: allow(Protocol::Http1(vec![].into())),
     };
 
@@ -216,6 +219,7 @@ async fn hinted_http1_supports_http2() {
             client_id: Some(client_id()),
             negotiated_protocol: None,
         }),
+        client_cert: None,
         policy: allow(Protocol::Http1(vec![].into())),
     };
 
@@ -252,6 +256,7 @@ async fn hinted_http2() {
             client_id: Some(client_id()),
             negotiated_protocol: None,
         }),
+        client_cert: None,
         policy: allow(Protocol::Http2(vec![].into())),
     };
 
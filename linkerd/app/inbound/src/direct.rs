@@ -11,9 +11,9 @@ use linkerd_app_core::{
     transport_header::{self, NewTransportHeaderServer, SessionProtocol, TransportHeader},
     Conditional, Error, Infallible, NameAddr, Result,
 };
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
 use thiserror::Error;
-use tracing::{debug_span, info_span};
+use tracing::{debug, debug_span, info_span};
 
 mod metrics;
 
@@ -36,14 +36,14 @@ struct TransportHeaderRequired(());
 pub(crate) struct LocalTcp {
     client_addr: Remote<ClientAddr>,
     server_addr: Remote<ServerAddr>,
-    client_id: tls::ClientId,
+    client_id: Option<tls::ClientId>,
     policy: policy::AllowPolicy,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct AuthorizedLocalTcp {
     addr: Remote<ServerAddr>,
-    client_id: tls::ClientId,
+    client_id: Option<tls::ClientId>,
     permit: policy::ServerPermit,
 }
 
@@ -62,13 +62,17 @@ pub struct GatewayTransportHeader {
     pub target: NameAddr,
     pub protocol: Option<SessionProtocol>,
     pub client: ClientInfo,
+    /// Gateway connections *must* have an authenticated client.
+    pub client_id: tls::ClientId,
     pub policy: policy::AllowPolicy,
 }
 
 /// Client connections *must* have an identity.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClientInfo {
-    pub client_id: tls::ClientId,
+    /// The client's identity. This is only unset when a trusted peer forwards
+    /// a connection on behalf of a client that has no identity.
+    pub client_id: Option<tls::ClientId>,
     pub alpn: Option<tls::NegotiatedProtocol>,
     pub client_addr: Remote<ClientAddr>,
     pub local_addr: OrigDstAddr,
@@ -102,7 +106,7 @@ impl<N> Inbound<N> {
     where
         T: Param<Remote<ClientAddr>> + Param<OrigDstAddr>,
         T: Clone + Send + 'static,
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         N: svc::NewService<AuthorizedLocalTcp, Service = NSvc>,
         N: Clone + Send + Sync + Unpin + 'static,
@@ -112,6 +116,7 @@ impl<N> Inbound<N> {
     {
         self.map_stack(|config, rt, inner| {
             let detect_timeout = config.proxy.detect_protocol_timeout;
+            let original_client_senders = config.original_client_senders.clone();
             let metrics = rt.metrics.direct.clone();
 
             let identity = rt
@@ -151,12 +156,13 @@ impl<N> Inbound<N> {
                 .push_switch(
                     {
                         let policies = policies.clone();
-                        move |(h, client): (TransportHeader, ClientInfo)| -> Result<_, Infallible> {
+                        move |(h, client): (TransportHeader, ClientInfo)| -> Result<_> {
                             match h {
                                 TransportHeader {
                                     port,
                                     name: None,
                                     protocol,
+                                    ..
                                 } => Ok(svc::Either::Left({
                                     // When the transport header targets an alternate port (but does
                                     // not identify an alternate target name), we check the new
@@ -188,15 +194,19 @@ impl<N> Inbound<N> {
                                     port,
                                     name: Some(name),
                                     protocol,
+                                    ..
                                 } => Ok(svc::Either::Right({
                                     // When the transport header provides an alternate target, the
                                     // connection is a gateway connection. We check the _gateway
                                     // address's_ policy (rather than the target address).
                                     let policy = policies.get_policy(client.local_addr);
+                                    let client_id =
+                                        client.client_id.clone().ok_or(RefusedNoIdentity(()))?;
                                     GatewayTransportHeader {
                                         target: NameAddr::from((name, port)),
                                         protocol,
                                         client,
+                                        client_id,
                                         policy,
                                     }
                                 })),
@@ -217,6 +227,12 @@ impl<N> Inbound<N> {
                 .check_new_service::<(TransportHeader, ClientInfo), _>()
                 // Use ALPN to determine whether a transport header should be read.
                 .push(metrics::NewRecord::layer(metrics))
+                // When a trusted peer forwards a connection on behalf of another
+                // client, the original client is used for policy and metrics.
+                .push_map_target(move |(h, client): (TransportHeader, ClientInfo)| {
+                    let client = client.with_original_client(&h, &original_client_senders);
+                    (h, client)
+                })
                 .push(svc::ArcNewService::layer())
                 .push(NewTransportHeaderServer::layer(detect_timeout))
                 .check_new_service::<ClientInfo, _>()
@@ -257,8 +273,9 @@ where
             Conditional::Some(tls::ServerTls::Established {
                 client_id: Some(client_id),
                 negotiated_protocol,
+                ..
             }) => Ok(Self {
-                client_id,
+                client_id: Some(client_id),
                 alpn: negotiated_protocol,
                 client_addr: addrs.param(),
                 local_addr: addrs.param(),
@@ -275,6 +292,40 @@ impl ClientInfo {
             .map(|tls::NegotiatedProtocol(p)| p == transport_header::PROTOCOL)
             .unwrap_or(false)
     }
+
+    /// Replaces the client's address and identity with those of the original
+    /// client described by the transport header, if the connection's peer is
+    /// trusted to forward connections on behalf of other clients.
+    ///
+    /// An unknown original client address is not replaced. An original client
+    /// without an identity is unauthenticated: the sender's identity is never
+    /// attributed to the clients it forwards connections for.
+    fn with_original_client(
+        self,
+        header: &TransportHeader,
+        senders: &HashSet<identity::Id>,
+    ) -> Self {
+        let Some(original) = header.original_client.as_ref() else {
+            return self;
+        };
+        if !self
+            .client_id
+            .as_ref()
+            .is_some_and(|tls::ClientId(id)| senders.contains(id))
+        {
+            debug!(client.id = ?self.client_id, "Ignoring original client from untrusted peer");
+            return self;
+        }
+        debug!(?original, "Using original client");
+        Self {
+            client_id: original.identity.clone().map(tls::ClientId),
+            client_addr: original
+                .addr
+                .map(|addr| Remote(ClientAddr(addr)))
+                .unwrap_or(self.client_addr),
+            ..self
+        }
+    }
 }
 
 // === impl LocalTcp ===
@@ -294,7 +345,7 @@ impl Param<AllowPolicy> for LocalTcp {
 impl Param<tls::ConditionalServerTls> for LocalTcp {
     fn param(&self) -> tls::ConditionalServerTls {
         tls::ConditionalServerTls::Some(tls::ServerTls::Established {
-            client_id: Some(self.client_id.clone()),
+            client_id: self.client_id.clone(),
             negotiated_protocol: None,
         })
     }
@@ -312,7 +363,7 @@ impl Param<transport::labels::Key> for AuthorizedLocalTcp {
     fn param(&self) -> transport::labels::Key {
         transport::labels::Key::inbound_server(
             tls::ConditionalServerTls::Some(tls::ServerTls::Established {
-                client_id: Some(self.client_id.clone()),
+                client_id: self.client_id.clone(),
                 negotiated_protocol: None,
             }),
             self.addr.into(),
@@ -345,7 +396,7 @@ impl Param<transport::labels::Key> for LocalHttp {
     fn param(&self) -> transport::labels::Key {
         transport::labels::Key::inbound_server(
             tls::ConditionalServerTls::Some(tls::ServerTls::Established {
-                client_id: Some(self.client.client_id.clone()),
+                client_id: self.client.client_id.clone(),
                 negotiated_protocol: None,
             }),
             self.addr.into(),
@@ -384,12 +435,19 @@ impl svc::Param<policy::ServerLabel> for LocalHttp {
 impl svc::Param<tls::ConditionalServerTls> for LocalHttp {
     fn param(&self) -> tls::ConditionalServerTls {
         tls::ConditionalServerTls::Some(tls::ServerTls::Established {
-            client_id: Some(self.client.client_id.clone()),
+            client_id: self.client.client_id.clone(),
             negotiated_protocol: self.client.alpn.clone(),
         })
     }
 }
 
+impl svc::Param<Option<Arc<identity::PeerCert>>> for LocalHttp {
+    fn param(&self) -> Option<Arc<identity::PeerCert>> {
+        // Client certificates are not forwarded on direct connections.
+        None
+    }
+}
+
 // === impl GatewayTransportHeader ===
 
 impl Param<GatewayAddr> for GatewayTransportHeader {
@@ -429,7 +487,7 @@ impl Param<Remote<ClientAddr>> for GatewayTransportHeader {
 impl Param<tls::ConditionalServerTls> for GatewayTransportHeader {
     fn param(&self) -> tls::ConditionalServerTls {
         tls::ConditionalServerTls::Some(tls::ServerTls::Established {
-            client_id: Some(self.client.client_id.clone()),
+            client_id: self.client.client_id.clone(),
             negotiated_protocol: self.client.alpn.clone(),
         })
     }
@@ -437,7 +495,7 @@ impl Param<tls::ConditionalServerTls> for GatewayTransportHeader {
 
 impl Param<tls::ClientId> for GatewayTransportHeader {
     fn param(&self) -> tls::ClientId {
-        self.client.client_id.clone()
+        self.client_id.clone()
     }
 }
 
@@ -488,3 +546,87 @@ impl<T> InsertParam<tls::ConditionalServerTls, T> for TlsParams {
         (tls, target)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn client() -> ClientInfo {
+        ClientInfo {
+            client_id: Some(
+                tls::ClientId::from_str(
+                    "gateway.linkerd-multicluster.serviceaccount.identity.linkerd.cluster.local",
+                )
+                .unwrap(),
+            ),
+            alpn: Some(tls::NegotiatedProtocol(transport_header::PROTOCOL.into())),
+            client_addr: Remote(ClientAddr(([192, 0, 2, 1], 50000).into())),
+            local_addr: OrigDstAddr(([192, 0, 2, 2], 4143).into()),
+        }
+    }
+
+    fn header() -> TransportHeader {
+        TransportHeader {
+            port: 8080,
+            name: None,
+            protocol: None,
+            original_client: Some(transport_header::OriginalClient {
+                addr: Some(([198, 51, 100, 7], 40000).into()),
+                identity: Some(
+                    identity::Id::from_str(
+                        "web.default.serviceaccount.identity.linkerd.cluster.local",
+                    )
+                    .unwrap(),
+                ),
+            }),
+        }
+    }
+
+    #[test]
+    fn original_client_from_trusted_sender() {
+        let senders = client().client_id.map(|id| id.0).into_iter().collect();
+        let client = client().with_original_client(&header(), &senders);
+        assert_eq!(
+            client.client_id,
+            Some(
+                tls::ClientId::from_str(
+                    "web.default.serviceaccount.identity.linkerd.cluster.local"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(
+            client.client_addr,
+            Remote(ClientAddr(([198, 51, 100, 7], 40000).into()))
+        );
+    }
+
+    #[test]
+    fn original_client_from_untrusted_sender() {
+        let senders = Some(identity::Id::from_str("other.example.com").unwrap())
+            .into_iter()
+            .collect();
+        let client = client().with_original_client(&header(), &senders);
+        assert_eq!(client, self::client());
+    }
+
+    #[test]
+    fn original_client_without_identity() {
+        let senders = client().client_id.map(|id| id.0).into_iter().collect();
+        let mut header = header();
+        header.original_client = Some(transport_header::OriginalClient {
+            addr: Some(([198, 51, 100, 7], 40000).into()),
+            identity: None,
+        });
+        let client = client().with_original_client(&header, &senders);
+        assert_eq!(
+            client.client_id, None,
+            "the sender's identity must not be attributed to the original client"
+        );
+        assert_eq!(
+            client.client_addr,
+            Remote(ClientAddr(([198, 51, 100, 7], 40000).into()))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::ClientInfo;
 use linkerd_app_core::{
+    dns,
     metrics::prom::{self, EncodeLabelSetMut},
     svc, tls,
     transport_header::{SessionProtocol, TransportHeader},
@@ -21,8 +22,10 @@ pub struct MetricsFamilies {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Labels {
-    header: TransportHeader,
-    client_id: tls::ClientId,
+    protocol: Option<SessionProtocol>,
+    port: u16,
+    name: Option<dns::Name>,
+    client_id: Option<tls::ClientId>,
 }
 
 impl MetricsFamilies {
@@ -57,7 +60,9 @@ where
         self.metrics
             .connections
             .get_or_create(&Labels {
-                header: header.clone(),
+                protocol: header.protocol.clone(),
+                port: header.port,
+                name: header.name.clone(),
                 client_id: client.client_id.clone(),
             })
             .inc();
@@ -71,15 +76,16 @@ impl prom::EncodeLabelSetMut for Labels {
         use prom::encoding::EncodeLabel;
         (
             "session_protocol",
-            self.header.protocol.as_ref().map(|p| match p {
+            self.protocol.as_ref().map(|p| match p {
                 SessionProtocol::Http1 => "http/1",
                 SessionProtocol::Http2 => "http/2",
             }),
         )
             .encode(enc.encode_label())?;
-        ("target_port", self.header.port).encode(enc.encode_label())?;
-        ("target_name", self.header.name.as_deref()).encode(enc.encode_label())?;
-        ("client_id", self.client_id.to_str()).encode(enc.encode_label())?;
+        ("target_port", self.port).encode(enc.encode_label())?;
+        ("target_name", self.name.as_deref()).encode(enc.encode_label())?;
+        let client_id = self.client_id.as_ref().map(|id| id.to_str());
+        ("client_id", client_id.as_deref()).encode(enc.encode_label())?;
         Ok(())
     }
 }
@@ -39,7 +39,7 @@ fn run_metric_test(header: TransportHeader) -> prom::Registry {
     let client_addr = Remote(ClientAddr(([127, 0, 0, 1], 40000).into()));
     let local_addr = OrigDstAddr(([127, 0, 0, 1], 4143).into());
     let client_info = ClientInfo {
-        client_id: client_id.clone(),
+        client_id: Some(client_id.clone()),
         alpn: Some(tls::NegotiatedProtocol("transport.l5d.io/v1".into())),
         client_addr,
         local_addr,
@@ -54,6 +54,7 @@ fn records_metrics_http1_local() {
         port: 8080,
         name: None,
         protocol: Some(SessionProtocol::Http1),
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "http/1", 8080, "", 1);
@@ -65,6 +66,7 @@ fn records_metrics_http2_local() {
         port: 8081,
         name: None,
         protocol: Some(SessionProtocol::Http2),
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "http/2", 8081, "", 1);
@@ -76,6 +78,7 @@ fn records_metrics_opaq_local() {
         port: 8082,
         name: None,
         protocol: None,
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "", 8082, "", 1);
@@ -87,6 +90,7 @@ fn records_metrics_http1_gateway() {
         port: 8080,
         name: Some("mysvc.myns.svc.cluster.local".parse().unwrap()),
         protocol: Some(SessionProtocol::Http1),
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "http/1", 8080, "mysvc.myns.svc.cluster.local", 1);
@@ -98,6 +102,7 @@ fn records_metrics_http2_gateway() {
         port: 8081,
         name: Some("mysvc.myns.svc.cluster.local".parse().unwrap()),
         protocol: Some(SessionProtocol::Http2),
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "http/2", 8081, "mysvc.myns.svc.cluster.local", 1);
@@ -109,6 +114,7 @@ fn records_metrics_opaq_gateway() {
         port: 8082,
         name: Some("mysvc.myns.svc.cluster.local".parse().unwrap()),
         protocol: None,
+        original_client: None,
     };
     let registry = run_metric_test(header);
     assert_counted!(&registry, "", 8082, "mysvc.myns.svc.cluster.local", 1);
@@ -118,7 +118,7 @@ pub mod fuzz {
         connect: Connect<Remote<ServerAddr>>,
     ) -> svc::ArcNewTcp<Target, I>
     where
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + Send + Unpin + 'static,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + Send + Unpin + 'static,
     {
         let connect = svc::stack(connect)
             .push_map_target(|t: Http| svc::Param::<Remote<ServerAddr>>::param(&t))
@@ -1,6 +1,6 @@
 use crate::{policy, stack_labels, Inbound};
 use linkerd_app_core::{
-    classify, errors, http_tracing, metrics, profiles,
+    classify, errors, http_tracing, io, metrics, profiles,
     proxy::{http, tap},
     svc::{self, ExtractParam, Param},
     tls,
@@ -77,7 +77,7 @@ impl<C> Inbound<C> {
         T: Clone + Send + Sync + Unpin + 'static,
         P: profiles::GetProfile<Error = Error>,
         C: svc::MakeConnection<Http> + Clone + Send + Sync + Unpin + 'static,
-        C::Connection: Send + Unpin,
+        C::Connection: io::TcpInfo + Send + Unpin,
         C::Metadata: Send,
         C::Future: Send,
     {
@@ -127,7 +127,11 @@ impl<C> Inbound<C> {
                             endpoint_labels(unsafe_authority_labels),
                         ),
                 )
-                .push_on_service(http_tracing::client(rt.span_sink.clone(), super::trace_labels()))
+                .push_on_service(http_tracing::client(
+                    rt.span_sink.clone(),
+                    config.trace_formats.clone(),
+                    super::trace_labels(),
+                ))
                 .push_on_service(http::BoxResponse::layer())
                 .arc_new_http();
 
@@ -242,8 +246,13 @@ impl<C> Inbound<C> {
                     LogicalPerRequest::from((permit.clone(), t.clone()))
                 }))
                 .check_new_service::<(policy::HttpRoutePermit, T), http::Request<http::BoxBody>>()
+                // Describe the route and client on the request's span.
+                .push(http_tracing::NewSpanAttributes::layer_via(span_attributes::<T>))
                 .push(svc::ArcNewService::layer())
-                .push(policy::NewHttpPolicy::layer(rt.metrics.http_authz.clone()))
+                .push(policy::NewHttpPolicy::layer(
+                    rt.metrics.http_authz.clone(),
+                    config.jwt_svid.clone(),
+                ))
                 // Used by tap.
                 .push_http_insert_target::<tls::ConditionalServerTls>()
                 .push_http_insert_target::<Remote<ClientAddr>>()
@@ -390,6 +399,32 @@ impl Param<transport::labels::Key> for Logical {
     }
 }
 
+/// Describes the matched route and the client's address and identity on
+/// server spans.
+fn span_attributes<T>((permit, t): &(policy::HttpRoutePermit, T)) -> http_tracing::TargetAttributes
+where
+    T: Param<Remote<ClientAddr>> + Param<tls::ConditionalServerTls>,
+{
+    let route = &permit.labels.route.route;
+    let Remote(ClientAddr(client_addr)) = t.param();
+    let client_id = match t.param() {
+        tls::ConditionalServerTls::Some(tls::ServerTls::Established {
+            client_id: Some(id),
+            ..
+        }) => Some(id.to_string()),
+        _ => None,
+    };
+    [
+        ("linkerd.route.group", route.group().to_string()),
+        ("linkerd.route.kind", route.kind().to_string()),
+        ("linkerd.route.name", route.name().to_string()),
+        ("linkerd.client.address", client_addr.to_string()),
+    ]
+    .into_iter()
+    .chain(client_id.map(|id| ("linkerd.client.identity", id)))
+    .collect()
+}
+
 fn endpoint_labels(
     unsafe_authority_labels: bool,
 ) -> impl svc::ExtractParam<metrics::EndpointLabels, Logical> + Clone {
@@ -3,7 +3,7 @@ use crate::{policy, Inbound};
 pub use linkerd_app_core::proxy::http::{normalize_uri, Variant};
 use linkerd_app_core::{
     config::ProxyConfig,
-    errors, http_tracing, io,
+    errors, http_tracing, identity, io,
     metrics::ServerLabel,
     proxy::http,
     svc::{self, ExtractParam, Param},
@@ -12,6 +12,7 @@ use linkerd_app_core::{
     Error, Result,
 };
 use linkerd_http_access_log::NewAccessLog;
+use std::sync::Arc;
 
 #[derive(Copy, Clone, Debug)]
 struct ServerRescue;
@@ -34,6 +35,7 @@ impl<H> Inbound<H> {
         T: Param<Variant>
             + Param<normalize_uri::DefaultAuthority>
             + Param<tls::ConditionalServerTls>
+            + Param<Option<Arc<identity::PeerCert>>>
             + Param<ServerLabel>
             + Param<OrigDstAddr>
             + Param<Remote<ClientAddr>>,
@@ -60,7 +62,7 @@ impl<H> Inbound<H> {
                 // `Client`. This must be below the `orig_proto::Downgrade` layer, since
                 // the request may have been downgraded from a HTTP/2 orig-proto request.
                 .push(http::NewNormalizeUri::layer())
-                .push(NewSetIdentityHeader::layer(()))
+                .push(NewSetIdentityHeader::layer((), config.forward_client_cert))
                 // Downgrades the protocol if upgraded by an outbound proxy.
                 .push_on_service(http::orig_proto::Downgrade::layer())
                 // Limit the number of in-flight inbound requests.
@@ -78,8 +80,13 @@ impl<H> Inbound<H> {
                 .push_on_service(svc::MapErr::layer_boxed())
                 .push(rt.metrics.http_errors.to_layer())
                 .push(ServerRescue::layer())
+                // Records trace IDs on log lines emitted while handling
+                // each request.
+                .push_on_service(http_tracing::correlate(config.trace_formats.clone()))
                 .push_on_service(http_tracing::server(
                     rt.span_sink.clone(),
+                    config.trace_formats.clone(),
+                    config.trace_sampler.clone(),
                     super::trace_labels(),
                 ))
                 // Record when an HTTP/1 URI was in absolute form
@@ -98,7 +105,7 @@ impl<H> Inbound<H> {
         T: Param<Variant>,
         T: Clone + Send + Unpin + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + Send + Unpin + 'static,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + Send + Unpin + 'static,
         // Inner HTTP stack.
         H: svc::NewService<T, Service = HSvc> + Clone + Send + Sync + Unpin + 'static,
         HSvc: svc::Service<
@@ -188,6 +195,10 @@ impl errors::HttpRescue<Error> for ServerRescue {
             return Ok(errors::SyntheticHttpResponse::permission_denied(error));
         }
 
+        if errors::is_caused_by::<policy::HttpRouteJwtSvidRequired>(&*error) {
+            return Ok(errors::SyntheticHttpResponse::unauthenticated(error));
+        }
+
         if errors::is_caused_by::<policy::HttpRouteInvalidRedirect>(&*error) {
             tracing::warn!(%error);
             return Ok(errors::SyntheticHttpResponse::unexpected_error());
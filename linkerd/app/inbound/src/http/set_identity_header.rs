@@ -1,12 +1,20 @@
-use linkerd_app_core::{proxy::http, svc, tls};
-use std::task::{Context, Poll};
+use linkerd_app_core::{identity, proxy::http, svc, tls};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
 use tracing::{debug, trace};
 
 const HEADER_NAME: &str = "l5d-client-id";
 
+/// Describes the client's certificate in the format of Envoy's
+/// `x-forwarded-client-cert` header.
+const CLIENT_CERT_HEADER_NAME: &str = "x-forwarded-client-cert";
+
 #[derive(Clone, Debug)]
 pub struct NewSetIdentityHeader<P, N> {
     params: P,
+    forward_client_cert: bool,
     inner: N,
 }
 
@@ -14,15 +22,28 @@ pub struct NewSetIdentityHeader<P, N> {
 pub struct SetIdentityHeader<M> {
     inner: M,
     value: Option<http::HeaderValue>,
+    forward_client_cert: bool,
+    client_cert: Option<http::HeaderValue>,
 }
 
 // === impl NewSetIdentityHeader ===
 
 impl<P: Clone, N> NewSetIdentityHeader<P, N> {
-    pub fn layer(params: P) -> impl svc::Layer<N, Service = Self> + Clone {
+    /// When `forward_client_cert` is set, the client's certificate is also
+    /// described in an `x-forwarded-client-cert` header, replacing any
+    /// client-supplied copies of this header. Otherwise, the header is left
+    /// untouched.
+    ///
+    /// The certificate is only extracted from the target when it is to be
+    /// forwarded.
+    pub fn layer(
+        params: P,
+        forward_client_cert: bool,
+    ) -> impl svc::Layer<N, Service = Self> + Clone {
         svc::layer::mk(move |inner| Self {
             inner,
             params: params.clone(),
+            forward_client_cert,
         })
     }
 }
@@ -30,37 +51,100 @@ impl<P: Clone, N> NewSetIdentityHeader<P, N> {
 impl<T, P, N> svc::NewService<T> for NewSetIdentityHeader<P, N>
 where
     P: svc::ExtractParam<tls::ConditionalServerTls, T>,
+    P: svc::ExtractParam<Option<Arc<identity::PeerCert>>, T>,
     N: svc::NewService<T>,
 {
     type Service = SetIdentityHeader<N::Service>;
 
     #[inline]
     fn new_service(&self, t: T) -> Self::Service {
-        let value = self
-            .params
-            .extract_param(&t)
-            .value()
-            .and_then(|tls| match tls {
-                tls::ServerTls::Established { client_id, .. } => {
-                    client_id.as_ref().and_then(|id| {
-                        match http::HeaderValue::from_str(&id.to_str()) {
-                            Ok(v) => Some(v),
-                            Err(error) => {
-                                tracing::warn!(%error, "identity not a valid header value");
-                                None
-                            }
+        let tls: tls::ConditionalServerTls = self.params.extract_param(&t);
+        let value = tls.value().and_then(|tls| match tls {
+            tls::ServerTls::Established { client_id, .. } => {
+                client_id
+                    .as_ref()
+                    .and_then(|id| match http::HeaderValue::from_str(&id.to_str()) {
+                        Ok(v) => Some(v),
+                        Err(error) => {
+                            tracing::warn!(%error, "identity not a valid header value");
+                            None
                         }
                     })
-                }
-                _ => None,
-            });
+            }
+            _ => None,
+        });
+        let client_cert = if self.forward_client_cert {
+            let cert: Option<Arc<identity::PeerCert>> = self.params.extract_param(&t);
+            cert.and_then(
+                |cert| match http::HeaderValue::from_str(&client_cert_value(&cert)) {
+                    Ok(v) => Some(v),
+                    Err(error) => {
+                        tracing::warn!(%error, "client certificate not a valid header value");
+                        None
+                    }
+                },
+            )
+        } else {
+            None
+        };
         SetIdentityHeader {
             value,
+            forward_client_cert: self.forward_client_cert,
+            client_cert,
             inner: self.inner.new_service(t),
         }
     }
 }
 
+/// Formats the client's certificate like Envoy's `x-forwarded-client-cert`
+/// header, with an additional `ChainDepth` field.
+///
+/// Characters that may not appear in a header value, such as non-ASCII
+/// characters in the certificate's subject, are percent-encoded as UTF-8.
+fn client_cert_value(cert: &identity::PeerCert) -> String {
+    let mut fields = vec![
+        format!("Hash={}", cert.hash),
+        format!("Subject={}", quote(&cert.subject)),
+    ];
+    fields.extend(
+        cert.uri_sans
+            .iter()
+            .map(|uri| format!("URI={}", field(uri))),
+    );
+    fields.extend(
+        cert.dns_sans
+            .iter()
+            .map(|dns| format!("DNS={}", field(dns))),
+    );
+    fields.push(format!("ChainDepth={}", cert.chain_depth));
+    percent_encode(&fields.join(";"))
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b == b' ' || b.is_ascii_graphic() {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Quotes values that contain the header's delimiters.
+fn field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', ';', '=', '"']) {
+        quote(value).into()
+    } else {
+        value.into()
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 // === impl Service ===
 
 impl<S, B> tower::Service<http::Request<B>> for SetIdentityHeader<S>
@@ -87,6 +171,154 @@ where
             debug!(header = %HEADER_NAME, ?value, "Stripped identity header");
         }
 
+        // When forwarding is enabled, any client-supplied descriptions
+        // (including repeated headers) are replaced so that applications can
+        // trust the header's value.
+        if self.forward_client_cert {
+            let prior = if let Some(cert) = self.client_cert.clone() {
+                trace!(header = %CLIENT_CERT_HEADER_NAME, ?cert, "Setting client certificate header");
+                req.headers_mut().insert(CLIENT_CERT_HEADER_NAME, cert)
+            } else {
+                req.headers_mut().remove(CLIENT_CERT_HEADER_NAME)
+            };
+            if let Some(value) = prior {
+                debug!(header = %CLIENT_CERT_HEADER_NAME, ?value, "Stripped client certificate header");
+            }
+        }
+
         self.inner.call(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_app_core::svc::{Layer, NewService, Param, ServiceExt};
+
+    #[derive(Clone, Debug)]
+    struct Target(tls::ConditionalServerTls, Option<Arc<identity::PeerCert>>);
+
+    fn peer_cert() -> identity::PeerCert {
+        identity::PeerCert {
+            hash: "b4d1".to_string(),
+            subject: "CN=client,O=\"Example, Inc.\"".to_string(),
+            uri_sans: vec!["spiffe://example.org/ns/foo/sa/bar".to_string()],
+            dns_sans: vec![
+                "bar.foo.example.com".to_string(),
+                "bar.example.com".to_string(),
+            ],
+            chain_depth: 2,
+        }
+    }
+
+    fn established(cert: Option<identity::PeerCert>) -> Target {
+        let tls = tls::ConditionalServerTls::Some(tls::ServerTls::Established {
+            client_id: Some(tls::ClientId(
+                "bar.foo.serviceaccount.identity.linkerd.cluster.local"
+                    .parse()
+                    .unwrap(),
+            )),
+            negotiated_protocol: None,
+        });
+        Target(tls, cert.map(Arc::new))
+    }
+
+    async fn forward(
+        forward_client_cert: bool,
+        target: Target,
+        req: http::Request<()>,
+    ) -> http::HeaderMap {
+        let new_svc = NewSetIdentityHeader::layer((), forward_client_cert).layer(|_: Target| {
+            svc::mk(|req: http::Request<()>| async move {
+                Ok::<_, std::convert::Infallible>(req.headers().clone())
+            })
+        });
+        new_svc.new_service(target).oneshot(req).await.unwrap()
+    }
+
+    impl Param<tls::ConditionalServerTls> for Target {
+        fn param(&self) -> tls::ConditionalServerTls {
+            self.0.clone()
+        }
+    }
+
+    impl Param<Option<Arc<identity::PeerCert>>> for Target {
+        fn param(&self) -> Option<Arc<identity::PeerCert>> {
+            self.1.clone()
+        }
+    }
+
+    #[test]
+    fn formats_client_cert() {
+        assert_eq!(
+            client_cert_value(&peer_cert()),
+            "Hash=b4d1;\
+             Subject=\"CN=client,O=\\\"Example, Inc.\\\"\";\
+             URI=spiffe://example.org/ns/foo/sa/bar;\
+             DNS=bar.foo.example.com;\
+             DNS=bar.example.com;\
+             ChainDepth=2"
+        );
+
+        let cert = identity::PeerCert {
+            subject: "CN=Zoë\n".to_string(),
+            uri_sans: vec![],
+            dns_sans: vec![],
+            ..peer_cert()
+        };
+        let value = client_cert_value(&cert);
+        assert_eq!(value, "Hash=b4d1;Subject=\"CN=Zo%C3%AB%0A\";ChainDepth=2");
+        assert!(http::HeaderValue::from_str(&value).is_ok());
+    }
+
+    #[tokio::test]
+    async fn forwards_client_cert() {
+        let req = http::Request::builder()
+            .header(CLIENT_CERT_HEADER_NAME, "Hash=forged")
+            .header(CLIENT_CERT_HEADER_NAME, "Hash=forged-again")
+            .body(())
+            .unwrap();
+        let headers = forward(true, established(Some(peer_cert())), req).await;
+        let values = headers
+            .get_all(CLIENT_CERT_HEADER_NAME)
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].to_str().unwrap(), client_cert_value(&peer_cert()));
+        assert_eq!(
+            headers.get(HEADER_NAME).unwrap(),
+            "bar.foo.serviceaccount.identity.linkerd.cluster.local"
+        );
+    }
+
+    #[tokio::test]
+    async fn strips_client_cert() {
+        let req = || {
+            http::Request::builder()
+                .header(CLIENT_CERT_HEADER_NAME, "Hash=forged")
+                .body(())
+                .unwrap()
+        };
+
+        let headers = forward(false, established(Some(peer_cert())), req()).await;
+        assert_eq!(
+            headers.get(CLIENT_CERT_HEADER_NAME).unwrap(),
+            "Hash=forged",
+            "header must be left untouched unless forwarding is enabled"
+        );
+
+        let headers = forward(
+            true,
+            Target(
+                tls::ConditionalServerTls::None(tls::NoServerTls::NoClientHello),
+                None,
+            ),
+            req(),
+        )
+        .await;
+        assert!(
+            headers.get(CLIENT_CERT_HEADER_NAME).is_none(),
+            "plaintext clients must not describe a certificate"
+        );
+    }
+}
@@ -31,7 +31,7 @@ fn build_server<I>(
     connect: Connect<Remote<ServerAddr>>,
 ) -> svc::ArcNewTcp<Target, I>
 where
-    I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + Send + Unpin + 'static,
+    I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + Send + Unpin + 'static,
 {
     Inbound::new(cfg, rt, &mut Default::default())
         .with_stack(connect)
@@ -977,6 +977,12 @@ impl svc::Param<tls::ConditionalServerTls> for Target {
     }
 }
 
+impl svc::Param<Option<Arc<identity::PeerCert>>> for Target {
+    fn param(&self) -> Option<Arc<identity::PeerCert>> {
+        None
+    }
+}
+
 impl svc::Param<policy::AllowPolicy> for Target {
     fn param(&self) -> policy::AllowPolicy {
         let authorizations = Arc::new([policy::Authorization {
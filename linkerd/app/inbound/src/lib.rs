@@ -8,11 +8,13 @@
 #![forbid(unsafe_code)]
 
 mod accept;
+mod connect;
 mod detect;
 pub mod direct;
 mod http;
 mod metrics;
 pub mod policy;
+mod proxy_protocol;
 mod server;
 
 #[cfg(any(test, feature = "test-util", fuzzing))]
@@ -26,7 +28,7 @@ pub use self::{
 use linkerd_app_core::{
     config::{ConnectConfig, ProxyConfig, QueueConfig},
     drain,
-    http_tracing::SpanSink,
+    http_tracing::{self, SpanSink},
     identity, io,
     metrics::prom,
     proxy::{tap, tcp},
@@ -34,7 +36,13 @@ use linkerd_app_core::{
     transport::{self, Remote, ServerAddr},
     Error, NameAddr, NameMatch, ProxyRuntime,
 };
-use std::{fmt::Debug, time::Duration};
+use rangemap::RangeInclusiveSet;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+    time::Duration,
+};
 use thiserror::Error;
 use tracing::debug_span;
 
@@ -58,6 +66,33 @@ pub struct Config {
 
     /// Enables unsafe authority labels.
     pub unsafe_authority_labels: bool,
+
+    /// Configures the ports on which connections may be prefixed with a PROXY
+    /// protocol header describing the original client.
+    pub proxy_protocol_ports: Arc<RangeInclusiveSet<u16>>,
+
+    /// Configures the mTLS identities of peers (e.g. multicluster gateways)
+    /// that are trusted to describe a forwarded connection's original client in
+    /// its transport header.
+    pub original_client_senders: Arc<HashSet<identity::Id>>,
+
+    /// Configures ports on which the application accepts connections over a
+    /// Unix domain socket rather than TCP.
+    pub unix_sockets: Arc<HashMap<u16, transport::UnixPath>>,
+
+    /// Determines whether the proxy starts a trace for requests that do not
+    /// carry a trace context.
+    pub trace_sampler: http_tracing::Sampler,
+
+    /// Configures the formats in which trace contexts are propagated.
+    pub trace_formats: http_tracing::Formats,
+
+    /// Requires JWT-SVIDs on the configured HTTP routes, if set.
+    pub jwt_svid: Option<policy::JwtSvidPolicy>,
+
+    /// Determines whether the client's certificate is described to the
+    /// application in an `x-forwarded-client-cert` header.
+    pub forward_client_cert: bool,
 }
 
 #[derive(Clone)]
@@ -121,7 +156,10 @@ impl<S> Inbound<S> {
     pub fn authorize_http<N>(
         &self,
     ) -> impl svc::layer::Layer<N, Service = policy::NewHttpPolicy<N>> + Clone {
-        policy::NewHttpPolicy::layer(self.runtime.metrics.http_authz.clone())
+        policy::NewHttpPolicy::layer(
+            self.runtime.metrics.http_authz.clone(),
+            self.config.jwt_svid.clone(),
+        )
     }
 
     /// A helper for gateways to instrument policy checks.
@@ -188,15 +226,15 @@ impl Inbound<()> {
         self.map_stack(move |_, _, _| svc::stack(stack))
     }
 
-    /// Readies the inbound stack to make TCP connections (for both TCP
-    // forwarding and HTTP proxying).
+    /// Readies the inbound stack to make connections to the application (for
+    // both TCP forwarding and HTTP proxying), over TCP or a Unix domain socket.
     pub fn into_tcp_connect<T>(
         self,
         proxy_port: u16,
     ) -> Inbound<
         impl svc::MakeConnection<
                 T,
-                Connection = impl Send + Unpin,
+                Connection = impl io::TcpInfo + Send + Unpin,
                 Metadata = impl Send + Unpin,
                 Error = Error,
                 Future = impl Send,
@@ -219,7 +257,8 @@ impl Inbound<()> {
             #[error("inbound connection must not target port {0}")]
             struct Loop(u16);
 
-            svc::stack(transport::ConnectTcp::new(*keepalive, *user_timeout))
+            let tcp = transport::ConnectTcp::new(*keepalive, *user_timeout);
+            svc::stack(connect::ConnectLocal::new(tcp, config.unix_sockets.clone()))
                 // Limits the time we wait for a connection to be established.
                 .push_connect_timeout(*timeout)
                 // Prevent connections that would target the inbound proxy port from looping.
@@ -261,7 +300,7 @@ impl<S> Inbound<S> {
         I: io::AsyncRead + io::AsyncWrite,
         I: Debug + Send + Unpin + 'static,
         S: svc::MakeConnection<T> + Clone + Send + Sync + Unpin + 'static,
-        S::Connection: Send + Unpin,
+        S::Connection: io::TcpInfo + Send + Unpin,
         S::Metadata: Send + Unpin,
         S::Future: Send,
     {
@@ -38,10 +38,11 @@ impl InboundMetrics {
             reg.sub_registry_with_prefix("tcp_transport_header"),
         );
 
+        let limits = proxy.cardinality.scoped("inbound");
         Self {
-            http_authz: authz::HttpAuthzMetrics::default(),
+            http_authz: authz::HttpAuthzMetrics::new(&limits),
             http_errors: error::HttpErrorMetrics::default(),
-            tcp_authz: authz::TcpAuthzMetrics::default(),
+            tcp_authz: authz::TcpAuthzMetrics::new(&limits),
             tcp_errors: error::TcpErrorMetrics::default(),
             proxy,
             detect,
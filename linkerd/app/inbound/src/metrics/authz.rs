@@ -1,14 +1,14 @@
 use crate::policy::{AllowPolicy, HttpRoutePermit, Meta, ServerPermit};
 use linkerd_app_core::{
     metrics::{
-        metrics, Counter, FmtLabels, FmtMetrics, RouteAuthzLabels, RouteLabels, ServerAuthzLabels,
-        ServerLabel, TargetAddr, TlsAccept,
+        metrics, CardinalityLimits, Counter, FmtLabels, FmtMetrics, RouteAuthzLabels, RouteLabels,
+        ServerAuthzLabels, ServerLabel, Store, TargetAddr, TlsAccept,
     },
     tls,
     transport::OrigDstAddr,
 };
 use parking_lot::Mutex;
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 metrics! {
     inbound_http_authz_allow_total: Counter {
@@ -44,17 +44,17 @@ pub(crate) struct TcpAuthzMetrics(Arc<TcpInner>);
 
 #[derive(Debug, Default)]
 struct HttpInner {
-    allow: Mutex<HashMap<RouteAuthzKey, Counter>>,
-    deny: Mutex<HashMap<RouteKey, Counter>>,
-    route_not_found: Mutex<HashMap<ServerKey, Counter>>,
-    http_local_rate_limit: Mutex<HashMap<HttpLocalRateLimitKey, Counter>>,
+    allow: Mutex<Store<RouteAuthzKey, Counter>>,
+    deny: Mutex<Store<RouteKey, Counter>>,
+    route_not_found: Mutex<Store<ServerKey, Counter>>,
+    http_local_rate_limit: Mutex<Store<HttpLocalRateLimitKey, Counter>>,
 }
 
 #[derive(Debug, Default)]
 struct TcpInner {
-    allow: Mutex<HashMap<ServerAuthzKey, Counter>>,
-    deny: Mutex<HashMap<ServerKey, Counter>>,
-    terminate: Mutex<HashMap<ServerKey, Counter>>,
+    allow: Mutex<Store<ServerAuthzKey, Counter>>,
+    deny: Mutex<Store<ServerKey, Counter>>,
+    terminate: Mutex<Store<ServerKey, Counter>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -80,12 +80,24 @@ type HttpLocalRateLimitKey = Key<HTTPLocalRateLimitLabels>;
 // === impl HttpAuthzMetrics ===
 
 impl HttpAuthzMetrics {
+    pub(crate) fn new(limits: &CardinalityLimits) -> Self {
+        Self(Arc::new(HttpInner {
+            allow: Mutex::new(Store::with_limit(limits.limit("http_authz_allow_total"))),
+            deny: Mutex::new(Store::with_limit(limits.limit("http_authz_deny_total"))),
+            route_not_found: Mutex::new(Store::with_limit(
+                limits.limit("http_route_not_found_total"),
+            )),
+            http_local_rate_limit: Mutex::new(Store::with_limit(
+                limits.limit("http_local_ratelimit_total"),
+            )),
+        }))
+    }
+
     pub fn allow(&self, permit: &HttpRoutePermit, tls: tls::ConditionalServerTls) {
         self.0
             .allow
             .lock()
-            .entry(RouteAuthzKey::from_permit(permit, tls))
-            .or_default()
+            .get_or_default(RouteAuthzKey::from_permit(permit, tls))
             .incr();
     }
 
@@ -98,8 +110,7 @@ impl HttpAuthzMetrics {
         self.0
             .route_not_found
             .lock()
-            .entry(ServerKey::new(labels, dst, tls))
-            .or_default()
+            .get_or_default(ServerKey::new(labels, dst, tls))
             .incr();
     }
 
@@ -107,8 +118,7 @@ impl HttpAuthzMetrics {
         self.0
             .deny
             .lock()
-            .entry(RouteKey::new(labels, dst, tls))
-            .or_default()
+            .get_or_default(RouteKey::new(labels, dst, tls))
             .incr();
     }
 
@@ -121,8 +131,7 @@ impl HttpAuthzMetrics {
         self.0
             .http_local_rate_limit
             .lock()
-            .entry(HttpLocalRateLimitKey::new(labels, dst, tls))
-            .or_default()
+            .get_or_default(HttpLocalRateLimitKey::new(labels, dst, tls))
             .incr();
     }
 }
@@ -132,25 +141,16 @@ impl FmtMetrics for HttpAuthzMetrics {
         let allow = self.0.allow.lock();
         if !allow.is_empty() {
             inbound_http_authz_allow_total.fmt_help(f)?;
-            inbound_http_authz_allow_total.fmt_scopes(
-                f,
-                allow
-                    .iter()
-                    .map(|(k, c)| ((k.target, (&k.labels, TlsAccept(&k.tls))), c)),
-                |c| c,
-            )?;
+            inbound_http_authz_allow_total
+                .fmt_scopes(f, allow.iter_with_overflow(), |c| c.as_ref())?;
         }
         drop(allow);
 
         let deny = self.0.deny.lock();
         if !deny.is_empty() {
             inbound_http_authz_deny_total.fmt_help(f)?;
-            inbound_http_authz_deny_total.fmt_scopes(
-                f,
-                deny.iter()
-                    .map(|(k, c)| ((k.target, (&k.labels, TlsAccept(&k.tls))), c)),
-                |c| c,
-            )?;
+            inbound_http_authz_deny_total
+                .fmt_scopes(f, deny.iter_with_overflow(), |c| c.as_ref())?;
         }
         drop(deny);
 
@@ -159,10 +159,8 @@ impl FmtMetrics for HttpAuthzMetrics {
             inbound_http_route_not_found_total.fmt_help(f)?;
             inbound_http_route_not_found_total.fmt_scopes(
                 f,
-                route_not_found
-                    .iter()
-                    .map(|(k, c)| ((k.target, (&k.labels, TlsAccept(&k.tls))), c)),
-                |c| c,
+                route_not_found.iter_with_overflow(),
+                |c| c.as_ref(),
             )?;
         }
         drop(route_not_found);
@@ -172,10 +170,8 @@ impl FmtMetrics for HttpAuthzMetrics {
             inbound_http_local_ratelimit_total.fmt_help(f)?;
             inbound_http_local_ratelimit_total.fmt_scopes(
                 f,
-                local_ratelimit
-                    .iter()
-                    .map(|(k, c)| ((k.target, (&k.labels, TlsAccept(&k.tls))), c)),
-                |c| c,
+                local_ratelimit.iter_with_overflow(),
+                |c| c.as_ref(),
             )?;
         }
         drop(local_ratelimit);
@@ -187,12 +183,19 @@ impl FmtMetrics for HttpAuthzMetrics {
 // === impl TcpAuthzMetrics ===
 
 impl TcpAuthzMetrics {
+    pub(crate) fn new(limits: &CardinalityLimits) -> Self {
+        Self(Arc::new(TcpInner {
+            allow: Mutex::new(Store::with_limit(limits.limit("tcp_authz_allow_total"))),
+            deny: Mutex::new(Store::with_limit(limits.limit("tcp_authz_deny_total"))),
+            terminate: Mutex::new(Store::with_limit(limits.limit("tcp_authz_terminate_total"))),
+        }))
+    }
+
     pub fn allow(&self, permit: &ServerPermit, tls: tls::ConditionalServerTls) {
         self.0
             .allow
             .lock()
-            .entry(ServerAuthzKey::from_permit(permit, tls))
-            .or_default()
+            .get_or_default(ServerAuthzKey::from_permit(permit, tls))
             .incr();
     }
 
@@ -200,8 +203,7 @@ impl TcpAuthzMetrics {
         self.0
             .deny
             .lock()
-            .entry(ServerKey::from_policy(policy, tls))
-            .or_default()
+            .get_or_default(ServerKey::from_policy(policy, tls))
             .incr();
     }
 
@@ -209,8 +211,7 @@ impl TcpAuthzMetrics {
         self.0
             .terminate
             .lock()
-            .entry(ServerKey::from_policy(policy, tls))
-            .or_default()
+            .get_or_default(ServerKey::from_policy(policy, tls))
             .incr();
     }
 }
@@ -220,21 +221,27 @@ impl FmtMetrics for TcpAuthzMetrics {
         let allow = self.0.allow.lock();
         if !allow.is_empty() {
             inbound_tcp_authz_allow_total.fmt_help(f)?;
-            inbound_tcp_authz_allow_total.fmt_scopes(f, &*allow, |c| c)?;
+            inbound_tcp_authz_allow_total
+                .fmt_scopes(f, allow.iter_with_overflow(), |c| c.as_ref())?;
         }
         drop(allow);
 
         let deny = self.0.deny.lock();
         if !deny.is_empty() {
             inbound_tcp_authz_deny_total.fmt_help(f)?;
-            inbound_tcp_authz_deny_total.fmt_scopes(f, &*deny, |c| c)?;
+            inbound_tcp_authz_deny_total
+                .fmt_scopes(f, deny.iter_with_overflow(), |c| c.as_ref())?;
         }
         drop(deny);
 
         let terminate = self.0.terminate.lock();
         if !terminate.is_empty() {
             inbound_tcp_authz_terminate_total.fmt_help(f)?;
-            inbound_tcp_authz_terminate_total.fmt_scopes(f, &*terminate, |c| c)?;
+            inbound_tcp_authz_terminate_total.fmt_scopes(
+                f,
+                terminate.iter_with_overflow(),
+                |c| c.as_ref(),
+            )?;
         }
         drop(terminate);
 
@@ -2,6 +2,7 @@ mod api;
 mod config;
 pub mod defaults;
 mod http;
+mod jwt_svid;
 mod store;
 mod tcp;
 
@@ -14,6 +15,7 @@ pub use self::{
         HttpInvalidPolicy, HttpRouteInvalidRedirect, HttpRouteNotFound, HttpRouteRedirect,
         HttpRouteUnauthorized, NewHttpPolicy,
     },
+    jwt_svid::{HttpRouteJwtSvidRequired, JwtSvidId, JwtSvidPolicy},
     tcp::NewTcpPolicy,
 };
 
@@ -211,6 +213,25 @@ fn is_authorized(
     is_tls_authorized(tls, authz)
 }
 
+/// Authorizes a request by the SPIFFE ID of the JWT-SVID it bears, as if it
+/// were the client's TLS identity.
+fn is_jwt_svid_authorized(
+    authz: &Authorization,
+    client_addr: Remote<ClientAddr>,
+    JwtSvidId(id): &JwtSvidId,
+) -> bool {
+    if !authz.networks.iter().any(|n| n.contains(&client_addr.ip())) {
+        return false;
+    }
+
+    match authz.authentication {
+        Authentication::TlsAuthenticated { ref identities, .. } => {
+            identities.contains(&*id.to_str())
+        }
+        _ => false,
+    }
+}
+
 // === impl Permit ===
 
 impl ServerPermit {
@@ -231,8 +252,12 @@ mod tests {
     use super::is_tls_authorized;
     use super::Meta;
     use super::Suffix;
+    use super::{is_jwt_svid_authorized, JwtSvidId};
     use super::{Authentication, Authorization};
-    use linkerd_app_core::tls;
+    use linkerd_app_core::{
+        tls,
+        transport::{ClientAddr, Remote},
+    };
     use std::collections::BTreeSet;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -250,6 +275,10 @@ mod tests {
         }
     }
 
+    fn client_addr() -> Remote<ClientAddr> {
+        Remote(ClientAddr(([192, 168, 3, 3], 30120).into()))
+    }
+
     fn server_tls(identity: &str) -> tls::ConditionalServerTls {
         let client_id = tls::ClientId::from_str(identity).expect("should parse id");
         tls::ConditionalServerTls::Some(tls::ServerTls::Established {
@@ -340,4 +369,24 @@ mod tests {
         );
         assert!(is_tls_authorized(&tls, &authz))
     }
+
+    #[test]
+    fn is_authorized_for_matching_jwt_svid_ids() {
+        let mut authz = authorization(
+            BTreeSet::from(["spiffe://some-root/some-workload".into()]),
+            vec![],
+        );
+        authz.networks = vec![std::net::IpAddr::from([192, 168, 3, 3]).into()];
+        let id = |s: &str| JwtSvidId(s.parse().unwrap());
+        assert!(is_jwt_svid_authorized(
+            &authz,
+            client_addr(),
+            &id("spiffe://some-root/some-workload")
+        ));
+        assert!(!is_jwt_svid_authorized(
+            &authz,
+            client_addr(),
+            &id("spiffe://some-root/some-workload-2")
+        ));
+    }
 }
@@ -1,7 +1,7 @@
 use super::{RoutePolicy, Routes};
 use crate::{
     metrics::authz::HttpAuthzMetrics,
-    policy::{AllowPolicy, HttpRoutePermit},
+    policy::{AllowPolicy, HttpRoutePermit, JwtSvidPolicy},
 };
 use futures::{future, TryFutureExt};
 use linkerd_app_core::{
@@ -27,6 +27,7 @@ mod tests;
 #[derive(Clone, Debug)]
 pub struct NewHttpPolicy<N> {
     metrics: HttpAuthzMetrics,
+    jwt_svid: Option<JwtSvidPolicy>,
     inner: N,
 }
 
@@ -36,6 +37,7 @@ pub struct HttpPolicyService<T, N> {
     connection: ConnectionMeta,
     policy: AllowPolicy,
     metrics: HttpAuthzMetrics,
+    jwt_svid: Option<JwtSvidPolicy>,
     inner: N,
 }
 
@@ -86,9 +88,13 @@ pub struct HttpInvalidPolicy(&'static str);
 // === impl NewHttpPolicy ===
 
 impl<N> NewHttpPolicy<N> {
-    pub fn layer(metrics: HttpAuthzMetrics) -> impl svc::layer::Layer<N, Service = Self> + Clone {
+    pub fn layer(
+        metrics: HttpAuthzMetrics,
+        jwt_svid: Option<JwtSvidPolicy>,
+    ) -> impl svc::layer::Layer<N, Service = Self> + Clone {
         svc::layer::mk(move |inner| Self {
             metrics: metrics.clone(),
+            jwt_svid: jwt_svid.clone(),
             inner,
         })
     }
@@ -113,6 +119,7 @@ where
             policy,
             connection: ConnectionMeta { client, dst, tls },
             metrics: self.metrics.clone(),
+            jwt_svid: self.jwt_svid.clone(),
             inner: self.inner.clone(),
         }
     }
@@ -160,12 +167,12 @@ where
         let permit = match self.policy.routes() {
             None => err!(self.mk_route_not_found()),
             Some(Routes::Http(routes)) => {
-                let (permit, mtch, route) = try_fut!(self.authorize(&routes, &req));
+                let (permit, mtch, route) = try_fut!(self.authorize(&routes, &mut req));
                 try_fut!(apply_http_filters(mtch, route, &mut req));
                 permit
             }
             Some(Routes::Grpc(routes)) => {
-                let (permit, _, route) = try_fut!(self.authorize(&routes, &req));
+                let (permit, _, route) = try_fut!(self.authorize(&routes, &mut req));
                 try_fut!(apply_grpc_filters(route, &mut req));
                 permit
             }
@@ -186,10 +193,13 @@ impl<T, N> HttpPolicyService<T, N> {
     /// Finds a matching route for the given request and checks that a
     /// sufficient authorization is present, returning a permit describing the
     /// authorization.
+    ///
+    /// If the route requires a JWT-SVID, its SPIFFE ID is recorded as a
+    /// request extension and may satisfy identity-based authorizations.
     fn authorize<'m, M: super::route::Match + 'm, P, B>(
         &self,
         routes: &'m [super::route::Route<M, RoutePolicy<P>>],
-        req: &::http::Request<B>,
+        req: &mut ::http::Request<B>,
     ) -> Result<(HttpRoutePermit, RouteMatch<M::Summary>, &'m RoutePolicy<P>)> {
         let (r#match, route) =
             super::route::find(routes, req).ok_or_else(|| self.mk_route_not_found())?;
@@ -199,11 +209,40 @@ impl<T, N> HttpPolicyService<T, N> {
             server: self.policy.server_label(),
         };
 
-        let authz = match route
-            .authorizations
-            .iter()
-            .find(|a| super::is_authorized(a, self.connection.client, &self.connection.tls))
-        {
+        let jwt_svid = match self.jwt_svid.as_ref() {
+            Some(jwt) if jwt.is_required(route.meta.name()) => match jwt.validate(req.headers()) {
+                Ok(id) => {
+                    tracing::debug!(id = %id.0, "Validated JWT-SVID");
+                    req.extensions_mut().insert(id.clone());
+                    Some(id)
+                }
+                Err(error) => {
+                    tracing::info!(
+                        server.group = %labels.server.0.group(),
+                        server.kind = %labels.server.0.kind(),
+                        server.name = %labels.server.0.name(),
+                        route.group = %labels.route.group(),
+                        route.kind = %labels.route.kind(),
+                        route.name = %labels.route.name(),
+                        client.tls = ?self.connection.tls,
+                        client.ip = %self.connection.client.ip(),
+                        %error,
+                        "Request denied",
+                    );
+                    self.metrics
+                        .deny(labels, self.connection.dst, self.connection.tls.clone());
+                    return Err(error.into());
+                }
+            },
+            _ => None,
+        };
+
+        let authz = match route.authorizations.iter().find(|a| {
+            super::is_authorized(a, self.connection.client, &self.connection.tls)
+                || jwt_svid
+                    .as_ref()
+                    .is_some_and(|id| super::is_jwt_svid_authorized(a, self.connection.client, id))
+        }) {
             Some(authz) => {
                 if authz.meta.is_audit() {
                     tracing::info!(
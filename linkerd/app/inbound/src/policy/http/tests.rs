@@ -39,6 +39,7 @@ macro_rules! new_svc {
             policy,
             connection: $conn,
             metrics: HttpAuthzMetrics::default(),
+            jwt_svid: None,
             inner: |(permit, _): (HttpRoutePermit, ())| {
                 let f = $rsp;
                 svc::mk(move |req: ::http::Request<BoxBody>| {
@@ -360,6 +361,99 @@ async fn http_filter_inject_failure() {
     );
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn http_route_jwt_svid() {
+    use crate::policy::{HttpRouteJwtSvidRequired, JwtSvidPolicy};
+    use linkerd_app_core::identity::client::spire::jwt::{JwtBundles, Validator};
+    use linkerd_proxy_server_policy::http::{r#match::MatchRequest, Policy, Route, Rule};
+
+    let rule = |method: ::http::Method, route: &str| Rule {
+        matches: vec![MatchRequest {
+            method: Some(method),
+            ..MatchRequest::default()
+        }],
+        policy: Policy {
+            authorizations: Arc::new([Authorization {
+                authentication: Authentication::Unauthenticated,
+                networks: vec![std::net::IpAddr::from([192, 168, 3, 3]).into()],
+                meta: Arc::new(Meta::Resource {
+                    group: "policy.linkerd.io".into(),
+                    kind: "AuthorizationPolicy".into(),
+                    name: "test".into(),
+                }),
+            }]),
+            filters: vec![],
+            meta: Arc::new(Meta::Resource {
+                group: "gateway.networking.k8s.io".into(),
+                kind: "httproute".into(),
+                name: route.into(),
+            }),
+        },
+    };
+    let proto = Protocol::Http1(Arc::new([Route {
+        hosts: vec![],
+        rules: vec![
+            rule(::http::Method::GET, "jwtrt"),
+            rule(::http::Method::POST, "testrt"),
+        ],
+    }]));
+    let inner = |_: HttpRoutePermit, req: ::http::Request<BoxBody>| {
+        let mut rsp = ::http::Response::builder()
+            .body(BoxBody::default())
+            .unwrap();
+        if let Some(authz) = req.headers().get(::http::header::AUTHORIZATION) {
+            rsp.headers_mut()
+                .insert(::http::header::AUTHORIZATION, authz.clone());
+        }
+        Ok::<_, Infallible>(rsp)
+    };
+    let (mut svc, _tx) = new_svc!(proto, conn!(), inner);
+    let (_bundles_tx, bundles) = tokio::sync::watch::channel(JwtBundles::default());
+    svc.jwt_svid = Some(JwtSvidPolicy::new(
+        Validator::new("test".into(), bundles),
+        ["jwtrt".to_string()],
+    ));
+
+    let req = |method: ::http::Method, authz: Option<&str>| {
+        let mut req = ::http::Request::builder().method(method);
+        if let Some(authz) = authz {
+            req = req.header(::http::header::AUTHORIZATION, authz);
+        }
+        req.body(BoxBody::default()).unwrap()
+    };
+
+    // Routes that do not require JWT-SVIDs forward other bearer tokens.
+    let rsp = svc
+        .call(req(::http::Method::POST, Some("Bearer opaque")))
+        .await
+        .expect("serves");
+    assert_eq!(
+        rsp.headers().get(::http::header::AUTHORIZATION).unwrap(),
+        "Bearer opaque"
+    );
+    svc.call(req(::http::Method::POST, None))
+        .await
+        .expect("serves");
+
+    // Routes that require JWT-SVIDs fail requests that do not bear one.
+    let err = svc
+        .call(req(::http::Method::GET, None))
+        .await
+        .expect_err("fails");
+    assert!(matches!(
+        err.downcast_ref::<HttpRouteJwtSvidRequired>(),
+        Some(HttpRouteJwtSvidRequired::Missing)
+    ));
+    let err = svc
+        .call(req(::http::Method::GET, Some("Bearer opaque")))
+        .await
+        .expect_err("fails");
+    assert!(matches!(
+        err.downcast_ref::<HttpRouteJwtSvidRequired>(),
+        Some(HttpRouteJwtSvidRequired::Invalid(_))
+    ));
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn rate_limit_allow() {
     use linkerd_app_core::{Ipv4Net, Ipv6Net};
@@ -0,0 +1,61 @@
+use linkerd_app_core::identity::{client::spire::jwt::Validator, Id};
+use std::{collections::HashSet, sync::Arc};
+
+pub use linkerd_app_core::identity::client::spire::jwt::InvalidJwtSvid;
+
+/// Requires that requests on the named inbound routes bear a JWT-SVID.
+///
+/// Bearer tokens on other routes are not inspected, so applications may use
+/// their own tokens there.
+#[derive(Clone, Debug)]
+pub struct JwtSvidPolicy {
+    validator: Validator,
+    routes: Arc<HashSet<String>>,
+}
+
+/// The SPIFFE ID of the JWT-SVID borne by a request, set as a request
+/// extension once the token has been validated.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct JwtSvidId(pub Id);
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpRouteJwtSvidRequired {
+    #[error("route requires a JWT-SVID")]
+    Missing,
+
+    #[error("invalid JWT-SVID: {0}")]
+    Invalid(#[source] InvalidJwtSvid),
+}
+
+// === impl JwtSvidPolicy ===
+
+impl JwtSvidPolicy {
+    pub fn new(validator: Validator, routes: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            validator,
+            routes: Arc::new(routes.into_iter().collect()),
+        }
+    }
+
+    /// Returns true if requests on the named route must bear a JWT-SVID.
+    pub(super) fn is_required(&self, route: &str) -> bool {
+        self.routes.contains(route)
+    }
+
+    /// Validates the JWT-SVID borne by a request, returning its SPIFFE ID.
+    pub(super) fn validate(
+        &self,
+        headers: &::http::HeaderMap,
+    ) -> Result<JwtSvidId, HttpRouteJwtSvidRequired> {
+        let token = headers
+            .get(::http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or(HttpRouteJwtSvidRequired::Missing)?;
+        let id = self
+            .validator
+            .validate(token.trim())
+            .map_err(HttpRouteJwtSvidRequired::Invalid)?;
+        Ok(JwtSvidId(id))
+    }
+}
@@ -0,0 +1,135 @@
+use crate::Inbound;
+use linkerd_app_core::{
+    io,
+    proxy_protocol::{self, Header},
+    svc,
+    transport::addrs::{ClientAddr, OrigDstAddr, Remote},
+    Error,
+};
+use std::fmt::Debug;
+
+/// An accepted connection, as described by an optional PROXY protocol header.
+#[derive(Clone, Debug)]
+pub(crate) struct Proxied<T> {
+    header: Option<Header>,
+    parent: T,
+}
+
+// === impl Inbound ===
+
+impl<N> Inbound<N> {
+    /// Builds a stack that decodes PROXY protocol headers on connections to the
+    /// configured ports so that the inner stack observes the original client
+    /// address.
+    pub(crate) fn push_proxy_protocol<T, I, NSvc>(self) -> Inbound<svc::ArcNewTcp<T, I>>
+    where
+        T: svc::Param<Remote<ClientAddr>> + svc::Param<OrigDstAddr>,
+        T: Clone + Send + 'static,
+        I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
+        N: svc::NewService<Proxied<T>, Service = NSvc> + Clone + Send + Sync + 'static,
+        NSvc: svc::Service<proxy_protocol::Io<I>, Response = ()> + Send + 'static,
+        NSvc::Error: Into<Error>,
+        NSvc::Future: Send,
+    {
+        self.map_stack(|cfg, _, accept| {
+            let ports = cfg.proxy_protocol_ports.clone();
+            let timeout = proxy_protocol::Timeout(cfg.proxy.detect_protocol_timeout);
+            accept
+                .push_map_target(|(header, parent)| Proxied { header, parent })
+                .push(proxy_protocol::NewDetectHeader::layer(move |t: &T| {
+                    let OrigDstAddr(addr) = t.param();
+                    ports.contains(&addr.port()).then_some(timeout)
+                }))
+                .arc_new_tcp()
+        })
+    }
+}
+
+// === impl Proxied ===
+
+impl<T> svc::Param<Remote<ClientAddr>> for Proxied<T>
+where
+    T: svc::Param<Remote<ClientAddr>>,
+{
+    fn param(&self) -> Remote<ClientAddr> {
+        self.header
+            .and_then(|h| h.client_addr())
+            .map(|addr| Remote(ClientAddr(addr)))
+            .unwrap_or_else(|| self.parent.param())
+    }
+}
+
+impl<T> svc::Param<OrigDstAddr> for Proxied<T>
+where
+    T: svc::Param<OrigDstAddr>,
+{
+    fn param(&self) -> OrigDstAddr {
+        self.parent.param()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+    use linkerd_app_core::svc::{NewService, ServiceExt};
+    use std::sync::Arc;
+
+    #[derive(Clone, Debug)]
+    struct Target(u16);
+
+    impl svc::Param<Remote<ClientAddr>> for Target {
+        fn param(&self) -> Remote<ClientAddr> {
+            Remote(ClientAddr(([192, 0, 2, 3], 50000).into()))
+        }
+    }
+
+    impl svc::Param<OrigDstAddr> for Target {
+        fn param(&self) -> OrigDstAddr {
+            OrigDstAddr(([192, 0, 2, 2], self.0).into())
+        }
+    }
+
+    fn inbound(ports: impl IntoIterator<Item = u16>) -> Inbound<()> {
+        let mut config = test_util::default_config();
+        config.proxy_protocol_ports = Arc::new(ports.into_iter().map(|p| p..=p).collect());
+        let (rt, _) = test_util::runtime();
+        Inbound::new(config, rt, &mut Default::default())
+    }
+
+    async fn client_addr(inbound: Inbound<()>, target: Target) -> Remote<ClientAddr> {
+        let (client_io, server_io) = io::duplex(100);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let svc = inbound
+            .with_stack(move |t: Proxied<Target>| {
+                let addr: Remote<ClientAddr> = svc::Param::param(&t);
+                let _ = tx.send(addr);
+                svc::mk(|_| futures::future::ok::<(), Error>(()))
+            })
+            .push_proxy_protocol()
+            .into_inner()
+            .new_service(target);
+
+        let mut client_io = client_io;
+        tokio::io::AsyncWriteExt::write_all(
+            &mut client_io,
+            b"PROXY TCP4 198.51.100.7 192.0.2.2 40000 8080\r\nhello",
+        )
+        .await
+        .unwrap();
+        svc.oneshot(server_io).await.expect("must succeed");
+        rx.recv().await.expect("target must be built")
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn uses_header_client_addr() {
+        let addr = client_addr(inbound([8080]), Target(8080)).await;
+        assert_eq!(addr, Remote(ClientAddr(([198, 51, 100, 7], 40000).into())));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn ignores_header_on_other_ports() {
+        let addr = client_addr(inbound([8081]), Target(8080)).await;
+        assert_eq!(addr, Remote(ClientAddr(([192, 0, 2, 3], 50000).into())));
+    }
+}
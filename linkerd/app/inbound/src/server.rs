@@ -3,7 +3,7 @@ use linkerd_app_core::{
     exp_backoff::ExponentialBackoff,
     io, profiles,
     proxy::http,
-    svc,
+    proxy_protocol, svc,
     transport::{self, addrs::*},
     Error,
 };
@@ -44,14 +44,17 @@ impl Inbound<()> {
         addr: Local<ServerAddr>,
         policies: impl policy::GetPolicy + Clone + Send + Sync + 'static,
         profiles: P,
-        gateway: svc::ArcNewTcp<direct::GatewayTransportHeader, direct::GatewayIo<I>>,
+        gateway: svc::ArcNewTcp<
+            direct::GatewayTransportHeader,
+            direct::GatewayIo<proxy_protocol::Io<I>>,
+        >,
     ) -> svc::ArcNewTcp<A, I>
     where
         A: svc::Param<Remote<ClientAddr>>,
         A: svc::Param<OrigDstAddr>,
         A: svc::Param<AddrPair>,
         A: Clone + Send + Sync + 'static,
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Unpin + Send + Sync + 'static,
         P: profiles::GetProfile<Error = Error>,
     {
@@ -101,6 +104,7 @@ impl Inbound<()> {
         http.push_http_tcp_server()
             .push_detect(detect_metrics, forward)
             .push_accept(addr.port(), policies, direct)
+            .push_proxy_protocol()
             .into_inner()
     }
 }
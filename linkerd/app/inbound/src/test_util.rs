@@ -59,6 +59,7 @@ pub fn default_config() -> Config {
         proxy: config::ProxyConfig {
             server: config::ServerConfig {
                 addr: DualListenAddr(([0, 0, 0, 0], 0).into(), None),
+                accept_shards: Default::default(),
                 keepalive: Keepalive(None),
                 user_timeout: UserTimeout(None),
                 http2: h2::ServerParams::default(),
@@ -90,13 +91,21 @@ pub fn default_config() -> Config {
         discovery_idle_timeout: Duration::from_secs(20),
         profile_skip_timeout: Duration::from_secs(1),
         unsafe_authority_labels: false,
+        proxy_protocol_ports: Default::default(),
+        original_client_senders: Default::default(),
+        unix_sockets: Default::default(),
+        trace_sampler: Default::default(),
+        trace_formats: Default::default(),
+        jwt_svid: None,
+        forward_client_cert: false,
     }
 }
 
 pub fn runtime() -> (ProxyRuntime, drain::Signal) {
     let (drain_tx, drain) = drain::channel();
     let (tap, _) = tap::new();
-    let (metrics, _) = metrics::Metrics::new(std::time::Duration::from_secs(10));
+    let (metrics, _) =
+        metrics::Metrics::new(std::time::Duration::from_secs(10), Default::default());
     let runtime = ProxyRuntime {
         identity: rustls::creds::default_for_test().1.into(),
         metrics: metrics.proxy,
@@ -35,6 +35,9 @@ ee() {
   openssl pkcs8 -topk8 -nocrypt -inform pem -outform der \
     -in "${ee_name}-key.pem" \
     -out "${dir_name}/key.p8"
+  openssl pkcs8 -topk8 -nocrypt -inform pem -outform pem \
+    -in "${ee_name}-key.pem" \
+    -out "${dir_name}/key.pem"
   openssl req -inform pem -outform der \
     -in "${ee_name}.csr" \
     -out "${dir_name}/csr.der"
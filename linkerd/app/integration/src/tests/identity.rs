@@ -234,6 +234,36 @@ async fn refresh() {
     assert_eventually!(refreshed.load(Ordering::SeqCst));
 }
 
+#[tokio::test]
+async fn loads_identity_from_files() {
+    let _trace = trace_init();
+    let id = "foo.ns1.serviceaccount.identity.linkerd.cluster.local";
+    let identity::Identity { mut env, .. } = identity::Identity::new("foo-ns1", id.to_string());
+
+    // The identity service never issues a certificate, so the proxy can only
+    // become ready by loading its credentials from files.
+    let (_tx, rx) = oneshot::channel();
+    let id_svc = controller::identity()
+        .certify_async(move |_| rx)
+        .run()
+        .await;
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/data/foo-ns1");
+    let path = |name: &str| dir.join(name).into_os_string().into_string().unwrap();
+    env.put(app::env::ENV_IDENTITY_CERT_PATH, path("ca1-cert.pem"));
+    env.put(app::env::ENV_IDENTITY_KEY_PATH, path("key.pem"));
+    let proxy = proxy::new().identity(id_svc).run_with_test_env(env).await;
+
+    let client = client::http1(proxy.admin, "localhost");
+    let ready = || async {
+        client
+            .request(client.request_builder("/ready").method("GET"))
+            .await
+            .unwrap()
+    };
+    assert_eventually!(ready().await.status() == http::StatusCode::OK);
+}
+
 mod require_id_header {
     macro_rules! generate_tests {
         (
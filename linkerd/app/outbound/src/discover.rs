@@ -198,6 +198,7 @@ pub fn synthesize_forward_policy(
             meta: meta.clone(),
             queue,
             dispatcher: policy::BackendDispatcher::Forward(addr, metadata),
+            tls_origination: None,
         },
     )
 }
@@ -221,6 +222,7 @@ pub fn synthesize_balance_policy(
                     path: addr.to_string(),
                 },
             ),
+            tls_origination: None,
         },
     )
 }
@@ -1,7 +1,7 @@
 use self::require_id_header::NewRequireIdentity;
 use crate::Outbound;
 use linkerd_app_core::{
-    metrics::prom,
+    metrics::{prom, CardinalityLimits},
     proxy::{
         api_resolve::{ConcreteAddr, Metadata},
         core::Resolve,
@@ -22,8 +22,11 @@ mod require_id_header;
 mod retry;
 mod server;
 
-pub use self::logical::{policy, profile, LogicalAddr, Routes};
 pub(crate) use self::require_id_header::IdentityRequired;
+pub use self::{
+    endpoint::TlsOrigination,
+    logical::{policy, profile, LogicalAddr, Routes},
+};
 pub use linkerd_app_core::proxy::http::{self as http, *};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -130,14 +133,26 @@ where
 // === impl HttpMetrics ===
 
 impl HttpMetrics {
-    pub fn register(registry: &mut prom::Registry) -> Self {
+    pub fn register(
+        registry: &mut prom::Registry,
+        histograms: &policy::RouteHistograms,
+        limits: &CardinalityLimits,
+    ) -> Self {
         let http = registry.sub_registry_with_prefix("http");
-        let http_route = policy::HttpRouteMetrics::register(http.sub_registry_with_prefix("route"));
+        let http_route = policy::HttpRouteMetrics::register(
+            http.sub_registry_with_prefix("route"),
+            histograms,
+            &limits.scoped("http_route"),
+        );
         let balancer =
             concrete::BalancerMetrics::register(http.sub_registry_with_prefix("balancer"));
 
         let grpc = registry.sub_registry_with_prefix("grpc");
-        let grpc_route = policy::GrpcRouteMetrics::register(grpc.sub_registry_with_prefix("route"));
+        let grpc_route = policy::GrpcRouteMetrics::register(
+            grpc.sub_registry_with_prefix("route"),
+            histograms,
+            &limits.scoped("grpc_route"),
+        );
 
         Self {
             balancer,
@@ -20,7 +20,7 @@ use linkerd_app_core::{
     transport::{self, addrs::*},
     Error, Infallible, NameAddr, Result,
 };
-use linkerd_proxy_client_policy::FailureAccrual;
+use linkerd_proxy_client_policy::{self as policy, FailureAccrual};
 use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 use tracing::info_span;
 
@@ -161,6 +161,15 @@ impl<T> svc::Param<Remote<ServerAddr>> for Endpoint<T> {
     }
 }
 
+impl<T> svc::Param<Option<policy::TlsOrigination>> for Endpoint<T>
+where
+    T: svc::Param<Option<policy::TlsOrigination>>,
+{
+    fn param(&self) -> Option<policy::TlsOrigination> {
+        self.parent.param()
+    }
+}
+
 impl<T> svc::Param<svc::queue::Capacity> for Endpoint<T> {
     fn param(&self) -> svc::queue::Capacity {
         svc::queue::Capacity(self.queue.capacity)
@@ -317,6 +326,18 @@ where
     }
 }
 
+impl<T> svc::Param<http::h1::PoolSettings> for Endpoint<T> {
+    fn param(&self) -> http::h1::PoolSettings {
+        self.http1
+    }
+}
+
+impl<T> svc::Param<http::h2::ClientParams> for Endpoint<T> {
+    fn param(&self) -> http::h2::ClientParams {
+        self.http2.clone()
+    }
+}
+
 impl<T> svc::Param<ProtocolHint> for Endpoint<T> {
     fn param(&self) -> ProtocolHint {
         self.metadata.protocol_hint()
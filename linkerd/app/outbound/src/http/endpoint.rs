@@ -4,20 +4,23 @@ use super::{
     handle_proxy_error_headers::{self, NewHandleProxyErrorHeaders},
     NewRequireIdentity,
 };
-use crate::{tcp::tagged_transport, zone::TcpZoneLabels, Outbound};
+use crate::{policy, tcp::tagged_transport, zone::TcpZoneLabels, Outbound};
 use linkerd_app_core::{
     classify, config, errors, http_tracing, metrics,
     proxy::{api_resolve::ProtocolHint, http, tap},
     svc::{self, ExtractParam},
     tls,
     transport::{self, Remote, ServerAddr},
-    transport_header::SessionProtocol,
+    transport_header::{OriginalClient, SessionProtocol},
     Error, Result, CANONICAL_DST_HEADER,
 };
 
+mod originate;
 #[cfg(test)]
 mod tests;
 
+pub use self::originate::TlsOrigination;
+
 #[derive(Clone, Debug)]
 pub struct Connect<T> {
     version: http::Variant,
@@ -42,24 +45,37 @@ impl<C> Outbound<C> {
     where
         // Http endpoint target.
         T: svc::Param<http::client::Params>,
+        T: svc::Param<http::h1::PoolSettings>,
+        T: svc::Param<http::h2::ClientParams>,
+        T: svc::Param<Option<policy::TlsOrigination>>,
+        T: svc::Param<tls::ConditionalClientTls>,
         T: Clone + Send + Sync + 'static,
         // Http endpoint body.
         B: http::Body<Error = Error> + std::fmt::Debug + Default + Unpin + Send + 'static,
         B::Data: Send + 'static,
         // TCP endpoint stack.
         C: svc::MakeConnection<Connect<T>> + Clone + Send + Sync + Unpin + 'static,
-        C::Connection: Send + Unpin,
-        C::Metadata: Send + Unpin,
+        C::Connection: Send + Unpin + 'static,
+        C::Metadata: Send + Unpin + 'static,
         C::Future: Send + Unpin + 'static,
     {
-        self.map_stack(|_, _, inner| {
+        self.map_stack(|config, _, inner| {
+            let originate = config.tls_origination.clone();
+
             // Initiates an HTTP client on the underlying transport. Prior-knowledge HTTP/2
             // is typically used (i.e. when communicating with other proxies); though
             // HTTP/1.x fallback is supported as needed.
-            svc::stack(inner.into_inner().into_service())
+            let connect = inner
+                // Upgrades connections to backends outside of the mesh to TLS
+                // when so configured.
+                .push(tls::Originate::layer_via(originate.clone(), originate))
+                .into_inner();
+            svc::stack(svc::MakeConnection::into_service(connect))
                 .check_service::<Connect<T>>()
                 .push_map_target(|(version, inner)| Connect { version, inner })
-                .push(http::client::layer())
+                // Clients for backends outside of the mesh speak the protocol
+                // negotiated with the server.
+                .push(originate::MakeClient::layer())
                 .push_on_service(svc::MapErr::layer_boxed())
                 .check_service::<T>()
                 .into_new_service()
@@ -110,6 +126,9 @@ impl<T> Outbound<svc::ArcNewHttp<T, http::BoxBody>> {
                 // double-counted--i.e., endpoint metrics track these responses and error metrics
                 // track proxy errors that occur higher in the stack.
                 .push(ClientRescue::layer(config.emit_headers))
+                // Describe the endpoint on responses so that it may be recorded
+                // in access logs.
+                .push_http_response_insert_target::<Remote<ServerAddr>>()
                 .push(tap::NewTapHttp::layer(rt.tap.clone()))
                 .push(
                     rt.metrics
@@ -117,8 +136,13 @@ impl<T> Outbound<svc::ArcNewHttp<T, http::BoxBody>> {
                         .http_endpoint
                         .to_layer::<classify::Response, _, _>(),
                 )
+                // Describe the endpoint on the client span.
+                .push(http_tracing::NewSpanAttributes::layer_via(
+                    span_attributes::<T>,
+                ))
                 .push_on_service(http_tracing::client(
                     rt.span_sink.clone(),
+                    config.trace_formats.clone(),
                     crate::trace_labels(),
                 ))
                 .push(NewRequireIdentity::layer())
@@ -133,6 +157,22 @@ impl<T> Outbound<svc::ArcNewHttp<T, http::BoxBody>> {
     }
 }
 
+/// Describes the endpoint's address and identity on client spans.
+fn span_attributes<T>(target: &T) -> http_tracing::TargetAttributes
+where
+    T: svc::Param<Remote<ServerAddr>>,
+    T: svc::Param<tls::ConditionalClientTls>,
+{
+    let Remote(ServerAddr(addr)) = target.param();
+    let tls: tls::ConditionalClientTls = target.param();
+    std::iter::once(("linkerd.endpoint.address", addr.to_string()))
+        .chain(
+            tls.value()
+                .map(|tls| ("linkerd.endpoint.identity", tls.server_id.to_string())),
+        )
+        .collect()
+}
+
 // === impl ClientRescue ===
 
 impl ClientRescue {
@@ -197,6 +237,15 @@ where
     }
 }
 
+impl<T> svc::Param<Option<OriginalClient>> for Connect<T> {
+    #[inline]
+    fn param(&self) -> Option<OriginalClient> {
+        // HTTP connections are shared by requests from many clients, so they
+        // cannot describe an original client.
+        None
+    }
+}
+
 impl<T: svc::Param<Remote<ServerAddr>>> svc::Param<Remote<ServerAddr>> for Connect<T> {
     #[inline]
     fn param(&self) -> Remote<ServerAddr> {
@@ -0,0 +1,488 @@
+use super::Connect;
+use crate::policy;
+use futures::prelude::*;
+use linkerd_app_core::{
+    identity::web_pki,
+    io,
+    proxy::http,
+    svc::{self, Layer, MakeConnection, NewService, Param, Service, ServiceExt},
+    tls, Error, Result,
+};
+use parking_lot::Mutex;
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tracing::debug;
+
+/// Configures the proxy to originate TLS to backends outside of the mesh.
+///
+/// Backends are configured for origination by the client policy (see
+/// [`policy::TlsOrigination`]). Applications send plaintext HTTP to these
+/// backends and the proxy upgrades connections to TLS, authenticating servers
+/// with web PKI rather than mesh identity. Endpoints with a mesh identity are
+/// never upgraded.
+#[derive(Clone, Debug, Default)]
+pub struct TlsOrigination {
+    client: Option<web_pki::NewClient>,
+}
+
+/// Upgrades a connection to TLS for a server outside of the mesh.
+///
+/// Connections fail if origination was not configured with a client.
+#[derive(Clone)]
+pub struct ConnectTls(Option<web_pki::Connect>);
+
+/// Builds HTTP clients for endpoints.
+///
+/// Clients for endpoints outside of the mesh speak the protocol that the
+/// server chose via ALPN, which is learned by establishing a connection
+/// before the client is built. All other clients are built as configured by
+/// the endpoint's [`http::client::Params`].
+pub struct MakeClient<C, B> {
+    connect: C,
+    _marker: PhantomData<fn(B)>,
+}
+
+pub enum Client<C, T, B> {
+    Mesh(http::client::Client<C, T, B>),
+    Originated {
+        client: svc::BoxHttp<B>,
+        is_h2: bool,
+    },
+}
+
+/// Connects to a server outside of the mesh, first handing out the connection
+/// that was established to negotiate the protocol.
+struct Negotiated<C, I, M> {
+    connect: C,
+    first: Arc<Mutex<Option<(I, M)>>>,
+    protocol: Option<tls::NegotiatedProtocol>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("server negotiated a different protocol than on its first connection")]
+pub struct ProtocolChanged(());
+
+const H2: &[u8] = b"h2";
+const HTTP1: &[u8] = b"http/1.1";
+
+/// Returns the name of the server outside of the mesh to which the target's
+/// connections are upgraded to TLS, if any.
+fn server_name<T>(target: &T) -> Option<tls::ServerName>
+where
+    T: Param<Option<policy::TlsOrigination>>,
+    T: Param<tls::ConditionalClientTls>,
+{
+    let policy::TlsOrigination { server_name } =
+        Param::<Option<policy::TlsOrigination>>::param(target)?;
+
+    // Endpoints that are known to be meshed or that are local to this proxy
+    // are never upgraded.
+    match target.param() {
+        tls::ConditionalClientTls::Some(_)
+        | tls::ConditionalClientTls::None(tls::NoClientTls::Loopback) => None,
+        tls::ConditionalClientTls::None(_) => Some(server_name.into()),
+    }
+}
+
+// === impl TlsOrigination ===
+
+impl TlsOrigination {
+    /// Originates TLS with `client` to the backends that are so configured.
+    pub fn new(client: web_pki::NewClient) -> Self {
+        Self {
+            client: Some(client),
+        }
+    }
+}
+
+impl<T> svc::ExtractParam<Option<tls::OriginateTls>, Connect<T>> for TlsOrigination
+where
+    T: Param<Option<policy::TlsOrigination>>,
+    T: Param<tls::ConditionalClientTls>,
+{
+    fn extract_param(&self, target: &Connect<T>) -> Option<tls::OriginateTls> {
+        let server_name = server_name(&target.inner)?;
+
+        // Both protocols are offered so that the server may choose one it
+        // supports, though the target's own protocol is preferred.
+        let protocols = match target.version {
+            http::Variant::H2 => [H2, HTTP1],
+            http::Variant::Http1 => [HTTP1, H2],
+        };
+        Some(tls::OriginateTls {
+            server_name,
+            alpn: Some(tls::client::AlpnProtocols(
+                protocols.iter().map(|p| p.to_vec()).collect(),
+            )),
+        })
+    }
+}
+
+impl NewService<tls::OriginateTls> for TlsOrigination {
+    type Service = ConnectTls;
+
+    fn new_service(&self, target: tls::OriginateTls) -> Self::Service {
+        ConnectTls(self.client.as_ref().map(|c| c.new_service(target)))
+    }
+}
+
+// === impl ConnectTls ===
+
+impl<I> Service<I> for ConnectTls
+where
+    web_pki::Connect: Service<I, Error = io::Error>,
+{
+    type Response = <web_pki::Connect as Service<I>>::Response;
+    type Error = io::Error;
+    type Future = future::Either<
+        <web_pki::Connect as Service<I>>::Future,
+        future::Ready<io::Result<Self::Response>>,
+    >;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.0 {
+            Some(ref mut connect) => connect.poll_ready(cx),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, io: I) -> Self::Future {
+        let Some(ref mut connect) = self.0 else {
+            return future::Either::Right(future::err(io::Error::other(
+                "TLS origination is not configured with a client",
+            )));
+        };
+        future::Either::Left(connect.call(io))
+    }
+}
+
+// === impl MakeClient ===
+
+impl<C, B> MakeClient<C, B> {
+    pub fn layer() -> impl svc::layer::Layer<C, Service = Self> + Clone {
+        svc::layer::mk(|connect| Self {
+            connect,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<C: Clone, B> Clone for MakeClient<C, B> {
+    fn clone(&self) -> Self {
+        Self {
+            connect: self.connect.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+type MakeFuture<C, T, B> = Pin<Box<dyn Future<Output = Result<Client<C, T, B>>> + Send + 'static>>;
+
+impl<C, M, T, B> Service<T> for MakeClient<C, B>
+where
+    T: Param<http::client::Params>,
+    T: Param<http::h1::PoolSettings>,
+    T: Param<http::h2::ClientParams>,
+    T: Param<Option<policy::TlsOrigination>>,
+    T: Param<tls::ConditionalClientTls>,
+    T: Clone + Send + Sync + 'static,
+    C: MakeConnection<(http::Variant, T), Metadata = (M, Option<tls::NegotiatedProtocol>)>,
+    C: Clone + Unpin + Send + Sync + 'static,
+    C::Connection: Unpin + Send + 'static,
+    C::Future: Unpin + Send + 'static,
+    M: Send + 'static,
+    B: http::Body + Send + Unpin + 'static,
+    B::Data: Send,
+    B::Error: Into<Error> + Send + Sync,
+{
+    type Response = Client<C, T, B>;
+    type Error = Error;
+    type Future = MakeFuture<C, T, B>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        let mut connect = self.connect.clone();
+
+        if server_name(&target).is_none() {
+            let client = http::client::layer().layer(connect).oneshot(target);
+            return Box::pin(client.map_ok(Client::Mesh));
+        }
+
+        let version = match target.param() {
+            http::client::Params::H2(_) => http::Variant::H2,
+            _ => http::Variant::Http1,
+        };
+        Box::pin(async move {
+            // The server chooses the protocol during the TLS handshake, so a
+            // connection must be established before the client is built.
+            let (io, (meta, protocol)) = connect
+                .connect((version, target.clone()))
+                .await
+                .map_err(Into::into)?;
+            let is_h2 = matches!(protocol, Some(tls::NegotiatedProtocol(ref p)) if p == H2);
+            debug!(is_h2, "Building HTTP client for server outside of the mesh");
+            let params = if is_h2 {
+                http::client::Params::H2(target.param())
+            } else {
+                http::client::Params::Http1(target.param())
+            };
+
+            let negotiated = Negotiated {
+                connect,
+                first: Arc::new(Mutex::new(Some((io, (meta, protocol.clone()))))),
+                protocol,
+            };
+            let client = http::client::layer_via(svc::CloneParam::from(params))
+                .layer(negotiated)
+                .oneshot(target)
+                .await?;
+            Ok(Client::Originated {
+                client: svc::BoxHttp::new(client),
+                is_h2,
+            })
+        })
+    }
+}
+
+// === impl Client ===
+
+type RspFuture = Pin<Box<dyn Future<Output = Result<http::Response<http::BoxBody>>> + Send>>;
+
+impl<C, T, B> Service<http::Request<B>> for Client<C, T, B>
+where
+    T: Clone + Send + Sync + 'static,
+    C: MakeConnection<(http::Variant, T)> + Clone + Send + Sync + 'static,
+    C::Connection: Unpin + Send,
+    C::Future: Unpin + Send + 'static,
+    B: http::Body + Send + Unpin + 'static,
+    B::Data: Send,
+    B::Error: Into<Error> + Send + Sync,
+{
+    type Response = http::Response<http::BoxBody>;
+    type Error = Error;
+    type Future = RspFuture;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self {
+            Self::Mesh(client) => client.poll_ready(cx),
+            Self::Originated { client, .. } => client.poll_ready(cx),
+        }
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let (client, is_h2) = match self {
+            Self::Mesh(client) => return Box::pin(client.call(req)),
+            Self::Originated { client, is_h2 } => (client, *is_h2),
+        };
+
+        // The application's protocol may differ from the one that the server
+        // chose, so requests are translated and responses are restored to
+        // the application's protocol.
+        let version = req.version();
+        if is_h2 && version != ::http::Version::HTTP_2 {
+            http::upgrade::strip_connection_headers(req.headers_mut());
+            req.headers_mut().remove(::http::header::TRANSFER_ENCODING);
+            req.headers_mut().remove(::http::header::HOST);
+            *req.version_mut() = ::http::Version::HTTP_2;
+        } else if !is_h2 && version == ::http::Version::HTTP_2 {
+            if let Some(host) = req
+                .uri()
+                .authority()
+                .and_then(|a| ::http::HeaderValue::from_str(a.as_str()).ok())
+            {
+                req.headers_mut()
+                    .entry(::http::header::HOST)
+                    .or_insert(host);
+            }
+            *req.version_mut() = ::http::Version::HTTP_11;
+        }
+
+        Box::pin(client.call(req).map_ok(move |mut rsp| {
+            *rsp.version_mut() = version;
+            rsp
+        }))
+    }
+}
+
+// === impl Negotiated ===
+
+impl<C, M, T> Service<(http::Variant, T)>
+    for Negotiated<C, C::Connection, (M, Option<tls::NegotiatedProtocol>)>
+where
+    C: MakeConnection<(http::Variant, T), Metadata = (M, Option<tls::NegotiatedProtocol>)>,
+    C::Connection: Send + 'static,
+    C::Future: Send + 'static,
+    M: Send + 'static,
+{
+    type Response = (C::Connection, C::Metadata);
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.connect.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, target: (http::Variant, T)) -> Self::Future {
+        if let Some(conn) = self.first.lock().take() {
+            return Box::pin(future::ok(conn));
+        }
+
+        let protocol = self.protocol.clone();
+        let connect = self.connect.connect(target);
+        Box::pin(async move {
+            let (io, (meta, negotiated)) = connect.await.map_err(Into::into)?;
+            if negotiated != protocol {
+                return Err(ProtocolChanged(()).into());
+            }
+            Ok((io, (meta, negotiated)))
+        })
+    }
+}
+
+impl<C: Clone, I, M> Clone for Negotiated<C, I, M> {
+    fn clone(&self) -> Self {
+        Self {
+            connect: self.connect.clone(),
+            first: self.first.clone(),
+            protocol: self.protocol.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_app_core::svc::ExtractParam;
+
+    #[derive(Clone, Debug)]
+    struct Target {
+        origination: Option<policy::TlsOrigination>,
+        tls: tls::ConditionalClientTls,
+    }
+
+    impl Param<Option<policy::TlsOrigination>> for Target {
+        fn param(&self) -> Option<policy::TlsOrigination> {
+            self.origination.clone()
+        }
+    }
+
+    impl Param<tls::ConditionalClientTls> for Target {
+        fn param(&self) -> tls::ConditionalClientTls {
+            self.tls.clone()
+        }
+    }
+
+    fn connect(
+        version: http::Variant,
+        origination: Option<&str>,
+        tls: tls::ConditionalClientTls,
+    ) -> Connect<Target> {
+        Connect {
+            version,
+            inner: Target {
+                origination: origination.map(|n| policy::TlsOrigination {
+                    server_name: n.parse().unwrap(),
+                }),
+                tls,
+            },
+        }
+    }
+
+    #[test]
+    fn originates_to_configured_backends() {
+        let originate = TlsOrigination::default();
+        let no_tls =
+            || tls::ConditionalClientTls::None(tls::NoClientTls::NotProvidedByServiceDiscovery);
+
+        let tls = originate
+            .extract_param(&connect(
+                http::Variant::Http1,
+                Some("api.example.com"),
+                no_tls(),
+            ))
+            .expect("configured backends must originate TLS");
+        assert_eq!(tls.server_name, "api.example.com".parse().unwrap());
+        assert_eq!(
+            tls.alpn,
+            Some(tls::client::AlpnProtocols(vec![
+                b"http/1.1".to_vec(),
+                b"h2".to_vec()
+            ]))
+        );
+
+        let tls = originate
+            .extract_param(&connect(
+                http::Variant::H2,
+                Some("api.example.com"),
+                no_tls(),
+            ))
+            .expect("configured backends must originate TLS");
+        assert_eq!(
+            tls.alpn,
+            Some(tls::client::AlpnProtocols(vec![
+                b"h2".to_vec(),
+                b"http/1.1".to_vec()
+            ]))
+        );
+
+        assert_eq!(
+            originate.extract_param(&connect(http::Variant::Http1, None, no_tls())),
+            None,
+            "other backends must not originate TLS"
+        );
+
+        let mesh = tls::ClientTls::new(
+            tls::ServerId(
+                "api.egress.serviceaccount.identity.linkerd.cluster.local"
+                    .parse()
+                    .unwrap(),
+            ),
+            "api.egress.serviceaccount.identity.linkerd.cluster.local"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            originate.extract_param(&connect(
+                http::Variant::Http1,
+                Some("api.example.com"),
+                tls::ConditionalClientTls::Some(mesh),
+            )),
+            None,
+            "meshed endpoints must not originate TLS"
+        );
+        assert_eq!(
+            originate.extract_param(&connect(
+                http::Variant::Http1,
+                Some("api.example.com"),
+                tls::ConditionalClientTls::None(tls::NoClientTls::Loopback),
+            )),
+            None,
+            "local endpoints must not originate TLS"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_without_client() {
+        let (io, _server) = tokio::io::duplex(64);
+        let target = tls::OriginateTls {
+            server_name: "api.example.com".parse().unwrap(),
+            alpn: None,
+        };
+        TlsOrigination::default()
+            .new_service(target)
+            .oneshot(io)
+            .await
+            .expect_err("connections must fail without a client");
+    }
+}
@@ -5,7 +5,7 @@ use linkerd_app_core::metrics::OutboundZoneLocality;
 use linkerd_app_core::{
     io,
     proxy::api_resolve::ProtocolHint,
-    svc::{http::TokioExecutor, NewService, ServiceExt},
+    svc::{http::TokioExecutor, Layer, NewService, ServiceExt},
     Infallible,
 };
 use linkerd_http_box::BoxBody;
@@ -37,6 +37,7 @@ async fn http11_forward() {
         addr: Remote(ServerAddr(addr)),
         version: http::Variant::Http1,
         hint: ProtocolHint::Unknown,
+        tls_origination: None,
     });
 
     let req = http::Request::builder()
@@ -73,6 +74,7 @@ async fn http2_forward() {
         addr: Remote(ServerAddr(addr)),
         version: http::Variant::H2,
         hint: ProtocolHint::Unknown,
+        tls_origination: None,
     });
 
     let req = http::Request::builder()
@@ -111,6 +113,7 @@ async fn orig_proto_upgrade() {
         addr: Remote(ServerAddr(addr)),
         version: http::Variant::Http1,
         hint: ProtocolHint::Http2,
+        tls_origination: None,
     });
 
     let req = http::Request::builder()
@@ -167,6 +170,7 @@ async fn orig_proto_skipped_on_http_upgrade() {
         addr: Remote(ServerAddr(addr)),
         version: http::Variant::Http1,
         hint: ProtocolHint::Http2,
+        tls_origination: None,
     });
 
     let req = http::Request::builder()
@@ -209,6 +213,7 @@ async fn orig_proto_http2_noop() {
         addr: Remote(ServerAddr(addr)),
         version: http::Variant::H2,
         hint: ProtocolHint::Http2,
+        tls_origination: None,
     });
 
     let req = http::Request::builder()
@@ -224,6 +229,70 @@ async fn orig_proto_http2_noop() {
 
 /// Helper server that reads the l5d-orig-proto header on requests and uses it to set the header
 /// value in `WAS_ORIG_PROTO`.
+/// Tests that clients to servers outside of the mesh speak HTTP/2 when the
+/// server negotiates it, regardless of the application's protocol.
+#[tokio::test(flavor = "current_thread")]
+async fn originated_h2_negotiated() {
+    let _trace = linkerd_tracing::test::trace_init();
+
+    let connect = svc::mk(|_: (http::Variant, Endpoint)| {
+        let alpn = tls::NegotiatedProtocol(b"h2".to_vec());
+        future::ready(serve(::http::Version::HTTP_2).map(|io| (io, ((), Some(alpn)))))
+    });
+    let svc = originate::MakeClient::<_, BoxBody>::layer()
+        .layer(connect)
+        .oneshot(originated_endpoint(http::Variant::Http1))
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .version(::http::Version::HTTP_11)
+        .uri("http://api.example.com")
+        .header(::http::header::HOST, "api.example.com")
+        .body(BoxBody::default())
+        .unwrap();
+    let rsp = svc.oneshot(req).await.unwrap();
+    assert_eq!(rsp.status(), http::StatusCode::NO_CONTENT);
+    assert_eq!(rsp.version(), ::http::Version::HTTP_11);
+}
+
+/// Tests that clients to servers outside of the mesh speak HTTP/1.1 when the
+/// server negotiates it, regardless of the application's protocol.
+#[tokio::test(flavor = "current_thread")]
+async fn originated_http1_negotiated() {
+    let _trace = linkerd_tracing::test::trace_init();
+
+    let connect = svc::mk(|_: (http::Variant, Endpoint)| {
+        let alpn = tls::NegotiatedProtocol(b"http/1.1".to_vec());
+        future::ready(serve(::http::Version::HTTP_11).map(|io| (io, ((), Some(alpn)))))
+    });
+    let svc = originate::MakeClient::<_, BoxBody>::layer()
+        .layer(connect)
+        .oneshot(originated_endpoint(http::Variant::H2))
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .version(::http::Version::HTTP_2)
+        .uri("https://api.example.com")
+        .body(BoxBody::default())
+        .unwrap();
+    let rsp = svc.oneshot(req).await.unwrap();
+    assert_eq!(rsp.status(), http::StatusCode::NO_CONTENT);
+    assert_eq!(rsp.version(), ::http::Version::HTTP_2);
+}
+
+fn originated_endpoint(version: http::Variant) -> Endpoint {
+    Endpoint {
+        addr: Remote(ServerAddr(SocketAddr::new([192, 0, 2, 41].into(), 443))),
+        version,
+        hint: ProtocolHint::Unknown,
+        tls_origination: Some(policy::TlsOrigination {
+            server_name: "api.example.com".parse().unwrap(),
+        }),
+    }
+}
+
 fn serve(version: ::http::Version) -> io::Result<io::BoxedIo> {
     let svc = hyper::service::service_fn(move |req: http::Request<_>| {
         tracing::debug!(?req);
@@ -267,6 +336,7 @@ struct Endpoint {
     addr: Remote<ServerAddr>,
     hint: ProtocolHint,
     version: http::Variant,
+    tls_origination: Option<policy::TlsOrigination>,
 }
 
 // === impl Endpoint ===
@@ -283,6 +353,12 @@ impl svc::Param<http::handle_proxy_error_headers::CloseServerConnection> for End
     }
 }
 
+impl svc::Param<Option<policy::TlsOrigination>> for Endpoint {
+    fn param(&self) -> Option<policy::TlsOrigination> {
+        self.tls_origination.clone()
+    }
+}
+
 impl svc::Param<tls::ConditionalClientTls> for Endpoint {
     fn param(&self) -> tls::ConditionalClientTls {
         tls::ConditionalClientTls::None(tls::NoClientTls::Disabled)
@@ -354,6 +430,21 @@ impl svc::Param<http::client::Params> for Endpoint {
     }
 }
 
+impl svc::Param<http::h1::PoolSettings> for Endpoint {
+    fn param(&self) -> http::h1::PoolSettings {
+        http::h1::PoolSettings {
+            max_idle: 1,
+            idle_timeout: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+impl svc::Param<http::h2::ClientParams> for Endpoint {
+    fn param(&self) -> http::h2::ClientParams {
+        Default::default()
+    }
+}
+
 impl svc::Param<ProtocolHint> for Endpoint {
     fn param(&self) -> ProtocolHint {
         self.hint
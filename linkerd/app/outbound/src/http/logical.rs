@@ -43,6 +43,7 @@ pub struct Concrete<T> {
     parent_ref: ParentRef,
     backend_ref: BackendRef,
     failure_accrual: policy::FailureAccrual,
+    tls_origination: Option<crate::policy::TlsOrigination>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -155,6 +156,7 @@ where
                                     authority: None,
                                     parent,
                                     failure_accrual: Default::default(),
+                                    tls_origination: None,
                                 })
                             }
                             Self::Profile(profile) => {
@@ -257,6 +259,12 @@ impl<T> svc::Param<policy::FailureAccrual> for Concrete<T> {
     }
 }
 
+impl<T> svc::Param<Option<crate::policy::TlsOrigination>> for Concrete<T> {
+    fn param(&self) -> Option<crate::policy::TlsOrigination> {
+        self.tls_origination.clone()
+    }
+}
+
 // === impl CanonicalDstHeader ===
 
 impl From<CanonicalDstHeader> for http::HeaderPair {
@@ -1,7 +1,8 @@
 use super::super::Concrete;
 use crate::{ParentRef, RouteRef};
-use linkerd_app_core::{classify, proxy::http, svc, Addr, Error, Result};
+use linkerd_app_core::{classify, http_tracing, proxy::http, svc, Addr, Error, Result};
 use linkerd_distribute as distribute;
+use linkerd_http_access_log::{NewClientAccessLog, RouteLabels};
 use linkerd_http_route as http_route;
 use linkerd_proxy_client_policy as policy;
 use std::{fmt::Debug, hash::Hash, sync::Arc};
@@ -15,7 +16,8 @@ pub(crate) mod retry;
 pub(crate) use self::backend::{Backend, MatchedBackend};
 pub use self::filters::errors;
 
-pub use self::metrics::{GrpcRouteMetrics, HttpRouteMetrics};
+pub use self::metrics::{GrpcRouteMetrics, HttpRouteMetrics, RouteHistograms};
+pub use linkerd_http_prom::HistogramBuckets;
 
 /// A target type that includes a summary of exactly how a request was matched.
 /// This match state is required to apply route filters.
@@ -131,11 +133,18 @@ where
                 // AND/OR headers
                 .push(extensions::NewSetExtensions::layer())
                 .push(metrics::layer(&metrics.requests, &metrics.body_data))
+                // Record an access log for each logical request, rather than
+                // for each attempt.
+                .push(NewClientAccessLog::layer())
                 .check_new::<Self>()
                 .check_new_service::<Self, http::Request<http::BoxBody>>()
                 // Configure a classifier to use in the endpoint stack.
                 // TODO(ver) move this into NewSetExtensions?
                 .push(classify::NewClassify::layer())
+                // Describe the route on the request's span.
+                .push(http_tracing::NewSpanAttributes::layer_via(|rt: &Self| {
+                    rt.params.route_ref.span_attributes()
+                }))
                 .push(svc::NewMapErr::layer_with(|rt: &Self| {
                     let route = rt.params.route_ref.clone();
                     move |source| RouteError {
@@ -149,12 +158,29 @@ where
     }
 }
 
+impl<T, M, F, P> svc::Param<RouteLabels> for MatchedRoute<T, M, F, P> {
+    fn param(&self) -> RouteLabels {
+        RouteLabels {
+            parent: label(&self.params.parent_ref),
+            route: label(&self.params.route_ref),
+        }
+    }
+}
+
 impl<T: Clone, M, F, P> svc::Param<BackendDistribution<T, F>> for MatchedRoute<T, M, F, P> {
     fn param(&self) -> BackendDistribution<T, F> {
         self.params.distribution.clone()
     }
 }
 
+/// Formats a resource reference for access logs.
+fn label(meta: &policy::Meta) -> Arc<str> {
+    match meta.namespace() {
+        "" => format!("{}/{}", meta.kind(), meta.name()).into(),
+        ns => format!("{}/{}/{}", meta.kind(), ns, meta.name()).into(),
+    }
+}
+
 // === impl Http ===
 
 impl<T> filters::Apply for Http<T> {
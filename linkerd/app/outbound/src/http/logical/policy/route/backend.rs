@@ -1,6 +1,7 @@
 use super::{super::Concrete, filters};
 use crate::{BackendRef, ParentRef, RouteRef};
-use linkerd_app_core::{proxy::http, svc, Error, Result};
+use linkerd_app_core::{http_tracing, proxy::http, svc, Error, Result};
+use linkerd_http_access_log::{BackendLabel, NewDescribeAttempt};
 use linkerd_http_prom::record_response::MkStreamLabel;
 use linkerd_http_route as http_route;
 use linkerd_proxy_client_policy as policy;
@@ -100,6 +101,12 @@ where
                 )
                 .push(filters::NewApplyFilters::<Self, _, _>::layer())
                 .push(metrics::layer(&metrics))
+                // Describe each attempt for the route's access log.
+                .push(NewDescribeAttempt::layer())
+                // Describe the backend on the request's span.
+                .push(http_tracing::NewSpanAttributes::layer_via(|t: &Self| {
+                    t.params.concrete.backend_ref.span_attributes()
+                }))
                 .push(svc::NewMapErr::layer_with(|t: &Self| {
                     let backend = t.params.concrete.backend_ref.clone();
                     move |source| {
@@ -133,6 +140,12 @@ impl<T, M, F> svc::Param<BackendRef> for MatchedBackend<T, M, F> {
     }
 }
 
+impl<T, M, F> svc::Param<BackendLabel> for MatchedBackend<T, M, F> {
+    fn param(&self) -> BackendLabel {
+        BackendLabel(super::label(&self.params.concrete.backend_ref))
+    }
+}
+
 // === impl Http ===
 
 impl<T> filters::Apply for Http<T> {
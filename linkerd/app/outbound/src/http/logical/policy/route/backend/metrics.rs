@@ -1,5 +1,8 @@
 use crate::{BackendRef, ParentRef, RouteRef};
-use linkerd_app_core::{metrics::prom, svc};
+use linkerd_app_core::{
+    metrics::{prom, CardinalityLimits},
+    svc,
+};
 use linkerd_http_prom::{
     body_data::response::{BodyDataMetrics, NewRecordBodyData, ResponseBodyFamilies},
     record_response::{self, NewResponseDuration, StreamLabel},
@@ -79,10 +82,14 @@ pub struct ExtractRecordBodyDataParams(ResponseBodyFamilies<labels::RouteBackend
 // === impl RouteBackendMetrics ===
 
 impl<L: StreamLabel> RouteBackendMetrics<L> {
-    pub fn register(reg: &mut prom::Registry, histo: impl IntoIterator<Item = f64>) -> Self {
-        let requests = RequestCountFamilies::register(reg);
-        let responses = record_response::ResponseMetrics::register(reg, histo);
-        let body_metrics = ResponseBodyFamilies::register(reg);
+    pub fn register(
+        reg: &mut prom::Registry,
+        histo: impl IntoIterator<Item = f64>,
+        limits: &CardinalityLimits,
+    ) -> Self {
+        let requests = RequestCountFamilies::register(reg, limits);
+        let responses = record_response::ResponseMetrics::register(reg, histo, limits);
+        let body_metrics = ResponseBodyFamilies::register(reg, limits);
         Self {
             requests,
             responses,
@@ -101,7 +108,10 @@ impl<L: StreamLabel> RouteBackendMetrics<L> {
     }
 
     #[cfg(test)]
-    pub(crate) fn get_statuses(&self, l: &L::StatusLabels) -> prom::Counter {
+    pub(crate) fn get_statuses(
+        &self,
+        l: &L::StatusLabels,
+    ) -> linkerd_http_prom::record_response::StatusCount {
         self.responses.get_statuses(l)
     }
 
@@ -450,6 +450,7 @@ fn mock_http_route_backend_metrics(
                     ),
                     authority: None,
                     failure_accrual: Default::default(),
+                    tls_origination: None,
                     parent: (),
                     parent_ref: parent_ref.clone(),
                     backend_ref: backend_ref.clone(),
@@ -506,6 +507,7 @@ fn mock_grpc_route_backend_metrics(
                     ),
                     authority: None,
                     failure_accrual: Default::default(),
+                    tls_origination: None,
                     parent: (),
                     parent_ref: parent_ref.clone(),
                     backend_ref: backend_ref.clone(),
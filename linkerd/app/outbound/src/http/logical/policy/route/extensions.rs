@@ -11,10 +11,7 @@ pub struct Params {
     pub allow_l5d_request_headers: bool,
 }
 
-// A request extension that marks the number of times a request has been
-// attempted.
-#[derive(Clone, Debug)]
-pub struct Attempt(pub std::num::NonZeroU16);
+pub use linkerd_http_retry::Attempt;
 
 #[derive(Clone, Debug)]
 pub struct NewSetExtensions<N> {
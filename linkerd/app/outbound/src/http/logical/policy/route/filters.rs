@@ -72,6 +72,10 @@ pub mod errors {
     #[derive(Debug, thiserror::Error)]
     #[error("invalid client policy: {0}")]
     pub struct HttpInvalidPolicy(pub &'static str);
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("no JWT-SVID available for audience {0}")]
+    pub struct HttpRouteJwtSvidUnavailable(pub Arc<str>);
 }
 
 pub(crate) trait Apply {
@@ -111,6 +115,14 @@ pub fn apply_http_request<B>(
                 rh.apply(req.headers_mut());
             }
 
+            http::Filter::InjectJwtSvid(jwt) => {
+                let Some(authz) = jwt.authorization.borrow().clone() else {
+                    return Err(errors::HttpRouteJwtSvidUnavailable(jwt.audience.clone()).into());
+                };
+                req.headers_mut()
+                    .insert(::http::header::AUTHORIZATION, authz);
+            }
+
             http::Filter::InternalError(msg) => {
                 return Err(errors::HttpInvalidPolicy(msg).into());
             }
@@ -131,6 +143,7 @@ pub fn apply_http_response<B>(
             http::Filter::InjectFailure(_) => {} // InjectFailure filter does not apply to responses.
             http::Filter::Redirect(_) => {}      // Redirect filter does not apply to responses.
             http::Filter::RequestHeaders(_) => {} // RequestHeaders filter does not apply to responses.
+            http::Filter::InjectJwtSvid(_) => {} // InjectJwtSvid filter does not apply to responses.
             http::Filter::InternalError(_) => {} // InternalError filter does not apply to responses.
             http::Filter::ResponseHeaders(rh) => rh.apply(rsp.headers_mut()),
         }
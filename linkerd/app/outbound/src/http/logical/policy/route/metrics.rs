@@ -1,12 +1,16 @@
 use super::{backend::metrics as backend, retry};
 use linkerd_app_core::{
-    metrics::prom::{self, EncodeLabelSetMut},
+    metrics::{
+        prom::{self, EncodeLabelSetMut},
+        CardinalityLimits,
+    },
     proxy::http,
     svc,
 };
 use linkerd_http_prom::{
     body_data::request::{NewRecordBodyData, RequestBodyFamilies},
     record_response::{self, StreamLabel},
+    HistogramBuckets,
 };
 
 pub use linkerd_http_prom::record_response::MkStreamLabel;
@@ -33,6 +37,16 @@ pub struct RouteMetrics<R: StreamLabel, B: StreamLabel> {
 pub type HttpRouteMetrics = RouteMetrics<LabelHttpRouteRsp, LabelHttpRouteBackendRsp>;
 pub type GrpcRouteMetrics = RouteMetrics<LabelGrpcRouteRsp, LabelGrpcRouteBackendRsp>;
 
+/// Configures the bucket layouts of route duration histograms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteHistograms {
+    /// Buckets for request durations, measured on routes.
+    pub request: HistogramBuckets,
+
+    /// Buckets for response durations, measured on route-backends.
+    pub response: HistogramBuckets,
+}
+
 /// Tracks HTTP streams to produce response labels.
 #[derive(Clone, Debug)]
 pub struct LabelHttpRsp<L> {
@@ -86,9 +100,9 @@ where
     })
 }
 
-// === impl RouteMetrics ===
+// === impl RouteHistograms ===
 
-impl<R: StreamLabel, B: StreamLabel> RouteMetrics<R, B> {
+impl RouteHistograms {
     // There are two histograms for which we need to register metrics: request
     // durations, measured on routes, and response durations, measured on
     // route-backends.
@@ -104,6 +118,17 @@ impl<R: StreamLabel, B: StreamLabel> RouteMetrics<R, B> {
     const RESPONSE_BUCKETS: &'static [f64] = &[0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 10.0];
 }
 
+impl Default for RouteHistograms {
+    fn default() -> Self {
+        Self {
+            request: Self::REQUEST_BUCKETS.into(),
+            response: Self::RESPONSE_BUCKETS.into(),
+        }
+    }
+}
+
+// === impl RouteMetrics ===
+
 impl<R: StreamLabel, B: StreamLabel> Default for RouteMetrics<R, B> {
     fn default() -> Self {
         Self {
@@ -127,16 +152,21 @@ impl<R: StreamLabel, B: StreamLabel> Clone for RouteMetrics<R, B> {
 }
 
 impl<R: StreamLabel, B: StreamLabel> RouteMetrics<R, B> {
-    pub fn register(reg: &mut prom::Registry) -> Self {
-        let requests = RequestMetrics::<R>::register(reg, Self::REQUEST_BUCKETS.iter().copied());
+    pub fn register(
+        reg: &mut prom::Registry,
+        histograms: &RouteHistograms,
+        limits: &CardinalityLimits,
+    ) -> Self {
+        let requests = RequestMetrics::<R>::register(reg, histograms.request.bounds(), limits);
 
         let backend = backend::RouteBackendMetrics::register(
             reg.sub_registry_with_prefix("backend"),
-            Self::RESPONSE_BUCKETS.iter().copied(),
+            histograms.response.bounds(),
+            &limits.scoped("backend"),
         );
 
         let retry = retry::RouteRetryMetrics::register(reg.sub_registry_with_prefix("retry"));
-        let body_data = RequestBodyFamilies::register(reg);
+        let body_data = RequestBodyFamilies::register(reg, limits);
 
         Self {
             requests,
@@ -1,15 +1,13 @@
 use http::Response;
 use http_body::Body;
 use http_body_util::BodyExt;
-use linkerd_app_core::{
-    metrics::prom::Counter,
-    svc::{self, http::BoxBody, Service, ServiceExt},
-};
+use linkerd_app_core::svc::{self, http::BoxBody, Service, ServiceExt};
+use linkerd_http_prom::record_response::StatusCount;
 
 pub use crate::test_util::MockBody;
 
 pub async fn send_assert_incremented(
-    counter: &Counter,
+    counter: &StatusCount,
     handle: &mut Handle,
     svc: &mut svc::BoxHttp,
     req: http::Request<BoxBody>,
@@ -8,7 +8,8 @@ use bytes::{Buf, Bytes};
 use http_body::Body;
 use http_body_util::BodyExt;
 use linkerd_app_core::{
-    dns,
+    dns, http_tracing,
+    metrics::{self, prom},
     svc::{
         self,
         http::{uri::Uri, BoxBody},
@@ -123,6 +124,76 @@ async fn http_request_statuses() {
     assert_eq!(mixed.get(), 1);
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn http_request_exemplars() {
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const SPAN_ID: &str = "00f067aa0ba902b7";
+    let _trace = linkerd_tracing::test::trace_init();
+
+    let mut registry = prom::Registry::default();
+    let super::HttpRouteMetrics {
+        requests,
+        body_data,
+        ..
+    } = super::HttpRouteMetrics::register(&mut registry, &Default::default(), &Default::default());
+    let parent_ref = crate::ParentRef(policy::Meta::new_default("parent"));
+    let route_ref = crate::RouteRef(policy::Meta::new_default("route"));
+    let (mut svc, mut handle) =
+        mock_http_route_metrics(&requests, &body_data, &parent_ref, &route_ref, true);
+
+    let ok = requests.get_statuses(&labels::Rsp(
+        labels::Route::new(parent_ref, route_ref, None),
+        labels::HttpRsp {
+            status: Some(http::StatusCode::OK),
+            error: None,
+        },
+    ));
+    // The request is recorded by the proxy's tracing layer, which identifies
+    // its own span on the request.
+    let mut req = http::Request::builder()
+        .header("traceparent", format!("00-{TRACE_ID}-0000000000000001-01"))
+        .body(BoxBody::default())
+        .unwrap();
+    req.extensions_mut().insert(http_tracing::SampledSpan {
+        trace_id: Bytes::from_static(&[
+            0x4b, 0xf9, 0x2f, 0x35, 0x77, 0xb3, 0x4d, 0xa6, 0xa3, 0xce, 0x92, 0x9d, 0x0e, 0x0e,
+            0x47, 0x36,
+        ])
+        .into(),
+        span_id: Bytes::from_static(&[0x00, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7]).into(),
+    });
+    send_assert_incremented(&ok, &mut handle, &mut svc, req, |tx| {
+        tx.send_response(
+            http::Response::builder()
+                .status(200)
+                .body(BoxBody::default())
+                .unwrap(),
+        )
+    })
+    .await;
+
+    let mut text = String::new();
+    metrics::exemplar::encode_text(&mut text, &registry).unwrap();
+    assert!(
+        !text.contains("trace_id"),
+        "exemplars must only be encoded as OpenMetrics:\n{text}"
+    );
+
+    let mut text = String::new();
+    metrics::exemplar::encode_openmetrics(&mut text, &registry).unwrap();
+    let exemplar = format!(r#"# {{trace_id="{TRACE_ID}",span_id="{SPAN_ID}"}}"#);
+    assert!(
+        text.lines()
+            .any(|l| l.starts_with("request_statuses_total{") && l.contains(&exemplar)),
+        "status counter must record an exemplar:\n{text}"
+    );
+    assert!(
+        text.lines()
+            .any(|l| l.starts_with("request_duration_seconds_bucket{") && l.contains(&exemplar)),
+        "duration histogram must record an exemplar:\n{text}"
+    );
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn http_request_hostnames() {
     const EXPORT_HOSTNAME_LABELS: bool = true;
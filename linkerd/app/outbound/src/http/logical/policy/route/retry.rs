@@ -3,7 +3,7 @@ use futures::future::{Either, Ready};
 use linkerd_app_core::{
     cause_ref, classify,
     exp_backoff::ExponentialBackoff,
-    is_caused_by,
+    http_tracing, is_caused_by,
     proxy::http::{self, stream_timeouts::ResponseTimeoutError},
     svc::{self, http::h2},
     Error, Result,
@@ -99,6 +99,12 @@ impl retry::Policy for RetryPolicy {
             dst.insert(client_handle);
         }
 
+        // The backend of each attempt is described on the span of the original
+        // request; each attempt is also recorded by its own client span.
+        if let Some(span) = src.get::<http_tracing::SpanAttributes>().cloned() {
+            dst.insert(span);
+        }
+
         // The legacy response classifier is set for the endpoint stack to use.
         // This informs endpoint-level behavior (failure accrual, etc.).
         // TODO(ver): This should ultimately be eliminated in favor of
@@ -124,7 +124,9 @@ where
         let mk_concrete = {
             let parent = parent.clone();
             let parent_ref = parent_ref.clone();
-            move |backend_ref: BackendRef, target: concrete::Dispatch| {
+            move |backend_ref: BackendRef,
+                  target: concrete::Dispatch,
+                  tls_origination: Option<policy::TlsOrigination>| {
                 // XXX With policies we don't have a top-level authority name at
                 // the moment. So, instead, we use the concrete addr used for
                 // discovery for now.
@@ -139,6 +141,7 @@ where
                     backend_ref,
                     parent_ref: parent_ref.clone(),
                     failure_accrual,
+                    tls_origination,
                 }
             }
         };
@@ -154,16 +157,19 @@ where
                         .expect("destination must be a nameaddr"),
                     http::balance::EwmaConfig { decay, default_rtt },
                 ),
+                bke.tls_origination.clone(),
             ),
             policy::BackendDispatcher::Forward(addr, ref md) => mk_concrete(
                 EndpointRef::new(md, addr.port().try_into().expect("port must not be 0")).into(),
                 concrete::Dispatch::Forward(Remote(ServerAddr(addr)), md.clone()),
+                bke.tls_origination.clone(),
             ),
             policy::BackendDispatcher::Fail { ref message } => mk_concrete(
                 BackendRef(policy::Meta::new_default("fail")),
                 concrete::Dispatch::Fail {
                     message: message.clone(),
                 },
+                None,
             ),
         };
 
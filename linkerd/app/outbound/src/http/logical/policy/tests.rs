@@ -36,6 +36,7 @@ async fn header_based_route() {
                 path: format!("{name}.ns.svc.cluster.local:8080"),
             },
         ),
+        tls_origination: None,
     };
     let mk_policy = |name: &'static str, backend: policy::Backend| policy::RoutePolicy {
         meta: Arc::new(policy::Meta::Resource {
@@ -188,6 +189,7 @@ async fn http_filter_request_headers() {
             failfast_timeout: time::Duration::from_secs(1),
         },
         dispatcher: policy::BackendDispatcher::Forward(addr, Default::default()),
+        tls_origination: None,
     };
 
     // Stack that produces mock services.
@@ -259,3 +261,97 @@ async fn http_filter_request_headers() {
     // Hold the router to prevent inner services from being dropped.
     drop(router);
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn http_filter_inject_jwt_svid() {
+    let _trace = trace::test::trace_init();
+
+    let addr = ([127, 0, 0, 1], 18080).into();
+    let backend = policy::Backend {
+        meta: policy::Meta::new_default("test"),
+        queue: policy::Queue {
+            capacity: 10,
+            failfast_timeout: time::Duration::from_secs(1),
+        },
+        dispatcher: policy::BackendDispatcher::Forward(addr, Default::default()),
+        tls_origination: None,
+    };
+
+    // Stack that produces mock services.
+    let (inner, mut handle) = tower_test::mock::pair();
+    let inner = move |_: Concrete<()>| inner.clone();
+
+    static BEARER: http::HeaderValue = http::HeaderValue::from_static("Bearer jwt-svid");
+    let (authz_tx, authorization) = tokio::sync::watch::channel(Some(BEARER.clone()));
+    let routes = Params::Http({
+        router::HttpParams {
+            addr: Addr::Socket(([127, 0, 0, 1], 8080).into()),
+            meta: ParentRef(policy::Meta::new_default("splinter")),
+            routes: Arc::new([policy::http::Route {
+                hosts: Default::default(),
+                rules: vec![policy::http::Rule {
+                    matches: vec![route::http::MatchRequest::default()],
+                    policy: policy::RoutePolicy {
+                        meta: policy::Meta::new_default("turtles"),
+                        params: Default::default(),
+                        filters: Arc::new([policy::http::Filter::InjectJwtSvid(
+                            policy::http::InjectJwtSvid {
+                                audience: "partner".into(),
+                                authorization,
+                            },
+                        )]),
+                        distribution: policy::RouteDistribution::FirstAvailable(Arc::new([
+                            policy::RouteBackend {
+                                backend: backend.clone(),
+                                filters: Arc::new([]),
+                            },
+                        ])),
+                    },
+                }],
+            }]),
+            backends: std::iter::once(backend).collect(),
+            failure_accrual: Default::default(),
+        }
+    });
+
+    let router = Policy::layer(Default::default(), Default::default())
+        .layer(inner)
+        .new_service(Policy::from((routes, ())));
+
+    // Any authorization set by the application is replaced.
+    handle.allow(1);
+    let req = http::Request::builder()
+        .header(http::header::AUTHORIZATION, "Bearer app")
+        .body(http::BoxBody::default())
+        .unwrap();
+    let (req, _rsp) = tokio::select! {
+        biased;
+        _ = router.clone().oneshot(req) => panic!("unexpected response"),
+        _ = time::sleep(time::Duration::from_secs(1)) => panic!("timed out"),
+        reqrsp = handle.next_request() => reqrsp.expect("request"),
+    };
+    assert_eq!(
+        req.headers()
+            .get_all(http::header::AUTHORIZATION)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![&BEARER],
+    );
+
+    // Requests fail when no token is available.
+    authz_tx.send_replace(None);
+    handle.allow(1);
+    let err = tokio::select! {
+        biased;
+        res = router.clone().oneshot(http::Request::default()) => res.expect_err("request must fail"),
+        _ = time::sleep(time::Duration::from_secs(1)) => panic!("timed out"),
+        _ = handle.next_request() => panic!("unexpected request"),
+    };
+    assert!(
+        linkerd_app_core::errors::is_caused_by::<errors::HttpRouteJwtSvidUnavailable>(&*err),
+        "{err}"
+    );
+
+    // Hold the router to prevent inner services from being dropped.
+    drop(router);
+}
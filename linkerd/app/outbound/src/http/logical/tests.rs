@@ -214,6 +214,7 @@ fn default_backend(path: impl ToString) -> client_policy::Backend {
                 path: path.to_string(),
             },
         ),
+        tls_origination: None,
     }
 }
 
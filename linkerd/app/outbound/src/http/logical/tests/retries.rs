@@ -11,6 +11,7 @@ use linkerd_proxy_client_policy::{
     grpc::{Codes, RouteParams as GrpcParams},
     http::{RouteParams as HttpParams, Timeouts},
 };
+use linkerd_tracing::test::CapturedSpans;
 use std::collections::BTreeSet;
 use tokio::time;
 use tonic::Code;
@@ -54,6 +55,53 @@ async fn http_5xx() {
     assert_eq!(rsp.expect("response").status(), StatusCode::NO_CONTENT);
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn http_5xx_access_log() {
+    let (spans, _trace) = CapturedSpans::init(linkerd_tracing::access_log::TRACE_TARGET);
+
+    const TIMEOUT: time::Duration = time::Duration::from_secs(2);
+    let (svc, mut handle) = mock_http(HttpParams {
+        timeouts: Timeouts {
+            request: Some(TIMEOUT),
+            ..Default::default()
+        },
+        retry: Some(client_policy::http::Retry {
+            max_retries: 1,
+            status_ranges: Default::default(),
+            max_request_bytes: 1000,
+            timeout: None,
+            backoff: None,
+        }),
+        ..Default::default()
+    });
+
+    tokio::spawn(
+        async move {
+            handle.allow(2);
+            serve(&mut handle, mk_rsp(StatusCode::INTERNAL_SERVER_ERROR, "")).await;
+            serve(&mut handle, mk_rsp(StatusCode::NO_CONTENT, "")).await;
+            handle
+        }
+        .in_current_span(),
+    );
+
+    let rsp = time::timeout(TIMEOUT, send_req(svc.clone(), http_get()))
+        .await
+        .expect("response");
+    assert_eq!(rsp.expect("response").status(), StatusCode::NO_CONTENT);
+
+    let logs = spans.closed();
+    assert_eq!(logs.len(), 1, "retried requests must be logged once");
+    let log = &logs[0];
+    assert_eq!(log["status"], "204");
+    assert_eq!(log["retries"], "1");
+    assert_eq!(log["client.id"], "-");
+    assert_eq!(log["parent"], "default/parent");
+    assert_eq!(log["route"], "default/route");
+    assert_eq!(log["backend"], "default/test");
+    assert_eq!(log["endpoint.addr"], "192.0.2.41:1234");
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn http_5xx_limited() {
     let _trace = trace::test::trace_init();
@@ -2,6 +2,7 @@ use futures::{future, FutureExt};
 use linkerd_app_core::{
     classify,
     http_metrics::retries::Handle,
+    http_tracing,
     metrics::{self, ProfileRouteLabels},
     profiles::{self, http::Route},
     proxy::http::{Body, ClientHandle, EraseResponse},
@@ -11,7 +12,7 @@ use linkerd_app_core::{
 use linkerd_http_classify::{Classify, ClassifyEos, ClassifyResponse};
 use linkerd_http_retry::{
     peek_trailers::{self, PeekTrailersBody},
-    ReplayBody,
+    Attempt, ReplayBody,
 };
 use linkerd_retry as retry;
 use std::sync::Arc;
@@ -146,6 +147,21 @@ where
             clone.extensions_mut().insert(classify);
         }
 
+        // The backend of each attempt is described on the span of the original
+        // request; each attempt is also recorded by its own client span.
+        if let Some(span) = http_tracing::SpanAttributes::get(req).cloned() {
+            clone.extensions_mut().insert(span);
+        }
+
+        // The clone is sent if the request is retried, so it is the next
+        // attempt.
+        let attempt = req
+            .extensions()
+            .get::<Attempt>()
+            .map_or(1.try_into().unwrap(), |Attempt(n)| *n)
+            .saturating_add(1);
+        clone.extensions_mut().insert(Attempt(attempt));
+
         Some(clone)
     }
 }
@@ -171,7 +187,8 @@ where
         self,
         req: http::Request<ReqB>,
     ) -> Either<(Self, Self::RetryRequest), http::Request<ReqB>> {
-        let (head, body) = req.into_parts();
+        let (mut head, body) = req.into_parts();
+        head.extensions.insert(Attempt(1.try_into().unwrap()));
         let replay_body = match ReplayBody::try_new(body, MAX_BUFFERED_BYTES) {
             Ok(body) => body,
             Err(body) => {
@@ -194,3 +211,42 @@ where
         PeekTrailersBody::map_response(rsp).map(Ok)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_app_core::{metrics::ProfileRouteLabels, profiles::LogicalAddr};
+    use linkerd_http_box::BoxBody;
+    use std::time::Duration;
+
+    type Rsp = http::Response<PeekTrailersBody<BoxBody>>;
+
+    fn attempt<B>(req: &http::Request<B>) -> Option<u16> {
+        req.extensions().get::<Attempt>().map(|Attempt(n)| n.get())
+    }
+
+    #[test]
+    fn counts_profile_retry_attempts() {
+        let route = Route::new(std::iter::empty(), Vec::new());
+        let addr = LogicalAddr("foo.ns.svc.cluster.local:8080".parse().unwrap());
+        let policy = RetryPolicy {
+            metrics: metrics::HttpProfileRouteRetry::default()
+                .get_handle(ProfileRouteLabels::outbound(addr, &route)),
+            budget: Arc::new(retry::TpsBudget::new(Duration::from_secs(10), 10, 0.2)),
+            response_classes: route.response_classes().clone(),
+        };
+
+        let Either::Left((mut policy, req)) = retry::PrepareRetry::<_, Rsp>::prepare_request(
+            policy,
+            http::Request::new(BoxBody::empty()),
+        ) else {
+            panic!("request must be retryable");
+        };
+        assert_eq!(attempt(&req), Some(1), "the first attempt is counted");
+
+        let retry = retry::Policy::<_, Rsp, Error>::clone_request(&mut policy, &req).unwrap();
+        assert_eq!(attempt(&retry), Some(2));
+        let retry = retry::Policy::<_, Rsp, Error>::clone_request(&mut policy, &retry).unwrap();
+        assert_eq!(attempt(&retry), Some(3));
+    }
+}
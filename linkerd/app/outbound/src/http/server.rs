@@ -47,8 +47,16 @@ impl<T> Outbound<svc::ArcNewCloneHttp<T>> {
                 .check_new_service::<T, http::Request<_>>()
                 .push(ServerRescue::layer(config.emit_headers))
                 .check_new_service::<T, http::Request<_>>()
+                // Records trace IDs on log lines emitted while handling
+                // each request.
+                .push_on_service(http_tracing::correlate(config.trace_formats.clone()))
                 // Initiates OpenTelemetry tracing.
-                .push_on_service(http_tracing::server(rt.span_sink.clone(), trace_labels()))
+                .push_on_service(http_tracing::server(
+                    rt.span_sink.clone(),
+                    config.trace_formats.clone(),
+                    config.trace_sampler.clone(),
+                    trace_labels(),
+                ))
                 .push_on_service(http::BoxResponse::layer())
                 // Convert origin form HTTP/1 URIs to absolute form for Hyper's
                 // `Client`.
@@ -71,7 +79,7 @@ impl<N> Outbound<N> {
         T: svc::Param<http::Variant>,
         T: Clone + Send + Unpin + 'static,
         // Server-side socket
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + Send + Unpin + 'static,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + Send + Unpin + 'static,
         // Inner stack
         N: svc::NewService<T, Service = NSvc> + Clone + Send + Sync + Unpin + 'static,
         NSvc: svc::Service<
@@ -164,6 +172,11 @@ impl errors::HttpRescue<Error> for ServerRescue {
             return Ok(errors::SyntheticHttpResponse::redirect(*status, location));
         }
 
+        // A route requires a JWT-SVID that could not be obtained.
+        if errors::is_caused_by::<policy::HttpRouteJwtSvidUnavailable>(&*error) {
+            return Ok(errors::SyntheticHttpResponse::unavailable(error));
+        }
+
         // Policy-driven request failures.
         if let Some(policy::HttpRouteInjectedFailure { status, message }) =
             errors::cause_ref(&*error)
@@ -8,6 +8,7 @@ use linkerd_app_core::{
     },
     svc::{self, ServiceExt},
     transport::addrs::*,
+    transport_header::OriginalClient,
     Addr, Error, Infallible, NameAddr, Result,
 };
 use once_cell::sync::Lazy;
@@ -79,7 +80,7 @@ impl Outbound<()> {
         T: svc::Param<OrigDstAddr>,
         T: Clone + Send + Sync + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Unpin + Send + Sync + 'static,
         // Endpoint resolver.
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
@@ -256,7 +257,7 @@ impl<N> Outbound<N> {
         T: svc::Param<OrigDstAddr>,
         T: Clone + Send + Sync + Unpin + 'static,
         // A server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo,
         I: std::fmt::Debug + Send + Unpin + 'static,
         // Fallback opaque stack.
         F: svc::NewService<T, Service = FSvc> + Clone + Send + Sync + 'static,
@@ -653,6 +654,12 @@ impl svc::Param<watch::Receiver<opaq::Routes>> for Opaq {
     }
 }
 
+impl svc::Param<Option<OriginalClient>> for Opaq {
+    fn param(&self) -> Option<OriginalClient> {
+        None
+    }
+}
+
 // === impl RequestTarget ===
 
 impl From<RequestTarget> for Addr {
@@ -10,7 +10,7 @@ use linkerd_app_core::{
     config::{ProxyConfig, QueueConfig},
     drain,
     exp_backoff::ExponentialBackoff,
-    http_tracing::SpanSink,
+    http_tracing::{self, SpanSink},
     identity, io,
     metrics::prom,
     profiles,
@@ -23,7 +23,7 @@ use linkerd_app_core::{
     svc::{self, ServiceExt},
     tls::ConnectMeta as TlsConnectMeta,
     transport::addrs::*,
-    AddrMatch, Error, NameAddr, ProxyRuntime,
+    AddrMatch, Error, IpMatch, NameAddr, ProxyRuntime,
 };
 use linkerd_tonic_stream::ReceiveLimits;
 use std::{
@@ -83,6 +83,26 @@ pub struct Config {
 
     // Whether the proxy may include informational headers on HTTP responses.
     pub emit_headers: bool,
+
+    /// Configures the networks to which opaque connections are forwarded with
+    /// a PROXY protocol v2 header describing the original client.
+    ///
+    /// This must only include servers that are not meshed.
+    pub proxy_protocol_networks: IpMatch,
+
+    /// Configures the bucket layouts of route duration histograms.
+    pub route_histograms: http::policy::RouteHistograms,
+
+    /// Determines whether the proxy starts a trace for requests that do not
+    /// carry a trace context.
+    pub trace_sampler: http_tracing::Sampler,
+
+    /// Configures the formats in which trace contexts are propagated.
+    pub trace_formats: http_tracing::Formats,
+
+    /// Configures the backends to which HTTP requests are sent over TLS
+    /// originated by the proxy.
+    pub tls_origination: http::TlsOrigination,
 }
 
 #[derive(Clone, Debug)]
@@ -124,7 +144,7 @@ pub struct EndpointRef(Arc<policy::Meta>);
 impl Outbound<()> {
     pub fn new(config: Config, runtime: ProxyRuntime, prom: &mut prom::Registry) -> Self {
         let runtime = Runtime {
-            metrics: OutboundMetrics::new(runtime.metrics, prom),
+            metrics: OutboundMetrics::new(runtime.metrics, prom, &config.route_histograms),
             identity: runtime.identity.new_client(),
             tap: runtime.tap,
             span_sink: runtime.span_sink,
@@ -143,7 +163,7 @@ impl Outbound<()> {
         client: C,
         backoff: ExponentialBackoff,
         limits: ReceiveLimits,
-        export_hostname_labels: bool,
+        overrides: policy::ClientPolicyOverrides,
     ) -> impl policy::GetPolicy
     where
         C: tonic::client::GrpcService<tonic::body::BoxBody, Error = Error>,
@@ -152,18 +172,12 @@ impl Outbound<()> {
         C::ResponseBody: Send + 'static,
         C::Future: Send,
     {
-        policy::Api::new(
-            workload,
-            limits,
-            Duration::from_secs(10),
-            export_hostname_labels,
-            client,
-        )
-        .into_watch(backoff)
-        .map_result(|res| match res {
-            Err(e) => Err(e.into()),
-            Ok(rsp) => Ok(rsp.into_inner()),
-        })
+        policy::Api::new(workload, limits, Duration::from_secs(10), overrides, client)
+            .into_watch(backoff)
+            .map_result(|res| match res {
+                Err(e) => Err(e.into()),
+                Ok(rsp) => Ok(rsp.into_inner()),
+            })
     }
 
     #[cfg(any(test, feature = "test-util"))]
@@ -242,7 +256,7 @@ impl Outbound<()> {
         T: svc::Param<OrigDstAddr>,
         T: Clone + Send + Sync + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Unpin + Send + Sync + 'static,
         // Endpoint resolution.
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
@@ -284,8 +298,38 @@ impl From<EndpointRef> for ParentRef {
     }
 }
 
+fn meta_span_attributes(
+    [group, kind, name, namespace]: [&'static str; 4],
+    meta: &policy::Meta,
+) -> http_tracing::TargetAttributes {
+    [
+        (group, meta.group()),
+        (kind, meta.kind()),
+        (name, meta.name()),
+        (namespace, meta.namespace()),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key, value.to_string()))
+    .collect()
+}
+
 // === impl RouteRef ===
 
+impl RouteRef {
+    /// Describes the route on the spans of the requests it handles.
+    fn span_attributes(&self) -> http_tracing::TargetAttributes {
+        meta_span_attributes(
+            [
+                "linkerd.route.group",
+                "linkerd.route.kind",
+                "linkerd.route.name",
+                "linkerd.route.namespace",
+            ],
+            &self.0,
+        )
+    }
+}
+
 impl std::ops::Deref for RouteRef {
     type Target = policy::Meta;
 
@@ -296,6 +340,21 @@ impl std::ops::Deref for RouteRef {
 
 // === impl BackendRef ===
 
+impl BackendRef {
+    /// Describes the backend on the spans of the requests it handles.
+    fn span_attributes(&self) -> http_tracing::TargetAttributes {
+        meta_span_attributes(
+            [
+                "linkerd.backend.group",
+                "linkerd.backend.kind",
+                "linkerd.backend.name",
+                "linkerd.backend.namespace",
+            ],
+            &self.0,
+        )
+    }
+}
+
 impl std::ops::Deref for BackendRef {
     type Target = policy::Meta;
 
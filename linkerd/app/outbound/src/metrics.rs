@@ -89,7 +89,11 @@ where
 // === impl PromMetrics ===
 
 impl PromMetrics {
-    pub fn register(registry: &mut prom::Registry) -> Self {
+    pub fn register(
+        registry: &mut prom::Registry,
+        route_histograms: &crate::http::policy::RouteHistograms,
+        limits: &CardinalityLimits,
+    ) -> Self {
         let protocol = crate::protocol::MetricsFamilies::register(
             registry.sub_registry_with_prefix("tcp_protocol"),
         );
@@ -100,7 +104,7 @@ impl PromMetrics {
 
         // NOTE: HTTP metrics are scoped internally, since this configures both
         // HTTP and gRPC scopes.
-        let http = crate::http::HttpMetrics::register(registry);
+        let http = crate::http::HttpMetrics::register(registry, route_histograms, limits);
 
         let opaq = crate::opaq::OpaqMetrics::register(registry.sub_registry_with_prefix("tcp"));
         let zone = crate::zone::TcpZoneMetrics::register(registry.sub_registry_with_prefix("tcp"));
@@ -120,12 +124,17 @@ impl PromMetrics {
 // === impl OutboundMetrics ===
 
 impl OutboundMetrics {
-    pub(crate) fn new(proxy: Proxy, registry: &mut prom::Registry) -> Self {
+    pub(crate) fn new(
+        proxy: Proxy,
+        registry: &mut prom::Registry,
+        route_histograms: &crate::http::policy::RouteHistograms,
+    ) -> Self {
+        let limits = proxy.cardinality.scoped("outbound");
         Self {
             proxy,
             http_errors: error::Http::default(),
             tcp_errors: error::Tcp::default(),
-            prom: PromMetrics::register(registry),
+            prom: PromMetrics::register(registry, route_histograms, &limits),
         }
     }
 }
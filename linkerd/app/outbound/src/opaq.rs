@@ -9,6 +9,7 @@ use linkerd_app_core::{
     },
     svc,
     transport::addrs::*,
+    transport_header::OriginalClient,
     Addr, Error, NameAddr,
 };
 use once_cell::sync::Lazy;
@@ -41,8 +42,9 @@ impl<C> Outbound<C> {
         // Opaque target
         T: Clone + Debug + PartialEq + Eq + Hash + Send + Sync + 'static,
         T: svc::Param<watch::Receiver<Routes>>,
+        T: svc::Param<Option<OriginalClient>>,
         // Server-side connection
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         // Endpoint discovery
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
@@ -50,7 +52,7 @@ impl<C> Outbound<C> {
         // TCP endpoint stack.
         C: svc::MakeConnection<tcp::Connect, Metadata = Local<ClientAddr>, Error = io::Error>,
         C: Clone + Send + Sync + Unpin + 'static,
-        C::Connection: Send + Unpin,
+        C::Connection: io::PeerAddr + io::TcpInfo + Send + Unpin,
         C::Future: Send + Unpin,
     {
         self.push_tcp_endpoint()
@@ -77,6 +79,15 @@ where
     }
 }
 
+impl<T> svc::Param<Option<OriginalClient>> for Opaq<T>
+where
+    T: svc::Param<Option<OriginalClient>>,
+{
+    fn param(&self) -> Option<OriginalClient> {
+        self.0.param()
+    }
+}
+
 // === impl OpaqMetrics ===
 
 impl OpaqMetrics {
@@ -189,6 +200,7 @@ fn routes_from_profile(addr: NameAddr, profile: &profiles::Profile) -> Routes {
                             path: target.addr.to_string(),
                         },
                     ),
+                    tls_origination: None,
                 },
                 filters: std::sync::Arc::new([]),
             };
@@ -19,10 +19,11 @@ use linkerd_app_core::{
         http::AuthorityOverride,
         tcp::{self, balance},
     },
+    proxy_protocol,
     svc::{self, layer::Layer},
     tls,
     transport::{self, addrs::*},
-    transport_header::SessionProtocol,
+    transport_header::{OriginalClient, SessionProtocol},
     Error, Infallible, NameAddr,
 };
 use std::{fmt::Debug, net::SocketAddr, sync::Arc};
@@ -143,13 +144,20 @@ impl<C> Outbound<C> {
         T: svc::Param<BackendRef>,
         T: svc::Param<ParentRef>,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + Debug + Send + Unpin + 'static,
+        I: io::AsyncRead
+            + io::AsyncWrite
+            + io::PeerAddr
+            + io::TcpInfo
+            + Debug
+            + Send
+            + Unpin
+            + 'static,
         // Endpoint resolution.
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
         R::Resolution: Unpin,
         // Endpoint connector.
         C: svc::MakeConnection<Endpoint<T>> + Clone + Send + 'static,
-        C::Connection: Send + Unpin,
+        C::Connection: io::PeerAddr + io::TcpInfo + Send + Unpin,
         C::Metadata: Send + Unpin,
         C::Future: Send,
         C: Send + Sync + 'static,
@@ -160,6 +168,7 @@ impl<C> Outbound<C> {
 
         self.map_stack(|config, rt, inner| {
             let queue = config.tcp_connection_queue;
+            let proxy_protocol_networks = config.proxy_protocol_networks.clone();
 
             let connect = inner
                 .push(svc::stack::WithoutConnectionMetadata::layer())
@@ -243,7 +252,7 @@ impl<C> Outbound<C> {
                     },
                     svc::stack(fail).check_new_clone().into_inner(),
                 )
-                .push_on_service(tcp::Forward::layer())
+                .push_on_service(proxy_protocol::Forward::layer(proxy_protocol_networks))
                 .push_on_service(drain::Retain::layer(rt.drain.clone()))
                 .push(svc::ArcNewService::layer())
         })
@@ -338,6 +347,15 @@ impl<T> svc::Param<Option<SessionProtocol>> for Endpoint<T> {
     }
 }
 
+impl<T> svc::Param<Option<OriginalClient>> for Endpoint<T>
+where
+    T: svc::Param<Option<OriginalClient>>,
+{
+    fn param(&self) -> Option<OriginalClient> {
+        self.parent.param()
+    }
+}
+
 impl<T> svc::Param<transport::labels::Key> for Endpoint<T>
 where
     T: svc::Param<Logical>,
@@ -1,6 +1,6 @@
 use super::concrete;
 use crate::{BackendRef, Outbound, ParentRef};
-use linkerd_app_core::{io, svc, Addr, Error};
+use linkerd_app_core::{io, svc, transport_header::OriginalClient, Addr, Error};
 use linkerd_proxy_client_policy as client_policy;
 use std::{fmt::Debug, hash::Hash, sync::Arc};
 use tokio::sync::watch;
@@ -121,3 +121,12 @@ impl<T> svc::Param<BackendRef> for Concrete<T> {
         self.backend_ref.clone()
     }
 }
+
+impl<T> svc::Param<Option<OriginalClient>> for Concrete<T>
+where
+    T: svc::Param<Option<OriginalClient>>,
+{
+    fn param(&self) -> Option<OriginalClient> {
+        self.parent.param()
+    }
+}
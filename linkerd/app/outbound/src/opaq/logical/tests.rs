@@ -235,6 +235,7 @@ fn default_service_policy(addr: NameAddr) -> policy::ClientPolicy {
                 path: addr.to_string(),
             },
         ),
+        tls_origination: None,
     };
 
     let opaque = policy::opaq::Opaque {
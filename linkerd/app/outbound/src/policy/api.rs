@@ -19,7 +19,7 @@ pub(crate) struct Api<S> {
     workload: Arc<str>,
     limits: ReceiveLimits,
     default_detect_timeout: time::Duration,
-    export_hostname_labels: bool,
+    overrides: ClientPolicyOverrides,
     client: Client<S>,
 }
 
@@ -40,14 +40,14 @@ where
         workload: Arc<str>,
         limits: ReceiveLimits,
         default_detect_timeout: time::Duration,
-        export_hostname_labels: bool,
+        overrides: ClientPolicyOverrides,
         client: S,
     ) -> Self {
         Self {
             workload,
             limits,
             default_detect_timeout,
-            export_hostname_labels,
+            overrides,
             client: Client::new(client),
         }
     }
@@ -89,9 +89,7 @@ where
         };
 
         let detect_timeout = self.default_detect_timeout;
-        let overrides = ClientPolicyOverrides {
-            export_hostname_labels: self.export_hostname_labels,
-        };
+        let overrides = self.overrides.clone();
         let limits = self.limits;
         let mut client = self.client.clone();
         Box::pin(async move {
@@ -102,7 +100,7 @@ where
                     // If the server returned an invalid client policy, we
                     // default to using an invalid policy that causes all
                     // requests to report an internal error.
-                    let policy = ClientPolicy::try_from(overrides, up).unwrap_or_else(|error| {
+                    let policy = ClientPolicy::try_from(&overrides, up).unwrap_or_else(|error| {
                         tracing::warn!(%error, "Client policy misconfigured");
                         INVALID_POLICY
                             .get_or_init(|| ClientPolicy::invalid(detect_timeout))
@@ -44,7 +44,7 @@ impl<N> Outbound<N> {
         T: svc::Param<ParentRef>,
         T: Eq + Hash + Clone + Debug + Send + Sync + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo,
         I: Debug + Send + Sync + Unpin + 'static,
         // Opaque connection stack.
         N: svc::NewService<T, Service = NSvc>,
@@ -11,6 +11,7 @@ use linkerd_app_core::{
     },
     svc,
     transport::addrs::*,
+    transport_header::OriginalClient,
     Addr, Error,
 };
 use std::fmt::Debug;
@@ -58,7 +59,7 @@ impl Outbound<()> {
         T: svc::Param<OrigDstAddr>,
         T: Clone + Send + Sync + 'static,
         // Server-side socket.
-        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr,
+        I: io::AsyncRead + io::AsyncWrite + io::Peek + io::PeerAddr + io::TcpInfo,
         I: Debug + Unpin + Send + Sync + 'static,
         // Endpoint resolver.
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
@@ -418,6 +419,12 @@ impl svc::Param<watch::Receiver<opaq::Routes>> for OpaqSidecar {
     }
 }
 
+impl svc::Param<Option<OriginalClient>> for OpaqSidecar {
+    fn param(&self) -> Option<OriginalClient> {
+        None
+    }
+}
+
 impl std::cmp::PartialEq for OpaqSidecar {
     fn eq(&self, other: &Self) -> bool {
         self.orig_dst == other.orig_dst
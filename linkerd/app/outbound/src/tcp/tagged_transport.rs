@@ -5,7 +5,7 @@ use linkerd_app_core::{
     proxy::http,
     svc, tls,
     transport::{Remote, ServerAddr},
-    transport_header::{SessionProtocol, TransportHeader, PROTOCOL},
+    transport_header::{OriginalClient, SessionProtocol, TransportHeader, PROTOCOL},
     Conditional, Error, Result,
 };
 use std::{
@@ -48,7 +48,8 @@ where
         + svc::Param<Remote<ServerAddr>>
         + svc::Param<Option<PortOverride>>
         + svc::Param<Option<http::AuthorityOverride>>
-        + svc::Param<Option<SessionProtocol>>,
+        + svc::Param<Option<SessionProtocol>>
+        + svc::Param<Option<OriginalClient>>,
     S: svc::MakeConnection<Connect, Metadata = ConnectMeta> + Send + 'static,
     S::Connection: Send + Unpin,
     S::Future: Send + 'static,
@@ -105,6 +106,7 @@ where
         }
 
         let protocol: Option<SessionProtocol> = ep.param();
+        let original_client: Option<OriginalClient> = ep.param();
 
         let connect = self.inner.connect(Connect::new(
             Remote(ServerAddr((addr.ip(), connect_port).into())),
@@ -120,6 +122,7 @@ where
                     port: target_port,
                     name,
                     protocol,
+                    original_client,
                 };
                 trace!(?header, "Writing transport header");
                 let sz = header.write(&mut io).await?;
@@ -149,6 +152,7 @@ mod test {
         authority: Option<http::uri::Authority>,
         identity: Option<tls::ClientTls>,
         proto: Option<SessionProtocol>,
+        original_client: Option<OriginalClient>,
     }
 
     impl svc::Param<tls::ConditionalClientTls> for Endpoint {
@@ -195,6 +199,12 @@ mod test {
         }
     }
 
+    impl svc::Param<Option<OriginalClient>> for Endpoint {
+        fn param(&self) -> Option<OriginalClient> {
+            self.original_client.clone()
+        }
+    }
+
     fn expect_header(
         header: TransportHeader,
     ) -> impl Fn(Connect) -> futures::future::Ready<Result<(tokio_test::io::Mock, ConnectMeta), io::Error>>
@@ -249,6 +259,7 @@ mod test {
                 port: 4321,
                 name: None,
                 protocol: None,
+                original_client: None,
             })),
         };
 
@@ -260,6 +271,7 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: None,
             proto: None,
+            original_client: None,
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -274,6 +286,7 @@ mod test {
                 port: 5555,
                 name: Some(dns::Name::from_str("foo.bar.example.com").unwrap()),
                 protocol: None,
+                original_client: None,
             })),
         };
 
@@ -285,6 +298,7 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: Some(http::uri::Authority::from_str("foo.bar.example.com:5555").unwrap()),
             proto: None,
+            original_client: None,
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -299,6 +313,7 @@ mod test {
                 port: 4321,
                 name: None,
                 protocol: None,
+                original_client: None,
             })),
         };
 
@@ -310,6 +325,7 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: None,
             proto: None,
+            original_client: None,
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -324,6 +340,7 @@ mod test {
                 port: 4321,
                 name: None,
                 protocol: Some(SessionProtocol::Http1),
+                original_client: None,
             })),
         };
 
@@ -335,6 +352,7 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: None,
             proto: Some(SessionProtocol::Http1),
+            original_client: None,
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -349,6 +367,7 @@ mod test {
                 port: 5555,
                 name: Some(dns::Name::from_str("foo.bar.example.com").unwrap()),
                 protocol: Some(SessionProtocol::Http1),
+                original_client: None,
             })),
         };
 
@@ -360,6 +379,7 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: Some(http::uri::Authority::from_str("foo.bar.example.com:5555").unwrap()),
             proto: Some(SessionProtocol::Http1),
+            original_client: None,
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -374,6 +394,7 @@ mod test {
                 port: 4321,
                 name: None,
                 protocol: Some(SessionProtocol::Http1),
+                original_client: None,
             })),
         };
 
@@ -385,6 +406,38 @@ mod test {
             identity: Some(tls::ClientTls::new(server_id, server_name)),
             authority: None,
             proto: Some(SessionProtocol::Http1),
+            original_client: None,
+        };
+        let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
+        io.write_all(b"hello").await.expect("Write must succeed");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn opaque_original_client() {
+        let _trace = linkerd_tracing::test::trace_init();
+
+        let original_client = OriginalClient {
+            addr: None,
+            identity: Some("client.id".parse().unwrap()),
+        };
+        let svc = TaggedTransport {
+            inner: service_fn(expect_header(TransportHeader {
+                port: 4321,
+                name: None,
+                protocol: None,
+                original_client: Some(original_client.clone()),
+            })),
+        };
+
+        let server_id = tls::ServerId("server.id".parse().unwrap());
+        let server_name = tls::ServerName("server.name".parse().unwrap());
+
+        let e = Endpoint {
+            port_override: Some(4143),
+            identity: Some(tls::ClientTls::new(server_id, server_name)),
+            authority: None,
+            proto: None,
+            original_client: Some(original_client),
         };
         let (mut io, _meta) = svc.oneshot(e).await.expect("Connect must not fail");
         io.write_all(b"hello").await.expect("Write must succeed");
@@ -21,10 +21,16 @@ pub(crate) fn default_config() -> Config {
     Config {
         ingress_mode: false,
         emit_headers: true,
+        proxy_protocol_networks: Default::default(),
+        route_histograms: Default::default(),
+        trace_sampler: Default::default(),
+        trace_formats: Default::default(),
+        tls_origination: Default::default(),
         allow_discovery: IpMatch::new(Some(IpNet::from_str("0.0.0.0/0").unwrap())).into(),
         proxy: config::ProxyConfig {
             server: config::ServerConfig {
                 addr: DualListenAddr(([0, 0, 0, 0], 0).into(), None),
+                accept_shards: Default::default(),
                 keepalive: Keepalive(None),
                 user_timeout: UserTimeout(None),
                 http2: h2::ServerParams::default(),
@@ -58,7 +64,8 @@ pub(crate) fn default_config() -> Config {
 pub(crate) fn runtime() -> (ProxyRuntime, drain::Signal) {
     let (drain_tx, drain) = drain::channel();
     let (tap, _) = tap::new();
-    let (metrics, _) = metrics::Metrics::new(std::time::Duration::from_secs(10));
+    let (metrics, _) =
+        metrics::Metrics::new(std::time::Duration::from_secs(10), Default::default());
     let runtime = ProxyRuntime {
         identity: linkerd_meshtls_rustls::creds::default_for_test().1.into(),
         metrics: metrics.proxy,
@@ -80,7 +80,7 @@ impl<C> Outbound<C> {
         T: Clone + Debug + PartialEq + Eq + Hash + Send + Sync + 'static,
         T: svc::Param<watch::Receiver<Routes>>,
         // Server-side connection
-        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::Peek,
+        I: io::AsyncRead + io::AsyncWrite + io::PeerAddr + io::TcpInfo + io::Peek,
         I: Debug + Send + Sync + Unpin + 'static,
         // Endpoint discovery
         R: Resolve<ConcreteAddr, Endpoint = Metadata, Error = Error>,
@@ -88,7 +88,7 @@ impl<C> Outbound<C> {
         // TCP endpoint stack.
         C: svc::MakeConnection<tcp::Connect, Metadata = Local<ClientAddr>, Error = io::Error>,
         C: Clone + Send + Sync + Unpin + 'static,
-        C::Connection: Send + Unpin,
+        C::Connection: io::PeerAddr + io::TcpInfo + Send + Unpin,
         C::Future: Send + Unpin,
     {
         self.push_tcp_endpoint()
@@ -21,7 +21,7 @@ use linkerd_app_core::{
     svc::{self, layer::Layer},
     tls::{self, ServerName},
     transport::{self, addrs::*},
-    transport_header::SessionProtocol,
+    transport_header::{OriginalClient, SessionProtocol},
     Error, Infallible, NameAddr,
 };
 use std::{fmt::Debug, net::SocketAddr, sync::Arc};
@@ -311,6 +311,12 @@ impl<T> svc::Param<Option<SessionProtocol>> for Endpoint<T> {
     }
 }
 
+impl<T> svc::Param<Option<OriginalClient>> for Endpoint<T> {
+    fn param(&self) -> Option<OriginalClient> {
+        None
+    }
+}
+
 impl<T> svc::Param<transport::labels::Key> for Endpoint<T>
 where
     T: svc::Param<ServerName>,
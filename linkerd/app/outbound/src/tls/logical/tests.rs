@@ -143,6 +143,7 @@ fn default_backend(addr: SocketAddr) -> client_policy::Backend {
             failfast_timeout: Duration::from_secs(10),
         },
         dispatcher: BackendDispatcher::Forward(addr, EndpointMetadata::default()),
+        tls_origination: None,
     }
 }
 
@@ -1,4 +1,6 @@
-use crate::{dns, gateway, identity, inbound, outbound, policy, spire, trace_collector};
+use crate::{
+    dns, gateway, identity, inbound, metrics_collector, outbound, policy, spire, trace_collector,
+};
 use linkerd_app_core::{
     addr,
     config::*,
@@ -6,8 +8,8 @@ use linkerd_app_core::{
     http_tracing::CollectorProtocol,
     proxy::http::{h1, h2},
     tls,
-    transport::{DualListenAddr, Keepalive, ListenAddr, UserTimeout},
-    AddrMatch, Conditional, IpNet,
+    transport::{AcceptShards, DualListenAddr, Keepalive, ListenAddr, UserTimeout},
+    AddrMatch, Conditional, IpMatch, IpNet,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -83,6 +85,20 @@ pub enum ParseError {
     ),
     #[error("not a valid port range")]
     NotAPortRange,
+    #[error("not a valid Unix domain socket mapping")]
+    NotAUnixSocket,
+    #[error("not a valid JWT-SVID route mapping")]
+    NotAJwtSvidRoute,
+    #[error("not a valid TLS origination backend mapping")]
+    NotATlsOriginationBackend,
+    #[error("not a valid histogram bucket layout")]
+    NotHistogramBuckets,
+    #[error("{0}")]
+    NotAKeyExchangeGroup(#[from] linkerd_app_core::identity::InvalidKeyExchangeGroup),
+    #[error("not a valid sampling ratio; must be between 0.0 and 1.0")]
+    NotASamplingRatio,
+    #[error("{0}")]
+    NotATraceFormat(#[from] linkerd_app_core::http_tracing::UnknownFormat),
     #[error("{0}")]
     AddrError(#[source] addr::Error),
     #[error("only two addresses are supported")]
@@ -109,6 +125,11 @@ pub const ENV_ADMIN_LISTEN_ADDR: &str = "LINKERD2_PROXY_ADMIN_LISTEN_ADDR";
 
 pub const ENV_METRICS_RETAIN_IDLE: &str = "LINKERD2_PROXY_METRICS_RETAIN_IDLE";
 
+/// Limits the number of distinct label sets in each traffic-labeled metric
+/// family. Once the limit is reached, new label sets are folded into a series
+/// labeled `overflow="true"` in place of the family's traffic labels.
+pub const ENV_METRICS_MAX_SERIES_PER_FAMILY: &str = "LINKERD2_PROXY_METRICS_MAX_SERIES_PER_FAMILY";
+
 pub const ENV_SHUTDOWN_ENDPOINT_ENABLED: &str = "LINKERD2_PROXY_SHUTDOWN_ENDPOINT_ENABLED";
 
 const ENV_INGRESS_MODE: &str = "LINKERD2_PROXY_INGRESS_MODE";
@@ -136,6 +157,11 @@ const ENV_OUTBOUND_CONNECT_KEEPALIVE: &str = "LINKERD2_PROXY_OUTBOUND_CONNECT_KE
 const ENV_INBOUND_ACCEPT_USER_TIMEOUT: &str = "LINKERD2_PROXY_INBOUND_ACCEPT_USER_TIMEOUT";
 const ENV_OUTBOUND_ACCEPT_USER_TIMEOUT: &str = "LINKERD2_PROXY_OUTBOUND_ACCEPT_USER_TIMEOUT";
 
+/// The number of `SO_REUSEPORT` sockets bound for each listener so that
+/// connections are accepted in parallel. Defaults to a single socket.
+const ENV_INBOUND_ACCEPT_SHARDS: &str = "LINKERD2_PROXY_INBOUND_ACCEPT_SHARDS";
+const ENV_OUTBOUND_ACCEPT_SHARDS: &str = "LINKERD2_PROXY_OUTBOUND_ACCEPT_SHARDS";
+
 const ENV_INBOUND_CONNECT_USER_TIMEOUT: &str = "LINKERD2_PROXY_INBOUND_CONNECT_USER_TIMEOUT";
 const ENV_OUTBOUND_CONNECT_USER_TIMEOUT: &str = "LINKERD2_PROXY_OUTBOUND_CONNECT_USER_TIMEOUT";
 
@@ -154,6 +180,35 @@ const ENV_OUTBOUND_METRICS_HOSTNAME_LABELS: &str =
 const ENV_INBOUND_METRICS_AUTHORITY_LABELS: &str =
     "LINKERD2_PROXY_INBOUND_METRICS_AUTHORITY_LABELS";
 
+/// Configures the upper bounds, in seconds, of the buckets of outbound route
+/// request and response duration histograms as comma-separated lists.
+pub const ENV_OUTBOUND_ROUTE_REQUEST_BUCKETS: &str =
+    "LINKERD2_PROXY_OUTBOUND_ROUTE_REQUEST_BUCKETS";
+pub const ENV_OUTBOUND_ROUTE_RESPONSE_BUCKETS: &str =
+    "LINKERD2_PROXY_OUTBOUND_ROUTE_RESPONSE_BUCKETS";
+
+/// When set, outbound route duration histograms use exponential classic
+/// buckets whose bounds grow by a factor of `2^(2^-scale)` for the given scale
+/// (-4 to 3), overriding any explicitly configured buckets. Finer scales are
+/// reduced so that each histogram has a bounded number of buckets. These are
+/// not Prometheus native histograms.
+pub const ENV_OUTBOUND_ROUTE_HISTOGRAM_EXPONENTIAL_SCALE: &str =
+    "LINKERD2_PROXY_OUTBOUND_ROUTE_HISTOGRAM_EXPONENTIAL_SCALE";
+
+const ENV_INBOUND_TRACE_SAMPLING_RATIO: &str = "LINKERD2_PROXY_INBOUND_TRACE_SAMPLING_RATIO";
+const ENV_INBOUND_TRACE_SAMPLING_MAX_PER_SECOND: &str =
+    "LINKERD2_PROXY_INBOUND_TRACE_SAMPLING_MAX_PER_SECOND";
+const ENV_OUTBOUND_TRACE_SAMPLING_RATIO: &str = "LINKERD2_PROXY_OUTBOUND_TRACE_SAMPLING_RATIO";
+const ENV_OUTBOUND_TRACE_SAMPLING_MAX_PER_SECOND: &str =
+    "LINKERD2_PROXY_OUTBOUND_TRACE_SAMPLING_MAX_PER_SECOND";
+
+/// Configures the comma-separated, ordered list of formats (`w3c`, `b3`,
+/// `datadog`, `xray`) from which trace contexts are extracted.
+const ENV_TRACE_EXTRACT_FORMATS: &str = "LINKERD2_PROXY_TRACE_EXTRACT_FORMATS";
+/// Configures the formats in which trace contexts are injected in addition
+/// to the format from which they were extracted.
+const ENV_TRACE_INJECT_FORMATS: &str = "LINKERD2_PROXY_TRACE_INJECT_FORMATS";
+
 const ENV_TRACE_ATTRIBUTES_PATH: &str = "LINKERD2_PROXY_TRACE_ATTRIBUTES_PATH";
 const ENV_TRACE_PROTOCOL: &str = "LINKERD2_PROXY_TRACE_PROTOCOL";
 const ENV_TRACE_SERVICE_NAME: &str = "LINKERD2_PROXY_TRACE_SERVICE_NAME";
@@ -193,6 +248,82 @@ pub const ENV_INBOUND_GATEWAY_SUFFIXES: &str = "LINKERD2_PROXY_INBOUND_GATEWAY_S
 pub const ENV_INBOUND_PORTS_DISABLE_PROTOCOL_DETECTION: &str =
     "LINKERD2_PROXY_INBOUND_PORTS_DISABLE_PROTOCOL_DETECTION";
 
+/// Configures inbound ports on which connections may be prefixed with a PROXY
+/// protocol (v1 or v2) header, e.g. by a load balancer. When a header is present,
+/// the client address it describes is used in place of the connection's peer
+/// address.
+///
+/// Only ports that receive connections exclusively from trusted load balancers
+/// should be configured, as the header is not authenticated.
+pub const ENV_INBOUND_PROXY_PROTOCOL_PORTS: &str = "LINKERD2_PROXY_INBOUND_PROXY_PROTOCOL_PORTS";
+
+/// Configures the networks to which the outbound proxy forwards opaque
+/// connections with a PROXY protocol v2 header describing the original client.
+///
+/// Only networks of non-meshed servers should be configured.
+pub const ENV_OUTBOUND_PROXY_PROTOCOL_NETWORKS: &str =
+    "LINKERD2_PROXY_OUTBOUND_PROXY_PROTOCOL_NETWORKS";
+
+/// Configures the client policies of backends to originate TLS, as a
+/// comma-separated list of `namespace/name=server-name` mappings. Plaintext
+/// HTTP requests to these backends are sent over TLS to servers that present a
+/// certificate for the server name.
+///
+/// The policy controller cannot yet describe backends outside of the mesh, so
+/// this is applied to the policies that it serves.
+pub const ENV_OUTBOUND_TLS_ORIGINATION_BACKENDS: &str =
+    "LINKERD2_PROXY_OUTBOUND_TLS_ORIGINATION_BACKENDS";
+
+/// Configures the path of a PEM-encoded bundle of the CA certificates that
+/// issue the certificates of servers to which TLS is originated. If unset, the
+/// system's CA bundle is used.
+pub const ENV_OUTBOUND_TLS_ORIGINATION_CA_BUNDLE_PATH: &str =
+    "LINKERD2_PROXY_OUTBOUND_TLS_ORIGINATION_CA_BUNDLE_PATH";
+
+/// Configures the paths of a PEM-encoded certificate chain and private key that
+/// the proxy presents to servers to which TLS is originated, if requested.
+pub const ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_CERT_PATH: &str =
+    "LINKERD2_PROXY_OUTBOUND_TLS_ORIGINATION_CLIENT_CERT_PATH";
+pub const ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_KEY_PATH: &str =
+    "LINKERD2_PROXY_OUTBOUND_TLS_ORIGINATION_CLIENT_KEY_PATH";
+
+/// Overrides the location of the system CA bundle.
+const ENV_SSL_CERT_FILE: &str = "SSL_CERT_FILE";
+
+/// Locations of the system CA bundle on common Linux distributions, used when
+/// `SSL_CERT_FILE` is not set.
+const SYSTEM_CA_BUNDLE_PATHS: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+];
+
+/// Configures the mTLS identities of peers (e.g. multicluster gateways) that are
+/// trusted to describe a forwarded connection's original client address and
+/// identity in its transport header. Original clients asserted by other peers are
+/// ignored.
+///
+/// Gateways only describe the original client's identity, and only on opaque
+/// connections: their stacks are shared by all of a client's connections (and
+/// HTTP connections by many clients), so client addresses are never conveyed
+/// through a gateway.
+pub const ENV_INBOUND_ORIGINAL_CLIENT_SENDERS: &str =
+    "LINKERD2_PROXY_INBOUND_ORIGINAL_CLIENT_SENDERS";
+
+/// Configures ports on which the application accepts connections over a Unix
+/// domain socket, as a comma-separated list of `<port>=<path>` entries.
+/// Inbound connections to these ports are forwarded to the socket rather than
+/// to the port over TCP.
+pub const ENV_INBOUND_UNIX_SOCKETS: &str = "LINKERD2_PROXY_INBOUND_UNIX_SOCKETS";
+
+/// Configures whether the mTLS client's certificate (its hash, subject, SANs,
+/// and chain depth) is described to the application in an Envoy-style
+/// `x-forwarded-client-cert` header. When enabled, client-supplied copies of
+/// this header are replaced.
+pub const ENV_INBOUND_FORWARD_CLIENT_CERT: &str = "LINKERD2_PROXY_INBOUND_FORWARD_CLIENT_CERT";
+
 pub const ENV_INBOUND_PORTS_REQUIRE_IDENTITY: &str =
     "LINKERD2_PROXY_INBOUND_PORTS_REQUIRE_IDENTITY";
 
@@ -216,10 +347,54 @@ pub const ENV_INBOUND_IPS: &str = "LINKERD2_PROXY_INBOUND_IPS";
 pub const ENV_IDENTITY_DISABLED: &str = "LINKERD2_PROXY_IDENTITY_DISABLED";
 pub const ENV_IDENTITY_DIR: &str = "LINKERD2_PROXY_IDENTITY_DIR";
 pub const ENV_IDENTITY_TRUST_ANCHORS: &str = "LINKERD2_PROXY_IDENTITY_TRUST_ANCHORS";
+
+/// Configures a path to a PEM-encoded trust bundle file. When set, the proxy
+/// reloads its trust anchors whenever the file's contents change. The bundle
+/// may contain multiple roots to support root rotation.
+///
+/// If `LINKERD2_PROXY_IDENTITY_TRUST_ANCHORS` is not set, the initial trust
+/// anchors are read from this file.
+pub const ENV_IDENTITY_TRUST_ANCHORS_PATH: &str = "LINKERD2_PROXY_IDENTITY_TRUST_ANCHORS_PATH";
+
+/// Configures a directory of DER- or PEM-encoded certificate revocation lists.
+/// When set, peer certificates are checked against these CRLs, which are
+/// reloaded whenever the directory's contents change.
+pub const ENV_IDENTITY_CRL_DIR: &str = "LINKERD2_PROXY_IDENTITY_CRL_DIR";
+
+/// If true, peer certificates are accepted when their revocation status cannot
+/// be determined (i.e. no CRL signed by their issuer is available).
+///
+/// By default, such certificates are rejected.
+pub const ENV_IDENTITY_CRL_SOFT_FAIL: &str = "LINKERD2_PROXY_IDENTITY_CRL_SOFT_FAIL";
+
+/// If true, peer certificates are rejected when their issuer's CRLs are past
+/// their next update.
+///
+/// By default, stale CRLs are still used to reject the certificates they list,
+/// and other certificates are accepted with a warning.
+pub const ENV_IDENTITY_CRL_REJECT_STALE: &str = "LINKERD2_PROXY_IDENTITY_CRL_REJECT_STALE";
+
+/// Configures a comma-separated list of TLS key exchange groups, in order of
+/// preference (e.g. `X25519MLKEM768,X25519,secp256r1`).
+///
+/// By default, the TLS backend's defaults are used. When the proxy is built
+/// with aws-lc, these prefer the hybrid post-quantum `X25519MLKEM768` group.
+pub const ENV_IDENTITY_TLS_KEY_EXCHANGE_GROUPS: &str =
+    "LINKERD2_PROXY_IDENTITY_TLS_KEY_EXCHANGE_GROUPS";
 pub const ENV_IDENTITY_TOKEN_FILE: &str = "LINKERD2_PROXY_IDENTITY_TOKEN_FILE";
 pub const ENV_IDENTITY_MIN_REFRESH: &str = "LINKERD2_PROXY_IDENTITY_MIN_REFRESH";
 pub const ENV_IDENTITY_MAX_REFRESH: &str = "LINKERD2_PROXY_IDENTITY_MAX_REFRESH";
 
+/// Configures paths to the proxy's PEM-encoded certificate chain and ECDSA
+/// private key (e.g. as mounted by cert-manager's CSI driver). When set, the
+/// proxy loads its identity from these files, reloading it whenever they
+/// change, instead of obtaining certificates from a control plane.
+///
+/// The key may be PKCS#8- or SEC1-encoded, so cert-manager's default
+/// `privateKey.encoding` may be used; RSA keys are not supported.
+pub const ENV_IDENTITY_CERT_PATH: &str = "LINKERD2_PROXY_IDENTITY_CERT_PATH";
+pub const ENV_IDENTITY_KEY_PATH: &str = "LINKERD2_PROXY_IDENTITY_KEY_PATH";
+
 /// This config is here for backwards compatibility. If set, both the tls id and the server
 /// name will be set to the value specified in this config. The values needs to be a DNS
 /// name
@@ -236,6 +411,20 @@ pub const ENV_IDENTITY_IDENTITY_SERVER_NAME: &str = "LINKERD2_PROXY_IDENTITY_SER
 // provider
 pub const ENV_IDENTITY_SPIRE_SOCKET: &str = "LINKERD2_PROXY_IDENTITY_SPIRE_SOCKET";
 pub const IDENTITY_SPIRE_BASE: &str = "LINKERD2_PROXY_IDENTITY_SPIRE";
+/// Configures the audiences of the JWT-SVIDs injected on outbound requests, as
+/// a comma-separated list of `namespace/name=audience` mappings from HTTP
+/// routes. Requires SPIRE identity.
+pub const ENV_IDENTITY_SPIRE_JWT_SVID_OUTBOUND_ROUTES: &str =
+    "LINKERD2_PROXY_IDENTITY_SPIRE_JWT_SVID_OUTBOUND_ROUTES";
+/// The audience for which the JWT-SVIDs required by inbound routes are
+/// validated. Requires SPIRE identity.
+pub const ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_AUDIENCE: &str =
+    "LINKERD2_PROXY_IDENTITY_SPIRE_JWT_SVID_INBOUND_AUDIENCE";
+/// Names the inbound HTTP and gRPC routes on which requests must bear a
+/// JWT-SVID, as a comma-separated list. Bearer tokens on other routes are not
+/// inspected. Requires an inbound audience.
+pub const ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_ROUTES: &str =
+    "LINKERD2_PROXY_IDENTITY_SPIRE_JWT_SVID_INBOUND_ROUTES";
 const DEFAULT_SPIRE_BACKOFF: ExponentialBackoff =
     ExponentialBackoff::new_unchecked(Duration::from_millis(100), Duration::from_secs(1), 0.1);
 const SPIFFE_ID_URI_SCHEME: &str = "spiffe";
@@ -248,6 +437,17 @@ pub const ENV_HOSTNAME: &str = "HOSTNAME";
 
 pub const ENV_TRACE_COLLECTOR_SVC_BASE: &str = "LINKERD2_PROXY_TRACE_COLLECTOR_SVC";
 
+/// Configures an OpenTelemetry collector to which the proxy's metrics are
+/// pushed, in addition to being served by the admin server.
+///
+/// The collector's resource attributes are configured by the same
+/// environment variables as the trace collector.
+pub const ENV_METRICS_COLLECTOR_SVC_BASE: &str = "LINKERD2_PROXY_METRICS_COLLECTOR_SVC";
+
+/// Configures how often metrics are pushed to the metrics collector.
+const ENV_METRICS_COLLECTOR_EXPORT_INTERVAL: &str =
+    "LINKERD2_PROXY_METRICS_COLLECTOR_EXPORT_INTERVAL";
+
 pub const ENV_DESTINATION_CONTEXT: &str = "LINKERD2_PROXY_DESTINATION_CONTEXT";
 pub const ENV_DESTINATION_PROFILE_INITIAL_TIMEOUT: &str =
     "LINKERD2_PROXY_DESTINATION_PROFILE_INITIAL_TIMEOUT";
@@ -284,6 +484,12 @@ pub const DEFAULT_CONTROL_LISTEN_ADDR: &str = "0.0.0.0:4190";
 
 const DEFAULT_ADMIN_LISTEN_ADDR: &str = "127.0.0.1:4191";
 const DEFAULT_METRICS_RETAIN_IDLE: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_METRICS_COLLECTOR_EXPORT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The range of durations, in seconds, covered by exponential route histogram
+/// buckets.
+const OUTBOUND_ROUTE_EXPONENTIAL_BUCKETS_MIN: f64 = 0.001;
+const OUTBOUND_ROUTE_EXPONENTIAL_BUCKETS_MAX: f64 = 60.0;
 
 const DEFAULT_INBOUND_HTTP_QUEUE_CAPACITY: usize = 10_000;
 const DEFAULT_INBOUND_HTTP_FAILFAST_TIMEOUT: Duration = Duration::from_secs(1);
@@ -397,6 +603,9 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
     let outbound_accept_user_timeout =
         parse(strings, ENV_OUTBOUND_ACCEPT_USER_TIMEOUT, parse_duration);
 
+    let inbound_accept_shards = parse(strings, ENV_INBOUND_ACCEPT_SHARDS, parse_number::<usize>);
+    let outbound_accept_shards = parse(strings, ENV_OUTBOUND_ACCEPT_SHARDS, parse_number::<usize>);
+
     let inbound_connect_user_timeout =
         parse(strings, ENV_INBOUND_CONNECT_USER_TIMEOUT, parse_duration);
     let outbound_connect_user_timeout =
@@ -424,6 +633,7 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
     let outbound_max_in_flight = parse(strings, ENV_OUTBOUND_MAX_IN_FLIGHT, parse_number);
 
     let metrics_retain_idle = parse(strings, ENV_METRICS_RETAIN_IDLE, parse_duration);
+    let metrics_max_series = parse(strings, ENV_METRICS_MAX_SERIES_PER_FAMILY, parse_number);
 
     let control_receive_limits = control::mk_receive_limits(strings)?;
 
@@ -445,6 +655,15 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
     let trace_service_name = strings.get(ENV_TRACE_SERVICE_NAME);
 
     let trace_collector_addr = parse_control_addr(strings, ENV_TRACE_COLLECTOR_SVC_BASE);
+    let trace_formats =
+        trace::parse_formats(strings, ENV_TRACE_EXTRACT_FORMATS, ENV_TRACE_INJECT_FORMATS)?;
+
+    let metrics_collector_addr = parse_control_addr(strings, ENV_METRICS_COLLECTOR_SVC_BASE);
+    let metrics_collector_export_interval = parse(
+        strings,
+        ENV_METRICS_COLLECTOR_EXPORT_INTERVAL,
+        parse_duration,
+    );
 
     let gateway_suffixes = parse(strings, ENV_INBOUND_GATEWAY_SUFFIXES, parse_dns_suffixes);
 
@@ -484,6 +703,13 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
         std::sync::Arc::new(ips)
     };
 
+    let tls_origination_backends = parse(
+        strings,
+        ENV_OUTBOUND_TLS_ORIGINATION_BACKENDS,
+        parse_tls_origination_backends,
+    )?
+    .unwrap_or_default();
+
     let outbound = {
         let ingress_mode = parse(strings, ENV_INGRESS_MODE, parse_bool)?.unwrap_or(false);
 
@@ -506,10 +732,14 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
             }
         };
 
+        let accept_shards = outbound_accept_shards?
+            .map(|n| AcceptShards(n.max(1)))
+            .unwrap_or_default();
         let keepalive = Keepalive(outbound_accept_keepalive?);
         let user_timeout = UserTimeout(outbound_accept_user_timeout?);
         let server = ServerConfig {
             addr,
+            accept_shards,
             keepalive,
             user_timeout,
             http2: http2::parse_server(strings, "LINKERD2_PROXY_OUTBOUND_SERVER_HTTP2")?,
@@ -561,9 +791,68 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
         let http_failfast_timeout =
             outbound_http_failfast_timeout?.unwrap_or(DEFAULT_OUTBOUND_HTTP_FAILFAST_TIMEOUT);
 
+        let proxy_protocol_networks = parse(
+            strings,
+            ENV_OUTBOUND_PROXY_PROTOCOL_NETWORKS,
+            parse_networks,
+        )?
+        .unwrap_or_default();
+
+        let route_histograms = {
+            use outbound::http::policy::{HistogramBuckets, RouteHistograms};
+
+            match parse(
+                strings,
+                ENV_OUTBOUND_ROUTE_HISTOGRAM_EXPONENTIAL_SCALE,
+                parse_histogram_scale,
+            )? {
+                Some(scale) => {
+                    let buckets = HistogramBuckets::Exponential {
+                        scale,
+                        min: OUTBOUND_ROUTE_EXPONENTIAL_BUCKETS_MIN,
+                        max: OUTBOUND_ROUTE_EXPONENTIAL_BUCKETS_MAX,
+                    };
+                    RouteHistograms {
+                        request: buckets.clone(),
+                        response: buckets,
+                    }
+                }
+                None => {
+                    let default = RouteHistograms::default();
+                    RouteHistograms {
+                        request: parse(
+                            strings,
+                            ENV_OUTBOUND_ROUTE_REQUEST_BUCKETS,
+                            parse_histogram_buckets,
+                        )?
+                        .map_or(default.request, HistogramBuckets::Explicit),
+                        response: parse(
+                            strings,
+                            ENV_OUTBOUND_ROUTE_RESPONSE_BUCKETS,
+                            parse_histogram_buckets,
+                        )?
+                        .map_or(default.response, HistogramBuckets::Explicit),
+                    }
+                }
+            }
+        };
+
+        let trace_sampler = trace::parse_sampler(
+            strings,
+            ENV_OUTBOUND_TRACE_SAMPLING_RATIO,
+            ENV_OUTBOUND_TRACE_SAMPLING_MAX_PER_SECOND,
+        )?;
+
+        let tls_origination = parse_tls_origination(strings, &tls_origination_backends)?;
+
         outbound::Config {
+            route_histograms,
+            trace_sampler,
+            tls_origination,
+            trace_formats: trace_formats.clone(),
             ingress_mode,
             emit_headers: !disable_headers,
+            proxy_protocol_networks: IpMatch::new(proxy_protocol_networks),
             allow_discovery: AddrMatch::new(dst_profile_suffixes.clone(), dst_profile_networks),
             proxy: ProxyConfig {
                 server,
@@ -598,10 +887,14 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
                 .unwrap_or_else(|| parse_socket_addr(DEFAULT_INBOUND_LISTEN_ADDR).unwrap()),
             None,
         );
+        let accept_shards = inbound_accept_shards?
+            .map(|n| AcceptShards(n.max(1)))
+            .unwrap_or_default();
         let keepalive = Keepalive(inbound_accept_keepalive?);
         let user_timeout = UserTimeout(inbound_accept_user_timeout?);
         let server = ServerConfig {
             addr,
+            accept_shards,
             keepalive,
             user_timeout,
             http2: http2::parse_server(strings, "LINKERD2_PROXY_INBOUND_SERVER_HTTP2")?,
@@ -719,6 +1012,26 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
             }
         };
 
+        let proxy_protocol_ports = parse(
+            strings,
+            ENV_INBOUND_PROXY_PROTOCOL_PORTS,
+            parse_port_range_set,
+        )?
+        .unwrap_or_default();
+
+        let original_client_senders = parse(
+            strings,
+            ENV_INBOUND_ORIGINAL_CLIENT_SENDERS,
+            parse_identities,
+        )?
+        .unwrap_or_default();
+
+        let unix_sockets =
+            parse(strings, ENV_INBOUND_UNIX_SOCKETS, parse_unix_sockets)?.unwrap_or_default();
+
+        let forward_client_cert =
+            parse(strings, ENV_INBOUND_FORWARD_CLIENT_CERT, parse_bool)?.unwrap_or(false);
+
         inbound::Config {
             allow_discovery: dst_profile_suffixes.into_iter().collect(),
             proxy: ProxyConfig {
@@ -741,6 +1054,19 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
                     .unwrap_or(DEFAULT_INBOUND_HTTP_FAILFAST_TIMEOUT),
             },
             unsafe_authority_labels,
+            proxy_protocol_ports: std::sync::Arc::new(proxy_protocol_ports),
+            original_client_senders: std::sync::Arc::new(original_client_senders),
+            unix_sockets: std::sync::Arc::new(unix_sockets),
+            trace_sampler: trace::parse_sampler(
+                strings,
+                ENV_INBOUND_TRACE_SAMPLING_RATIO,
+                ENV_INBOUND_TRACE_SAMPLING_MAX_PER_SECOND,
+            )?,
+            trace_formats,
+            // Validation requires a Workload API client, so it is
+            // configured when the application is built.
+            jwt_svid: None,
+            forward_client_cert,
         }
     };
 
@@ -814,13 +1140,16 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
             workload,
             limits,
             export_hostname_labels,
+            tls_origination_backends: std::sync::Arc::new(tls_origination_backends),
         }
     };
 
     let admin = super::admin::Config {
         metrics_retain_idle: metrics_retain_idle?.unwrap_or(DEFAULT_METRICS_RETAIN_IDLE),
+        metrics_max_series: metrics_max_series?,
         server: ServerConfig {
             addr: DualListenAddr(admin_listener_addr, None),
+            accept_shards: AcceptShards::default(),
             keepalive: inbound.proxy.server.keepalive,
             user_timeout: inbound.proxy.server.user_timeout,
             http2: inbound.proxy.server.http2.clone(),
@@ -839,6 +1168,30 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
         max_ttl: dns_max_ttl?,
     };
 
+    let resource_attributes = || {
+        let mut attributes = trace_attributes_file_path
+            .as_ref()
+            .map(
+                |path| match path.as_ref().and_then(|p| p.parse::<PathBuf>().ok()) {
+                    Some(path) => trace::read_trace_attributes(&path),
+                    None => HashMap::new(),
+                },
+            )
+            .unwrap_or_default();
+        if let Ok(Some(attrs)) = &trace_extra_attributes {
+            if !attrs.is_empty() {
+                attributes.extend(trace::parse_env_trace_attributes(attrs));
+            }
+        }
+        if let Ok(Some(attrs)) = &trace_otel_attributes {
+            if !attrs.is_empty() {
+                attributes.extend(trace::parse_env_trace_attributes(attrs));
+            }
+        }
+        attributes
+    };
+    let trace_service_name = trace_service_name.ok().flatten();
+
     let trace_collector = match trace_collector_addr? {
         None => trace_collector::Config::Disabled,
         Some(addr) => {
@@ -852,22 +1205,7 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
             } else {
                 outbound.http_request_queue.failfast_timeout
             };
-            let mut attributes = trace_attributes_file_path
-                .map(|path| match path.and_then(|p| p.parse::<PathBuf>().ok()) {
-                    Some(path) => trace::read_trace_attributes(&path),
-                    None => HashMap::new(),
-                })
-                .unwrap_or_default();
-            if let Ok(Some(attrs)) = trace_extra_attributes {
-                if !attrs.is_empty() {
-                    attributes.extend(trace::parse_env_trace_attributes(&attrs));
-                }
-            }
-            if let Ok(Some(attrs)) = trace_otel_attributes {
-                if !attrs.is_empty() {
-                    attributes.extend(trace::parse_env_trace_attributes(&attrs));
-                }
-            }
+            let attributes = resource_attributes();
 
             let trace_protocol = trace_protocol
                 .map(|proto| proto.and_then(|p| p.parse::<CollectorProtocol>().ok()))
@@ -875,12 +1213,42 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
                 .flatten()
                 .unwrap_or_default();
 
-            let trace_service_name = trace_service_name.ok().flatten();
-
             trace_collector::Config::Enabled(Box::new(trace_collector::EnabledConfig {
                 attributes,
+                hostname: hostname.clone()?,
+                service_name: trace_service_name.clone(),
+                control: ControlConfig {
+                    addr,
+                    connect,
+                    buffer: QueueConfig {
+                        capacity: DEFAULT_CONTROL_QUEUE_CAPACITY,
+                        failfast_timeout,
+                    },
+                },
+                kind: trace_protocol,
+            }))
+        }
+    };
+
+    let metrics_collector = match metrics_collector_addr? {
+        None => metrics_collector::Config::Disabled,
+        Some(addr) => {
+            let connect = if addr.addr.is_loopback() {
+                inbound.proxy.connect.clone()
+            } else {
+                outbound.proxy.connect.clone()
+            };
+            let failfast_timeout = if addr.addr.is_loopback() {
+                inbound.http_request_queue.failfast_timeout
+            } else {
+                outbound.http_request_queue.failfast_timeout
+            };
+            metrics_collector::Config::Enabled(Box::new(metrics_collector::EnabledConfig {
+                attributes: resource_attributes(),
                 hostname: hostname?,
                 service_name: trace_service_name,
+                interval: metrics_collector_export_interval?
+                    .unwrap_or(DEFAULT_METRICS_COLLECTOR_EXPORT_INTERVAL),
                 control: ControlConfig {
                     addr,
                     connect,
@@ -889,7 +1257,6 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
                         failfast_timeout,
                     },
                 },
-                kind: trace_protocol,
             }))
         }
     };
@@ -899,6 +1266,7 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
             permitted_client_ids: ids,
             config: ServerConfig {
                 addr: DualListenAddr(addr, None),
+                accept_shards: AcceptShards::default(),
                 keepalive: inbound.proxy.server.keepalive,
                 user_timeout: inbound.proxy.server.user_timeout,
                 http2: inbound.proxy.server.http2.clone(),
@@ -908,9 +1276,16 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
 
     let identity = {
         let tls = tls?;
+        let files = parse_identity_files(strings)?;
 
-        match strings.get(ENV_IDENTITY_SPIRE_SOCKET)? {
-            Some(socket) => match &tls.id {
+        match (strings.get(ENV_IDENTITY_SPIRE_SOCKET)?, files) {
+            (Some(_), Some(_)) => {
+                error!(
+                    "{ENV_IDENTITY_SPIRE_SOCKET} and {ENV_IDENTITY_CERT_PATH} cannot both be set"
+                );
+                return Err(EnvError::InvalidEnvVar);
+            }
+            (Some(socket), None) => match &tls.id {
                 // TODO: perform stricter SPIFFE ID validation following:
                 // https://github.com/spiffe/spiffe/blob/27b59b81ba8c56885ac5d4be73b35b9b3305fd7a/standards/SPIFFE-ID.md
                 identity::Id::Uri(uri)
@@ -933,7 +1308,8 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
                     return Err(EnvError::InvalidEnvVar);
                 }
             },
-            None => {
+            (None, Some(files)) => identity::Config::File { files, tls },
+            (None, None) => {
                 let (addr, certify) = parse_linkerd_identity_config(strings)?;
 
                 // If the address doesn't have a server identity, then we're on localhost.
@@ -964,17 +1340,67 @@ pub fn parse_config<S: Strings>(strings: &S) -> Result<super::Config, EnvError>
         }
     };
 
+    let jwt_svid = {
+        let outbound_routes = parse(
+            strings,
+            ENV_IDENTITY_SPIRE_JWT_SVID_OUTBOUND_ROUTES,
+            parse_jwt_svid_routes,
+        )?
+        .unwrap_or_default();
+        let inbound_audience = strings
+            .get(ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_AUDIENCE)?
+            .filter(|aud| !aud.trim().is_empty())
+            .map(|aud| aud.trim().into());
+        let inbound_routes = parse(
+            strings,
+            ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_ROUTES,
+            parse_route_names,
+        )?
+        .unwrap_or_default();
+        let inbound = match inbound_audience {
+            Some(audience) if !inbound_routes.is_empty() => Some((audience, inbound_routes)),
+            Some(_) => {
+                warn!("No inbound routes require JWT-SVIDs; ignoring the inbound audience");
+                None
+            }
+            None if !inbound_routes.is_empty() => {
+                error!("{ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_ROUTES} requires {ENV_IDENTITY_SPIRE_JWT_SVID_INBOUND_AUDIENCE}");
+                return Err(EnvError::InvalidEnvVar);
+            }
+            None => None,
+        };
+
+        match identity {
+            _ if outbound_routes.is_empty() && inbound.is_none() => None,
+            identity::Config::Spire {
+                ref client,
+                ref tls,
+            } => Some(spire::JwtConfig {
+                client: client.clone(),
+                spiffe_id: tls.id.clone(),
+                outbound_routes,
+                inbound,
+            }),
+            _ => {
+                error!("JWT-SVIDs require Spire identity");
+                return Err(EnvError::InvalidEnvVar);
+            }
+        }
+    };
+
     Ok(super::Config {
         admin,
         dns,
         dst,
         tap,
         trace_collector,
+        metrics_collector,
         policy,
         identity,
         outbound,
         gateway,
         inbound,
+        jwt_svid,
         shutdown_grace_period: shutdown_grace_period?.unwrap_or(DEFAULT_SHUTDOWN_GRACE_PERIOD),
     })
 }
@@ -1156,6 +1582,77 @@ pub fn parse_control_addr<S: Strings>(
     }
 }
 
+fn parse_tls_origination<S: Strings>(
+    strings: &S,
+    backends: &HashMap<outbound::policy::BackendName, outbound::policy::TlsOrigination>,
+) -> Result<outbound::http::TlsOrigination, EnvError> {
+    if backends.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let read = |var: &str, path: &std::path::Path| match std::fs::read_to_string(path) {
+        Ok(pem) => Ok(pem),
+        Err(error) => {
+            error!(%error, "Failed to read {var}={}", path.display());
+            Err(EnvError::InvalidEnvVar)
+        }
+    };
+
+    let roots_pem = match strings.get(ENV_OUTBOUND_TLS_ORIGINATION_CA_BUNDLE_PATH)? {
+        Some(path) => read(ENV_OUTBOUND_TLS_ORIGINATION_CA_BUNDLE_PATH, path.as_ref())?,
+        None => {
+            let path = strings
+                .get(ENV_SSL_CERT_FILE)?
+                .map(PathBuf::from)
+                .or_else(|| {
+                    SYSTEM_CA_BUNDLE_PATHS
+                        .iter()
+                        .map(PathBuf::from)
+                        .find(|p| p.is_file())
+                });
+            let Some(path) = path else {
+                error!(
+                    "{ENV_OUTBOUND_TLS_ORIGINATION_CA_BUNDLE_PATH} must be set when no system CA bundle is available"
+                );
+                return Err(EnvError::InvalidEnvVar);
+            };
+            debug!(path = %path.display(), "Using system CA bundle for TLS origination");
+            read(ENV_SSL_CERT_FILE, &path)?
+        }
+    };
+
+    let client_cert = match (
+        strings.get(ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_CERT_PATH)?,
+        strings.get(ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_KEY_PATH)?,
+    ) {
+        (None, None) => None,
+        (Some(cert), Some(key)) => Some((
+            read(ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_CERT_PATH, cert.as_ref())?,
+            read(ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_KEY_PATH, key.as_ref())?,
+        )),
+        _ => {
+            error!(
+                "{ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_CERT_PATH} and {ENV_OUTBOUND_TLS_ORIGINATION_CLIENT_KEY_PATH} must be set together"
+            );
+            return Err(EnvError::InvalidEnvVar);
+        }
+    };
+
+    let client = linkerd_app_core::identity::web_pki::NewClient::new(
+        linkerd_app_core::identity::Mode::default(),
+        &roots_pem,
+        client_cert.as_ref().map(|(chain_pem, key_pem)| {
+            linkerd_app_core::identity::web_pki::ClientCert { chain_pem, key_pem }
+        }),
+    )
+    .map_err(|error| {
+        error!(%error, "Invalid TLS origination configuration");
+        EnvError::InvalidEnvVar
+    })?;
+
+    Ok(outbound::http::TlsOrigination::new(client))
+}
+
 pub fn parse_tls_params<S: Strings>(strings: &S) -> Result<identity::TlsParams, EnvError> {
     let ta = parse(strings, ENV_IDENTITY_TRUST_ANCHORS, |s| {
         if s.is_empty() {
@@ -1163,6 +1660,20 @@ pub fn parse_tls_params<S: Strings>(strings: &S) -> Result<identity::TlsParams,
         }
         Ok(s.to_string())
     });
+    let ta_path = parse(strings, ENV_IDENTITY_TRUST_ANCHORS_PATH, |s| {
+        if s.is_empty() {
+            return Err(ParseError::InvalidTrustAnchors);
+        }
+        Ok(PathBuf::from(s))
+    });
+    let crl_dir = parse(strings, ENV_IDENTITY_CRL_DIR, |s| Ok(PathBuf::from(s)));
+    let crl_soft_fail = parse(strings, ENV_IDENTITY_CRL_SOFT_FAIL, parse_bool);
+    let crl_reject_stale = parse(strings, ENV_IDENTITY_CRL_REJECT_STALE, parse_bool);
+    let key_exchange_groups = parse(
+        strings,
+        ENV_IDENTITY_TLS_KEY_EXCHANGE_GROUPS,
+        parse_key_exchange_groups,
+    );
 
     // The assumtion here is that if `ENV_IDENTITY_IDENTITY_LOCAL_NAME` has been set
     // we will use that for both tls id and server name.
@@ -1191,12 +1702,50 @@ pub fn parse_tls_params<S: Strings>(strings: &S) -> Result<identity::TlsParams,
         return Err(EnvError::InvalidEnvVar);
     }
 
-    match (ta?, server_id?, server_name?) {
+    let trust_anchors_path = ta_path?;
+    let ta = match (ta?, &trust_anchors_path) {
+        (None, Some(path)) => match std::fs::read_to_string(path) {
+            Ok(pem) if !pem.is_empty() => Some(pem),
+            Ok(_) => {
+                error!(
+                    "{ENV_IDENTITY_TRUST_ANCHORS_PATH}={} is empty",
+                    path.display()
+                );
+                return Err(EnvError::InvalidEnvVar);
+            }
+            Err(error) => {
+                error!(%error, "Failed to read {ENV_IDENTITY_TRUST_ANCHORS_PATH}={}", path.display());
+                return Err(EnvError::InvalidEnvVar);
+            }
+        },
+        (ta, _) => ta,
+    };
+
+    let crl_soft_fail = crl_soft_fail?.unwrap_or(false);
+    let crl_reject_stale = crl_reject_stale?.unwrap_or(false);
+    let revocation = crl_dir?.map(|crl_dir| identity::RevocationParams {
+        crl_dir,
+        unknown_status: if crl_soft_fail {
+            identity::UnknownStatus::Allow
+        } else {
+            identity::UnknownStatus::Deny
+        },
+        stale_status: if crl_reject_stale {
+            identity::UnknownStatus::Deny
+        } else {
+            identity::UnknownStatus::Allow
+        },
+    });
+
+    match (ta, server_id?, server_name?) {
         (Some(trust_anchors_pem), Some(server_id), Some(server_name)) => {
             let params = identity::TlsParams {
                 id: server_id,
                 server_name,
                 trust_anchors_pem,
+                trust_anchors_path,
+                revocation,
+                key_exchange_groups: key_exchange_groups?,
             };
             Ok(params)
         }
@@ -1215,6 +1764,22 @@ pub fn parse_tls_params<S: Strings>(strings: &S) -> Result<identity::TlsParams,
     }
 }
 
+fn parse_identity_files<S: Strings>(strings: &S) -> Result<Option<identity::FileConfig>, EnvError> {
+    let cert_path = parse(strings, ENV_IDENTITY_CERT_PATH, |s| Ok(PathBuf::from(s)))?;
+    let key_path = parse(strings, ENV_IDENTITY_KEY_PATH, |s| Ok(PathBuf::from(s)))?;
+    match (cert_path, key_path) {
+        (None, None) => Ok(None),
+        (Some(cert_path), Some(key_path)) => Ok(Some(identity::FileConfig {
+            cert_path,
+            key_path,
+        })),
+        _ => {
+            error!("{ENV_IDENTITY_CERT_PATH} and {ENV_IDENTITY_KEY_PATH} must be set together");
+            Err(EnvError::InvalidEnvVar)
+        }
+    }
+}
+
 pub fn parse_linkerd_identity_config<S: Strings>(
     strings: &S,
 ) -> Result<(ControlAddr, identity::client::linkerd::Config), EnvError> {
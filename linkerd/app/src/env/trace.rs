@@ -1,3 +1,5 @@
+use super::{parse, parse_number, EnvError, ParseError, Strings};
+use linkerd_app_core::http_tracing::{Format, Formats, Sampler};
 use std::collections::HashMap;
 
 pub(super) fn read_trace_attributes(path: &std::path::Path) -> HashMap<String, String> {
@@ -18,6 +20,52 @@ pub(super) fn parse_env_trace_attributes(attrs: &str) -> HashMap<String, String>
     parse_attrs(attrs)
 }
 
+/// Configures whether the proxy starts traces for requests that do not carry
+/// a trace context.
+pub(super) fn parse_sampler(
+    strings: &dyn Strings,
+    ratio: &str,
+    max_per_second: &str,
+) -> Result<Sampler, EnvError> {
+    let ratio = parse(strings, ratio, parse_sampling_ratio)?;
+    let max_per_second = parse(strings, max_per_second, parse_number)?;
+    Ok(Sampler::new(ratio, max_per_second))
+}
+
+/// Configures the formats in which trace contexts are extracted and injected.
+pub(super) fn parse_formats(
+    strings: &dyn Strings,
+    extract: &str,
+    inject: &str,
+) -> Result<Formats, EnvError> {
+    let default = Formats::default();
+    let extract = parse(strings, extract, parse_format_list)?;
+    let inject = parse(strings, inject, parse_format_list)?;
+    Ok(match (extract, inject) {
+        (None, None) => default,
+        (extract, inject) => Formats::new(
+            extract.unwrap_or_else(|| default.extract().to_vec()),
+            inject.unwrap_or_default(),
+        ),
+    })
+}
+
+fn parse_format_list(s: &str) -> Result<Vec<Format>, ParseError> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().map_err(Into::into))
+        .collect()
+}
+
+fn parse_sampling_ratio(s: &str) -> Result<f64, ParseError> {
+    let ratio = parse_number::<f64>(s)?;
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err(ParseError::NotASamplingRatio);
+    }
+    Ok(ratio)
+}
+
 fn parse_attrs(attrs: &str) -> HashMap<String, String> {
     attrs
         .lines()
@@ -73,3 +121,25 @@ fn parse_attrs_different_values() {
 
     assert_eq!(parse_attrs(attrs), expected);
 }
+
+#[cfg(test)]
+#[test]
+fn parse_sampling_ratios() {
+    assert_eq!(parse_sampling_ratio("0").unwrap(), 0.0);
+    assert_eq!(parse_sampling_ratio("0.25").unwrap(), 0.25);
+    assert_eq!(parse_sampling_ratio("1.0").unwrap(), 1.0);
+    assert!(parse_sampling_ratio("1.5").is_err());
+    assert!(parse_sampling_ratio("-0.1").is_err());
+    assert!(parse_sampling_ratio("NaN").is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn parse_format_lists() {
+    assert_eq!(
+        parse_format_list("datadog, w3c").unwrap(),
+        vec![Format::Datadog, Format::W3C]
+    );
+    assert_eq!(parse_format_list("").unwrap(), vec![]);
+    assert!(parse_format_list("w3c,jaeger").is_err());
+}
@@ -1,10 +1,16 @@
 use super::ParseError;
-use linkerd_app_core::{dns, identity, Addr, IpNet};
+use crate::outbound::{
+    http::policy::HistogramBuckets,
+    policy::{http::RouteName, BackendName, TlsOrigination},
+};
+use linkerd_app_core::{dns, identity, transport::UnixPath, Addr, IpNet};
 use rangemap::RangeInclusiveSet;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     net::{IpAddr, SocketAddr},
+    path::Path,
     str::FromStr,
+    sync::Arc,
     time::Duration,
 };
 use tracing::error;
@@ -23,6 +29,17 @@ pub(crate) fn parse_identity(s: &str) -> Result<identity::Id, ParseError> {
     })
 }
 
+pub(super) fn parse_identities(list: &str) -> Result<HashSet<identity::Id>, ParseError> {
+    let mut ids = HashSet::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if !item.is_empty() {
+            ids.insert(parse_identity(item)?);
+        }
+    }
+    Ok(ids)
+}
+
 pub(super) fn parse_bool(s: &str) -> Result<bool, ParseError> {
     s.parse().map_err(Into::into)
 }
@@ -129,6 +146,158 @@ pub(super) fn parse_port_range_set(s: &str) -> Result<RangeInclusiveSet<u16>, Pa
     Ok(set)
 }
 
+pub(super) fn parse_unix_sockets(list: &str) -> Result<HashMap<u16, UnixPath>, ParseError> {
+    let mut sockets = HashMap::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let Some((port, path)) = item.split_once('=') else {
+            error!("Not a valid Unix domain socket mapping: {item}");
+            return Err(ParseError::NotAUnixSocket);
+        };
+        let port = parse_number::<u16>(port.trim())?;
+        let path = Path::new(path.trim());
+        if !path.is_absolute() {
+            error!("Unix domain socket path must be absolute: {item}");
+            return Err(ParseError::NotAUnixSocket);
+        }
+        sockets.insert(port, UnixPath(path.into()));
+    }
+    Ok(sockets)
+}
+
+/// Parses a comma-separated list of `namespace/name=audience` mappings from
+/// HTTP routes to JWT-SVID audiences.
+pub(super) fn parse_jwt_svid_routes(
+    list: &str,
+) -> Result<HashMap<RouteName, Arc<str>>, ParseError> {
+    let mut routes = HashMap::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let Some((route, audience)) = item.split_once('=') else {
+            error!("Not a valid JWT-SVID route mapping: {item}");
+            return Err(ParseError::NotAJwtSvidRoute);
+        };
+        let Some((namespace, name)) = route.trim().split_once('/') else {
+            error!("JWT-SVID route must be qualified by its namespace: {item}");
+            return Err(ParseError::NotAJwtSvidRoute);
+        };
+        let (namespace, name, audience) = (namespace.trim(), name.trim(), audience.trim());
+        if namespace.is_empty() || name.is_empty() || audience.is_empty() {
+            error!("Not a valid JWT-SVID route mapping: {item}");
+            return Err(ParseError::NotAJwtSvidRoute);
+        }
+        let route = RouteName {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+        routes.insert(route, audience.into());
+    }
+    Ok(routes)
+}
+
+/// Parses a comma-separated list of `namespace/name=server-name` mappings from
+/// backends to the names of the servers to which TLS is originated.
+/// Parses a comma-separated list of route names.
+pub(super) fn parse_route_names(list: &str) -> Result<HashSet<String>, ParseError> {
+    Ok(list
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .collect())
+}
+
+pub(super) fn parse_tls_origination_backends(
+    list: &str,
+) -> Result<HashMap<BackendName, TlsOrigination>, ParseError> {
+    let mut backends = HashMap::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let Some((backend, server_name)) = item.split_once('=') else {
+            error!("Not a valid TLS origination backend: {item}");
+            return Err(ParseError::NotATlsOriginationBackend);
+        };
+        let Some((namespace, name)) = backend.trim().split_once('/') else {
+            error!("TLS origination backend must be qualified by its namespace: {item}");
+            return Err(ParseError::NotATlsOriginationBackend);
+        };
+        let (namespace, name) = (namespace.trim(), name.trim());
+        if namespace.is_empty() || name.is_empty() {
+            error!("Not a valid TLS origination backend: {item}");
+            return Err(ParseError::NotATlsOriginationBackend);
+        }
+        let Ok(server_name) = server_name.trim().parse::<dns::Name>() else {
+            error!("Not a valid TLS server name: {item}");
+            return Err(ParseError::NotATlsOriginationBackend);
+        };
+        let backend = BackendName {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+        backends.insert(backend, TlsOrigination { server_name });
+    }
+    Ok(backends)
+}
+
+pub(super) fn parse_histogram_buckets(list: &str) -> Result<Arc<[f64]>, ParseError> {
+    let mut bounds = Vec::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let bound = parse_number::<f64>(item)?;
+        if !bound.is_finite() || bound <= 0.0 || bounds.last().is_some_and(|&b| b >= bound) {
+            error!("Histogram buckets must be positive and strictly increasing: {list}");
+            return Err(ParseError::NotHistogramBuckets);
+        }
+        bounds.push(bound);
+    }
+    if bounds.is_empty() {
+        error!("At least one histogram bucket must be configured");
+        return Err(ParseError::NotHistogramBuckets);
+    }
+    Ok(bounds.into())
+}
+
+pub(super) fn parse_histogram_scale(s: &str) -> Result<i8, ParseError> {
+    let scale = parse_number::<i8>(s)?;
+    if !HistogramBuckets::SCALES.contains(&scale) {
+        error!(
+            "Histogram scale must be between {} and {}: {scale}",
+            HistogramBuckets::SCALES.start(),
+            HistogramBuckets::SCALES.end(),
+        );
+        return Err(ParseError::NotHistogramBuckets);
+    }
+    Ok(scale)
+}
+
+pub(super) fn parse_key_exchange_groups(
+    list: &str,
+) -> Result<Vec<identity::KeyExchangeGroup>, ParseError> {
+    let mut groups = Vec::new();
+    for item in list.split(',') {
+        let item = item.trim();
+        if !item.is_empty() {
+            let group = item.parse()?;
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+    }
+    Ok(groups)
+}
+
 pub(super) fn parse_dns_suffixes(list: &str) -> Result<HashSet<dns::Suffix>, ParseError> {
     let mut suffixes = HashSet::new();
     for item in list.split(',') {
@@ -169,6 +338,92 @@ pub(super) fn parse_networks(list: &str) -> Result<HashSet<IpNet>, ParseError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_key_exchange_groups_preserves_order() {
+        use identity::KeyExchangeGroup as Group;
+        assert_eq!(
+            parse_key_exchange_groups("X25519MLKEM768, x25519,P-256,X25519").unwrap(),
+            vec![Group::X25519MlKem768, Group::X25519, Group::Secp256r1],
+        );
+        assert!(parse_key_exchange_groups("ffdhe2048").is_err());
+    }
+
+    #[test]
+    fn parse_unix_sockets_valid() {
+        let sockets =
+            parse_unix_sockets(" 8080=/var/run/app.sock, 9090 = /tmp/admin.sock,").unwrap();
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(
+            sockets.get(&8080),
+            Some(&UnixPath(Path::new("/var/run/app.sock").into()))
+        );
+        assert_eq!(
+            sockets.get(&9090),
+            Some(&UnixPath(Path::new("/tmp/admin.sock").into()))
+        );
+    }
+
+    #[test]
+    fn parse_jwt_svid_routes_valid() {
+        let routes = parse_jwt_svid_routes(" ns/foo=partner-a, ns / bar = partner-b,").unwrap();
+        let route = |name: &str| RouteName {
+            namespace: "ns".to_string(),
+            name: name.to_string(),
+        };
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes.get(&route("foo")).map(|a| &**a), Some("partner-a"));
+        assert_eq!(routes.get(&route("bar")).map(|a| &**a), Some("partner-b"));
+    }
+
+    #[test]
+    fn parse_jwt_svid_routes_invalid() {
+        assert!(parse_jwt_svid_routes("ns/foo").is_err());
+        assert!(parse_jwt_svid_routes("foo=partner-a").is_err());
+        assert!(parse_jwt_svid_routes("ns/=partner-a").is_err());
+        assert!(parse_jwt_svid_routes("ns/foo=").is_err());
+    }
+
+    #[test]
+    fn parse_route_names_valid() {
+        let names = parse_route_names(" foo, bar ,,").unwrap();
+        assert_eq!(names, HashSet::from(["foo".to_string(), "bar".to_string()]));
+    }
+
+    #[test]
+    fn parse_tls_origination_backends_valid() {
+        let backends = parse_tls_origination_backends(
+            " egress/api=api.example.com, egress / db = db.example.com,",
+        )
+        .unwrap();
+        assert_eq!(backends.len(), 2);
+        assert_eq!(
+            backends.get(&BackendName {
+                namespace: "egress".to_string(),
+                name: "db".to_string(),
+            }),
+            Some(&TlsOrigination {
+                server_name: "db.example.com".parse().unwrap()
+            })
+        );
+        assert!(parse_tls_origination_backends("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_tls_origination_backends_invalid() {
+        assert!(parse_tls_origination_backends("egress/api").is_err());
+        assert!(parse_tls_origination_backends("api=api.example.com").is_err());
+        assert!(parse_tls_origination_backends("egress/=api.example.com").is_err());
+        assert!(parse_tls_origination_backends("egress/api=").is_err());
+        assert!(parse_tls_origination_backends("egress/api=not a name").is_err());
+    }
+
+    #[test]
+    fn parse_unix_sockets_invalid() {
+        assert!(parse_unix_sockets("8080").is_err());
+        assert!(parse_unix_sockets("http=/var/run/app.sock").is_err());
+        assert!(parse_unix_sockets("8080=app.sock").is_err());
+    }
+
     fn test_unit<F: Fn(u64) -> Duration>(unit: &str, to_duration: F) {
         for v in &[0, 1, 23, 456_789] {
             let d = to_duration(*v);
@@ -180,6 +435,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_histogram_buckets_valid() {
+        assert_eq!(
+            &*parse_histogram_buckets("0.005, 0.01,0.02,1").unwrap(),
+            &[0.005, 0.01, 0.02, 1.0]
+        );
+    }
+
+    #[test]
+    fn parse_histogram_buckets_invalid() {
+        assert!(parse_histogram_buckets("").is_err());
+        assert!(parse_histogram_buckets("0.1,0.05").is_err());
+        assert!(parse_histogram_buckets("0.1,0.1").is_err());
+        assert!(parse_histogram_buckets("-1").is_err());
+        assert!(parse_histogram_buckets("inf").is_err());
+        assert!(parse_histogram_buckets("fast").is_err());
+        assert!(parse_histogram_scale("4").is_err());
+        assert!(parse_histogram_scale("-5").is_err());
+        assert_eq!(parse_histogram_scale("3").unwrap(), 3);
+    }
+
     #[test]
     fn parse_duration_unit_ms() {
         test_unit("ms", Duration::from_millis);
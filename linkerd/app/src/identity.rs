@@ -1,17 +1,26 @@
 use crate::spire;
 
-pub use linkerd_app_core::identity::{client, Id};
+pub use linkerd_app_core::identity::{client, Id, UnknownStatus};
 use linkerd_app_core::{
     control, dns,
     identity::{
-        client::linkerd::Certify, creds, CertMetrics, Credentials, DerX509, Mode, WithCertMetrics,
+        client::linkerd::Certify, creds, trust_anchors, CertMetrics, Credentials, DerX509,
+        HandshakeMetrics, KeyExchangeGroup, Mode, PemCredentials, Revocation, RevocationMetrics,
+        TrustAnchorMetrics, TrustBundles, WithCertMetrics,
     },
     metrics::{prom, ControlHttp as ClientMetrics},
-    Result,
+    Error, Result,
+};
+use parking_lot::Mutex;
+use std::{
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
-use std::{future::Future, pin::Pin, time::SystemTime};
 use tokio::sync::watch;
-use tracing::Instrument;
+use tracing::{debug, info, warn, Instrument};
 
 #[derive(Debug, thiserror::Error)]
 #[error("linkerd identity requires a TLS Id and server name to be the same")]
@@ -29,6 +38,20 @@ pub enum Config {
         client: spire::Config,
         tls: TlsParams,
     },
+    /// Loads the proxy's certificate and key from files (e.g. as mounted by
+    /// cert-manager's CSI driver) without involving a control plane.
+    File { files: FileConfig, tls: TlsParams },
+}
+
+/// Configures the files from which the proxy's certificate and key are loaded.
+/// Credentials are reloaded whenever either file's contents change.
+#[derive(Clone, Debug)]
+pub struct FileConfig {
+    /// A PEM-encoded certificate chain, starting with the proxy's leaf
+    /// certificate.
+    pub cert_path: PathBuf,
+    /// The leaf certificate's PEM-encoded PKCS#8 or SEC1 private key.
+    pub key_path: PathBuf,
 }
 
 #[derive(Clone, Debug)]
@@ -36,6 +59,28 @@ pub struct TlsParams {
     pub id: Id,
     pub server_name: dns::Name,
     pub trust_anchors_pem: String,
+    /// If set, trust anchors are reloaded whenever this file's contents
+    /// change.
+    pub trust_anchors_path: Option<PathBuf>,
+    pub revocation: Option<RevocationParams>,
+    /// If set, overrides the TLS backend's default key exchange groups, in
+    /// order of preference.
+    pub key_exchange_groups: Option<Vec<KeyExchangeGroup>>,
+}
+
+/// Configures revocation checking for peer certificates.
+#[derive(Clone, Debug)]
+pub struct RevocationParams {
+    /// A directory of DER- or PEM-encoded CRLs. CRLs are reloaded whenever
+    /// the directory's contents change.
+    pub crl_dir: PathBuf,
+    /// Determines whether certificates are accepted when no CRL is available
+    /// for their issuer.
+    pub unknown_status: UnknownStatus,
+    /// Determines whether certificates are accepted when their issuer's CRLs
+    /// are past their next update. Certificates that are listed as revoked
+    /// are always rejected.
+    pub stale_status: UnknownStatus,
 }
 
 pub struct Identity {
@@ -49,21 +94,74 @@ pub type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
 #[derive(Clone, Debug, Default)]
 pub struct IdentityMetrics {
     cert: CertMetrics,
+    trust_anchors: TrustAnchorMetrics,
+    revocation: RevocationMetrics,
+    handshakes: HandshakeMetrics,
     client: control::Metrics,
 }
 
 /// Wraps a credential with a watch sender that notifies receivers when the store has been updated
 /// at least once.
 struct NotifyReady {
-    store: creds::Store,
+    store: Arc<Mutex<creds::Store>>,
     tx: watch::Sender<bool>,
 }
 
+/// Periodically reloads trust anchors and CRLs from the filesystem.
+struct Reload {
+    trust_anchors: Option<WatchTrustAnchors>,
+    crls: Option<WatchCrls>,
+}
+
+/// Polls the proxy's certificate and key files, updating the credential store
+/// when either changes.
+struct WatchCertificate {
+    files: FileConfig,
+    /// The contents of the certificate and key files that were last loaded.
+    loaded: Option<(String, String)>,
+    credentials: WithCertMetrics<NotifyReady>,
+}
+
+/// Polls a trust bundle file, updating the credential store's roots when the
+/// bundle changes.
+struct WatchTrustAnchors {
+    path: PathBuf,
+    pem: String,
+    store: Arc<Mutex<creds::Store>>,
+    metrics: TrustAnchorMetrics,
+}
+
+/// Polls a directory of CRLs, updating the credential store's revocation
+/// checks when the directory's contents change.
+struct WatchCrls {
+    params: RevocationParams,
+    files: Vec<Vec<u8>>,
+    store: Arc<Mutex<creds::Store>>,
+    metrics: RevocationMetrics,
+}
+
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
 impl IdentityMetrics {
     pub fn register(registry: &mut prom::Registry) -> Self {
         let cert = CertMetrics::register(registry.sub_registry_with_prefix("cert"));
+        let trust_anchors =
+            TrustAnchorMetrics::register(registry.sub_registry_with_prefix("trust_anchor"));
+        let revocation =
+            RevocationMetrics::register(registry.sub_registry_with_prefix("revocation"));
         let client = control::Metrics::register(registry);
-        Self { cert, client }
+        Self {
+            cert,
+            trust_anchors,
+            revocation,
+            handshakes: HandshakeMetrics::default(),
+            client,
+        }
+    }
+
+    /// Records TLS handshakes negotiated with the proxy's credentials.
+    pub fn with_handshakes(self, handshakes: HandshakeMetrics) -> Self {
+        Self { handshakes, ..self }
     }
 }
 
@@ -91,14 +189,15 @@ impl Config {
                 };
 
                 let certify = Certify::from(certify);
-                let (store, receiver, ready) = watch(tls, metrics.cert)?;
+                let (store, receiver, ready, reload) = watch(tls, &metrics)?;
 
                 let task = {
                     let addr = client.addr.clone();
                     let svc =
                         client.build(dns, client_metrics, metrics.client, receiver.new_client());
 
-                    Box::pin(certify.run(name, store, svc).instrument(
+                    let certify = certify.run(name, store, svc);
+                    Box::pin(reload.run_with(certify).instrument(
                         tracing::info_span!("identity", server.addr = %addr).or_current(),
                     ))
                 };
@@ -112,12 +211,31 @@ impl Config {
                 let addr = client.socket_addr.clone();
                 let spire = spire::client::Spire::new(tls.id.clone());
 
-                let (store, receiver, ready) = watch(tls, metrics.cert)?;
+                let (store, receiver, ready, reload) = watch(tls, &metrics)?;
+                let spire = spire.run(store, spire::Client::from(client));
                 let task =
-                    Box::pin(spire.run(store, spire::Client::from(client)).instrument(
+                    Box::pin(reload.run_with(spire).instrument(
                         tracing::info_span!("spire", server.addr = %addr).or_current(),
                     ));
 
+                Identity {
+                    receiver,
+                    ready,
+                    task,
+                }
+            }
+            Self::File { files, tls } => {
+                let cert_path = files.cert_path.clone();
+                let (store, receiver, ready, reload) = watch(tls, &metrics)?;
+                let certificate = WatchCertificate {
+                    files,
+                    loaded: None,
+                    credentials: store,
+                };
+                let task = Box::pin(reload.run_with(certificate.run()).instrument(
+                    tracing::info_span!("identity", cert.path = %cert_path.display()).or_current(),
+                ));
+
                 Identity {
                     receiver,
                     ready,
@@ -130,17 +248,138 @@ impl Config {
 
 fn watch(
     tls: TlsParams,
-    metrics: CertMetrics,
+    metrics: &IdentityMetrics,
 ) -> Result<(
     WithCertMetrics<NotifyReady>,
     creds::Receiver,
     watch::Receiver<bool>,
+    Reload,
 )> {
     let (tx, ready) = watch::channel(false);
-    let (store, receiver) =
-        Mode::default().watch(tls.id, tls.server_name, &tls.trust_anchors_pem)?;
-    let cred = WithCertMetrics::new(metrics, NotifyReady { store, tx });
-    Ok((cred, receiver, ready))
+    let (mut store, receiver) = Mode::default().watch(
+        tls.id,
+        tls.server_name,
+        &tls.trust_anchors_pem,
+        metrics.handshakes.clone(),
+    )?;
+    if let Some(groups) = &tls.key_exchange_groups {
+        store.set_key_exchange_groups(groups)?;
+    }
+    match trust_anchors(&tls.trust_anchors_pem) {
+        Ok(anchors) => metrics.trust_anchors.set_anchors(&anchors),
+        Err(error) => warn!(%error, "Failed to describe trust anchors"),
+    }
+
+    // CRLs must be loadable at startup so that misconfigurations are not
+    // silently ignored.
+    let crls = match tls.revocation {
+        Some(params) => {
+            let files = read_crls(&params.crl_dir)?;
+            let revocation = Revocation::new(
+                &files,
+                params.unknown_status,
+                params.stale_status,
+                metrics.revocation.clone(),
+            )?;
+            store.set_revocation(Some(revocation));
+            Some((params, files))
+        }
+        None => None,
+    };
+
+    let store = Arc::new(Mutex::new(store));
+    let reload = Reload {
+        trust_anchors: tls.trust_anchors_path.map(|path| WatchTrustAnchors {
+            path,
+            pem: tls.trust_anchors_pem,
+            store: store.clone(),
+            metrics: metrics.trust_anchors.clone(),
+        }),
+        crls: crls.map(|(params, files)| WatchCrls {
+            params,
+            files,
+            store: store.clone(),
+            metrics: metrics.revocation.clone(),
+        }),
+    };
+    let cred = WithCertMetrics::new(metrics.cert.clone(), NotifyReady { store, tx });
+    Ok((cred, receiver, ready, reload))
+}
+
+/// Reads the contents of each file in a CRL directory, ordered by file name.
+///
+/// This blocks on filesystem IO, so it must not be called on the runtime.
+fn read_crls(dir: &std::path::Path) -> std::io::Result<Vec<Vec<u8>>> {
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        // Skip subdirectories, e.g. those used by Kubernetes to atomically
+        // update mounted volumes.
+        if path.is_file() {
+            files.push(std::fs::read(path)?);
+        }
+    }
+    Ok(files)
+}
+
+/// Reads the certificate and key files, returning `None` if either cannot be
+/// read or if the certificate changes while the key is being read.
+///
+/// This blocks on filesystem IO, so it must not be called on the runtime.
+fn read_credentials(files: &FileConfig) -> Option<(String, String)> {
+    let FileConfig {
+        cert_path,
+        key_path,
+    } = files;
+    let read = |path: &PathBuf| {
+        std::fs::read_to_string(path)
+            .map_err(|error| {
+                warn!(%error, path = %path.display(), "Failed to read identity credentials");
+            })
+            .ok()
+    };
+    let cert = read(cert_path)?;
+    let key = read(key_path)?;
+
+    // The files may be replaced between reads, in which case the certificate
+    // would not match the key; so the certificate is read again to ensure
+    // that it has not changed.
+    if read(cert_path)? != cert {
+        debug!("Identity certificate changed while loading; retrying");
+        return None;
+    }
+
+    Some((cert, key))
+}
+
+// === impl Reload ===
+
+impl Reload {
+    /// Runs the given task alongside the configured watchers, if any.
+    async fn run_with(mut self, task: impl Future<Output = ()>) {
+        if self.trust_anchors.is_none() && self.crls.is_none() {
+            return task.await;
+        }
+
+        let reload = async move {
+            let mut interval = tokio::time::interval(RELOAD_POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Some(trust_anchors) = self.trust_anchors.as_mut() {
+                    trust_anchors.reload().await;
+                }
+                if let Some(crls) = self.crls.as_mut() {
+                    crls.reload().await;
+                }
+            }
+        };
+        futures::future::select(std::pin::pin!(task), std::pin::pin!(reload)).await;
+    }
 }
 
 // === impl NotifyReady ===
@@ -153,10 +392,159 @@ impl Credentials for NotifyReady {
         key: Vec<u8>,
         exp: SystemTime,
     ) -> Result<()> {
-        self.store.set_certificate(leaf, chain, key, exp)?;
+        self.store.lock().set_certificate(leaf, chain, key, exp)?;
         let _ = self.tx.send(true);
         Ok(())
     }
+
+    fn set_trust_bundles(&mut self, bundles: TrustBundles) -> Result<()> {
+        self.store.lock().set_trust_bundles(bundles)
+    }
+}
+
+// === impl WatchCertificate ===
+
+impl WatchCertificate {
+    /// Loads the certificate and then polls its files for changes.
+    async fn run(mut self) {
+        let mut interval = tokio::time::interval(RELOAD_POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.reload().await;
+        }
+    }
+
+    /// Reads the certificate and key files and, if either has changed,
+    /// updates the proxy's credentials.
+    ///
+    /// Credentials that cannot be loaded (e.g. because the files have not yet
+    /// been written) are retried on subsequent ticks, while the previously
+    /// loaded credentials remain in use.
+    async fn reload(&mut self) {
+        let files = self.files.clone();
+        let read = tokio::task::spawn_blocking(move || read_credentials(&files))
+            .await
+            .map_err(|error| warn!(%error, "Failed to read identity credentials"));
+        let Ok(Some(contents)) = read else { return };
+        let cert_path = &self.files.cert_path;
+        if self.loaded.as_ref() == Some(&contents) {
+            return;
+        }
+
+        let (cert, key) = &contents;
+        let creds = match PemCredentials::parse(cert, key) {
+            Ok(creds) => creds,
+            Err(error) => {
+                warn!(%error, path = %cert_path.display(), "Invalid identity credentials");
+                return;
+            }
+        };
+        let expiry = creds.expiry;
+        if let Err(error) = self.credentials.set_certificate(
+            creds.leaf,
+            creds.intermediates,
+            creds.key,
+            creds.expiry,
+        ) {
+            warn!(%error, path = %cert_path.display(), "Failed to update identity credentials");
+            return;
+        }
+
+        info!(expiry = ?expiry, "Loaded identity certificate");
+        self.loaded = Some(contents);
+    }
+}
+
+// === impl WatchTrustAnchors ===
+
+impl WatchTrustAnchors {
+    /// Reads the trust bundle file and, if it has changed, updates the roots
+    /// used to validate peers.
+    ///
+    /// Bundles that cannot be loaded are retried on subsequent ticks, so that
+    /// the update is applied once, e.g., a certificate issued by the new root
+    /// has been obtained.
+    async fn reload(&mut self) {
+        let path = self.path.clone();
+        let read = tokio::task::spawn_blocking(move || std::fs::read_to_string(path))
+            .await
+            .map_err(Error::from)
+            .and_then(|pem| pem.map_err(Error::from));
+        let pem = match read {
+            Ok(pem) => pem,
+            Err(error) => {
+                warn!(%error, path = %self.path.display(), "Failed to read trust anchors");
+                self.metrics.reload_failed();
+                return;
+            }
+        };
+        if pem == self.pem {
+            return;
+        }
+
+        let anchors = match trust_anchors(&pem) {
+            Ok(anchors) => anchors,
+            Err(error) => {
+                warn!(%error, path = %self.path.display(), "Invalid trust anchors");
+                self.metrics.reload_failed();
+                return;
+            }
+        };
+        if let Err(error) = self.store.lock().set_roots(&pem) {
+            warn!(%error, path = %self.path.display(), "Failed to update trust anchors");
+            self.metrics.reload_failed();
+            return;
+        }
+
+        info!(
+            anchors = ?anchors.iter().map(|a| &a.fingerprint).collect::<Vec<_>>(),
+            "Reloaded trust anchors",
+        );
+        self.metrics.reloaded(&anchors);
+        self.pem = pem;
+    }
+}
+
+// === impl WatchCrls ===
+
+impl WatchCrls {
+    /// Reads the CRL directory and, if its contents have changed, updates the
+    /// revocation checks applied to peer certificates.
+    ///
+    /// If the CRLs cannot be loaded, the previously loaded CRLs remain in use.
+    async fn reload(&mut self) {
+        let dir = self.params.crl_dir.clone();
+        let read = tokio::task::spawn_blocking(move || read_crls(&dir))
+            .await
+            .map_err(Error::from)
+            .and_then(|files| files.map_err(Error::from));
+        let dir = self.params.crl_dir.display();
+        let files = match read {
+            Ok(files) => files,
+            Err(error) => {
+                warn!(%error, %dir, "Failed to read CRLs");
+                return;
+            }
+        };
+        if files == self.files {
+            return;
+        }
+
+        match Revocation::new(
+            &files,
+            self.params.unknown_status,
+            self.params.stale_status,
+            self.metrics.clone(),
+        ) {
+            Ok(revocation) => {
+                self.store.lock().set_revocation(Some(revocation));
+                info!(%dir, files = files.len(), "Reloaded CRLs");
+            }
+            Err(error) => warn!(%error, %dir, "Invalid CRLs"),
+        }
+        self.files = files;
+    }
 }
 
 // === impl Identity ===
@@ -7,6 +7,7 @@
 pub mod dst;
 pub mod env;
 pub mod identity;
+pub mod metrics_collector;
 pub mod policy;
 pub mod spire;
 pub mod tap;
@@ -19,13 +20,18 @@ use linkerd_app_core::{
     config::ServerConfig,
     control::{ControlAddr, Metrics as ControlMetrics},
     dns, drain,
-    metrics::{prom, FmtMetrics},
-    serve,
+    identity::HandshakeMetrics,
+    metrics::{prom, CardinalityLimits, FmtMetrics},
+    opentelemetry, serve,
     svc::Param,
     transport::{addrs::*, listen::Bind},
     Error, ProxyRuntime,
 };
-pub use linkerd_app_core::{metrics, trace, transport::BindTcp, BUILD_INFO};
+pub use linkerd_app_core::{
+    metrics, trace,
+    transport::{AcceptMetrics, BindTcp},
+    BUILD_INFO,
+};
 use linkerd_app_gateway as gateway;
 use linkerd_app_inbound::{self as inbound, Inbound};
 use linkerd_app_outbound::{self as outbound, Outbound};
@@ -37,6 +43,9 @@ use tokio::{
 };
 use tracing::{debug, error, info, info_span, Instrument};
 
+/// How often metrics that have not been updated recently are evicted.
+const EVICT_IDLE_METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Spawns a sidecar proxy.
 ///
 /// The proxy binds two listeners:
@@ -62,6 +71,10 @@ pub struct Config {
     pub admin: admin::Config,
     pub tap: tap::Config,
     pub trace_collector: trace_collector::Config,
+    pub metrics_collector: metrics_collector::Config,
+
+    /// Configures JWT-SVIDs fetched from the SPIRE Workload API, if enabled.
+    pub jwt_svid: Option<spire::JwtConfig>,
 
     /// Grace period for graceful shutdowns.
     ///
@@ -77,6 +90,8 @@ pub struct App {
     identity: identity::Identity,
     inbound_addr: Local<ServerAddr>,
     trace_collector: trace_collector::TraceCollector,
+    metrics_collector: metrics_collector::MetricsCollector,
+    evict_idle_metrics: Pin<Box<dyn std::future::Future<Output = ()> + Send + 'static>>,
     outbound_addr: Local<ServerAddr>,
     outbound_addr_additional: Option<Local<ServerAddr>>,
     start_proxy: Pin<Box<dyn std::future::Future<Output = ()> + Send + 'static>>,
@@ -123,15 +138,25 @@ impl Config {
             dst,
             policy,
             identity,
-            inbound,
+            mut inbound,
             trace_collector,
+            metrics_collector,
             outbound,
             gateway,
             tap,
+            jwt_svid,
             ..
         } = self;
         debug!("Building app");
-        let (metrics, report) = Metrics::new(admin.metrics_retain_idle);
+        let cardinality = CardinalityLimits::register(
+            registry.sub_registry_with_prefix("metrics"),
+            admin.metrics_max_series,
+        );
+        let (metrics, report) = Metrics::new(admin.metrics_retain_idle, cardinality);
+
+        if let Some(access_log) = log_level.access_log_metrics() {
+            access_log.register(registry.sub_registry_with_prefix("access_log"));
+        }
 
         debug!("Building DNS client");
         let dns = dns.build(registry.sub_registry_with_prefix("control_dns"));
@@ -141,7 +166,10 @@ impl Config {
         let identity = {
             let id_metrics = identity::IdentityMetrics::register(
                 registry.sub_registry_with_prefix("control_identity"),
-            );
+            )
+            .with_handshakes(HandshakeMetrics::register(
+                registry.sub_registry_with_prefix("tls"),
+            ));
 
             info_span!("identity").in_scope(|| {
                 identity.build(
@@ -177,8 +205,26 @@ impl Config {
             })
         }?;
 
+        debug!("Building JWT-SVID clients");
+        let jwt_svids = info_span!("jwt_svid")
+            .in_scope(|| jwt_svid.map(spire::JwtConfig::build).transpose())?;
+        let jwt_svid_routes = match jwt_svids {
+            Some(spire::JwtSvids {
+                outbound_routes,
+                inbound: jwt_svid,
+            }) => {
+                inbound.jwt_svid = jwt_svid;
+                outbound_routes
+            }
+            None => Default::default(),
+        };
+
         debug!("Building Policy client");
-        let export_hostname_labels = policy.export_hostname_labels;
+        let overrides = outbound::policy::ClientPolicyOverrides {
+            export_hostname_labels: policy.export_hostname_labels,
+            jwt_svid_routes: std::sync::Arc::new(jwt_svid_routes),
+            tls_origination_backends: policy.tls_origination_backends.clone(),
+        };
         let policies = {
             let control_metrics =
                 ControlMetrics::register(registry.sub_registry_with_prefix("control_policy"));
@@ -218,6 +264,26 @@ impl Config {
             })
         }?;
 
+        // Metrics must be registered before the registry is frozen into a
+        // report, so the collector's metrics are registered eagerly and the
+        // collector is built once the report is available.
+        let (metrics_collector_control_metrics, metrics_export_metrics) =
+            if let Some(prefix) = metrics_collector.metrics_prefix() {
+                (
+                    ControlMetrics::register(registry.sub_registry_with_prefix(prefix)),
+                    opentelemetry::metrics_export::ExportMetrics::register(
+                        registry.sub_registry_with_prefix("metrics_export"),
+                    ),
+                )
+            } else {
+                let mut registry = prom::Registry::default();
+                (
+                    ControlMetrics::register(&mut registry),
+                    opentelemetry::metrics_export::ExportMetrics::register(&mut registry),
+                )
+            };
+        let metrics_collector_client_metrics = metrics.control.clone();
+
         let runtime = ProxyRuntime {
             identity: identity.receiver(),
             metrics: metrics.proxy,
@@ -248,7 +314,7 @@ impl Config {
             policies.client.clone(),
             policies.backoff,
             policies.limits,
-            export_hostname_labels,
+            overrides,
         );
 
         let dst_addr = dst.addr.clone();
@@ -305,15 +371,46 @@ impl Config {
         }
         registry.register("proxy_build_info", "Proxy build info", BUILD_INFO.metric());
 
+        let report = inbound_metrics
+            .clone()
+            .and_report(outbound_metrics)
+            .and_report(report)
+            .and_report(prom::Report::from(registry));
+
+        // Idle metrics are evicted periodically, rather than when metrics are
+        // scraped or exported, so that they are bounded even when neither
+        // happens.
+        let evict_idle_metrics = {
+            let report = report.clone();
+            Box::pin(async move {
+                let mut interval = time::interval(EVICT_IDLE_METRICS_INTERVAL);
+                interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+                loop {
+                    interval.tick().await;
+                    report.evict_idle();
+                }
+            })
+        };
+
+        debug!(config = ?metrics_collector, "Building metrics collector");
+        let metrics_collector = {
+            let identity = identity.receiver().new_client();
+            let dns = dns.resolver("metrics_collector");
+            info_span!("metrics").in_scope(|| {
+                metrics_collector.build(
+                    report.clone(),
+                    identity,
+                    dns,
+                    metrics_export_metrics,
+                    metrics_collector_control_metrics,
+                    metrics_collector_client_metrics,
+                )
+            })
+        }?;
+
         let admin = {
             let identity = identity.receiver().server();
-            let metrics = inbound_metrics.clone();
-            let report = inbound_metrics
-                .and_report(outbound_metrics)
-                .and_report(report)
-                // The prom registry reports an "# EOF" at the end of its export, so
-                // it should be emitted last.
-                .and_report(prom::Report::from(registry));
+            let metrics = inbound_metrics;
             info_span!("admin").in_scope(move || {
                 admin.build(
                     bind_admin,
@@ -335,6 +432,8 @@ impl Config {
             identity,
             inbound_addr,
             trace_collector,
+            metrics_collector,
+            evict_idle_metrics,
             outbound_addr,
             outbound_addr_additional,
             start_proxy,
@@ -403,12 +502,21 @@ impl App {
         }
     }
 
+    pub fn metrics_collector_addr(&self) -> Option<&ControlAddr> {
+        match self.metrics_collector {
+            metrics_collector::MetricsCollector::Disabled => None,
+            metrics_collector::MetricsCollector::Enabled(ref collector) => Some(&collector.addr),
+        }
+    }
+
     pub fn spawn(self) -> drain::Signal {
         let App {
             admin,
             drain,
             identity,
             trace_collector: collector,
+            metrics_collector,
+            evict_idle_metrics,
             start_proxy,
             tap,
             ..
@@ -477,6 +585,16 @@ impl App {
                             tokio::spawn(collector.task.instrument(info_span!("tracing")));
                         }
 
+                        if let metrics_collector::MetricsCollector::Enabled(collector) =
+                            metrics_collector
+                        {
+                            tokio::spawn(collector.task.instrument(info_span!("metrics")));
+                        }
+
+                        tokio::spawn(
+                            evict_idle_metrics.instrument(info_span!("evict_metrics").or_current()),
+                        );
+
                         // we don't care if the admin shutdown channel is
                         // dropped or actually triggered.
                         let _ = admin_shutdown_rx.await;
@@ -0,0 +1,99 @@
+use crate::trace_collector::otel_collector::{self, OtelCollectorAttributes};
+use linkerd_app_core::{
+    control, dns, identity,
+    metrics::{ControlHttp as HttpMetrics, FmtMetrics},
+    opentelemetry::{self, metrics_export::ExportMetrics},
+    svc::NewService,
+};
+use linkerd_error::Error;
+use std::{collections::HashMap, future::Future, pin::Pin, time::Duration};
+use tracing::Instrument;
+
+const SERVICE_NAME: &str = "linkerd-proxy";
+
+#[derive(Clone, Debug)]
+pub enum Config {
+    Disabled,
+    Enabled(Box<EnabledConfig>),
+}
+
+#[derive(Clone, Debug)]
+pub struct EnabledConfig {
+    pub control: control::Config,
+    pub interval: Duration,
+    pub attributes: HashMap<String, String>,
+    pub hostname: Option<String>,
+    pub service_name: Option<String>,
+}
+
+pub type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Pushes the proxy's metrics to an OpenTelemetry collector.
+pub enum MetricsCollector {
+    Disabled,
+    Enabled(Box<EnabledCollector>),
+}
+
+pub struct EnabledCollector {
+    pub addr: control::ControlAddr,
+    pub task: Task,
+}
+
+impl Config {
+    pub fn metrics_prefix(&self) -> Option<&'static str> {
+        match self {
+            Config::Disabled => None,
+            Config::Enabled(_) => Some("metrics_collector"),
+        }
+    }
+
+    pub fn build<R>(
+        self,
+        report: R,
+        identity: identity::NewClient,
+        dns: dns::Resolver,
+        export_metrics: ExportMetrics,
+        control_metrics: control::Metrics,
+        client_metrics: HttpMetrics,
+    ) -> Result<MetricsCollector, Error>
+    where
+        R: FmtMetrics + Send + 'static,
+    {
+        match self {
+            Config::Disabled => Ok(MetricsCollector::Disabled),
+            Config::Enabled(inner) => {
+                let addr = inner.control.addr.clone();
+                let svc = inner
+                    .control
+                    .build(dns, client_metrics, control_metrics, identity)
+                    .new_service(());
+
+                let resource = otel_collector::resource_attributes(OtelCollectorAttributes {
+                    hostname: inner.hostname,
+                    service_name: inner
+                        .service_name
+                        .unwrap_or_else(|| SERVICE_NAME.to_string()),
+                    extra: inner.attributes,
+                });
+
+                let task = Box::pin(
+                    opentelemetry::export_metrics(
+                        svc,
+                        report,
+                        resource,
+                        inner.interval,
+                        export_metrics,
+                    )
+                    .instrument(
+                        tracing::debug_span!("opentelemetry", peer.addr = %addr).or_current(),
+                    ),
+                );
+
+                Ok(MetricsCollector::Enabled(Box::new(EnabledCollector {
+                    addr,
+                    task,
+                })))
+            }
+        }
+    }
+}
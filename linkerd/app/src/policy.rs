@@ -6,9 +6,10 @@ use linkerd_app_core::{
     svc::{self, NewService, ServiceExt},
     Error,
 };
+use linkerd_app_outbound::policy::{BackendName, TlsOrigination};
 use linkerd_tonic_stream::ReceiveLimits;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -16,6 +17,9 @@ pub struct Config {
     pub workload: String,
     pub limits: ReceiveLimits,
     pub export_hostname_labels: bool,
+
+    /// Backends whose client policies are configured to originate TLS.
+    pub tls_origination_backends: Arc<HashMap<BackendName, TlsOrigination>>,
 }
 
 /// Handles to policy service clients.
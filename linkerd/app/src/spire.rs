@@ -1,6 +1,10 @@
-use linkerd_app_core::{exp_backoff::ExponentialBackoff, Error};
-use std::sync::Arc;
-use tokio::sync::watch;
+use linkerd_app_core::{exp_backoff::ExponentialBackoff, identity::Id, Error};
+use linkerd_app_inbound::policy::JwtSvidPolicy;
+use linkerd_app_outbound::policy::http::{InjectJwtSvid, RouteName};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 pub use linkerd_app_core::identity::client::spire as client;
 
@@ -15,12 +19,94 @@ pub struct Config {
     pub backoff: ExponentialBackoff,
 }
 
+/// Configures the JWT-SVIDs fetched from the Workload API.
+#[derive(Clone, Debug)]
+pub struct JwtConfig {
+    pub client: Config,
+
+    /// The SPIFFE ID for which JWT-SVIDs are requested.
+    pub spiffe_id: Id,
+
+    /// The audiences of the JWT-SVIDs injected on requests to each outbound
+    /// HTTP route.
+    pub outbound_routes: HashMap<RouteName, Arc<str>>,
+
+    /// The audience for which inbound JWT-SVIDs are validated and the names
+    /// of the inbound routes that require them, if any.
+    pub inbound: Option<(Arc<str>, HashSet<String>)>,
+}
+
+/// Watches of the JWT-SVIDs and JWT bundles served by the Workload API.
+pub struct JwtSvids {
+    pub outbound_routes: HashMap<RouteName, InjectJwtSvid>,
+    pub inbound: Option<JwtSvidPolicy>,
+}
+
 // Connects to SPIRE workload API via Unix Domain Socket
 pub struct Client {
     #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
     config: Config,
 }
 
+// === impl JwtConfig ===
+
+impl JwtConfig {
+    /// Spawns background tasks that fetch JWT-SVIDs for each configured
+    /// audience and, if inbound validation is enabled, JWT bundles.
+    ///
+    /// The Workload API is connected lazily, so this does not wait for the
+    /// first tokens to be issued.
+    #[cfg(target_os = "linux")]
+    pub fn build(self) -> Result<JwtSvids, Error> {
+        let Self {
+            client: Config {
+                socket_addr,
+                backoff,
+            },
+            spiffe_id,
+            outbound_routes,
+            inbound,
+        } = self;
+        let chan = connect_lazy(&socket_addr)?;
+
+        // Routes that share an audience share its tokens.
+        let mut audiences = HashMap::<Arc<str>, InjectJwtSvid>::new();
+        let outbound_routes = outbound_routes
+            .into_iter()
+            .map(|(route, audience)| {
+                let filter = audiences
+                    .entry(audience.clone())
+                    .or_insert_with(|| InjectJwtSvid {
+                        authorization: client::jwt::spawn_authorization_watch(
+                            chan.clone(),
+                            &spiffe_id,
+                            audience.clone(),
+                            backoff,
+                        ),
+                        audience,
+                    })
+                    .clone();
+                (route, filter)
+            })
+            .collect();
+
+        let inbound = inbound.map(|(audience, routes)| {
+            let bundles = client::jwt::watch_bundles(chan, backoff);
+            JwtSvidPolicy::new(client::jwt::Validator::new(audience, bundles), routes)
+        });
+
+        Ok(JwtSvids {
+            outbound_routes,
+            inbound,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn build(self) -> Result<JwtSvids, Error> {
+        Err("Spire is supported on Linux only".into())
+    }
+}
+
 // === impl Client ===
 
 #[cfg(target_os = "linux")]
@@ -39,7 +125,7 @@ impl From<Config> for Client {
 
 #[cfg(target_os = "linux")]
 impl tower::Service<()> for Client {
-    type Response = tonic::Response<watch::Receiver<client::SvidUpdate>>;
+    type Response = client::Updates;
     type Error = Error;
     type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -54,36 +140,19 @@ impl tower::Service<()> for Client {
         let socket = self.config.socket_addr.clone();
         let backoff = self.config.backoff;
         Box::pin(async move {
-            use tokio::net::UnixStream;
-            use tonic::transport::{Endpoint, Uri};
-
-            // Strip the 'unix:' prefix for tonic compatibility.
-            let stripped_path = socket
-                .strip_prefix(UNIX_PREFIX)
-                .unwrap_or(socket.as_str())
-                .to_string();
-
-            // We will ignore this uri because uds do not use it
-            // if your connector does use the uri it will be provided
-            // as the request to the `MakeConnection`.
-            let chan = Endpoint::try_from(TONIC_DEFAULT_URI)?
-                .connect_with_connector(tower::util::service_fn(move |_: Uri| {
-                    use futures::TryFutureExt;
-                    UnixStream::connect(stripped_path.clone()).map_ok(hyper_util::rt::TokioIo::new)
-                }))
-                .await?;
-
-            let api = client::Api::watch(chan, backoff);
-            let receiver = api.spawn_watch(()).await?;
-
-            Ok(receiver)
+            let (endpoint, connector) = endpoint(&socket)?;
+            let chan = endpoint.connect_with_connector(connector).await?;
+
+            let updates = client::watch(chan, backoff).await?;
+
+            Ok(updates)
         })
     }
 }
 
 #[cfg(not(target_os = "linux"))]
 impl tower::Service<()> for Client {
-    type Response = tonic::Response<watch::Receiver<client::SvidUpdate>>;
+    type Response = client::Updates;
     type Error = Error;
     type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -98,3 +167,38 @@ impl tower::Service<()> for Client {
         unimplemented!("Spire is supported on Linux only")
     }
 }
+
+#[cfg(target_os = "linux")]
+type Connector = tower::util::BoxCloneService<
+    tonic::transport::Uri,
+    hyper_util::rt::TokioIo<tokio::net::UnixStream>,
+    std::io::Error,
+>;
+
+#[cfg(target_os = "linux")]
+fn connect_lazy(socket: &str) -> Result<tonic::transport::Channel, Error> {
+    let (endpoint, connector) = endpoint(socket)?;
+    Ok(endpoint.connect_with_connector_lazy(connector))
+}
+
+#[cfg(target_os = "linux")]
+fn endpoint(socket: &str) -> Result<(tonic::transport::Endpoint, Connector), Error> {
+    use tokio::net::UnixStream;
+    use tonic::transport::{Endpoint, Uri};
+
+    // Strip the 'unix:' prefix for tonic compatibility.
+    let stripped_path = socket
+        .strip_prefix(UNIX_PREFIX)
+        .unwrap_or(socket)
+        .to_string();
+
+    // We will ignore this uri because uds do not use it
+    // if your connector does use the uri it will be provided
+    // as the request to the `MakeConnection`.
+    let endpoint = Endpoint::try_from(TONIC_DEFAULT_URI)?;
+    let connector = tower::util::service_fn(move |_: Uri| {
+        use futures::TryFutureExt;
+        UnixStream::connect(stripped_path.clone()).map_ok(hyper_util::rt::TokioIo::new)
+    });
+    Ok((endpoint, Connector::new(connector)))
+}
@@ -18,7 +18,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use tonic::{body::BoxBody, client::GrpcService};
 use tracing::Instrument;
 
-pub(super) struct OtelCollectorAttributes {
+pub(crate) struct OtelCollectorAttributes {
     pub hostname: Option<String>,
     pub service_name: String,
     pub extra: HashMap<String, String>,
@@ -40,6 +40,26 @@ where
     let (span_sink, spans_rx) = mpsc::channel(crate::trace_collector::SPAN_BUFFER_CAPACITY);
     let spans_rx = ReceiverStream::new(spans_rx);
 
+    let resources = resource_attributes(attributes);
+
+    let addr = addr.clone();
+    let task = Box::pin(
+        opentelemetry::export_spans(svc, spans_rx, resources, legacy_metrics)
+            .instrument(tracing::debug_span!("opentelemetry", peer.addr = %addr).or_current()),
+    );
+
+    EnabledCollector {
+        addr,
+        task,
+        span_sink,
+        kind: CollectorProtocol::OpenTelemetry,
+    }
+}
+
+/// Describes the proxy as an OpenTelemetry resource.
+pub(crate) fn resource_attributes(
+    attributes: OtelCollectorAttributes,
+) -> ResourceAttributesWithSchema {
     let mut resources = ResourceAttributesWithSchema::default();
 
     resources
@@ -72,18 +92,7 @@ where
             .map(|(key, value)| value.with_key(&key)),
     );
 
-    let addr = addr.clone();
-    let task = Box::pin(
-        opentelemetry::export_spans(svc, spans_rx, resources, legacy_metrics)
-            .instrument(tracing::debug_span!("opentelemetry", peer.addr = %addr).or_current()),
-    );
-
-    EnabledCollector {
-        addr,
-        task,
-        span_sink,
-        kind: CollectorProtocol::OpenTelemetry,
-    }
+    resources
 }
 
 trait IntoAnyValue
@@ -62,6 +62,7 @@ impl ClientPolicies {
                 meta: Meta::new_default("test"),
                 queue,
                 dispatcher,
+                tls_origination: None,
             }
         };
 
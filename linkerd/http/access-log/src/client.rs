@@ -0,0 +1,229 @@
+use super::{header, now, trace_id, AccessLogFuture};
+use futures_core::TryFuture;
+use linkerd_http_retry::Attempt;
+use linkerd_proxy_http::ClientHandle;
+use linkerd_stack as svc;
+use linkerd_tls as tls;
+use linkerd_tracing::access_log::TRACE_TARGET;
+use pin_project::pin_project;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use svc::{NewService, Param};
+use tracing::{field, span, Level};
+
+/// Describes the route to which an outbound request is dispatched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteLabels {
+    pub parent: Arc<str>,
+    pub route: Arc<str>,
+}
+
+/// Describes the route backend to which an attempt is dispatched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BackendLabel(pub Arc<str>);
+
+/// Records access logs for outbound requests.
+///
+/// A single access log line is recorded for each logical request, regardless
+/// of how many times it is attempted. The backend and number of retries are
+/// recorded from the response that is ultimately returned (as described by
+/// [`NewDescribeAttempt`]).
+#[derive(Clone, Debug)]
+pub struct NewClientAccessLog<N> {
+    inner: N,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientAccessLogContext<S> {
+    inner: S,
+    route: RouteLabels,
+}
+
+/// Describes each attempt of an outbound request on its response, so that it
+/// may be recorded by [`NewClientAccessLog`].
+#[derive(Clone, Debug)]
+pub struct NewDescribeAttempt<N> {
+    inner: N,
+}
+
+#[derive(Clone, Debug)]
+pub struct DescribeAttempt<S> {
+    inner: S,
+    backend: BackendLabel,
+}
+
+/// A response extension describing the attempt that produced the response.
+#[derive(Clone, Debug)]
+pub(crate) struct AttemptLabels {
+    pub(crate) backend: BackendLabel,
+    pub(crate) retries: u16,
+}
+
+#[pin_project]
+pub struct DescribeAttemptFuture<F> {
+    labels: Option<AttemptLabels>,
+
+    #[pin]
+    inner: F,
+}
+
+// === impl NewClientAccessLog ===
+
+impl<N> NewClientAccessLog<N> {
+    /// Returns a new `NewClientAccessLog` layer that wraps an inner service
+    /// with access logging middleware.
+    ///
+    /// In addition to the fields recorded by [`super::NewAccessLog`], outbound
+    /// access logs record the request's route and backend, the number of
+    /// times the request has been retried, and the address of the endpoint
+    /// that served it.
+    ///
+    /// The client's identity is only known when it is set as a request
+    /// extension (e.g. by the gateway, which authenticates its clients).
+    /// Otherwise, the client is the unauthenticated local application and no
+    /// `client.id` is recorded.
+    #[inline]
+    pub fn layer() -> impl svc::layer::Layer<N, Service = Self> + Clone {
+        svc::layer::mk(|inner| NewClientAccessLog { inner })
+    }
+}
+
+impl<N, T> NewService<T> for NewClientAccessLog<N>
+where
+    T: Param<RouteLabels>,
+    N: NewService<T>,
+{
+    type Service = ClientAccessLogContext<N::Service>;
+
+    fn new_service(&self, target: T) -> Self::Service {
+        let route = target.param();
+        let inner = self.inner.new_service(target);
+        ClientAccessLogContext { inner, route }
+    }
+}
+
+// === impl ClientAccessLogContext ===
+
+impl<S, B1, B2> svc::Service<http::Request<B1>> for ClientAccessLogContext<S>
+where
+    S: svc::Service<http::Request<B1>, Response = http::Response<B2>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = AccessLogFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B1>) -> Self::Future {
+        let client_addr = request
+            .extensions()
+            .get::<ClientHandle>()
+            .map_or_else(|| "-".to_string(), |h| h.addr.to_string());
+        let client_id = request
+            .extensions()
+            .get::<tls::ClientId>()
+            .map_or_else(|| "-".to_string(), |tls::ClientId(id)| id.to_string());
+
+        let span = span!(target: TRACE_TARGET, Level::INFO, "http",
+            client.addr = %client_addr,
+            client.id = %client_id,
+            timestamp = %now(),
+            method = request.method().as_str(),
+            uri =  %request.uri(),
+            version = ?request.version(),
+            trace_id = trace_id(&request),
+            request_bytes = header(&request, http::header::CONTENT_LENGTH),
+            status = field::Empty,
+            response_bytes = field::Empty,
+            total_ns = field::Empty,
+            processing_ns = field::Empty,
+            user_agent = header(&request, http::header::USER_AGENT),
+            host = header(&request, http::header::HOST),
+            parent = %self.route.parent,
+            route = %self.route.route,
+            backend = field::Empty,
+            endpoint.addr = field::Empty,
+            retries = field::Empty,
+        );
+
+        AccessLogFuture::new(span, self.inner.call(request))
+    }
+}
+
+// === impl NewDescribeAttempt ===
+
+impl<N> NewDescribeAttempt<N> {
+    /// Returns a layer that describes each attempt's backend and retry count
+    /// on its response.
+    #[inline]
+    pub fn layer() -> impl svc::layer::Layer<N, Service = Self> + Clone {
+        svc::layer::mk(|inner| NewDescribeAttempt { inner })
+    }
+}
+
+impl<N, T> NewService<T> for NewDescribeAttempt<N>
+where
+    T: Param<BackendLabel>,
+    N: NewService<T>,
+{
+    type Service = DescribeAttempt<N::Service>;
+
+    fn new_service(&self, target: T) -> Self::Service {
+        let backend = target.param();
+        let inner = self.inner.new_service(target);
+        DescribeAttempt { inner, backend }
+    }
+}
+
+// === impl DescribeAttempt ===
+
+impl<S, B1, B2> svc::Service<http::Request<B1>> for DescribeAttempt<S>
+where
+    S: svc::Service<http::Request<B1>, Response = http::Response<B2>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = DescribeAttemptFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B1>) -> Self::Future {
+        let retries = request
+            .extensions()
+            .get::<Attempt>()
+            .map_or(0, |Attempt(n)| n.get() - 1);
+        DescribeAttemptFuture {
+            labels: Some(AttemptLabels {
+                backend: self.backend.clone(),
+                retries,
+            }),
+            inner: self.inner.call(request),
+        }
+    }
+}
+
+impl<F, B> Future for DescribeAttemptFuture<F>
+where
+    F: TryFuture<Ok = http::Response<B>>,
+{
+    type Output = Result<F::Ok, F::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut rsp = futures_core::ready!(this.inner.try_poll(cx))?;
+        if let Some(labels) = this.labels.take() {
+            rsp.extensions_mut().insert(labels);
+        }
+        Poll::Ready(Ok(rsp))
+    }
+}
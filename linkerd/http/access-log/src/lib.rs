@@ -3,7 +3,7 @@
 
 use futures_core::TryFuture;
 use linkerd_identity as identity;
-use linkerd_proxy_transport::{ClientAddr, Remote};
+use linkerd_proxy_transport::{ClientAddr, Remote, ServerAddr};
 use linkerd_stack as svc;
 use linkerd_tls as tls;
 use linkerd_tracing::access_log::TRACE_TARGET;
@@ -19,6 +19,14 @@ use svc::{NewService, Param};
 use tokio::time::Instant;
 use tracing::{field, span, Level, Span};
 
+mod client;
+
+use self::client::AttemptLabels;
+pub use self::client::{
+    BackendLabel, ClientAccessLogContext, DescribeAttempt, DescribeAttemptFuture,
+    NewClientAccessLog, NewDescribeAttempt, RouteLabels,
+};
+
 #[derive(Clone, Debug)]
 pub struct NewAccessLog<N> {
     inner: N,
@@ -100,24 +108,6 @@ where
     }
 
     fn call(&mut self, request: http::Request<B1>) -> Self::Future {
-        let get_header = |name: http::header::HeaderName| {
-            request
-                .headers()
-                .get(name)
-                .and_then(|x| x.to_str().ok())
-                .unwrap_or_default()
-        };
-
-        let trace_id = || {
-            let headers = request.headers();
-            headers
-                .get("x-b3-traceid")
-                .or_else(|| headers.get("x-request-id"))
-                .or_else(|| headers.get("x-amzn-trace-id"))
-                .and_then(|x| x.to_str().ok())
-                .unwrap_or_default()
-        };
-
         let client_id: std::borrow::Cow<'_, str> = self
             .client_id
             .as_ref()
@@ -131,33 +121,38 @@ where
             method = request.method().as_str(),
             uri =  %request.uri(),
             version = ?request.version(),
-            trace_id = trace_id(),
-            request_bytes = get_header(http::header::CONTENT_LENGTH),
+            trace_id = trace_id(&request),
+            request_bytes = header(&request, http::header::CONTENT_LENGTH),
             status = field::Empty,
             response_bytes = field::Empty,
             total_ns = field::Empty,
             processing_ns = field::Empty,
-            user_agent = get_header(http::header::USER_AGENT),
-            host = get_header(http::header::HOST),
+            user_agent = header(&request, http::header::USER_AGENT),
+            host = header(&request, http::header::HOST),
         );
 
+        AccessLogFuture::new(span, self.inner.call(request))
+    }
+}
+
+// === impl AccessLogFuture ===
+
+impl<F> AccessLogFuture<F> {
+    fn new(span: Span, inner: F) -> Self {
         // The access log span is only enabled by the `tracing` subscriber if
         // access logs are being recorded. If it's disabled, we can skip
         // recording additional data in the response future.
         if span.is_disabled() {
-            return AccessLogFuture {
-                data: None,
-                inner: self.inner.call(request),
-            };
+            return Self { data: None, inner };
         }
 
-        AccessLogFuture {
+        Self {
             data: Some(ResponseFutureInner {
                 span,
                 start: Instant::now(),
                 processing: Duration::from_secs(0),
             }),
-            inner: self.inner.call(request),
+            inner,
         }
     }
 }
@@ -201,6 +196,21 @@ where
             .and_then(|x| x.to_str().ok())
             .map(|x| span.record("response_bytes", x));
 
+        // The endpoint address is only set on outbound responses.
+        if let Some(Remote(ServerAddr(addr))) = response.extensions().get() {
+            span.record("endpoint.addr", field::display(addr));
+        }
+
+        // Outbound responses describe the attempt that produced them.
+        if let Some(AttemptLabels {
+            backend: BackendLabel(backend),
+            retries,
+        }) = response.extensions().get()
+        {
+            span.record("backend", field::display(backend));
+            span.record("retries", retries);
+        }
+
         span.record("status", response.status().as_u16());
         span.record("total_ns", field::display(total_ns));
         span.record("processing_ns", field::display(processing_ns));
@@ -209,6 +219,24 @@ where
     }
 }
 
+fn header<B>(request: &http::Request<B>, name: http::header::HeaderName) -> &str {
+    request
+        .headers()
+        .get(name)
+        .and_then(|x| x.to_str().ok())
+        .unwrap_or_default()
+}
+
+fn trace_id<B>(request: &http::Request<B>) -> &str {
+    let headers = request.headers();
+    headers
+        .get("x-b3-traceid")
+        .or_else(|| headers.get("x-request-id"))
+        .or_else(|| headers.get("x-amzn-trace-id"))
+        .and_then(|x| x.to_str().ok())
+        .unwrap_or_default()
+}
+
 #[inline]
 fn now() -> String {
     jiff::Timestamp::now().to_string()
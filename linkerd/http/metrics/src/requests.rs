@@ -4,9 +4,12 @@ mod service;
 pub use self::service::{NewHttpMetrics, ResponseBody};
 use super::Report;
 use linkerd_http_classify::ClassifyResponse;
-use linkerd_metrics::{latency, Counter, FmtMetrics, Histogram, LastUpdate, NewMetrics};
+use linkerd_metrics::{
+    latency, CardinalityLimit, Counter, FmtMetrics, Histogram, LastUpdate, NewMetrics, Store,
+};
 use linkerd_stack::{self as svc, layer};
-use std::{collections::HashMap, fmt::Debug, hash::Hash};
+use parking_lot::Mutex;
+use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::Arc};
 use tokio::time::{Duration, Instant};
 
 type Registry<T, C> = super::Registry<T, Metrics<C>>;
@@ -50,6 +53,12 @@ impl<T: Hash + Eq, C: Hash + Eq> Default for Requests<T, C> {
 }
 
 impl<T: Hash + Eq, C: Hash + Eq> Requests<T, C> {
+    /// Returns a registry that tracks at most `limit` targets. Requests for
+    /// additional targets are recorded in an overflow target.
+    pub fn with_limit(limit: CardinalityLimit) -> Self {
+        Requests(Arc::new(Mutex::new(Store::with_limit(limit))))
+    }
+
     pub fn into_report(self, retain_idle: Duration) -> Report<T, Metrics<C>>
     where
         Report<T, Metrics<C>>: FmtMetrics,
@@ -212,4 +221,61 @@ mod tests {
 
         drop((registry, report));
     }
+
+    #[test]
+    fn overflow_labels() {
+        use linkerd_metrics::{prom, CardinalityLimits, FmtLabels, FmtMetrics};
+        use std::fmt;
+
+        #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+        struct Target(usize);
+        impl FmtLabels for Target {
+            fn fmt_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "direction=\"inbound\",n=\"{}\"", self.0)
+            }
+        }
+
+        #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+        struct Class;
+        impl FmtLabels for Class {
+            fn fmt_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("classification=\"success\"")
+            }
+        }
+
+        let limits = CardinalityLimits::register(&mut prom::Registry::default(), Some(1));
+        let r = super::Requests::<Target, Class>::with_limit(limits.limit("request_total"));
+        let report = r
+            .clone()
+            .into_report(std::time::Duration::from_secs(1))
+            .without_latencies();
+        for n in 0..3 {
+            let mut registry = r.0.lock();
+            let mut metrics = registry.get_or_default(Target(n)).lock();
+            metrics.total.incr();
+            metrics
+                .by_status
+                .entry(Some(http::StatusCode::OK))
+                .or_default()
+                .by_class
+                .entry(Class)
+                .or_default()
+                .total
+                .incr();
+        }
+
+        assert_eq!(
+            report.as_display().to_string(),
+            "\
+# HELP request_total Total count of HTTP requests.
+# TYPE request_total counter
+request_total{direction=\"inbound\",n=\"0\"} 1
+request_total{overflow=\"true\"} 2
+# HELP response_total Total count of HTTP responses.
+# TYPE response_total counter
+response_total{direction=\"inbound\",n=\"0\",status_code=\"200\",classification=\"success\"} 1
+response_total{overflow=\"true\",status_code=\"200\",classification=\"success\"} 2
+"
+        );
+    }
 }
@@ -69,7 +69,7 @@ where
         N: fmt::Display,
         M: FmtMetric,
     {
-        for (tgt, tm) in registry.iter() {
+        for (tgt, tm) in registry.iter_with_overflow() {
             let tm = tm.lock();
             for (status, m) in &tm.by_status {
                 let status = status.as_ref().map(|s| Status(*s));
@@ -91,7 +91,7 @@ where
         N: fmt::Display,
         M: FmtMetric,
     {
-        for (tgt, tm) in registry.iter() {
+        for (tgt, tm) in registry.iter_with_overflow() {
             let tm = tm.lock();
             for (status, sm) in &tm.by_status {
                 for (cls, m) in &sm.by_class {
@@ -112,7 +112,7 @@ where
     C: FmtLabels + Hash + Eq,
 {
     fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut registry = self.registry.lock();
+        let registry = self.registry.lock();
         trace!(
             prefix = self.prefix,
             targets = registry.len(),
@@ -138,10 +138,14 @@ where
         metric.fmt_help(f)?;
         Self::fmt_by_class(&registry, f, metric, |s| &s.total)?;
 
-        registry.retain_since(Instant::now() - self.retain_idle);
-
         Ok(())
     }
+
+    fn evict_idle(&self) {
+        self.registry
+            .lock()
+            .retain_since(Instant::now() - self.retain_idle);
+    }
 }
 
 impl FmtLabels for Status {
@@ -97,7 +97,7 @@ where
     T: FmtLabels + Hash + Eq,
 {
     fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut registry = self.registry.lock();
+        let registry = self.registry.lock();
         trace!(
             prfefix = %self.prefix,
             targets = %registry.len(),
@@ -117,10 +117,14 @@ where
                 .fmt_metric_labeled(f, &metric.name, (tgt, NoBudgetLabel))?;
         }
 
-        registry.retain_since(Instant::now() - self.retain_idle);
-
         Ok(())
     }
+
+    fn evict_idle(&self) {
+        self.registry
+            .lock()
+            .retain_since(Instant::now() - self.retain_idle);
+    }
 }
 
 impl FmtLabels for NoBudgetLabel {
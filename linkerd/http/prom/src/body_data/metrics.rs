@@ -1,21 +1,22 @@
 //! Prometheus counters for request and response bodies.
 
-use linkerd_metrics::prom::{
-    self, metrics::family::MetricConstructor, Family, Histogram, Registry, Unit,
+use linkerd_metrics::{
+    prom::{self, metrics::family::MetricConstructor, Histogram, Registry, Unit},
+    CardinalityLimit, CardinalityLimits, LimitedFamily,
 };
 
 /// Counters for request body frames.
 #[derive(Clone, Debug)]
 pub struct RequestBodyFamilies<L> {
     /// Counts the number of request body frames by size.
-    frame_sizes: Family<L, Histogram, NewHisto>,
+    frame_sizes: LimitedFamily<L, Histogram, NewHisto>,
 }
 
 /// Counters for response body frames.
 #[derive(Clone, Debug)]
 pub struct ResponseBodyFamilies<L> {
     /// Counts the number of response body frames by size.
-    frame_sizes: Family<L, Histogram, NewHisto>,
+    frame_sizes: LimitedFamily<L, Histogram, NewHisto>,
 }
 
 /// Counters to instrument a request or response body.
@@ -25,7 +26,7 @@ pub struct BodyDataMetrics {
     pub frame_size: Histogram,
 }
 
-/// A constructor for creating new [`Histogram`]s in a [`Family`].
+/// A constructor for creating new [`Histogram`]s in a [`LimitedFamily`].
 #[derive(Clone, Copy)]
 struct NewHisto;
 
@@ -45,7 +46,7 @@ where
 {
     fn default() -> Self {
         Self {
-            frame_sizes: Family::new_with_constructor(NewHisto),
+            frame_sizes: LimitedFamily::new_with_constructor(CardinalityLimit::default(), NewHisto),
         }
     }
 }
@@ -62,8 +63,9 @@ where
         + 'static,
 {
     /// Registers and returns a new family of body data metrics.
-    pub fn register(registry: &mut Registry) -> Self {
-        let frame_sizes = Family::new_with_constructor(NewHisto);
+    pub fn register(registry: &mut Registry, limits: &CardinalityLimits) -> Self {
+        let frame_sizes =
+            LimitedFamily::new_with_constructor(limits.limit("request_frame_size"), NewHisto);
         registry.register_with_unit(
             "request_frame_size",
             "Request data frame sizes",
@@ -92,7 +94,7 @@ where
 {
     fn default() -> Self {
         Self {
-            frame_sizes: Family::new_with_constructor(NewHisto),
+            frame_sizes: LimitedFamily::new_with_constructor(CardinalityLimit::default(), NewHisto),
         }
     }
 }
@@ -109,8 +111,9 @@ where
         + 'static,
 {
     /// Registers and returns a new family of body data metrics.
-    pub fn register(registry: &mut Registry) -> Self {
-        let frame_sizes = Family::new_with_constructor(NewHisto);
+    pub fn register(registry: &mut Registry, limits: &CardinalityLimits) -> Self {
+        let frame_sizes =
+            LimitedFamily::new_with_constructor(limits.limit("response_frame_size"), NewHisto);
         registry.register_with_unit(
             "response_frame_size",
             "Response data frame sizes",
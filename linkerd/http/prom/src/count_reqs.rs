@@ -1,18 +1,15 @@
 //! A Tower middleware for counting requests processed by a service.
 
+use linkerd_metrics::{CardinalityLimits, LimitedFamily};
 use linkerd_stack as svc;
-use prometheus_client::{
-    encoding::EncodeLabelSet,
-    metrics::{counter::Counter, family::Family},
-    registry::Registry,
-};
+use prometheus_client::{encoding::EncodeLabelSet, metrics::counter::Counter, registry::Registry};
 use std::task::{Context, Poll};
 
-/// A [`Family`] of counters with `L`-encoded labels.
+/// A [`LimitedFamily`] of counters with `L`-encoded labels.
 ///
 /// See [`EncodeLabelSet`] for more information about encoding labels.
 #[derive(Clone, Debug)]
-pub struct RequestCountFamilies<L>(Family<L, Counter>);
+pub struct RequestCountFamilies<L>(LimitedFamily<L, Counter>);
 
 // A single [`Counter`] that tracks the number of requests.
 #[derive(Clone, Debug)]
@@ -103,7 +100,7 @@ where
     L: Eq + Clone,
 {
     fn default() -> Self {
-        Self(Family::default())
+        Self(LimitedFamily::default())
     }
 }
 
@@ -113,8 +110,8 @@ where
     L: Eq + Clone + Send + Sync + 'static,
 {
     /// Registers this family of counters with the given [`Registry`].
-    pub fn register(registry: &mut Registry) -> Self {
-        let requests = Family::default();
+    pub fn register(registry: &mut Registry, limits: &CardinalityLimits) -> Self {
+        let requests = LimitedFamily::new(limits.limit("requests"));
         registry.register(
             "requests",
             "The total number of requests dispatched",
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+/// Describes the bucket layout of a duration histogram.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HistogramBuckets {
+    /// Buckets with explicit upper bounds, in seconds.
+    Explicit(Arc<[f64]>),
+
+    /// Classic buckets spanning `min..=max` seconds whose upper bounds are
+    /// integer powers of `2^(2^-scale)`, so that higher scales produce more,
+    /// finer-grained buckets.
+    ///
+    /// These are ordinary (classic) histogram buckets: every bucket is exported
+    /// for every series, so the scale is reduced as needed to limit each
+    /// histogram to [`HistogramBuckets::MAX_EXPONENTIAL_BUCKETS`] buckets.
+    Exponential { scale: i8, min: f64, max: f64 },
+}
+
+// === impl HistogramBuckets ===
+
+impl HistogramBuckets {
+    /// The range of scales supported for exponential buckets.
+    pub const SCALES: std::ops::RangeInclusive<i8> = -4..=3;
+
+    /// The maximum number of classic buckets exported for exponential
+    /// histograms.
+    pub const MAX_EXPONENTIAL_BUCKETS: usize = 40;
+
+    /// Returns the upper bounds of the histogram's buckets, in seconds.
+    pub fn bounds(&self) -> Vec<f64> {
+        match self {
+            Self::Explicit(bounds) => bounds.to_vec(),
+            Self::Exponential { scale, min, max } => {
+                let scale = (*scale).clamp(*Self::SCALES.start(), *Self::SCALES.end());
+                let mut bounds = Self::exponential(scale, *min, *max);
+                for coarser in (*Self::SCALES.start()..scale).rev() {
+                    if bounds.len() <= Self::MAX_EXPONENTIAL_BUCKETS {
+                        break;
+                    }
+                    bounds = Self::exponential(coarser, *min, *max);
+                }
+                bounds
+            }
+        }
+    }
+
+    fn exponential(scale: i8, min: f64, max: f64) -> Vec<f64> {
+        let factor = 2f64.powf(2f64.powi(-i32::from(scale)));
+        let first = min.log(factor).ceil() as i32;
+        let last = max.log(factor).ceil() as i32;
+        (first..=last).map(|i| factor.powi(i)).collect()
+    }
+}
+
+impl From<&[f64]> for HistogramBuckets {
+    fn from(bounds: &[f64]) -> Self {
+        Self::Explicit(bounds.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_bounds_are_powers_of_the_scale_factor() {
+        let bounds = HistogramBuckets::Exponential {
+            scale: 0,
+            min: 0.001,
+            max: 1.0,
+        }
+        .bounds();
+        assert_eq!(bounds.first(), Some(&2f64.powi(-9)));
+        assert_eq!(bounds.last(), Some(&1.0));
+        assert_eq!(bounds.len(), 10);
+
+        let fine = HistogramBuckets::Exponential {
+            scale: 2,
+            min: 0.001,
+            max: 1.0,
+        }
+        .bounds();
+        assert_eq!(fine.len(), 40);
+        assert!(fine.windows(2).all(|w| w[0] < w[1]));
+        assert!((fine[1] / fine[0] - 2f64.powf(0.25)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exponential_bounds_are_limited() {
+        // At scale 3, 1ms..60s would require 127 buckets.
+        let bounds = HistogramBuckets::Exponential {
+            scale: 3,
+            min: 0.001,
+            max: 60.0,
+        }
+        .bounds();
+        assert!(bounds.len() <= HistogramBuckets::MAX_EXPONENTIAL_BUCKETS);
+        // The scale is reduced to 1, whose boundaries are powers of sqrt(2).
+        assert_eq!(bounds.len(), 32);
+        assert!((bounds[1] / bounds[0] - 2f64.sqrt()).abs() < 1e-9);
+    }
+}
@@ -3,6 +3,10 @@
 
 pub mod body_data;
 mod count_reqs;
+mod histogram;
 pub mod record_response;
 
-pub use self::count_reqs::{CountRequests, NewCountRequests, RequestCount, RequestCountFamilies};
+pub use self::{
+    count_reqs::{CountRequests, NewCountRequests, RequestCount, RequestCountFamilies},
+    histogram::HistogramBuckets,
+};
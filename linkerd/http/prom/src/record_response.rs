@@ -1,15 +1,12 @@
 use http_body::Body;
 use linkerd_error::Error;
 use linkerd_http_box::BoxBody;
-use linkerd_metrics::prom::Counter;
-use linkerd_stack as svc;
-use prometheus_client::{
-    encoding::EncodeLabelSet,
-    metrics::{
-        family::{Family, MetricConstructor},
-        histogram::Histogram,
-    },
+use linkerd_metrics::{
+    exemplar::{CounterWithExemplar, HistogramWithExemplars},
+    LimitedFamily,
 };
+use linkerd_stack as svc;
+use prometheus_client::{encoding::EncodeLabelSet, metrics::family::MetricConstructor};
 use std::{
     future::Future,
     pin::Pin,
@@ -93,6 +90,18 @@ pub struct Params<L: MkStreamLabel, M> {
 #[error("request was cancelled before completion")]
 pub struct RequestCancelled(());
 
+/// Exemplar labels that link an observation to the span that the proxy
+/// recorded for the request that produced it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub struct TraceExemplar {
+    trace_id: String,
+    span_id: String,
+}
+
+/// Counts completed streams, retaining an exemplar for the latest sampled
+/// trace.
+pub type StatusCounter = CounterWithExemplar<TraceExemplar>;
+
 /// Builds RecordResponse instances by extracing M-typed parameters from stack
 /// targets
 #[derive(Clone, Debug)]
@@ -130,21 +139,53 @@ struct ResponseBody<L: StreamLabel> {
 
 struct ResponseState<L: StreamLabel> {
     labeler: L,
-    statuses: Family<L::StatusLabels, Counter>,
+    statuses: LimitedFamily<L::StatusLabels, StatusCounter>,
     duration: DurationFamily<L::DurationLabels>,
     start: oneshot::Receiver<time::Instant>,
+    exemplar: Option<TraceExemplar>,
 }
 
-type DurationFamily<L> = Family<L, Histogram, MkDurationHistogram>;
+type DurationFamily<L> =
+    LimitedFamily<L, HistogramWithExemplars<TraceExemplar>, MkDurationHistogram>;
 
 #[derive(Clone, Debug)]
 struct MkDurationHistogram(Arc<[f64]>);
 
+/// A handle to a status counter, for tests.
+#[cfg(feature = "test-util")]
+#[derive(Clone, Debug)]
+pub struct StatusCount(StatusCounter);
+
 // === impl MkDurationHistogram ===
 
-impl MetricConstructor<Histogram> for MkDurationHistogram {
-    fn new_metric(&self) -> Histogram {
-        Histogram::new(self.0.iter().copied())
+impl MetricConstructor<HistogramWithExemplars<TraceExemplar>> for MkDurationHistogram {
+    fn new_metric(&self) -> HistogramWithExemplars<TraceExemplar> {
+        HistogramWithExemplars::new(self.0.iter().copied())
+    }
+}
+
+// === impl StatusCount ===
+
+#[cfg(feature = "test-util")]
+impl StatusCount {
+    pub fn get(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+// === impl TraceExemplar ===
+
+impl TraceExemplar {
+    /// Returns an exemplar describing the proxy's span for the request, if
+    /// the request is part of a sampled trace.
+    pub fn from_request<B>(req: &http::Request<B>) -> Option<Self> {
+        let span = req
+            .extensions()
+            .get::<linkerd_trace_context::SampledSpan>()?;
+        Some(Self {
+            trace_id: span.trace_id.to_string(),
+            span_id: span.span_id.to_string(),
+        })
     }
 }
 
@@ -310,6 +351,7 @@ fn end_stream<L>(
         statuses: total,
         mut start,
         mut labeler,
+        exemplar,
     }) = state.take()
     else {
         return;
@@ -317,7 +359,9 @@ fn end_stream<L>(
 
     labeler.end_response(res);
 
-    total.get_or_create(&labeler.status_labels()).inc();
+    total
+        .get_or_create(&labeler.status_labels())
+        .inc(exemplar.clone());
 
     let elapsed = if let Ok(start) = start.try_recv() {
         time::Instant::now().saturating_duration_since(start)
@@ -326,5 +370,5 @@ fn end_stream<L>(
     };
     duration
         .get_or_create(&labeler.duration_labels())
-        .observe(elapsed.as_secs_f64());
+        .observe(elapsed.as_secs_f64(), exemplar);
 }
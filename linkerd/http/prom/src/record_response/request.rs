@@ -1,10 +1,9 @@
 use linkerd_error::Error;
 use linkerd_http_box::BoxBody;
-use linkerd_metrics::prom::Counter;
+use linkerd_metrics::{CardinalityLimit, CardinalityLimits, LimitedFamily};
 use linkerd_stack as svc;
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::family::Family,
     registry::{Registry, Unit},
 };
 use std::{
@@ -13,13 +12,13 @@ use std::{
 };
 use tokio::{sync::oneshot, time};
 
-use super::{DurationFamily, MkDurationHistogram, MkStreamLabel};
+use super::{DurationFamily, MkDurationHistogram, MkStreamLabel, StatusCounter, TraceExemplar};
 
 /// Metrics type that tracks completed requests.
 #[derive(Debug)]
 pub struct RequestMetrics<DurL, StatL> {
     duration: DurationFamily<DurL>,
-    statuses: Family<StatL, Counter>,
+    statuses: LimitedFamily<StatL, StatusCounter>,
 }
 
 pub type NewRequestDuration<L, X, N> = super::NewRecordResponse<
@@ -42,9 +41,15 @@ where
     DurL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
     StatL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
 {
-    pub fn register(reg: &mut Registry, histo: impl IntoIterator<Item = f64>) -> Self {
-        let duration =
-            DurationFamily::new_with_constructor(MkDurationHistogram(histo.into_iter().collect()));
+    pub fn register(
+        reg: &mut Registry,
+        histo: impl IntoIterator<Item = f64>,
+        limits: &CardinalityLimits,
+    ) -> Self {
+        let duration = DurationFamily::new_with_constructor(
+            limits.limit("request_duration"),
+            MkDurationHistogram(histo.into_iter().collect()),
+        );
         reg.register_with_unit(
             "request_duration",
             "The time between request initialization and response completion",
@@ -52,7 +57,7 @@ where
             duration.clone(),
         );
 
-        let statuses = Family::default();
+        let statuses = LimitedFamily::new(limits.limit("request_statuses"));
         reg.register(
             "request_statuses",
             "Completed request-response streams",
@@ -69,8 +74,8 @@ where
     StatL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
     DurL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
 {
-    pub fn get_statuses(&self, labels: &StatL) -> Counter {
-        (*self.statuses.get_or_create(labels)).clone()
+    pub fn get_statuses(&self, labels: &StatL) -> super::StatusCount {
+        super::StatusCount((*self.statuses.get_or_create(labels)).clone())
     }
 
     // TODO(kate): it'd be nice if we could avoid creating a time series if it does not exist,
@@ -84,7 +89,10 @@ where
 {
     fn default() -> Self {
         Self {
-            duration: DurationFamily::new_with_constructor(MkDurationHistogram(Arc::new([]))),
+            duration: DurationFamily::new_with_constructor(
+                CardinalityLimit::default(),
+                MkDurationHistogram(Arc::new([])),
+            ),
             statuses: Default::default(),
         }
     }
@@ -115,6 +123,7 @@ where
 
     fn call(&mut self, req: http::Request<ReqB>) -> Self::Future {
         let state = self.labeler.mk_stream_labeler(&req).map(|labeler| {
+            let exemplar = TraceExemplar::from_request(&req);
             let (tx, start) = oneshot::channel();
             tx.send(time::Instant::now()).unwrap();
             let RequestMetrics { statuses, duration } = self.metric.clone();
@@ -123,6 +132,7 @@ where
                 start,
                 duration,
                 statuses,
+                exemplar,
             }
         });
 
@@ -1,11 +1,10 @@
 use http_body::Frame;
 use linkerd_error::Error;
 use linkerd_http_box::BoxBody;
-use linkerd_metrics::prom::Counter;
+use linkerd_metrics::{CardinalityLimit, CardinalityLimits, LimitedFamily};
 use linkerd_stack as svc;
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::family::Family,
     registry::{Registry, Unit},
 };
 use std::{
@@ -15,12 +14,12 @@ use std::{
 };
 use tokio::{sync::oneshot, time};
 
-use super::{DurationFamily, MkDurationHistogram, MkStreamLabel};
+use super::{DurationFamily, MkDurationHistogram, MkStreamLabel, StatusCounter, TraceExemplar};
 
 #[derive(Debug)]
 pub struct ResponseMetrics<DurL, StatL> {
     duration: DurationFamily<DurL>,
-    statuses: Family<StatL, Counter>,
+    statuses: LimitedFamily<StatL, StatusCounter>,
 }
 
 pub type NewResponseDuration<L, X, N> = super::NewRecordResponse<
@@ -51,9 +50,15 @@ where
     DurL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
     StatL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
 {
-    pub fn register(reg: &mut Registry, histo: impl IntoIterator<Item = f64>) -> Self {
-        let duration =
-            DurationFamily::new_with_constructor(MkDurationHistogram(histo.into_iter().collect()));
+    pub fn register(
+        reg: &mut Registry,
+        histo: impl IntoIterator<Item = f64>,
+        limits: &CardinalityLimits,
+    ) -> Self {
+        let duration = DurationFamily::new_with_constructor(
+            limits.limit("response_duration"),
+            MkDurationHistogram(histo.into_iter().collect()),
+        );
         reg.register_with_unit(
             "response_duration",
             "The time between request completion and response completion",
@@ -61,7 +66,7 @@ where
             duration.clone(),
         );
 
-        let statuses = Family::default();
+        let statuses = LimitedFamily::new(limits.limit("response_statuses"));
         reg.register("response_statuses", "Completed responses", statuses.clone());
 
         Self { duration, statuses }
@@ -74,8 +79,8 @@ where
     StatL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
     DurL: EncodeLabelSet + Clone + Eq + std::fmt::Debug + std::hash::Hash + Send + Sync + 'static,
 {
-    pub fn get_statuses(&self, labels: &StatL) -> Counter {
-        (*self.statuses.get_or_create(labels)).clone()
+    pub fn get_statuses(&self, labels: &StatL) -> super::StatusCount {
+        super::StatusCount((*self.statuses.get_or_create(labels)).clone())
     }
 }
 
@@ -86,7 +91,10 @@ where
 {
     fn default() -> Self {
         Self {
-            duration: DurationFamily::new_with_constructor(MkDurationHistogram(Arc::new([]))),
+            duration: DurationFamily::new_with_constructor(
+                CardinalityLimit::default(),
+                MkDurationHistogram(Arc::new([])),
+            ),
             statuses: Default::default(),
         }
     }
@@ -119,6 +127,7 @@ where
         // If there's a labeler, wrap the request body to record the time that
         // the respond flushes.
         let state = if let Some(labeler) = self.labeler.mk_stream_labeler(&req) {
+            let exemplar = TraceExemplar::from_request(&req);
             let (tx, start) = oneshot::channel();
             req = req.map(|inner| {
                 BoxBody::new(RequestBody {
@@ -132,6 +141,7 @@ where
                 start,
                 duration,
                 statuses,
+                exemplar,
             })
         } else {
             None
@@ -39,6 +39,11 @@ pub trait Policy: Clone + Sized {
     fn set_extensions(&self, _dst: &mut http::Extensions, _orig: &http::Extensions) {}
 }
 
+/// A request extension that marks the number of times a request has been
+/// attempted.
+#[derive(Clone, Debug)]
+pub struct Attempt(pub std::num::NonZeroU16);
+
 #[derive(Clone, Debug)]
 pub struct Params {
     pub max_retries: usize,
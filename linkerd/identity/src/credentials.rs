@@ -1,5 +1,5 @@
 use linkerd_error::Result;
-use std::{ops::Deref, time::SystemTime};
+use std::{collections::HashMap, ops::Deref, time::SystemTime};
 
 /// Publishes certificates to be used by TLS implementations.
 pub trait Credentials {
@@ -13,8 +13,20 @@ pub trait Credentials {
         key: Vec<u8>,
         expiry: SystemTime,
     ) -> Result<()>;
+
+    /// Set the per-trust-domain bundles used to verify SPIFFE peers.
+    ///
+    /// Fails if federation is not supported or if the current certificate is
+    /// not valid for the new bundles.
+    fn set_trust_bundles(&mut self, _bundles: TrustBundles) -> Result<()> {
+        Err("trust domain federation is not supported".into())
+    }
 }
 
+/// X.509 trust bundles, keyed by SPIFFE trust domain name (e.g.
+/// `example.org`).
+pub type TrustBundles = HashMap<String, Vec<DerX509>>;
+
 /// DER-formatted X.509 data.
 #[derive(Clone, Debug)]
 pub struct DerX509(pub Vec<u8>);
@@ -8,8 +8,11 @@ use linkerd_error::{Error, Result};
 use std::str::FromStr;
 
 pub use self::{
-    credentials::{Credentials, DerX509},
-    metrics::{CertMetrics, WithCertMetrics},
+    credentials::{Credentials, DerX509, TrustBundles},
+    metrics::{
+        CertMetrics, HandshakeDirection, HandshakeFailure, HandshakeMetrics, Negotiated,
+        RevocationMetrics, TrustAnchor, TrustAnchorMetrics, WithCertMetrics,
+    },
 };
 
 /// An endpoint identity descriptor used for authentication.
@@ -30,6 +33,19 @@ pub enum Id {
 #[error("invalid TLS id: {0}")]
 pub struct InvalidId(#[source] Error);
 
+/// Describes the certificate chain presented by a TLS peer.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct PeerCert {
+    /// The hex-encoded SHA-256 digest of the leaf certificate's DER encoding.
+    pub hash: String,
+    /// The leaf certificate's subject distinguished name.
+    pub subject: String,
+    pub uri_sans: Vec<String>,
+    pub dns_sans: Vec<String>,
+    /// The number of certificates presented by the peer, including the leaf.
+    pub chain_depth: usize,
+}
+
 // === impl Id ===
 
 impl std::str::FromStr for Id {
@@ -1,9 +1,9 @@
-use crate::{Credentials, DerX509};
+use crate::{Credentials, DerX509, TrustBundles};
 use linkerd_error::Result;
 use linkerd_metrics::prom;
 use std::{
     sync::atomic::AtomicU64,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 #[derive(Clone, Debug, Default)]
@@ -14,6 +14,103 @@ pub struct CertMetrics {
     errors: prom::Counter,
 }
 
+/// Metrics describing the set of trust anchors used to validate peer certificates.
+#[derive(Clone, Debug, Default)]
+pub struct TrustAnchorMetrics {
+    expiry_ts: prom::Family<TrustAnchorLabels, prom::Gauge<f64, AtomicU64>>,
+    reloads: prom::Counter,
+    errors: prom::Counter,
+}
+
+/// Describes a single trust anchor certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustAnchor {
+    /// The hex-encoded SHA-256 digest of the anchor's DER encoding.
+    pub fingerprint: String,
+    pub subject: String,
+    pub expiry: SystemTime,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct TrustAnchorLabels {
+    fingerprint: String,
+    subject: String,
+}
+
+/// Metrics describing peer certificates rejected by revocation checks.
+#[derive(Clone, Debug, Default)]
+pub struct RevocationMetrics {
+    revoked: prom::Counter,
+    unknown: prom::Counter,
+}
+
+/// Metrics describing TLS handshakes, labeled by the direction of the
+/// connection.
+#[derive(Clone, Debug)]
+pub struct HandshakeMetrics {
+    duration: prom::Family<DirectionLabels, prom::Histogram, MkDurations>,
+    negotiated: prom::Family<NegotiatedLabels, prom::Counter>,
+    errors: prom::Family<ErrorLabels, prom::Counter>,
+}
+
+/// Whether a handshake was initiated by a peer (inbound) or by this proxy
+/// (outbound).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HandshakeDirection {
+    Inbound,
+    Outbound,
+}
+
+/// The parameters negotiated by a completed TLS handshake.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Negotiated {
+    pub tls_version: &'static str,
+    pub alpn: Option<String>,
+    pub cipher_suite: &'static str,
+    pub key_exchange_group: &'static str,
+    /// Whether a previously established session was resumed.
+    pub resumed: bool,
+}
+
+/// Classifies the reason a TLS handshake failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HandshakeFailure {
+    /// The peer's certificate was not issued by a trusted root, or the peer
+    /// rejected this proxy's certificate for the same reason.
+    UnknownCa,
+    /// The peer's certificate does not match the expected identity.
+    SanMismatch,
+    /// The peer's certificate is expired or not yet valid.
+    Expired,
+    /// The peer does not appear to speak TLS.
+    NotTls,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct DirectionLabels {
+    direction: HandshakeDirection,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct NegotiatedLabels {
+    direction: HandshakeDirection,
+    tls_version: &'static str,
+    alpn: String,
+    cipher_suite: &'static str,
+    key_exchange_group: &'static str,
+    resumed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct ErrorLabels {
+    direction: HandshakeDirection,
+    reason: HandshakeFailure,
+}
+
+#[derive(Clone, Debug, Default)]
+struct MkDurations;
+
 /// Implements `Credentials`, recording metrics about certificate updates.
 pub struct WithCertMetrics<C> {
     inner: C,
@@ -73,6 +170,236 @@ impl CertMetrics {
     }
 }
 
+impl TrustAnchorMetrics {
+    pub fn register(registry: &mut prom::Registry) -> Self {
+        let expiry_ts = prom::Family::default();
+        registry.register_with_unit(
+            "expiration_timestamp",
+            "Time when each of this proxy's active trust anchors will expire (in seconds since the UNIX epoch)",
+            prom::Unit::Seconds,
+            expiry_ts.clone(),
+        );
+
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+        struct ReloadLabelSet {
+            result: ReloadResult,
+        }
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelValue)]
+        #[allow(non_camel_case_types)]
+        enum ReloadResult {
+            ok,
+            error,
+        }
+        let reloads_fam = prom::Family::<_, prom::Counter>::default();
+        registry.register(
+            "reloads",
+            "The total number of times this proxy has reloaded its trust anchors",
+            reloads_fam.clone(),
+        );
+        let reloads = reloads_fam
+            .get_or_create(&ReloadLabelSet {
+                result: ReloadResult::ok,
+            })
+            .clone();
+        let errors = reloads_fam
+            .get_or_create(&ReloadLabelSet {
+                result: ReloadResult::error,
+            })
+            .clone();
+
+        Self {
+            expiry_ts,
+            reloads,
+            errors,
+        }
+    }
+
+    /// Replaces the set of active trust anchors.
+    ///
+    /// Anchors that are no longer active are removed from the expiration
+    /// gauge so that retired roots stop being reported.
+    pub fn set_anchors(&self, anchors: &[TrustAnchor]) {
+        self.expiry_ts.clear();
+        for TrustAnchor {
+            fingerprint,
+            subject,
+            expiry,
+        } in anchors
+        {
+            let labels = TrustAnchorLabels {
+                fingerprint: fingerprint.clone(),
+                subject: subject.clone(),
+            };
+            match expiry.duration_since(UNIX_EPOCH) {
+                Ok(exp) => {
+                    self.expiry_ts.get_or_create(&labels).set(exp.as_secs_f64());
+                }
+                Err(_) => {
+                    tracing::warn!(%fingerprint, "Trust anchor expiry is before the UNIX epoch; not setting metric");
+                }
+            }
+        }
+    }
+
+    /// Records a successful reload of the trust anchors.
+    pub fn reloaded(&self, anchors: &[TrustAnchor]) {
+        self.reloads.inc();
+        self.set_anchors(anchors);
+    }
+
+    /// Records a failed attempt to reload the trust anchors.
+    pub fn reload_failed(&self) {
+        self.errors.inc();
+    }
+}
+
+impl RevocationMetrics {
+    pub fn register(registry: &mut prom::Registry) -> Self {
+        let revoked = prom::Counter::default();
+        registry.register(
+            "revoked",
+            "The total number of peer certificates rejected because they have been revoked",
+            revoked.clone(),
+        );
+
+        let unknown = prom::Counter::default();
+        registry.register(
+            "unknown_status",
+            "The total number of peer certificates whose revocation status could not be determined",
+            unknown.clone(),
+        );
+
+        Self { revoked, unknown }
+    }
+
+    /// Records that a revoked peer certificate was rejected.
+    pub fn revoked(&self) {
+        self.revoked.inc();
+    }
+
+    /// Records that a peer certificate's revocation status was unknown.
+    pub fn unknown(&self) {
+        self.unknown.inc();
+    }
+}
+
+impl Default for HandshakeMetrics {
+    fn default() -> Self {
+        Self {
+            duration: prom::Family::new_with_constructor(MkDurations),
+            negotiated: prom::Family::default(),
+            errors: prom::Family::default(),
+        }
+    }
+}
+
+impl HandshakeMetrics {
+    pub fn register(registry: &mut prom::Registry) -> Self {
+        let metrics = Self::default();
+        registry.register_with_unit(
+            "handshake_duration",
+            "The time taken to complete or fail TLS handshakes",
+            prom::Unit::Seconds,
+            metrics.duration.clone(),
+        );
+        registry.register(
+            "handshakes",
+            "The total number of completed TLS handshakes by negotiated parameters",
+            metrics.negotiated.clone(),
+        );
+        registry.register(
+            "handshake_errors",
+            "The total number of failed TLS handshakes by failure reason",
+            metrics.errors.clone(),
+        );
+        metrics
+    }
+
+    /// Records a completed handshake with the given negotiated parameters.
+    pub fn negotiated(
+        &self,
+        direction: HandshakeDirection,
+        elapsed: Duration,
+        negotiated: Negotiated,
+    ) {
+        self.observe(direction, elapsed);
+        self.negotiated
+            .get_or_create(&NegotiatedLabels {
+                direction,
+                tls_version: negotiated.tls_version,
+                alpn: negotiated.alpn.unwrap_or_default(),
+                cipher_suite: negotiated.cipher_suite,
+                key_exchange_group: negotiated.key_exchange_group,
+                resumed: negotiated.resumed,
+            })
+            .inc();
+    }
+
+    /// Records a failed handshake.
+    pub fn failed(
+        &self,
+        direction: HandshakeDirection,
+        elapsed: Duration,
+        reason: HandshakeFailure,
+    ) {
+        self.observe(direction, elapsed);
+        self.errors
+            .get_or_create(&ErrorLabels { direction, reason })
+            .inc();
+    }
+
+    fn observe(&self, direction: HandshakeDirection, elapsed: Duration) {
+        self.duration
+            .get_or_create(&DirectionLabels { direction })
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+// === impl HandshakeDirection ===
+
+impl HandshakeDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Inbound => "inbound",
+            Self::Outbound => "outbound",
+        }
+    }
+}
+
+impl prom::encoding::EncodeLabelValue for HandshakeDirection {
+    fn encode(&self, enc: &mut prom::encoding::LabelValueEncoder<'_>) -> std::fmt::Result {
+        self.as_str().encode(enc)
+    }
+}
+
+// === impl HandshakeFailure ===
+
+impl HandshakeFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnknownCa => "unknown_ca",
+            Self::SanMismatch => "san_mismatch",
+            Self::Expired => "expired",
+            Self::NotTls => "not_tls",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl prom::encoding::EncodeLabelValue for HandshakeFailure {
+    fn encode(&self, enc: &mut prom::encoding::LabelValueEncoder<'_>) -> std::fmt::Result {
+        self.as_str().encode(enc)
+    }
+}
+
+// === impl MkDurations ===
+
+impl prom::metrics::family::MetricConstructor<prom::Histogram> for MkDurations {
+    fn new_metric(&self) -> prom::Histogram {
+        prom::Histogram::new([0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0])
+    }
+}
+
 impl<C> WithCertMetrics<C> {
     pub fn new(metrics: CertMetrics, inner: C) -> Self {
         Self { inner, metrics }
@@ -117,11 +444,16 @@ where
 
         Ok(())
     }
+
+    fn set_trust_bundles(&mut self, bundles: TrustBundles) -> Result<()> {
+        self.inner.set_trust_bundles(bundles)
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{sync::Arc, time::Duration};
+    use std::sync::Arc;
 
     struct StubCreds(Arc<AtomicU64>, Result<(), ()>);
 
@@ -202,4 +534,94 @@ mod tests {
         assert_eq!(with_cert_metrics.metrics.expiry_ts.get(), 0.0);
         assert_eq!(called.load(std::sync::atomic::Ordering::Relaxed), 1);
     }
+
+    #[test]
+    fn test_trust_anchors() {
+        let metrics = TrustAnchorMetrics::register(&mut prom::Registry::default());
+
+        let anchor = |fingerprint: &str, days: u64| TrustAnchor {
+            fingerprint: fingerprint.to_string(),
+            subject: "CN=root.linkerd.cluster.local".to_string(),
+            expiry: UNIX_EPOCH + Duration::from_secs(60 * 60 * 24 * days),
+        };
+        let labels = |fingerprint: &str| TrustAnchorLabels {
+            fingerprint: fingerprint.to_string(),
+            subject: "CN=root.linkerd.cluster.local".to_string(),
+        };
+
+        metrics.set_anchors(&[anchor("aaaa", 1)]);
+        assert_eq!(
+            metrics.expiry_ts.get_or_create(&labels("aaaa")).get(),
+            (60 * 60 * 24) as f64
+        );
+        assert_eq!(metrics.reloads.get(), 0);
+
+        // During a rotation, both the old and new roots are active.
+        metrics.reloaded(&[anchor("aaaa", 1), anchor("bbbb", 2)]);
+        assert_eq!(metrics.reloads.get(), 1);
+        assert_eq!(
+            metrics.expiry_ts.get_or_create(&labels("bbbb")).get(),
+            (60 * 60 * 24 * 2) as f64
+        );
+
+        // Once the old root is retired, it is no longer reported.
+        metrics.reloaded(&[anchor("bbbb", 2)]);
+        assert_eq!(metrics.reloads.get(), 2);
+        assert_eq!(metrics.expiry_ts.get_or_create(&labels("aaaa")).get(), 0.0);
+
+        metrics.reload_failed();
+        assert_eq!(metrics.errors.get(), 1);
+        assert_eq!(metrics.reloads.get(), 2);
+    }
+
+    #[test]
+    fn test_handshakes() {
+        let mut registry = prom::Registry::default();
+        let metrics = HandshakeMetrics::register(registry.sub_registry_with_prefix("tls"));
+
+        metrics.negotiated(
+            HandshakeDirection::Inbound,
+            Duration::from_millis(2),
+            Negotiated {
+                tls_version: "TLSv1_3",
+                alpn: Some("h2".to_string()),
+                cipher_suite: "TLS13_CHACHA20_POLY1305_SHA256",
+                key_exchange_group: "X25519",
+                resumed: false,
+            },
+        );
+        metrics.failed(
+            HandshakeDirection::Outbound,
+            Duration::from_millis(3),
+            HandshakeFailure::UnknownCa,
+        );
+        metrics.failed(
+            HandshakeDirection::Inbound,
+            Duration::from_millis(1),
+            HandshakeFailure::NotTls,
+        );
+
+        let mut text = String::new();
+        prom::encoding::text::encode(&mut text, &registry).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert!(lines.contains(
+            &"tls_handshakes_total{direction=\"inbound\",tls_version=\"TLSv1_3\",alpn=\"h2\",cipher_suite=\"TLS13_CHACHA20_POLY1305_SHA256\",key_exchange_group=\"X25519\",resumed=\"false\"} 1"
+        ), "{text}");
+        assert!(
+            lines.contains(
+                &"tls_handshake_errors_total{direction=\"outbound\",reason=\"unknown_ca\"} 1"
+            ),
+            "{text}"
+        );
+        assert!(
+            lines.contains(
+                &"tls_handshake_errors_total{direction=\"inbound\",reason=\"not_tls\"} 1"
+            ),
+            "{text}"
+        );
+        assert!(
+            lines.contains(&"tls_handshake_duration_seconds_count{direction=\"inbound\"} 2"),
+            "{text}"
+        );
+    }
 }
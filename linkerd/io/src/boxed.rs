@@ -1,4 +1,4 @@
-use super::{AsyncRead, AsyncWrite, IoSlice, PeerAddr, Poll, ReadBuf, Result};
+use super::{AsyncRead, AsyncWrite, IoSlice, PeerAddr, Poll, ReadBuf, Result, TcpInfo};
 use std::{pin::Pin, task::Context};
 
 /// A public wrapper around a `Box<Io>`.
@@ -10,14 +10,14 @@ pub struct BoxedIo(Pin<Box<dyn Io + Unpin>>);
 /// This is necessary for `BoxedIo`, as `dyn AsyncRead + AsyncWrite + PeerAddr`
 /// is not a valid trait object. However, it needn't be public --- it's just
 /// used internally.
-trait Io: AsyncRead + AsyncWrite + PeerAddr + Send {}
+trait Io: AsyncRead + AsyncWrite + PeerAddr + TcpInfo + Send {}
 
-impl<I> Io for I where I: AsyncRead + AsyncWrite + PeerAddr + Send {}
+impl<I> Io for I where I: AsyncRead + AsyncWrite + PeerAddr + TcpInfo + Send {}
 
 impl BoxedIo {
     pub fn new<T>(io: T) -> Self
     where
-        T: AsyncRead + AsyncWrite + PeerAddr + Send + Unpin + 'static,
+        T: AsyncRead + AsyncWrite + PeerAddr + TcpInfo + Send + Unpin + 'static,
     {
         BoxedIo(Box::pin(io))
     }
@@ -29,6 +29,12 @@ impl PeerAddr for BoxedIo {
     }
 }
 
+impl TcpInfo for BoxedIo {
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.0.tcp_socket()
+    }
+}
+
 impl AsyncRead for BoxedIo {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -84,6 +90,12 @@ mod tests {
         }
     }
 
+    impl TcpInfo for WriteBufDetector {
+        fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+            None
+        }
+    }
+
     impl AsyncRead for WriteBufDetector {
         fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut ReadBuf<'_>) -> Poll<()> {
             unreachable!("not called in test")
@@ -33,6 +33,16 @@ impl<L: io::PeerAddr, R: io::PeerAddr> io::PeerAddr for EitherIo<L, R> {
     }
 }
 
+impl<L: io::TcpInfo, R: io::TcpInfo> io::TcpInfo for EitherIo<L, R> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        match self {
+            Self::Left(l) => l.tcp_socket(),
+            Self::Right(r) => r.tcp_socket(),
+        }
+    }
+}
+
 impl<L: io::AsyncRead, R: io::AsyncRead> io::AsyncRead for EitherIo<L, R> {
     #[inline]
     fn poll_read(
@@ -6,6 +6,7 @@ mod either;
 mod prefixed;
 mod scoped;
 mod sensor;
+mod tcp_info;
 
 pub use self::{
     boxed::BoxedIo,
@@ -13,6 +14,7 @@ pub use self::{
     prefixed::PrefixedIo,
     scoped::ScopedIo,
     sensor::{Sensor, SensorIo},
+    tcp_info::TcpInfo,
 };
 pub use std::io::*;
 use std::net::SocketAddr;
@@ -50,6 +50,13 @@ impl<I: io::PeerAddr> io::PeerAddr for PrefixedIo<I> {
     }
 }
 
+impl<I: io::TcpInfo> io::TcpInfo for PrefixedIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.io.tcp_socket()
+    }
+}
+
 impl<I: io::AsyncRead> io::AsyncRead for PrefixedIo<I> {
     fn poll_read(
         self: Pin<&mut Self>,
@@ -77,6 +77,13 @@ impl<I: io::PeerAddr> io::PeerAddr for ScopedIo<I> {
     }
 }
 
+impl<I: io::TcpInfo> io::TcpInfo for ScopedIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.io.tcp_socket()
+    }
+}
+
 impl<I: io::AsyncRead> io::AsyncRead for ScopedIo<I> {
     #[inline]
     fn poll_read(
@@ -1,4 +1,4 @@
-use crate::{IoSlice, Peek, PeerAddr, Poll};
+use crate::{IoSlice, Peek, PeerAddr, Poll, TcpInfo};
 use futures::ready;
 use linkerd_errno::Errno;
 use pin_project::pin_project;
@@ -11,6 +11,12 @@ pub trait Sensor {
     fn record_write(&mut self, sz: usize);
     fn record_close(&mut self, eos: Option<Errno>);
     fn record_error<T>(&mut self, op: Poll<T>) -> Poll<T>;
+
+    /// Offers the transport's TCP socket to the sensor so that its statistics
+    /// may be sampled. This is called as the transport is read from and
+    /// written to and, with `closing` set, when it reaches EOF or is shut down;
+    /// the sensor determines how often samples are actually taken.
+    fn record_tcp_info<I: TcpInfo + ?Sized>(&mut self, _io: &I, _closing: bool) {}
 }
 
 /// Wraps a transport with telemetry.
@@ -31,19 +37,26 @@ impl<T, S: Sensor> SensorIo<T, S> {
     }
 }
 
-impl<T: AsyncRead + AsyncWrite, S: Sensor> AsyncRead for SensorIo<T, S> {
+impl<T: AsyncRead + AsyncWrite + TcpInfo, S: Sensor> AsyncRead for SensorIo<T, S> {
     fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<()> {
-        let this = self.project();
+        let mut this = self.project();
         let prev_filled = buf.filled().len();
-        ready!(this.sensor.record_error(this.io.poll_read(cx, buf)))?;
-        this.sensor.record_read(buf.filled().len() - prev_filled);
+        ready!(this
+            .sensor
+            .record_error(this.io.as_mut().poll_read(cx, buf)))?;
+        let sz = buf.filled().len() - prev_filled;
+        this.sensor.record_read(sz);
+        let eof = sz == 0 && buf.remaining() > 0;
+        this.sensor.record_tcp_info(this.io.as_ref().get_ref(), eof);
         Poll::Ready(Ok(()))
     }
 }
 
-impl<T: AsyncRead + AsyncWrite, S: Sensor> AsyncWrite for SensorIo<T, S> {
+impl<T: AsyncRead + AsyncWrite + TcpInfo, S: Sensor> AsyncWrite for SensorIo<T, S> {
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
         let this = self.project();
+        this.sensor
+            .record_tcp_info(this.io.as_ref().get_ref(), true);
         this.sensor.record_error(this.io.poll_shutdown(cx))
     }
 
@@ -53,9 +66,13 @@ impl<T: AsyncRead + AsyncWrite, S: Sensor> AsyncWrite for SensorIo<T, S> {
     }
 
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<usize> {
-        let this = self.project();
-        let bytes = ready!(this.sensor.record_error(this.io.poll_write(cx, buf)))?;
+        let mut this = self.project();
+        let bytes = ready!(this
+            .sensor
+            .record_error(this.io.as_mut().poll_write(cx, buf)))?;
         this.sensor.record_write(bytes);
+        this.sensor
+            .record_tcp_info(this.io.as_ref().get_ref(), false);
         Poll::Ready(Ok(bytes))
     }
 
@@ -64,11 +81,13 @@ impl<T: AsyncRead + AsyncWrite, S: Sensor> AsyncWrite for SensorIo<T, S> {
         cx: &mut Context<'_>,
         bufs: &[IoSlice<'_>],
     ) -> Poll<usize> {
-        let this = self.project();
+        let mut this = self.project();
         let bytes = ready!(this
             .sensor
-            .record_error(this.io.poll_write_vectored(cx, bufs)))?;
+            .record_error(this.io.as_mut().poll_write_vectored(cx, bufs)))?;
         this.sensor.record_write(bytes);
+        this.sensor
+            .record_tcp_info(this.io.as_ref().get_ref(), false);
         Poll::Ready(Ok(bytes))
     }
 
@@ -83,6 +102,12 @@ impl<T: PeerAddr, S> PeerAddr for SensorIo<T, S> {
     }
 }
 
+impl<T: TcpInfo, S> TcpInfo for SensorIo<T, S> {
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.io.tcp_socket()
+    }
+}
+
 #[async_trait::async_trait]
 impl<I: Peek + Send + Sync, S: Sensor + Sync> Peek for SensorIo<I, S> {
     async fn peek(&self, buf: &mut [u8]) -> Result<usize> {
@@ -0,0 +1,36 @@
+use std::os::fd::{AsFd, BorrowedFd};
+
+/// Provides access to the TCP socket backing a transport so that its kernel
+/// statistics may be sampled.
+pub trait TcpInfo {
+    /// Returns the transport's underlying TCP socket, if it has one.
+    ///
+    /// The socket is borrowed, so that sampling does not hold an additional
+    /// file descriptor for the lifetime of the connection.
+    fn tcp_socket(&self) -> Option<BorrowedFd<'_>>;
+}
+
+impl TcpInfo for tokio::net::TcpStream {
+    fn tcp_socket(&self) -> Option<BorrowedFd<'_>> {
+        Some(self.as_fd())
+    }
+}
+
+impl TcpInfo for tokio::net::UnixStream {
+    fn tcp_socket(&self) -> Option<BorrowedFd<'_>> {
+        None
+    }
+}
+
+impl TcpInfo for tokio::io::DuplexStream {
+    fn tcp_socket(&self) -> Option<BorrowedFd<'_>> {
+        None
+    }
+}
+
+#[cfg(feature = "tokio-test")]
+impl TcpInfo for tokio_test::io::Mock {
+    fn tcp_socket(&self) -> Option<BorrowedFd<'_>> {
+        None
+    }
+}
@@ -1,14 +1,18 @@
 use crate::creds::CredsRx;
-use linkerd_identity as id;
+use linkerd_identity::{self as id, HandshakeDirection::Outbound, HandshakeFailure};
 use linkerd_io as io;
 use linkerd_meshtls_verifier as verifier;
 use linkerd_stack::{NewService, Service};
 use linkerd_tls::{client::AlpnProtocols, ClientTls, NegotiatedProtocolRef, ServerName};
 use std::{future::Future, pin::Pin, sync::Arc, task::Context};
+use tokio::time;
 use tracing::{debug, trace};
 
 #[derive(Clone)]
-pub struct NewClient(CredsRx);
+pub struct NewClient {
+    rx: CredsRx,
+    metrics: id::HandshakeMetrics,
+}
 
 #[derive(Clone)]
 pub struct Connect {
@@ -16,18 +20,19 @@ pub struct Connect {
     alpn: Option<Arc<[Vec<u8>]>>,
     id: id::Id,
     server: ServerName,
+    metrics: id::HandshakeMetrics,
 }
 
 pub type ConnectFuture<I> = Pin<Box<dyn Future<Output = io::Result<ClientIo<I>>> + Send>>;
 
 #[derive(Debug)]
-pub struct ClientIo<I>(tokio_boring::SslStream<I>);
+pub struct ClientIo<I>(pub(crate) tokio_boring::SslStream<I>);
 
 // === impl NewClient ===
 
 impl NewClient {
-    pub(crate) fn new(rx: CredsRx) -> Self {
-        Self(rx)
+    pub(crate) fn new(rx: CredsRx, metrics: id::HandshakeMetrics) -> Self {
+        Self { rx, metrics }
     }
 }
 
@@ -35,7 +40,7 @@ impl NewService<ClientTls> for NewClient {
     type Service = Connect;
 
     fn new_service(&self, target: ClientTls) -> Self::Service {
-        Connect::new(target, self.0.clone())
+        Connect::new(target, self.rx.clone(), self.metrics.clone())
     }
 }
 
@@ -48,12 +53,13 @@ impl std::fmt::Debug for NewClient {
 // === impl Connect ===
 
 impl Connect {
-    pub(crate) fn new(client_tls: ClientTls, rx: CredsRx) -> Self {
+    pub(crate) fn new(client_tls: ClientTls, rx: CredsRx, metrics: id::HandshakeMetrics) -> Self {
         Self {
             rx,
             alpn: client_tls.alpn.map(|AlpnProtocols(ps)| ps.into()),
             server: client_tls.server_name,
             id: client_tls.server_id.into(),
+            metrics,
         }
     }
 }
@@ -73,6 +79,7 @@ where
     fn call(&mut self, io: I) -> Self::Future {
         let server_name = self.server.clone();
         let server_id = self.id.clone();
+        let metrics = self.metrics.clone();
         let connector = self
             .rx
             .borrow()
@@ -90,8 +97,13 @@ where
             // peer's certificate actually matches the `server_name`. Instead,
             // the `server_id` is used to perform the appropriate form of
             // verification after the session is established.
-            let io = tokio_boring::connect(config.verify_hostname(false), server_name.as_str(), io)
-                .await
+            let started = time::Instant::now();
+            let res =
+                tokio_boring::connect(config.verify_hostname(false), server_name.as_str(), io)
+                    .await;
+            let elapsed = time::Instant::now().saturating_duration_since(started);
+            let io = res
+                .inspect_err(|e| super::record_failure(&metrics, Outbound, elapsed, e.ssl()))
                 .map_err(|e| match e.as_io_error() {
                     // TODO(ver) boring should let us take ownership of the error directly.
                     Some(ioe) => io::Error::new(ioe.kind(), ioe.to_string()),
@@ -104,10 +116,14 @@ where
             // Servers must present a peer certificate. We extract the x509 cert
             // and verify it manually against the `server_id`.
             let cert = io.ssl().peer_certificate().ok_or_else(|| {
+                metrics.failed(Outbound, elapsed, HandshakeFailure::Other);
                 io::Error::new(io::ErrorKind::Other, "could not extract peer cert")
             })?;
             let cert_der = id::DerX509(cert.to_der()?);
-            verifier::verify_id(&cert_der, &server_id)?;
+            verifier::verify_id(&cert_der, &server_id).inspect_err(|_| {
+                metrics.failed(Outbound, elapsed, HandshakeFailure::SanMismatch)
+            })?;
+            super::record_handshake(&metrics, Outbound, elapsed, io.ssl());
 
             debug!(
                 tls = io.ssl().version_str(),
@@ -182,3 +198,10 @@ impl<I: io::PeerAddr> io::PeerAddr for ClientIo<I> {
         self.0.get_ref().peer_addr()
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ClientIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.0.get_ref().tcp_socket()
+    }
+}
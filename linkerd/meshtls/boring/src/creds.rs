@@ -5,26 +5,25 @@ pub use self::{receiver::Receiver, store::Store};
 use boring::{
     pkey::{PKey, Private},
     ssl,
-    x509::{store::X509StoreBuilder, X509},
+    x509::{store::X509StoreBuilder, X509StoreContext, X509},
 };
 use linkerd_dns_name as dns;
 use linkerd_error::Result;
 use linkerd_identity as id;
-use std::sync::Arc;
+use linkerd_meshtls_verifier::{self as verifier, Issuer, Revocation};
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::watch;
 
 pub fn watch(
     local_id: id::Id,
     server_name: dns::Name,
     roots_pem: &str,
+    metrics: id::HandshakeMetrics,
 ) -> Result<(Store, Receiver)> {
-    let creds = {
-        let roots = X509::stack_from_pem(roots_pem.as_bytes())?;
-        Arc::new(BaseCreds { roots })
-    };
+    let creds = Arc::new(BaseCreds::from_pem(roots_pem)?);
 
     let (tx, rx) = watch::channel(Creds::from(creds.clone()));
-    let rx = Receiver::new(local_id.clone(), server_name, rx);
+    let rx = Receiver::new(local_id.clone(), server_name, rx, metrics);
     let store = Store::new(creds, local_id, tx);
 
     Ok((store, rx))
@@ -37,6 +36,12 @@ pub(crate) struct Creds {
 
 struct BaseCreds {
     roots: Vec<X509>,
+    /// Trust roots for each SPIFFE trust domain, keyed by trust domain name.
+    bundles: HashMap<String, Vec<X509>>,
+    revocation: Option<Revocation>,
+    /// A colon-separated list of key exchange groups, in order of preference.
+    /// If unset, boring's defaults are used.
+    curves: Option<String>,
 }
 
 struct Certs {
@@ -49,6 +54,100 @@ pub(crate) type CredsRx = watch::Receiver<Creds>;
 
 type CredsTx = watch::Sender<Creds>;
 
+// === impl BaseCreds ===
+
+impl BaseCreds {
+    /// Parses a PEM-encoded bundle of trust roots.
+    ///
+    /// The bundle may contain multiple roots, e.g. while a root is being rotated.
+    fn from_pem(roots_pem: &str) -> Result<Self> {
+        let roots = X509::stack_from_pem(roots_pem.as_bytes())?;
+        if roots.is_empty() {
+            return Err("no trust roots in PEM file".into());
+        }
+        Ok(Self {
+            roots,
+            bundles: HashMap::new(),
+            revocation: None,
+            curves: None,
+        })
+    }
+
+    /// Builds a store with the default roots and all trust bundles' roots.
+    ///
+    /// Boring verifies chains against a single store, so
+    /// [`BaseCreds::check_trust_domain`] ensures that SPIFFE certificates
+    /// chain to their own trust domain's roots.
+    fn root_store(&self) -> Result<boring::x509::store::X509Store> {
+        let mut store = X509StoreBuilder::new()?;
+        let mut added = Vec::new();
+        for c in self.roots.iter().chain(self.bundles.values().flatten()) {
+            let der = c.to_der()?;
+            if !added.contains(&der) {
+                store.add_cert(c.to_owned())?;
+                added.push(der);
+            }
+        }
+
+        Ok(store.build())
+    }
+
+    /// Ensures that a certificate naming a SPIFFE ID was issued by a root in
+    /// its trust domain's bundle, given the DER-encoded end-entity
+    /// certificate and the trust anchor of its verified chain.
+    ///
+    /// All certificates are accepted when no trust bundles are configured.
+    fn check_trust_domain(&self, leaf: &[u8], anchor: &[u8]) -> Result<()> {
+        if self.bundles.is_empty() {
+            return Ok(());
+        }
+        let Some(domain) = verifier::spiffe_trust_domain(leaf)? else {
+            return Ok(());
+        };
+
+        let bundle = self
+            .bundles
+            .get(&domain)
+            .ok_or_else(|| format!("no trust bundle for SPIFFE trust domain {domain}"))?;
+        for root in bundle {
+            if root.to_der()? == anchor {
+                return Ok(());
+            }
+        }
+        Err(format!("certificate is not issued by SPIFFE trust domain {domain}").into())
+    }
+}
+
+// === impl Certs ===
+
+impl Certs {
+    /// Ensures that the certificate chain is issued by one of the given roots.
+    fn verify(&self, base: &BaseCreds) -> Result<()> {
+        let mut context = X509StoreContext::new()?;
+        let roots = base.root_store()?;
+
+        let mut chain = boring::stack::Stack::new()?;
+        for i in &self.intermediates {
+            chain.push(i.to_owned())?;
+        }
+        let anchor = context.init(&roots, &self.leaf, &chain, |c| {
+            if !c.verify_cert()? {
+                return Ok(None);
+            }
+            c.chain()
+                .and_then(|chain| chain.iter().last())
+                .map(|anchor| anchor.to_der())
+                .transpose()
+        })?;
+        let Some(anchor) = anchor else {
+            return Err("certificate could not be validated against the trust chain".into());
+        };
+        base.check_trust_domain(&self.leaf.to_der()?, &anchor)?;
+
+        Ok(())
+    }
+}
+
 // === impl Creds ===
 
 impl From<Arc<BaseCreds>> for Creds {
@@ -78,9 +177,12 @@ impl Creds {
             "Configuring acceptor roots",
         );
         conn.set_cert_store(roots);
+        if let Some(curves) = &self.base.curves {
+            conn.set_curves_list(curves)?;
+        }
 
         // Ensure that client certificates are validated when present.
-        conn.set_verify(ssl::SslVerifyMode::PEER);
+        self.set_verify(&mut conn);
 
         if let Some(certs) = &self.certs {
             tracing::debug!(
@@ -128,6 +230,10 @@ impl Creds {
         );
         let roots = self.root_store()?;
         conn.set_cert_store(roots);
+        if let Some(curves) = &self.base.curves {
+            conn.set_curves_list(curves)?;
+        }
+        self.set_verify(&mut conn);
 
         if let Some(certs) = &self.certs {
             tracing::debug!(
@@ -152,19 +258,65 @@ impl Creds {
     }
 
     fn root_store(&self) -> Result<boring::x509::store::X509Store> {
-        let mut store = X509StoreBuilder::new()?;
-        for c in &self.base.roots {
-            store.add_cert(c.to_owned())?;
+        self.base.root_store()
+    }
+
+    /// Configures peer certificate verification, ensuring that end-entity
+    /// certificates chain to their SPIFFE trust domain's bundle and have not
+    /// been revoked when revocation checking is enabled.
+    fn set_verify(&self, conn: &mut ssl::SslContextBuilder) {
+        if self.base.bundles.is_empty() && self.base.revocation.is_none() {
+            conn.set_verify(ssl::SslVerifyMode::PEER);
+            return;
         }
 
-        Ok(store.build())
+        let base = self.base.clone();
+        conn.set_verify_callback(ssl::SslVerifyMode::PEER, move |ok, ctx| {
+            if !ok || ctx.error_depth() != 0 {
+                return ok;
+            }
+            let Some(der) = ctx.current_cert().and_then(|c| c.to_der().ok()) else {
+                return false;
+            };
+            let Some(anchor) = ctx
+                .chain()
+                .and_then(|chain| chain.iter().last())
+                .and_then(|c| c.to_der().ok())
+            else {
+                return false;
+            };
+
+            if let Err(error) = base.check_trust_domain(&der, &anchor) {
+                tracing::debug!(%error, "Peer certificate failed trust domain check");
+                return false;
+            }
+            if let Some(revocation) = &base.revocation {
+                // The verified chain includes the peer certificate's issuer.
+                let chain = ctx
+                    .chain()
+                    .map(|chain| {
+                        chain
+                            .iter()
+                            .skip(1)
+                            .filter_map(|c| c.to_der().ok())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                let issuers = chain.iter().filter_map(|c| Issuer::from_certificate(c));
+                if let Err(error) = revocation.check(&der, issuers) {
+                    tracing::debug!(%error, "Peer certificate failed revocation check");
+                    return false;
+                }
+            }
+            true
+        });
     }
 }
 
 /// Encodes a list of ALPN protocols into a slice of bytes.
 ///
 /// `boring` requires that the list of protocols be encoded in the wire format.
-fn serialize_alpn(protocols: &[Vec<u8>]) -> Result<Vec<u8>> {
+pub(crate) fn serialize_alpn(protocols: &[Vec<u8>]) -> Result<Vec<u8>> {
     // Allocate a buffer to hold the encoded protocols.
     let mut bytes = {
         // One additional byte for each protocol's length prefix.
@@ -8,11 +8,22 @@ pub struct Receiver {
     id: id::Id,
     name: dns::Name,
     rx: CredsRx,
+    metrics: id::HandshakeMetrics,
 }
 
 impl Receiver {
-    pub(crate) fn new(id: id::Id, name: dns::Name, rx: CredsRx) -> Self {
-        Self { id, name, rx }
+    pub(crate) fn new(
+        id: id::Id,
+        name: dns::Name,
+        rx: CredsRx,
+        metrics: id::HandshakeMetrics,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            rx,
+            metrics,
+        }
     }
 
     /// Returns the local identity.
@@ -27,12 +38,12 @@ impl Receiver {
 
     /// Returns a `NewClient` that can be used to establish TLS on client connections.
     pub fn new_client(&self) -> NewClient {
-        NewClient::new(self.rx.clone())
+        NewClient::new(self.rx.clone(), self.metrics.clone())
     }
 
     /// Returns a `Server` that can be used to terminate TLS on server connections.
     pub fn server(&self) -> Server {
-        Server::new(self.name.clone(), self.rx.clone())
+        Server::new(self.name.clone(), self.rx.clone(), self.metrics.clone())
     }
 }
 
@@ -1,10 +1,10 @@
 use super::{BaseCreds, Certs, Creds, CredsTx};
 use boring::pkey::PKey;
-use boring::x509::{X509StoreContext, X509};
+use boring::x509::X509;
 use linkerd_error::Result;
 use linkerd_identity as id;
-use linkerd_meshtls_verifier as verifier;
-use std::sync::Arc;
+use linkerd_meshtls_verifier::{self as verifier, KeyExchangeGroup, Revocation};
+use std::{collections::HashMap, sync::Arc};
 
 pub struct Store {
     creds: Arc<BaseCreds>,
@@ -18,6 +18,79 @@ impl Store {
     pub(super) fn new(creds: Arc<BaseCreds>, id: id::Id, tx: CredsTx) -> Self {
         Self { creds, id, tx }
     }
+
+    /// Replaces the trust roots used to validate peers and publishes new
+    /// credentials.
+    ///
+    /// Established connections retain the configuration with which they were
+    /// created, so they are not disrupted. The current certificate, if any,
+    /// must be valid for the new roots; otherwise the update is rejected.
+    pub fn set_roots(&mut self, roots_pem: &str) -> Result<()> {
+        let creds = Arc::new(BaseCreds {
+            bundles: self.creds.bundles.clone(),
+            revocation: self.creds.revocation.clone(),
+            curves: self.creds.curves.clone(),
+            ..BaseCreds::from_pem(roots_pem)?
+        });
+        if let Some(certs) = self.tx.borrow().certs.as_ref() {
+            certs.verify(&creds)?;
+        }
+
+        self.creds = creds.clone();
+        self.tx.send_modify(|c| c.base = creds);
+
+        Ok(())
+    }
+
+    /// Configures revocation checking for peer certificates and publishes new
+    /// credentials.
+    ///
+    /// Established connections retain the configuration with which they were
+    /// created, so they are not disrupted.
+    pub fn set_revocation(&mut self, revocation: Option<Revocation>) {
+        let creds = Arc::new(BaseCreds {
+            roots: self.creds.roots.clone(),
+            bundles: self.creds.bundles.clone(),
+            revocation,
+            curves: self.creds.curves.clone(),
+        });
+        self.creds = creds.clone();
+        self.tx.send_modify(|c| c.base = creds);
+    }
+
+    /// Sets the key exchange groups offered and accepted by TLS clients and
+    /// servers, in order of preference, and publishes new credentials.
+    ///
+    /// The update is rejected if any of the groups is not supported by boring.
+    pub fn set_key_exchange_groups(&mut self, groups: &[KeyExchangeGroup]) -> Result<()> {
+        if groups.is_empty() {
+            return Err("no TLS key exchange groups configured".into());
+        }
+        let curves = groups
+            .iter()
+            .map(|g| match g {
+                KeyExchangeGroup::X25519MlKem768 => "X25519MLKEM768",
+                KeyExchangeGroup::X25519 => "X25519",
+                KeyExchangeGroup::Secp256r1 => "P-256",
+                KeyExchangeGroup::Secp384r1 => "P-384",
+            })
+            .collect::<Vec<_>>()
+            .join(":");
+        // Ensure that boring supports all of the groups before publishing them.
+        boring::ssl::SslContextBuilder::new(boring::ssl::SslMethod::tls())?
+            .set_curves_list(&curves)?;
+
+        let creds = Arc::new(BaseCreds {
+            roots: self.creds.roots.clone(),
+            bundles: self.creds.bundles.clone(),
+            revocation: self.creds.revocation.clone(),
+            curves: Some(curves),
+        });
+        self.creds = creds.clone();
+        self.tx.send_modify(|c| c.base = creds);
+
+        Ok(())
+    }
 }
 
 impl id::Credentials for Store {
@@ -39,33 +112,55 @@ impl id::Credentials for Store {
             .collect::<Result<Vec<_>>>()?;
 
         let key = PKey::private_key_from_pkcs8(&key_pkcs8)?;
+        let certs = Certs {
+            leaf,
+            intermediates,
+            key,
+        };
+        certs.verify(&self.creds)?;
+
         let creds = Creds {
             base: self.creds.clone(),
-            certs: Some(Certs {
-                leaf,
-                intermediates,
-                key,
-            }),
+            certs: Some(certs),
         };
 
-        let mut context = X509StoreContext::new()?;
-        let roots = creds.root_store()?;
+        // If receivers are dropped, we don't return an error (as this would likely cause the
+        // updater to retry more aggressively). It's fine to silently ignore these errors.
+        let _ = self.tx.send(creds);
 
-        let mut chain = boring::stack::Stack::new()?;
-        for i in &creds.certs.as_ref().unwrap().intermediates {
-            chain.push(i.to_owned())?;
+        Ok(())
+    }
+
+    /// Publishes credentials that verify SPIFFE peers against their trust
+    /// domain's bundle.
+    ///
+    /// The current certificate, if any, must be valid for the new bundles;
+    /// otherwise the update is rejected.
+    fn set_trust_bundles(&mut self, bundles: id::TrustBundles) -> Result<()> {
+        let mut roots = HashMap::with_capacity(bundles.len());
+        for (domain, certs) in bundles {
+            let certs = certs
+                .into_iter()
+                .map(|id::DerX509(der)| X509::from_der(&der).map_err(Into::into))
+                .collect::<Result<Vec<_>>>()?;
+            if certs.is_empty() {
+                return Err(format!("no trust roots for trust domain {domain}").into());
+            }
+            roots.insert(domain, certs);
         }
-        let init = {
-            let leaf = &creds.certs.as_ref().unwrap().leaf;
-            context.init(&roots, leaf, &chain, |c| c.verify_cert())?
-        };
-        if !init {
-            return Err("certificate could not be validated against the trust chain".into());
+
+        let creds = Arc::new(BaseCreds {
+            roots: self.creds.roots.clone(),
+            bundles: roots,
+            revocation: self.creds.revocation.clone(),
+            curves: self.creds.curves.clone(),
+        });
+        if let Some(certs) = self.tx.borrow().certs.as_ref() {
+            certs.verify(&creds)?;
         }
 
-        // If receivers are dropped, we don't return an error (as this would likely cause the
-        // updater to retry more aggressively). It's fine to silently ignore these errors.
-        let _ = self.tx.send(creds);
+        self.creds = creds.clone();
+        self.tx.send_modify(|c| c.base = creds);
 
         Ok(())
     }
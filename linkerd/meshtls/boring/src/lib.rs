@@ -28,6 +28,10 @@ pub mod creds;
 mod server;
 #[cfg(test)]
 mod tests;
+pub mod web_pki;
+
+use linkerd_identity::{HandshakeDirection, HandshakeFailure, HandshakeMetrics, Negotiated};
+use std::time::Duration;
 
 pub use self::{
     client::{ClientIo, Connect, ConnectFuture, NewClient},
@@ -38,3 +42,61 @@ fn fingerprint(c: &boring::x509::X509Ref) -> Option<String> {
     let digest = c.digest(boring::hash::MessageDigest::sha256()).ok()?;
     Some(hex::encode(digest)[0..8].to_string())
 }
+
+/// Records the parameters negotiated by a completed handshake.
+fn record_handshake(
+    metrics: &HandshakeMetrics,
+    direction: HandshakeDirection,
+    elapsed: Duration,
+    ssl: &boring::ssl::SslRef,
+) {
+    let negotiated = Negotiated {
+        tls_version: ssl.version_str(),
+        alpn: ssl
+            .selected_alpn_protocol()
+            .map(|p| String::from_utf8_lossy(p).into_owned()),
+        cipher_suite: ssl
+            .current_cipher()
+            .and_then(|c| c.standard_name())
+            .unwrap_or("unknown"),
+        key_exchange_group: ssl.curve().and_then(|c| c.name()).unwrap_or("unknown"),
+        resumed: ssl.session_reused(),
+    };
+    tracing::debug!(
+        tls.version = %negotiated.tls_version,
+        tls.suite = %negotiated.cipher_suite,
+        tls.group = %negotiated.key_exchange_group,
+        tls.resumed = negotiated.resumed,
+        "Negotiated TLS parameters"
+    );
+    metrics.negotiated(direction, elapsed, negotiated);
+}
+
+/// Records a failed handshake, classifying it by the result of certificate
+/// verification.
+///
+/// tokio-boring does not expose the underlying error stack, so failures that
+/// are not certificate verification errors are recorded as `other`.
+fn record_failure(
+    metrics: &HandshakeMetrics,
+    direction: HandshakeDirection,
+    elapsed: Duration,
+    ssl: Option<&boring::ssl::SslRef>,
+) {
+    use boring::x509::X509VerifyError;
+
+    let reason = match ssl.map(|ssl| ssl.verify_result()) {
+        Some(Err(
+            X509VerifyError::UNABLE_TO_GET_ISSUER_CERT
+            | X509VerifyError::UNABLE_TO_GET_ISSUER_CERT_LOCALLY
+            | X509VerifyError::SELF_SIGNED_CERT_IN_CHAIN
+            | X509VerifyError::DEPTH_ZERO_SELF_SIGNED_CERT,
+        )) => HandshakeFailure::UnknownCa,
+        Some(Err(X509VerifyError::CERT_HAS_EXPIRED | X509VerifyError::CERT_NOT_YET_VALID)) => {
+            HandshakeFailure::Expired
+        }
+        _ => HandshakeFailure::Other,
+    };
+    tracing::debug!(reason = %reason.as_str(), "TLS handshake failed");
+    metrics.failed(direction, elapsed, reason);
+}
@@ -1,10 +1,12 @@
 use crate::creds::CredsRx;
 use linkerd_dns_name as dns;
+use linkerd_identity::{HandshakeDirection::Inbound, HandshakeMetrics, PeerCert};
 use linkerd_io as io;
 use linkerd_meshtls_verifier as verifier;
 use linkerd_stack::{Param, Service};
 use linkerd_tls::{ClientId, NegotiatedProtocol, ServerName, ServerTls};
 use std::{future::Future, pin::Pin, sync::Arc, task::Context};
+use tokio::time;
 use tracing::debug;
 
 #[derive(Clone)]
@@ -12,6 +14,7 @@ pub struct Server {
     name: dns::Name,
     rx: CredsRx,
     alpn: Option<Arc<[Vec<u8>]>>,
+    metrics: HandshakeMetrics,
 }
 
 pub type TerminateFuture<I> =
@@ -23,11 +26,12 @@ pub struct ServerIo<I>(tokio_boring::SslStream<I>);
 // === impl Server ===
 
 impl Server {
-    pub(crate) fn new(name: dns::Name, rx: CredsRx) -> Self {
+    pub(crate) fn new(name: dns::Name, rx: CredsRx, metrics: HandshakeMetrics) -> Self {
         Self {
             name,
             rx,
             alpn: None,
+            metrics,
         }
     }
 
@@ -67,11 +71,15 @@ where
             .rx
             .borrow()
             .acceptor(self.alpn.as_deref().unwrap_or(&[]));
+        let metrics = self.metrics.clone();
         Box::pin(async move {
             let acc = acceptor.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-            let io = tokio_boring::accept(&acc, io)
-                .await
+            let started = time::Instant::now();
+            let res = tokio_boring::accept(&acc, io).await;
+            let elapsed = time::Instant::now().saturating_duration_since(started);
+            let io = res
                 .map(ServerIo)
+                .inspect_err(|e| super::record_failure(&metrics, Inbound, elapsed, e.ssl()))
                 .map_err(|e| match e.as_io_error() {
                     Some(ioe) => io::Error::new(ioe.kind(), ioe.to_string()),
                     // XXX(ver) to use the boring error directly here we have to constraint the
@@ -79,6 +87,7 @@ where
                     None => io::Error::new(io::ErrorKind::Other, "unexpected TLS handshake error"),
                 })?;
 
+            super::record_handshake(&metrics, Inbound, elapsed, io.0.ssl());
             let client_id = io.client_identity();
             let negotiated_protocol = io.negotiated_protocol();
 
@@ -128,6 +137,31 @@ impl<I> ServerIo<I> {
             }
         }
     }
+
+    /// Describes the certificate presented by the client, if any.
+    ///
+    /// The certificate is parsed on each call, so callers should only do so
+    /// when the description is needed.
+    pub fn client_cert(&self) -> Option<PeerCert> {
+        let ssl = self.0.ssl();
+        let leaf = ssl.peer_certificate()?.to_der().ok()?;
+
+        // On the server side, the peer's chain may omit its leaf certificate,
+        // so the leaf is counted separately.
+        let intermediates = ssl
+            .peer_cert_chain()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .filter(|c| c.to_der().map_or(true, |der| der != leaf))
+                    .count()
+            })
+            .unwrap_or(0);
+
+        verifier::peer_cert(&leaf, intermediates + 1)
+            .map_err(|error| tracing::warn!(%error, "Failed to describe client end cert"))
+            .ok()
+    }
 }
 
 impl<I: io::AsyncRead + io::AsyncWrite + Unpin> io::AsyncRead for ServerIo<I> {
@@ -178,3 +212,10 @@ impl<I: io::PeerAddr> io::PeerAddr for ServerIo<I> {
         self.0.get_ref().peer_addr()
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ServerIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.0.get_ref().tcp_socket()
+    }
+}
@@ -0,0 +1,149 @@
+//! Originates TLS to servers outside of the mesh.
+//!
+//! Servers are authenticated by web PKI: they must present a certificate for
+//! the requested server name that chains to one of the configured roots.
+
+use crate::client::{ClientIo, ConnectFuture};
+use boring::{
+    pkey::PKey,
+    ssl,
+    x509::{store::X509StoreBuilder, X509},
+};
+use linkerd_error::Result;
+use linkerd_io as io;
+use linkerd_stack::{NewService, Service};
+use linkerd_tls::{client::AlpnProtocols, OriginateTls, ServerName};
+use std::{sync::Arc, task::Context};
+use tracing::debug;
+
+/// A `NewService` that produces `Connect` services for servers outside of the
+/// mesh.
+#[derive(Clone)]
+pub struct NewClient {
+    connector: ssl::SslConnector,
+}
+
+/// A `Service` that initiates client-side TLS connections to servers outside
+/// of the mesh.
+#[derive(Clone)]
+pub struct Connect {
+    server_name: ServerName,
+    alpn: Option<Arc<[u8]>>,
+    connector: ssl::SslConnector,
+}
+
+/// A PEM-encoded certificate chain and private key with which the client
+/// authenticates to servers that request a client certificate.
+#[derive(Clone)]
+pub struct ClientCert<'p> {
+    pub chain_pem: &'p str,
+    pub key_pem: &'p str,
+}
+
+// === impl NewClient ===
+
+impl NewClient {
+    /// Builds a client that trusts the roots in `roots_pem`.
+    pub fn new(roots_pem: &str, client_cert: Option<ClientCert<'_>>) -> Result<Self> {
+        let roots = X509::stack_from_pem(roots_pem.as_bytes())?;
+        if roots.is_empty() {
+            return Err("no trust roots in PEM file".into());
+        }
+        let mut store = X509StoreBuilder::new()?;
+        for root in roots {
+            store.add_cert(root)?;
+        }
+
+        // Unlike the mesh's connector, servers outside of the mesh may only
+        // support TLS 1.2, so only the legacy protocol versions are disabled.
+        let mut conn = ssl::SslConnector::builder(ssl::SslMethod::tls_client())?;
+        conn.set_options(ssl::SslOptions::NO_TLSV1 | ssl::SslOptions::NO_TLSV1_1);
+        conn.set_cert_store(store.build());
+        conn.set_verify(ssl::SslVerifyMode::PEER);
+
+        if let Some(ClientCert { chain_pem, key_pem }) = client_cert {
+            let mut chain = X509::stack_from_pem(chain_pem.as_bytes())?.into_iter();
+            let leaf = chain
+                .next()
+                .ok_or("no certificates in client certificate PEM")?;
+            let key = PKey::private_key_from_pem(key_pem.as_bytes())?;
+            conn.set_private_key(&key)?;
+            conn.set_certificate(&leaf)?;
+            conn.check_private_key()?;
+            for c in chain {
+                conn.add_extra_chain_cert(c)?;
+            }
+        }
+
+        Ok(Self {
+            connector: conn.build(),
+        })
+    }
+}
+
+impl NewService<OriginateTls> for NewClient {
+    type Service = Connect;
+
+    fn new_service(&self, target: OriginateTls) -> Self::Service {
+        let alpn = target.alpn.and_then(|AlpnProtocols(ps)| {
+            crate::creds::serialize_alpn(&ps)
+                .map_err(|error| debug!(%error, "Ignoring invalid ALPN protocols"))
+                .ok()
+                .map(Into::into)
+        });
+        Connect {
+            server_name: target.server_name,
+            alpn,
+            connector: self.connector.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for NewClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewClient").finish()
+    }
+}
+
+// === impl Connect ===
+
+impl<I> Service<I> for Connect
+where
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
+{
+    type Response = ClientIo<I>;
+    type Error = io::Error;
+    type Future = ConnectFuture<I>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> io::Poll<()> {
+        io::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, io: I) -> Self::Future {
+        let server_name = self.server_name.clone();
+        let config = self.connector.configure().and_then(|mut config| {
+            if let Some(alpn) = self.alpn.as_deref() {
+                config.set_alpn_protos(alpn)?;
+            }
+            Ok(config)
+        });
+        Box::pin(async move {
+            let config = config.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+            // Unlike mesh connections, the server's certificate must be valid
+            // for the server name.
+            let io = tokio_boring::connect(config, server_name.as_str(), io)
+                .await
+                .map_err(|e| match e.as_io_error() {
+                    Some(ioe) => io::Error::new(ioe.kind(), ioe.to_string()),
+                    None => io::Error::new(io::ErrorKind::Other, "TLS handshake failed"),
+                })?;
+            debug!(
+                tls = io.ssl().version_str(),
+                alpn = ?io.ssl().selected_alpn_protocol(),
+                "Established TLS connection"
+            );
+            Ok(ClientIo(io))
+        })
+    }
+}
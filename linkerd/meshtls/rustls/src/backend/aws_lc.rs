@@ -1,11 +1,20 @@
-pub use aws_lc_rs::default_provider;
+pub use aws_lc_rs::{default_provider, Ticketer};
+use linkerd_meshtls_verifier::KeyExchangeGroup;
 use tokio_rustls::rustls::{
     self,
-    crypto::{aws_lc_rs, WebPkiSupportedAlgorithms},
+    crypto::{aws_lc_rs, SupportedKxGroup, WebPkiSupportedAlgorithms},
 };
 
 pub static TLS_SUPPORTED_CIPHERSUITES: &[rustls::SupportedCipherSuite] =
     &[rustls::crypto::aws_lc_rs::cipher_suite::TLS13_CHACHA20_POLY1305_SHA256];
+/// Prefer the hybrid post-quantum group, falling back to classical groups for
+/// peers that do not support it.
+pub static TLS_DEFAULT_KX_GROUPS: &[KeyExchangeGroup] = &[
+    KeyExchangeGroup::X25519MlKem768,
+    KeyExchangeGroup::X25519,
+    KeyExchangeGroup::Secp256r1,
+    KeyExchangeGroup::Secp384r1,
+];
 pub static SUPPORTED_SIG_ALGS: &WebPkiSupportedAlgorithms = &WebPkiSupportedAlgorithms {
     all: &[
         webpki::aws_lc_rs::ECDSA_P256_SHA256,
@@ -61,3 +70,15 @@ pub static SUPPORTED_SIG_ALGS: &WebPkiSupportedAlgorithms = &WebPkiSupportedAlgo
         ),
     ],
 };
+
+/// Returns the implementation of a key exchange group. aws-lc supports all
+/// groups, including hybrid post-quantum groups.
+pub fn kx_group(group: KeyExchangeGroup) -> Option<&'static dyn SupportedKxGroup> {
+    let group = match group {
+        KeyExchangeGroup::X25519MlKem768 => aws_lc_rs::kx_group::X25519MLKEM768,
+        KeyExchangeGroup::X25519 => aws_lc_rs::kx_group::X25519,
+        KeyExchangeGroup::Secp256r1 => aws_lc_rs::kx_group::SECP256R1,
+        KeyExchangeGroup::Secp384r1 => aws_lc_rs::kx_group::SECP384R1,
+    };
+    Some(group)
+}
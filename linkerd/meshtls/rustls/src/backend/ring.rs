@@ -1,11 +1,17 @@
-pub use ring::default_provider;
+use linkerd_meshtls_verifier::KeyExchangeGroup;
+pub use ring::{default_provider, Ticketer};
 use tokio_rustls::rustls::{
     self,
-    crypto::{ring, WebPkiSupportedAlgorithms},
+    crypto::{ring, SupportedKxGroup, WebPkiSupportedAlgorithms},
 };
 
 pub static TLS_SUPPORTED_CIPHERSUITES: &[rustls::SupportedCipherSuite] =
     &[rustls::crypto::ring::cipher_suite::TLS13_CHACHA20_POLY1305_SHA256];
+pub static TLS_DEFAULT_KX_GROUPS: &[KeyExchangeGroup] = &[
+    KeyExchangeGroup::X25519,
+    KeyExchangeGroup::Secp256r1,
+    KeyExchangeGroup::Secp384r1,
+];
 // A subset of the algorithms supported by rustls+ring, imported from
 // https://github.com/rustls/rustls/blob/v/0.23.21/rustls/src/crypto/ring/mod.rs#L107
 pub static SUPPORTED_SIG_ALGS: &WebPkiSupportedAlgorithms = &WebPkiSupportedAlgorithms {
@@ -50,3 +56,17 @@ pub static SUPPORTED_SIG_ALGS: &WebPkiSupportedAlgorithms = &WebPkiSupportedAlgo
         ),
     ],
 };
+
+/// Returns the implementation of a key exchange group, if it is supported by
+/// ring.
+///
+/// ring does not implement ML-KEM, so hybrid post-quantum groups are not
+/// supported.
+pub fn kx_group(group: KeyExchangeGroup) -> Option<&'static dyn SupportedKxGroup> {
+    match group {
+        KeyExchangeGroup::X25519MlKem768 => None,
+        KeyExchangeGroup::X25519 => Some(ring::kx_group::X25519),
+        KeyExchangeGroup::Secp256r1 => Some(ring::kx_group::SECP256R1),
+        KeyExchangeGroup::Secp384r1 => Some(ring::kx_group::SECP384R1),
+    }
+}
@@ -1,17 +1,20 @@
 use futures::prelude::*;
-use linkerd_identity as id;
+use linkerd_identity::{
+    self as id, HandshakeDirection::Outbound, HandshakeFailure, HandshakeMetrics,
+};
 use linkerd_io as io;
 use linkerd_meshtls_verifier as verifier;
 use linkerd_stack::{NewService, Service};
 use linkerd_tls::{client::AlpnProtocols, ClientTls, NegotiatedProtocolRef};
 use std::{convert::TryFrom, pin::Pin, sync::Arc, task::Context};
-use tokio::sync::watch;
+use tokio::{sync::watch, time};
 use tokio_rustls::rustls::{self, pki_types::CertificateDer, ClientConfig};
 
 /// A `NewService` that produces `Connect` services from a dynamic TLS configuration.
 #[derive(Clone)]
 pub struct NewClient {
     config: watch::Receiver<Arc<ClientConfig>>,
+    metrics: HandshakeMetrics,
 }
 
 /// A `Service` that initiates client-side TLS connections.
@@ -20,18 +23,22 @@ pub struct Connect {
     server_id: id::Id,
     server_name: rustls::pki_types::ServerName<'static>,
     config: Arc<ClientConfig>,
+    metrics: HandshakeMetrics,
 }
 
 pub type ConnectFuture<I> = Pin<Box<dyn Future<Output = io::Result<ClientIo<I>>> + Send>>;
 
 #[derive(Debug)]
-pub struct ClientIo<I>(tokio_rustls::client::TlsStream<I>);
+pub struct ClientIo<I>(pub(crate) tokio_rustls::client::TlsStream<I>);
 
 // === impl NewClient ===
 
 impl NewClient {
-    pub(crate) fn new(config: watch::Receiver<Arc<ClientConfig>>) -> Self {
-        Self { config }
+    pub(crate) fn new(
+        config: watch::Receiver<Arc<ClientConfig>>,
+        metrics: HandshakeMetrics,
+    ) -> Self {
+        Self { config, metrics }
     }
 }
 
@@ -39,7 +46,11 @@ impl NewService<ClientTls> for NewClient {
     type Service = Connect;
 
     fn new_service(&self, target: ClientTls) -> Self::Service {
-        Connect::new(target, (*self.config.borrow()).clone())
+        Connect::new(
+            target,
+            (*self.config.borrow()).clone(),
+            self.metrics.clone(),
+        )
     }
 }
 
@@ -52,7 +63,11 @@ impl std::fmt::Debug for NewClient {
 // === impl Connect ===
 
 impl Connect {
-    pub(crate) fn new(client_tls: ClientTls, config: Arc<ClientConfig>) -> Self {
+    pub(crate) fn new(
+        client_tls: ClientTls,
+        config: Arc<ClientConfig>,
+        metrics: HandshakeMetrics,
+    ) -> Self {
         // If ALPN protocols are configured by the endpoint, we have to clone the entire
         // configuration and set the protocols. If there are no ALPN options, clone the Arc'd base
         // configuration without extra allocation.
@@ -76,6 +91,7 @@ impl Connect {
             server_id: client_tls.server_id.into(),
             server_name,
             config,
+            metrics,
         }
     }
 }
@@ -101,6 +117,8 @@ where
 
     fn call(&mut self, io: I) -> Self::Future {
         let server_id = self.server_id.clone();
+        let metrics = self.metrics.clone();
+        let started = time::Instant::now();
         Box::pin(
             // Connect to the server, sending the `server_name` SNI in the
             // client handshake. The provided config should use the
@@ -111,10 +129,18 @@ where
                 // XXX(eliza): it's a bummer that the server name has to be cloned here...
                 .connect(self.server_name.clone(), io)
                 .map(move |s| {
-                    let s = s?;
+                    let elapsed = time::Instant::now().saturating_duration_since(started);
+                    let s = s.inspect_err(|error| {
+                        crate::record_failure(&metrics, Outbound, elapsed, error)
+                    })?;
                     let (_, conn) = s.get_ref();
-                    let end_cert = extract_cert(conn)?;
-                    verifier::verify_id(end_cert, &server_id)?;
+                    let end_cert = extract_cert(conn).inspect_err(|_| {
+                        metrics.failed(Outbound, elapsed, HandshakeFailure::Other)
+                    })?;
+                    verifier::verify_id(end_cert, &server_id).inspect_err(|_| {
+                        metrics.failed(Outbound, elapsed, HandshakeFailure::SanMismatch)
+                    })?;
+                    crate::record_handshake(&metrics, Outbound, elapsed, conn);
                     Ok(ClientIo(s))
                 }),
         )
@@ -182,3 +208,10 @@ impl<I: io::PeerAddr> io::PeerAddr for ClientIo<I> {
         self.0.get_ref().0.peer_addr()
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ClientIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.0.get_ref().0.tcp_socket()
+    }
+}
@@ -8,11 +8,15 @@ pub use self::{receiver::Receiver, store::Store};
 use linkerd_dns_name as dns;
 use linkerd_error::Result;
 use linkerd_identity as id;
+use linkerd_meshtls_verifier::KeyExchangeGroup;
 use ring::error::KeyRejected;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::watch;
-use tokio_rustls::rustls::{self, crypto::CryptoProvider};
+use tokio_rustls::rustls::{
+    self,
+    crypto::{CryptoProvider, SupportedKxGroup},
+};
 use tracing::warn;
 
 #[derive(Debug, Error)]
@@ -23,11 +27,72 @@ pub struct InvalidKey(#[source] KeyRejected);
 #[error("invalid trust roots")]
 pub struct InvalidTrustRoots(());
 
+#[derive(Debug, Error)]
+#[error("TLS key exchange group {0} is not supported by this TLS backend")]
+pub struct UnsupportedKeyExchangeGroup(KeyExchangeGroup);
+
+type KxGroups = Vec<&'static dyn SupportedKxGroup>;
+
 pub fn watch(
     local_id: id::Id,
     server_name: dns::Name,
     roots_pem: &str,
+    metrics: id::HandshakeMetrics,
 ) -> Result<(Store, Receiver)> {
+    let roots = load_roots(roots_pem)?;
+    let kx_groups = default_kx_groups();
+
+    // XXX: Rustls's built-in verifiers don't let us tweak things as fully as we'd like (e.g.
+    // controlling the set of trusted signature algorithms), but they provide good enough
+    // defaults for now.
+    // TODO: lock down the verification further.
+    let server_cert_verifier = Arc::new(verify::AnySanVerifier::new(
+        roots.clone(),
+        Default::default(),
+        None,
+    ));
+
+    // Since we don't have a certificate yet, build a client configuration that
+    // doesn't attempt client authentication and a server configuration that
+    // always fails handshakes. Once we get a certificate, the `Store` will
+    // publish new configurations with certificate resolvers.
+    let (client_tx, client_rx) = watch::channel(store::client_config(
+        &kx_groups,
+        server_cert_verifier.clone(),
+        None,
+    ));
+    let (server_tx, server_rx) = watch::channel(store::server_config(
+        &kx_groups,
+        roots.clone(),
+        &Default::default(),
+        None,
+        None,
+    ));
+
+    let rx = Receiver::new(
+        local_id.clone(),
+        server_name.clone(),
+        client_rx,
+        server_rx,
+        metrics,
+    );
+    let store = Store::new(
+        roots,
+        kx_groups,
+        server_cert_verifier,
+        local_id,
+        server_name,
+        client_tx,
+        server_tx,
+    );
+
+    Ok((store, rx))
+}
+
+/// Parses a PEM-encoded bundle of trust roots.
+///
+/// The bundle may contain multiple roots, e.g. while a root is being rotated.
+pub(crate) fn load_roots(roots_pem: &str) -> Result<rustls::RootCertStore> {
     let mut roots = rustls::RootCertStore::empty();
     let certs = match rustls_pemfile::certs(&mut std::io::Cursor::new(roots_pem))
         .collect::<Result<Vec<_>, _>>()
@@ -51,59 +116,50 @@ pub fn watch(
         return Err("no trust roots loaded".into());
     }
 
-    // XXX: Rustls's built-in verifiers don't let us tweak things as fully as we'd like (e.g.
-    // controlling the set of trusted signature algorithms), but they provide good enough
-    // defaults for now.
-    // TODO: lock down the verification further.
-    let server_cert_verifier = Arc::new(verify::AnySanVerifier::new(roots.clone()));
-
-    let (client_tx, client_rx) = {
-        // Since we don't have a certificate yet, build a client configuration
-        // that doesn't attempt client authentication. Once we get a
-        // certificate, the `Store` will publish a new configuration with a
-        // client certificate resolver.
-        let mut c =
-            store::client_config_builder(server_cert_verifier.clone()).with_no_client_auth();
-
-        // Disable session resumption for the time-being until resumption is
-        // more tested.
-        c.resumption = rustls::client::Resumption::disabled();
-
-        watch::channel(Arc::new(c))
-    };
-    let (server_tx, server_rx) = {
-        // Since we don't have a certificate yet, use an empty cert resolver so
-        // that handshaking always fails. Once we get a certificate, the `Store`
-        // will publish a new configuration with a server certificate resolver.
-        let empty_resolver = Arc::new(rustls::server::ResolvesServerCertUsingSni::new());
-        watch::channel(store::server_config(roots.clone(), empty_resolver))
-    };
-
-    let rx = Receiver::new(local_id.clone(), server_name.clone(), client_rx, server_rx);
-    let store = Store::new(
-        roots,
-        server_cert_verifier,
-        local_id,
-        server_name,
-        client_tx,
-        server_tx,
-    );
-
-    Ok((store, rx))
+    Ok(roots)
 }
 
-fn default_provider() -> CryptoProvider {
+fn provider(kx_groups: &[&'static dyn SupportedKxGroup]) -> CryptoProvider {
     let mut provider = backend::default_provider();
     provider.cipher_suites = params::TLS_SUPPORTED_CIPHERSUITES.to_vec();
+    provider.kx_groups = kx_groups.to_vec();
     provider
 }
 
+/// Returns the backend's preferred key exchange groups.
+///
+/// When FIPS mode is enabled, groups without a FIPS-approved implementation
+/// are omitted.
+fn default_kx_groups() -> KxGroups {
+    params::TLS_DEFAULT_KX_GROUPS
+        .iter()
+        .filter_map(|g| backend::kx_group(*g))
+        .filter(|g| !params::FIPS || g.fips())
+        .collect()
+}
+
+/// Resolves the configured key exchange groups, in order of preference.
+fn kx_groups(groups: &[KeyExchangeGroup]) -> Result<KxGroups> {
+    if groups.is_empty() {
+        return Err("no TLS key exchange groups configured".into());
+    }
+
+    groups
+        .iter()
+        .map(|g| match backend::kx_group(*g) {
+            Some(kx) if !params::FIPS || kx.fips() => Ok(kx),
+            _ => Err(UnsupportedKeyExchangeGroup(*g).into()),
+        })
+        .collect()
+}
+
 #[cfg(feature = "test-util")]
 pub fn for_test(ent: &linkerd_tls_test_util::Entity) -> (Store, Receiver) {
     watch(
         ent.name.parse().expect("id must be valid"),
         ent.name.parse().expect("name must be valid"),
         std::str::from_utf8(ent.trust_anchors).expect("roots must be PEM"),
+        Default::default(),
     )
     .expect("credentials must be valid")
 }
@@ -128,4 +184,9 @@ mod params {
     pub static TLS_VERSIONS: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS13];
     pub static TLS_SUPPORTED_CIPHERSUITES: &[rustls::SupportedCipherSuite] =
         backend::TLS_SUPPORTED_CIPHERSUITES;
+    pub static TLS_DEFAULT_KX_GROUPS: &[linkerd_meshtls_verifier::KeyExchangeGroup] =
+        backend::TLS_DEFAULT_KX_GROUPS;
+    pub const FIPS: bool = cfg!(feature = "aws-lc-fips");
+    /// The maximum number of servers for which clients cache session tickets.
+    pub const CLIENT_SESSION_CACHE_SIZE: usize = 256;
 }
@@ -1,6 +1,6 @@
 use crate::{NewClient, Server};
 use linkerd_dns_name as dns;
-use linkerd_identity::Id;
+use linkerd_identity::{HandshakeMetrics, Id};
 use std::sync::Arc;
 use tokio::sync::watch;
 use tokio_rustls::rustls;
@@ -12,6 +12,7 @@ pub struct Receiver {
     name: dns::Name,
     client_rx: watch::Receiver<Arc<rustls::ClientConfig>>,
     server_rx: watch::Receiver<Arc<rustls::ServerConfig>>,
+    metrics: HandshakeMetrics,
 }
 
 // === impl Receiver ===
@@ -22,12 +23,14 @@ impl Receiver {
         name: dns::Name,
         client_rx: watch::Receiver<Arc<rustls::ClientConfig>>,
         server_rx: watch::Receiver<Arc<rustls::ServerConfig>>,
+        metrics: HandshakeMetrics,
     ) -> Self {
         Self {
             id,
             name,
             client_rx,
             server_rx,
+            metrics,
         }
     }
 
@@ -43,12 +46,16 @@ impl Receiver {
 
     /// Returns a `NewClient` that can be used to establish TLS on client connections.
     pub fn new_client(&self) -> NewClient {
-        NewClient::new(self.client_rx.clone())
+        NewClient::new(self.client_rx.clone(), self.metrics.clone())
     }
 
     /// Returns a `Server` that can be used to terminate TLS on server connections.
     pub fn server(&self) -> Server {
-        Server::new(self.name.clone(), self.server_rx.clone())
+        Server::new(
+            self.name.clone(),
+            self.server_rx.clone(),
+            self.metrics.clone(),
+        )
     }
 }
 
@@ -100,6 +107,7 @@ mod tests {
             id: "example".parse().unwrap(),
             server_rx,
             client_rx,
+            metrics: Default::default(),
         };
 
         let server = receiver.server();
@@ -124,6 +132,7 @@ mod tests {
             name: "example".parse().unwrap(),
             server_rx,
             client_rx,
+            metrics: Default::default(),
         };
 
         let server = receiver
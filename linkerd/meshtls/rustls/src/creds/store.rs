@@ -1,21 +1,28 @@
-use super::{default_provider, params::*, InvalidKey};
+use super::{params::*, provider, verify, InvalidKey, KxGroups};
+use crate::backend;
 use linkerd_dns_name as dns;
 use linkerd_error::Result;
 use linkerd_identity as id;
-use linkerd_meshtls_verifier as verifier;
+use linkerd_meshtls_verifier::{self as verifier, KeyExchangeGroup, Revocation};
 use ring::{rand, signature::EcdsaKeyPair};
 use std::{convert::TryFrom, sync::Arc};
 use tokio::sync::watch;
-use tokio_rustls::rustls::{self, pki_types::UnixTime, server::WebPkiClientVerifier};
-use tracing::debug;
+use tokio_rustls::rustls::{
+    self, crypto::SupportedKxGroup, pki_types::UnixTime, server::WebPkiClientVerifier,
+};
+use tracing::{debug, warn};
 
 pub struct Store {
     roots: rustls::RootCertStore,
+    kx_groups: KxGroups,
+    bundles: verify::TrustBundles,
     server_cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier>,
     server_id: id::Id,
     server_name: dns::Name,
     client_tx: watch::Sender<Arc<rustls::ClientConfig>>,
     server_tx: watch::Sender<Arc<rustls::ServerConfig>>,
+    resolver: Option<Arc<CertResolver>>,
+    revocation: Option<Revocation>,
     random: ring::rand::SystemRandom,
 }
 
@@ -23,12 +30,13 @@ pub struct Store {
 struct Key(Arc<EcdsaKeyPair>);
 
 #[derive(Clone, Debug)]
-struct CertResolver(Arc<rustls::sign::CertifiedKey>);
+pub(super) struct CertResolver(Arc<rustls::sign::CertifiedKey>);
 
-pub(super) fn client_config_builder(
+fn client_config_builder(
+    kx_groups: &[&'static dyn SupportedKxGroup],
     cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier>,
 ) -> rustls::ConfigBuilder<rustls::ClientConfig, rustls::client::WantsClientCert> {
-    rustls::ClientConfig::builder_with_provider(Arc::new(default_provider()))
+    rustls::ClientConfig::builder_with_provider(Arc::new(provider(kx_groups)))
         .with_protocol_versions(TLS_VERSIONS)
         .expect("client config must be valid")
         // XXX: Rustls's built-in verifiers don't let us tweak things as fully
@@ -38,37 +46,108 @@ pub(super) fn client_config_builder(
         //
         // NOTE(eliza): Rustls considers setting a custom server cert verifier
         // to be a "dangerous configuration", but we're doing *exactly* what its
-        // builder API does internally. However, we want to share the verifier
-        // with the `Store` so that it can be used in `Store::validate` which
-        // requires using this API.
+        // builder API does internally. However, our verifier omits DNS SAN
+        // validation, which requires using this API.
         .dangerous()
         .with_custom_certificate_verifier(cert_verifier)
 }
 
+/// Builds a new TLS client configuration.
+///
+/// If no certificate is available, the configuration does not attempt client
+/// authentication.
+pub(super) fn client_config(
+    kx_groups: &[&'static dyn SupportedKxGroup],
+    cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier>,
+    resolver: Option<Arc<CertResolver>>,
+) -> Arc<rustls::ClientConfig> {
+    let builder = client_config_builder(kx_groups, cert_verifier);
+    let mut cfg = match resolver {
+        Some(resolver) => builder.with_client_cert_resolver(resolver),
+        None => builder.with_no_client_auth(),
+    };
+
+    // Cache session tickets by server name. Proxies derive server names from
+    // identities, and a resumed session's certificate is still checked against
+    // the expected identity once the handshake completes. A new cache is used
+    // for each configuration so that sessions established with prior
+    // credentials are not resumed.
+    cfg.resumption = rustls::client::Resumption::store(Arc::new(
+        rustls::client::ClientSessionMemoryCache::new(CLIENT_SESSION_CACHE_SIZE),
+    ));
+
+    cfg.into()
+}
+
+/// Builds a new TLS server configuration.
+///
+/// If no certificate is available, an empty cert resolver is used so that
+/// handshaking always fails.
 pub(super) fn server_config(
+    kx_groups: &[&'static dyn SupportedKxGroup],
     roots: rustls::RootCertStore,
-    resolver: Arc<dyn rustls::server::ResolvesServerCert>,
+    bundles: &verify::TrustBundles,
+    resolver: Option<Arc<CertResolver>>,
+    revocation: Option<Revocation>,
 ) -> Arc<rustls::ServerConfig> {
+    let resolver: Arc<dyn rustls::server::ResolvesServerCert> = match resolver {
+        Some(resolver) => resolver,
+        None => Arc::new(rustls::server::ResolvesServerCertUsingSni::new()),
+    };
+
     // Ask TLS clients for a certificate and accept any certificate issued by our trusted CA(s).
     //
     // XXX: Rustls's built-in verifiers don't let us tweak things as fully as we'd like (e.g.
     // controlling the set of trusted signature algorithms), but they provide good enough
     // defaults for now.
     // TODO: lock down the verification further.
-    let provider = Arc::new(default_provider());
-
-    let client_cert_verifier =
-        WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+    let provider = Arc::new(provider(kx_groups));
+    let verifier = |roots: Arc<rustls::RootCertStore>| {
+        WebPkiClientVerifier::builder_with_provider(roots, provider.clone())
             .allow_unauthenticated()
             .build()
-            .expect("server verifier must be valid");
+            .expect("server verifier must be valid")
+    };
+
+    let roots = Arc::new(roots);
+    let mut client_cert_verifier = verifier(roots.clone());
+    if !bundles.is_empty() {
+        let bundles = bundles
+            .iter()
+            .map(|(domain, roots)| (domain.clone(), verifier(roots.clone())))
+            .collect();
+        client_cert_verifier = Arc::new(verify::FederatedClientVerifier::new(
+            client_cert_verifier,
+            bundles,
+        ));
+    }
+    if let Some(revocation) = revocation {
+        client_cert_verifier = Arc::new(verify::RevocationClientVerifier::new(
+            client_cert_verifier,
+            roots,
+            bundles.clone(),
+            revocation,
+        ));
+    }
 
-    rustls::ServerConfig::builder_with_provider(provider)
+    let mut cfg = rustls::ServerConfig::builder_with_provider(provider)
         .with_protocol_versions(TLS_VERSIONS)
         .expect("server config must be valid")
         .with_client_cert_verifier(client_cert_verifier)
-        .with_cert_resolver(resolver)
-        .into()
+        .with_cert_resolver(resolver);
+
+    // Issue stateless session tickets, encrypted with periodically rotated
+    // keys. A new ticketer is used for each configuration so that tickets
+    // issued with prior credentials are rejected.
+    match backend::Ticketer::new() {
+        Ok(ticketer) => {
+            cfg.ticketer = ticketer;
+            cfg.session_storage = Arc::new(rustls::server::NoServerSessionStorage {});
+        }
+        Err(error) => warn!(%error, "Failed to initialize session tickets"),
+    }
+
+    cfg.into()
 }
 
 // === impl Store ===
@@ -77,6 +156,7 @@ impl Store {
     #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         roots: rustls::RootCertStore,
+        kx_groups: KxGroups,
         server_cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier>,
         server_id: id::Id,
         server_name: dns::Name,
@@ -85,37 +165,115 @@ impl Store {
     ) -> Self {
         Self {
             roots,
+            kx_groups,
+            bundles: verify::TrustBundles::default(),
             server_cert_verifier,
             server_id,
             server_name,
             client_tx,
             server_tx,
+            resolver: None,
+            revocation: None,
             random: ring::rand::SystemRandom::new(),
         }
     }
 
-    /// Builds a new TLS client configuration.
-    fn client_config(&self, resolver: Arc<CertResolver>) -> Arc<rustls::ClientConfig> {
-        let mut cfg = client_config_builder(self.server_cert_verifier.clone())
-            .with_client_cert_resolver(resolver);
+    /// Replaces the trust roots used to validate peers and publishes new TLS
+    /// client and server configurations.
+    ///
+    /// Established connections retain the configuration with which they were
+    /// created, so they are not disrupted. The current certificate, if any,
+    /// must be valid for the new roots; otherwise the update is rejected.
+    pub fn set_roots(&mut self, roots_pem: &str) -> Result<()> {
+        let roots = super::load_roots(roots_pem)?;
+        let server_cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier> =
+            Arc::new(verify::AnySanVerifier::new(
+                roots.clone(),
+                self.bundles.clone(),
+                self.revocation.clone(),
+            ));
+
+        if let Some(CertResolver(key)) = self.resolver.as_deref() {
+            self.validate(&roots, &self.bundles, &key.cert)?;
+        }
+
+        self.roots = roots;
+        self.server_cert_verifier = server_cert_verifier;
+        self.publish();
+
+        Ok(())
+    }
+
+    /// Configures revocation checking for peer certificates and publishes new
+    /// TLS client and server configurations.
+    ///
+    /// Established connections retain the configuration with which they were
+    /// created, so they are not disrupted.
+    pub fn set_revocation(&mut self, revocation: Option<Revocation>) {
+        self.server_cert_verifier = Arc::new(verify::AnySanVerifier::new(
+            self.roots.clone(),
+            self.bundles.clone(),
+            revocation.clone(),
+        ));
+        self.revocation = revocation;
+        self.publish();
+    }
+
+    /// Sets the key exchange groups offered and accepted by TLS clients and
+    /// servers, in order of preference, and publishes new TLS client and
+    /// server configurations.
+    ///
+    /// The update is rejected if any of the groups is not supported by the
+    /// crypto backend.
+    pub fn set_key_exchange_groups(&mut self, groups: &[KeyExchangeGroup]) -> Result<()> {
+        self.kx_groups = super::kx_groups(groups)?;
+        self.publish();
+        Ok(())
+    }
 
-        // Disable session resumption for the time-being until resumption is
-        // more tested.
-        cfg.resumption = rustls::client::Resumption::disabled();
+    /// Publishes TLS client and server configurations built from the current
+    /// roots and certificate.
+    fn publish(&self) {
+        let client = client_config(
+            &self.kx_groups,
+            self.server_cert_verifier.clone(),
+            self.resolver.clone(),
+        );
+        let server = server_config(
+            &self.kx_groups,
+            self.roots.clone(),
+            &self.bundles,
+            self.resolver.clone(),
+            self.revocation.clone(),
+        );
 
-        cfg.into()
+        // If receivers are dropped, we don't return an error (as this would likely cause the
+        // updater to retry more aggressively). It's fine to silently ignore these errors.
+        let _ = self.client_tx.send(client);
+        let _ = self.server_tx.send(server);
     }
 
     /// Ensures the certificate is valid for the services we terminate for TLS. This assumes that
     /// server cert validation does the same or more validation than client cert validation.
-    fn validate(&self, certs: &[rustls::pki_types::CertificateDer<'_>]) -> Result<()> {
+    ///
+    /// The local certificate is not checked for revocation: peers check its
+    /// revocation status when they verify it.
+    fn validate(
+        &self,
+        roots: &rustls::RootCertStore,
+        bundles: &verify::TrustBundles,
+        certs: &[rustls::pki_types::CertificateDer<'_>],
+    ) -> Result<()> {
+        let server_cert_verifier =
+            verify::AnySanVerifier::new(roots.clone(), bundles.clone(), None);
         let name = rustls::pki_types::ServerName::try_from(self.server_name.as_str())
             .expect("server name must be a valid DNS name");
         static NO_OCSP: &[u8] = &[];
         let end_entity = &certs[0];
         let intermediates = &certs[1..];
         let now = UnixTime::now();
-        self.server_cert_verifier.verify_server_cert(
+        rustls::client::danger::ServerCertVerifier::verify_server_cert(
+            &server_cert_verifier,
             end_entity,
             intermediates,
             &name,
@@ -144,8 +302,8 @@ impl id::Credentials for Store {
                 .map(|id::DerX509(der)| rustls::pki_types::CertificateDer::from(der)),
         );
 
-        // Use the client's verifier to validate the certificate for our local name.
-        self.validate(&chain)?;
+        // Validate the certificate for our local name as clients would.
+        self.validate(&self.roots, &self.bundles, &chain)?;
 
         let key = EcdsaKeyPair::from_pkcs8(SIGNATURE_ALG_RING_SIGNING, &key, &self.random)
             .map_err(InvalidKey)?;
@@ -155,13 +313,49 @@ impl id::Credentials for Store {
             Arc::new(Key(Arc::new(key))),
         ))));
 
-        // Build new client and server TLS configs.
-        let client = self.client_config(resolver.clone());
-        let server = server_config(self.roots.clone(), resolver);
+        // Build and publish new client and server TLS configs.
+        self.resolver = Some(resolver);
+        self.publish();
 
-        // Publish the new configs.
-        let _ = self.client_tx.send(client);
-        let _ = self.server_tx.send(server);
+        Ok(())
+    }
+
+    /// Publishes TLS client and server configurations that verify SPIFFE peers
+    /// against their trust domain's bundle.
+    ///
+    /// The current certificate, if any, must be valid for the new bundles;
+    /// otherwise the update is rejected.
+    fn set_trust_bundles(&mut self, bundles: id::TrustBundles) -> Result<()> {
+        let mut stores = verify::TrustBundles::with_capacity(bundles.len());
+        for (domain, certs) in bundles {
+            let mut roots = rustls::RootCertStore::empty();
+            let (added, skipped) = roots.add_parsable_certificates(
+                certs
+                    .into_iter()
+                    .map(|id::DerX509(der)| rustls::pki_types::CertificateDer::from(der)),
+            );
+            if skipped != 0 {
+                warn!(%domain, "Skipped {} invalid trust anchors", skipped);
+            }
+            if added == 0 {
+                return Err(format!("no trust roots for trust domain {domain}").into());
+            }
+            stores.insert(domain, Arc::new(roots));
+        }
+
+        let server_cert_verifier: Arc<dyn rustls::client::danger::ServerCertVerifier> =
+            Arc::new(verify::AnySanVerifier::new(
+                self.roots.clone(),
+                stores.clone(),
+                self.revocation.clone(),
+            ));
+        if let Some(CertResolver(key)) = self.resolver.as_deref() {
+            self.validate(&self.roots, &stores, &key.cert)?;
+        }
+
+        self.bundles = stores;
+        self.server_cert_verifier = server_cert_verifier;
+        self.publish();
 
         Ok(())
     }
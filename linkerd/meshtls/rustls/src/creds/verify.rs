@@ -1,5 +1,6 @@
 use crate::creds::params::SUPPORTED_SIG_ALGS;
-use std::{convert::TryFrom, sync::Arc};
+use linkerd_meshtls_verifier::{self as verifier, Issuer, Revocation, RevocationError};
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
 use tokio_rustls::rustls::{
     self,
     client::{
@@ -7,20 +8,54 @@ use tokio_rustls::rustls::{
         danger::{ServerCertVerified, ServerCertVerifier},
     },
     pki_types::{CertificateDer, ServerName, UnixTime},
-    server::ParsedCertificate,
-    RootCertStore,
+    server::{
+        danger::{ClientCertVerified, ClientCertVerifier},
+        ParsedCertificate,
+    },
+    CertificateError, DistinguishedName, RootCertStore,
 };
 use tracing::trace;
 
+/// Trust roots for each SPIFFE trust domain, keyed by trust domain name.
+pub(crate) type TrustBundles = HashMap<String, Arc<RootCertStore>>;
+
 #[derive(Debug)]
 pub(crate) struct AnySanVerifier {
     roots: Arc<RootCertStore>,
+    bundles: TrustBundles,
+    revocation: Option<Revocation>,
+}
+
+/// Verifies client certificates that name a SPIFFE ID against their trust
+/// domain's bundle.
+///
+/// Certificates without a SPIFFE ID are verified against the default roots.
+#[derive(Debug)]
+pub(crate) struct FederatedClientVerifier {
+    default: Arc<dyn ClientCertVerifier>,
+    bundles: HashMap<String, Arc<dyn ClientCertVerifier>>,
+}
+
+/// Wraps a client certificate verifier, ensuring that client certificates
+/// have not been revoked.
+#[derive(Debug)]
+pub(crate) struct RevocationClientVerifier {
+    inner: Arc<dyn ClientCertVerifier>,
+    roots: Arc<RootCertStore>,
+    bundles: TrustBundles,
+    revocation: Revocation,
 }
 
 impl AnySanVerifier {
-    pub(crate) fn new(roots: impl Into<Arc<RootCertStore>>) -> Self {
+    pub(crate) fn new(
+        roots: impl Into<Arc<RootCertStore>>,
+        bundles: TrustBundles,
+        revocation: Option<Revocation>,
+    ) -> Self {
         Self {
             roots: roots.into(),
+            bundles,
+            revocation,
         }
     }
 }
@@ -36,7 +71,8 @@ impl AnySanVerifier {
 // want to support alternative SAN types (e.g. URI).
 impl ServerCertVerifier for AnySanVerifier {
     /// Will verify the certificate is valid in the following ways:
-    /// - Signed by a  trusted `RootCertStore` CA
+    /// - Signed by a  trusted `RootCertStore` CA; or, when trust bundles are
+    ///   configured, by its SPIFFE trust domain's CA
     /// - Not Expired
     fn verify_server_cert(
         &self,
@@ -48,14 +84,21 @@ impl ServerCertVerifier for AnySanVerifier {
     ) -> Result<ServerCertVerified, rustls::Error> {
         let cert = ParsedCertificate::try_from(end_entity)?;
 
+        let roots = select_roots(&self.roots, &self.bundles, end_entity)?;
         client::verify_server_cert_signed_by_trust_anchor(
             &cert,
-            &self.roots,
+            roots,
             intermediates,
             now,
             SUPPORTED_SIG_ALGS.all,
         )?;
 
+        if let Some(revocation) = &self.revocation {
+            revocation
+                .check(end_entity, issuers(intermediates, roots))
+                .map_err(revocation_error)?;
+        }
+
         if !ocsp_response.is_empty() {
             trace!("Unvalidated OCSP response: {ocsp_response:?}");
         }
@@ -85,3 +128,184 @@ impl ServerCertVerifier for AnySanVerifier {
         SUPPORTED_SIG_ALGS.supported_schemes()
     }
 }
+
+// === impl FederatedClientVerifier ===
+
+impl FederatedClientVerifier {
+    pub(crate) fn new(
+        default: Arc<dyn ClientCertVerifier>,
+        bundles: HashMap<String, Arc<dyn ClientCertVerifier>>,
+    ) -> Self {
+        Self { default, bundles }
+    }
+}
+
+impl ClientCertVerifier for FederatedClientVerifier {
+    fn offer_client_auth(&self) -> bool {
+        self.default.offer_client_auth()
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        self.default.client_auth_mandatory()
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        self.default.root_hint_subjects()
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        select_roots(&self.default, &self.bundles, end_entity)?.verify_client_cert(
+            end_entity,
+            intermediates,
+            now,
+        )
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.default.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.default.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.default.supported_verify_schemes()
+    }
+}
+
+// === impl RevocationClientVerifier ===
+
+impl RevocationClientVerifier {
+    pub(crate) fn new(
+        inner: Arc<dyn ClientCertVerifier>,
+        roots: Arc<RootCertStore>,
+        bundles: TrustBundles,
+        revocation: Revocation,
+    ) -> Self {
+        Self {
+            inner,
+            roots,
+            bundles,
+            revocation,
+        }
+    }
+}
+
+impl ClientCertVerifier for RevocationClientVerifier {
+    fn offer_client_auth(&self) -> bool {
+        self.inner.offer_client_auth()
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        self.inner.client_auth_mandatory()
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        self.inner.root_hint_subjects()
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        let verified = self
+            .inner
+            .verify_client_cert(end_entity, intermediates, now)?;
+        let roots = select_roots(&self.roots, &self.bundles, end_entity)?;
+        self.revocation
+            .check(end_entity, issuers(intermediates, roots))
+            .map_err(revocation_error)?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<client::danger::HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+fn revocation_error(error: RevocationError) -> rustls::Error {
+    match error {
+        RevocationError::Revoked => CertificateError::Revoked.into(),
+        RevocationError::UnknownStatus => CertificateError::UnknownRevocationStatus.into(),
+        RevocationError::InvalidCertificate(_) => CertificateError::BadEncoding.into(),
+    }
+}
+
+/// Returns the certificate authorities that may have issued a verified peer
+/// certificate.
+fn issuers<'c>(
+    intermediates: &'c [CertificateDer<'_>],
+    roots: &'c RootCertStore,
+) -> impl Iterator<Item = Issuer<'c>> {
+    intermediates
+        .iter()
+        .filter_map(|cert| Issuer::from_certificate(cert))
+        .chain(roots.roots.iter().map(|anchor| Issuer {
+            subject: &anchor.subject,
+            spki: &anchor.subject_public_key_info,
+        }))
+}
+
+/// Selects the trust roots with which a peer certificate is verified.
+///
+/// When no trust bundles are configured, all certificates are verified against
+/// the default roots. Otherwise, a certificate that names a SPIFFE ID must
+/// chain to its trust domain's bundle so that a federated CA cannot issue
+/// identities in other trust domains.
+fn select_roots<'t, T>(
+    default: &'t T,
+    bundles: &'t HashMap<String, T>,
+    end_entity: &CertificateDer<'_>,
+) -> Result<&'t T, rustls::Error> {
+    if bundles.is_empty() {
+        return Ok(default);
+    }
+
+    match verifier::spiffe_trust_domain(end_entity) {
+        Ok(None) => Ok(default),
+        Ok(Some(domain)) => bundles.get(&domain).ok_or_else(|| {
+            trace!(%domain, "No trust bundle for SPIFFE trust domain");
+            CertificateError::UnknownIssuer.into()
+        }),
+        Err(error) => {
+            trace!(%error, "Invalid SPIFFE ID");
+            Err(CertificateError::BadEncoding.into())
+        }
+    }
+}
@@ -7,8 +7,85 @@ pub mod creds;
 mod server;
 #[cfg(test)]
 mod tests;
+pub mod web_pki;
+
+use linkerd_identity::{HandshakeDirection, HandshakeFailure, HandshakeMetrics, Negotiated};
+use linkerd_io as io;
+use tokio::time;
+use tokio_rustls::rustls;
 
 pub use self::{
     client::{ClientIo, Connect, ConnectFuture, NewClient},
     server::{Server, ServerIo, TerminateFuture},
 };
+
+/// Records the parameters negotiated by a completed handshake.
+fn record_handshake(
+    metrics: &HandshakeMetrics,
+    direction: HandshakeDirection,
+    elapsed: time::Duration,
+    conn: &rustls::CommonState,
+) {
+    let negotiated = Negotiated {
+        tls_version: conn
+            .protocol_version()
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown"),
+        alpn: conn
+            .alpn_protocol()
+            .map(|p| String::from_utf8_lossy(p).into_owned()),
+        cipher_suite: conn
+            .negotiated_cipher_suite()
+            .and_then(|s| s.suite().as_str())
+            .unwrap_or("unknown"),
+        key_exchange_group: conn
+            .negotiated_key_exchange_group()
+            .and_then(|g| g.name().as_str())
+            .unwrap_or("unknown"),
+        resumed: conn.handshake_kind() == Some(rustls::HandshakeKind::Resumed),
+    };
+    tracing::debug!(
+        tls.version = %negotiated.tls_version,
+        tls.suite = %negotiated.cipher_suite,
+        tls.group = %negotiated.key_exchange_group,
+        tls.resumed = negotiated.resumed,
+        "Negotiated TLS parameters"
+    );
+    metrics.negotiated(direction, elapsed, negotiated);
+}
+
+/// Records a failed handshake, classifying the error returned by rustls.
+fn record_failure(
+    metrics: &HandshakeMetrics,
+    direction: HandshakeDirection,
+    elapsed: time::Duration,
+    error: &io::Error,
+) {
+    let reason = match error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<rustls::Error>())
+    {
+        Some(error) => classify(error),
+        None => HandshakeFailure::Other,
+    };
+    tracing::debug!(%error, reason = %reason.as_str(), "TLS handshake failed");
+    metrics.failed(direction, elapsed, reason);
+}
+
+fn classify(error: &rustls::Error) -> HandshakeFailure {
+    use rustls::{AlertDescription, CertificateError, Error};
+
+    match error {
+        Error::InvalidCertificate(CertificateError::UnknownIssuer)
+        | Error::AlertReceived(AlertDescription::UnknownCA) => HandshakeFailure::UnknownCa,
+        Error::InvalidCertificate(
+            CertificateError::Expired
+            | CertificateError::ExpiredContext { .. }
+            | CertificateError::NotValidYet
+            | CertificateError::NotValidYetContext { .. },
+        )
+        | Error::AlertReceived(AlertDescription::CertificateExpired) => HandshakeFailure::Expired,
+        Error::InvalidMessage(_) => HandshakeFailure::NotTls,
+        _ => HandshakeFailure::Other,
+    }
+}
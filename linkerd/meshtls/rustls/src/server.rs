@@ -1,12 +1,13 @@
 use futures::prelude::*;
 use linkerd_dns_name as dns;
+use linkerd_identity::{HandshakeDirection::Inbound, HandshakeMetrics, PeerCert};
 use linkerd_io as io;
 use linkerd_meshtls_verifier as verifier;
 use linkerd_stack::{Param, Service};
 use linkerd_tls::{ClientId, NegotiatedProtocol, NegotiatedProtocolRef, ServerName, ServerTls};
-use std::{pin::Pin, sync::Arc, task::Context};
+use std::{future::Future, pin::Pin, sync::Arc, task::Context};
 use thiserror::Error;
-use tokio::sync::watch;
+use tokio::{sync::watch, time};
 use tokio_rustls::rustls::{pki_types::CertificateDer, ServerConfig};
 use tracing::debug;
 
@@ -15,12 +16,11 @@ use tracing::debug;
 pub struct Server {
     name: dns::Name,
     rx: watch::Receiver<Arc<ServerConfig>>,
+    metrics: HandshakeMetrics,
 }
 
-pub type TerminateFuture<I> = futures::future::MapOk<
-    tokio_rustls::Accept<I>,
-    fn(tokio_rustls::server::TlsStream<I>) -> (ServerTls, ServerIo<I>),
->;
+pub type TerminateFuture<I> =
+    Pin<Box<dyn Future<Output = io::Result<(ServerTls, ServerIo<I>)>> + Send>>;
 
 #[derive(Debug)]
 pub struct ServerIo<I>(tokio_rustls::server::TlsStream<I>);
@@ -30,8 +30,12 @@ pub struct ServerIo<I>(tokio_rustls::server::TlsStream<I>);
 pub struct LostStore(());
 
 impl Server {
-    pub(crate) fn new(name: dns::Name, rx: watch::Receiver<Arc<ServerConfig>>) -> Self {
-        Self { name, rx }
+    pub(crate) fn new(
+        name: dns::Name,
+        rx: watch::Receiver<Arc<ServerConfig>>,
+        metrics: HandshakeMetrics,
+    ) -> Self {
+        Self { name, rx, metrics }
     }
 
     #[cfg(test)]
@@ -79,7 +83,7 @@ impl Server {
             }
         });
 
-        Ok(Self::new(self.name, rx))
+        Ok(Self::new(self.name, rx, self.metrics))
     }
 }
 
@@ -91,7 +95,7 @@ impl Param<ServerName> for Server {
 
 impl<I> Service<I> for Server
 where
-    I: io::AsyncRead + io::AsyncWrite + Send + Unpin,
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
 {
     type Response = (ServerTls, ServerIo<I>);
     type Error = std::io::Error;
@@ -102,27 +106,36 @@ where
         io::Poll::Ready(Ok(()))
     }
 
-    #[inline]
     fn call(&mut self, io: I) -> Self::Future {
-        tokio_rustls::TlsAcceptor::from((*self.rx.borrow()).clone())
-            .accept(io)
-            .map_ok(|io| {
-                // Determine the peer's identity, if it exist.
-                let client_id = client_identity(&io);
-
-                let negotiated_protocol = io
-                    .get_ref()
-                    .1
-                    .alpn_protocol()
-                    .map(|b| NegotiatedProtocol(b.into()));
-
-                debug!(client.id = ?client_id, alpn = ?negotiated_protocol, "Accepted TLS connection");
-                let tls = ServerTls::Established {
-                    client_id,
-                    negotiated_protocol,
-                };
-                (tls, ServerIo(io))
-            })
+        let metrics = self.metrics.clone();
+        let started = time::Instant::now();
+        Box::pin(
+            tokio_rustls::TlsAcceptor::from((*self.rx.borrow()).clone())
+                .accept(io)
+                .map(move |res| {
+                    let elapsed = time::Instant::now().saturating_duration_since(started);
+                    let io = res.inspect_err(|error| {
+                        crate::record_failure(&metrics, Inbound, elapsed, error)
+                    })?;
+                    crate::record_handshake(&metrics, Inbound, elapsed, io.get_ref().1);
+
+                    // Determine the peer's identity, if it exist.
+                    let client_id = client_identity(&io);
+
+                    let negotiated_protocol = io
+                        .get_ref()
+                        .1
+                        .alpn_protocol()
+                        .map(|b| NegotiatedProtocol(b.into()));
+
+                    debug!(client.id = ?client_id, alpn = ?negotiated_protocol, "Accepted TLS connection");
+                    let tls = ServerTls::Established {
+                        client_id,
+                        negotiated_protocol,
+                    };
+                    Ok((tls, ServerIo(io)))
+                }),
+        )
     }
 }
 
@@ -136,6 +149,22 @@ fn client_identity<I>(tls: &tokio_rustls::server::TlsStream<I>) -> Option<Client
 
 // === impl ServerIo ===
 
+impl<I> ServerIo<I> {
+    /// Describes the certificate presented by the client, if any.
+    ///
+    /// The certificate is parsed on each call, so callers should only do so
+    /// when the description is needed.
+    pub fn client_cert(&self) -> Option<PeerCert> {
+        let (_io, session) = self.0.get_ref();
+        let certs = session.peer_certificates()?;
+        let c = certs.first().map(CertificateDer::as_ref)?;
+
+        verifier::peer_cert(c, certs.len())
+            .map_err(|error| tracing::warn!(%error, "Failed to describe client end cert"))
+            .ok()
+    }
+}
+
 impl<I: io::AsyncRead + io::AsyncWrite + Unpin> io::AsyncRead for ServerIo<I> {
     #[inline]
     fn poll_read(
@@ -195,3 +224,10 @@ impl<I: io::PeerAddr> io::PeerAddr for ServerIo<I> {
         self.0.get_ref().0.peer_addr()
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ServerIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        self.0.get_ref().0.tcp_socket()
+    }
+}
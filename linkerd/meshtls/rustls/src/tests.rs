@@ -1,4 +1,5 @@
-use linkerd_identity::{Credentials, DerX509};
+use linkerd_identity::{Credentials, DerX509, RevocationMetrics};
+use linkerd_meshtls_verifier::{Revocation, UnknownStatus};
 use linkerd_tls_test_util::*;
 use std::time::{Duration, SystemTime};
 
@@ -8,6 +9,7 @@ fn load(ent: &Entity) -> crate::creds::Store {
         ent.name.parse().unwrap(),
         ent.name.parse().unwrap(),
         roots_pem,
+        Default::default(),
     )
     .expect("credentials must be readable");
     store
@@ -48,3 +50,400 @@ fn recognize_cert_is_not_valid_for_identity() {
         )
         .is_err());
 }
+
+#[test]
+fn rotates_trust_roots() {
+    let ca1 = std::str::from_utf8(FOO_NS1.trust_anchors).expect("valid PEM");
+    let ca2 = std::str::from_utf8(FOO_NS1_CA2.trust_anchors).expect("valid PEM");
+    let expiry = SystemTime::now() + Duration::from_secs(1000);
+
+    let mut store = load(&FOO_NS1);
+    store
+        .set_certificate(
+            DerX509(FOO_NS1.crt.to_vec()),
+            vec![],
+            FOO_NS1.key.to_vec(),
+            expiry,
+        )
+        .expect("certificate must be valid");
+
+    // Both roots are trusted during the rotation window.
+    store
+        .set_roots(&format!("{ca1}{ca2}"))
+        .expect("bundle must include the current issuer");
+    store
+        .set_certificate(
+            DerX509(FOO_NS1_CA2.crt.to_vec()),
+            vec![],
+            FOO_NS1_CA2.key.to_vec(),
+            expiry,
+        )
+        .expect("certificate issued by the new root must be valid");
+
+    // The old root may be retired once the certificate has been reissued.
+    store.set_roots(ca2).expect("new root must be valid");
+}
+
+#[test]
+fn configures_key_exchange_groups() {
+    use linkerd_meshtls_verifier::KeyExchangeGroup;
+
+    let roots_pem = std::str::from_utf8(FOO_NS1.trust_anchors).expect("valid PEM");
+    let (mut store, rx) = crate::creds::watch(
+        FOO_NS1.name.parse().unwrap(),
+        FOO_NS1.name.parse().unwrap(),
+        roots_pem,
+        Default::default(),
+    )
+    .expect("credentials must be readable");
+    let groups = || {
+        rx.server()
+            .config()
+            .crypto_provider()
+            .kx_groups
+            .iter()
+            .map(|g| g.name().as_str().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    #[cfg(feature = "aws-lc")]
+    assert_eq!(
+        groups()[0],
+        "X25519MLKEM768",
+        "hybrid group must be preferred"
+    );
+    #[cfg(not(feature = "aws-lc"))]
+    assert!(!groups().contains(&"X25519MLKEM768"));
+
+    store
+        .set_key_exchange_groups(&[KeyExchangeGroup::Secp384r1, KeyExchangeGroup::X25519])
+        .expect("groups must be supported");
+    assert_eq!(groups(), ["secp384r1", "X25519"]);
+
+    assert!(store.set_key_exchange_groups(&[]).is_err());
+    let hybrid = store.set_key_exchange_groups(&[KeyExchangeGroup::X25519MlKem768]);
+    #[cfg(feature = "aws-lc")]
+    assert!(hybrid.is_ok());
+    #[cfg(not(feature = "aws-lc"))]
+    {
+        assert!(hybrid.is_err());
+        assert_eq!(groups(), ["secp384r1", "X25519"], "groups must not change");
+    }
+}
+
+#[test]
+fn recognize_roots_did_not_issue_cert() {
+    let mut store = load(&FOO_NS1);
+    store
+        .set_certificate(
+            DerX509(FOO_NS1.crt.to_vec()),
+            vec![],
+            FOO_NS1.key.to_vec(),
+            SystemTime::now() + Duration::from_secs(1000),
+        )
+        .expect("certificate must be valid");
+
+    let ca2 = std::str::from_utf8(FOO_NS1_CA2.trust_anchors).expect("valid PEM");
+    assert!(store.set_roots(ca2).is_err());
+}
+
+/// Builds a CRL signed by the given CA, revoking the given serials.
+fn crl(ca: &rcgen::Certificate, key: &rcgen::KeyPair, revoked: &[u8]) -> Vec<u8> {
+    use rcgen::{
+        date_time_ymd, CertificateRevocationListParams, KeyIdMethod, RevokedCertParams,
+        SerialNumber,
+    };
+
+    CertificateRevocationListParams {
+        this_update: date_time_ymd(2020, 1, 1),
+        next_update: date_time_ymd(2100, 1, 1),
+        crl_number: SerialNumber::from(1u64),
+        issuing_distribution_point: None,
+        revoked_certs: revoked
+            .iter()
+            .map(|serial| RevokedCertParams {
+                serial_number: SerialNumber::from(vec![*serial]),
+                revocation_time: date_time_ymd(2020, 1, 1),
+                reason_code: None,
+                invalidity_date: None,
+            })
+            .collect(),
+        key_identifier_method: KeyIdMethod::Sha256,
+    }
+    .signed_by(ca, key)
+    .unwrap()
+    .der()
+    .to_vec()
+}
+
+/// Generates a CA with the given common name.
+fn ca(cn: &str) -> (rcgen::Certificate, rcgen::KeyPair) {
+    use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair, KeyUsagePurpose};
+
+    let key = KeyPair::generate().unwrap();
+    let mut params = CertificateParams::default();
+    params.distinguished_name.push(DnType::CommonName, cn);
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    (params.self_signed(&key).unwrap(), key)
+}
+
+#[test]
+fn rejects_revoked_peer_certs() {
+    use crate::creds::verify::{AnySanVerifier, RevocationClientVerifier};
+    use rcgen::{CertificateParams, KeyPair, SerialNumber};
+    use std::{convert::TryFrom, sync::Arc};
+    use tokio_rustls::rustls::{
+        client::danger::ServerCertVerifier,
+        pki_types::{CertificateDer, ServerName, UnixTime},
+        server::{danger::ClientCertVerifier, WebPkiClientVerifier},
+        CertificateError, RootCertStore,
+    };
+
+    let (root, root_key) = ca("root");
+    let (issuer, issuer_key) = {
+        use rcgen::{BasicConstraints, DnType, IsCa, KeyUsagePurpose};
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.distinguished_name.push(DnType::CommonName, "issuer");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        (params.signed_by(&key, &root, &root_key).unwrap(), key)
+    };
+    let signed_by = |serial: u8, ca: &rcgen::Certificate, ca_key: &KeyPair| {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec!["foo.ns1.svc".to_string()]).unwrap();
+        params.serial_number = Some(SerialNumber::from(vec![serial]));
+        let cert = params.signed_by(&key, ca, ca_key).unwrap();
+        CertificateDer::from(cert.der().to_vec())
+    };
+    let leaf = |serial: u8| signed_by(serial, &issuer, &issuer_key);
+    let intermediates = [issuer.der().clone()];
+
+    let mut roots = RootCertStore::empty();
+    roots.add(root.der().clone()).unwrap();
+    let roots = Arc::new(roots);
+
+    // A CRL naming the issuer that is not signed by the issuer's key is
+    // ignored.
+    let (forged, forged_key) = ca("issuer");
+    let revocation = Revocation::new(
+        &[
+            crl(&issuer, &issuer_key, &[2]),
+            crl(&forged, &forged_key, &[1]),
+            crl(&root, &root_key, &[3]),
+        ],
+        UnknownStatus::Deny,
+        UnknownStatus::Allow,
+        RevocationMetrics::default(),
+    )
+    .unwrap();
+
+    let server = AnySanVerifier::new(roots.clone(), Default::default(), Some(revocation.clone()));
+    let name = ServerName::try_from("foo.ns1.svc").unwrap();
+    let now = UnixTime::now();
+    assert!(server
+        .verify_server_cert(&leaf(1), &intermediates, &name, &[], now)
+        .is_ok());
+    assert_eq!(
+        server
+            .verify_server_cert(&leaf(2), &intermediates, &name, &[], now)
+            .unwrap_err(),
+        CertificateError::Revoked.into(),
+    );
+    assert_eq!(
+        server
+            .verify_server_cert(&signed_by(3, &root, &root_key), &[], &name, &[], now)
+            .unwrap_err(),
+        CertificateError::Revoked.into(),
+    );
+
+    let client = RevocationClientVerifier::new(
+        WebPkiClientVerifier::builder_with_provider(
+            roots.clone(),
+            Arc::new(crate::backend::default_provider()),
+        )
+        .build()
+        .unwrap(),
+        roots,
+        Default::default(),
+        revocation,
+    );
+    assert!(client
+        .verify_client_cert(&leaf(1), &intermediates, now)
+        .is_ok());
+    assert_eq!(
+        client
+            .verify_client_cert(&leaf(2), &intermediates, now)
+            .unwrap_err(),
+        CertificateError::Revoked.into(),
+    );
+}
+
+#[test]
+fn does_not_check_local_cert_revocation() {
+    let (other, other_key) = ca("other");
+    let revocation = Revocation::new(
+        &[crl(&other, &other_key, &[1])],
+        UnknownStatus::Deny,
+        UnknownStatus::Deny,
+        RevocationMetrics::default(),
+    )
+    .unwrap();
+
+    let mut store = load(&FOO_NS1);
+    store.set_revocation(Some(revocation));
+    assert!(store
+        .set_certificate(
+            DerX509(FOO_NS1.crt.to_vec()),
+            vec![],
+            FOO_NS1.key.to_vec(),
+            SystemTime::now() + Duration::from_secs(1000),
+        )
+        .is_ok());
+}
+
+#[test]
+fn client_verifier_requires_trust_domain_bundle() {
+    use crate::creds::verify::FederatedClientVerifier;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair, SanType};
+    use std::{collections::HashMap, sync::Arc};
+    use tokio_rustls::rustls::{
+        pki_types::{CertificateDer, UnixTime},
+        server::{danger::ClientCertVerifier, WebPkiClientVerifier},
+        RootCertStore,
+    };
+
+    let root = || {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        (params.self_signed(&key).unwrap(), key)
+    };
+    let leaf_with_sans = |(root, root_key): &(rcgen::Certificate, KeyPair), sans: Vec<SanType>| {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.subject_alt_names = sans;
+        let cert = params.signed_by(&key, root, root_key).unwrap();
+        CertificateDer::from(cert.der().to_vec())
+    };
+    let leaf = |root: &(rcgen::Certificate, KeyPair), id: &str| {
+        leaf_with_sans(root, vec![SanType::URI(id.parse().unwrap())])
+    };
+    let verifier = |roots: &[&(rcgen::Certificate, KeyPair)]| {
+        let mut store = RootCertStore::empty();
+        for (root, _) in roots {
+            store.add(root.der().clone()).unwrap();
+        }
+        WebPkiClientVerifier::builder_with_provider(
+            Arc::new(store),
+            Arc::new(crate::backend::default_provider()),
+        )
+        .build()
+        .unwrap()
+    };
+
+    let domain_a = root();
+    let domain_b = root();
+    let federated = FederatedClientVerifier::new(
+        verifier(&[&domain_a, &domain_b]),
+        HashMap::from([
+            ("a.example".to_string(), verifier(&[&domain_a])),
+            ("b.example".to_string(), verifier(&[&domain_b])),
+        ]),
+    );
+
+    let now = UnixTime::now();
+    let valid = leaf(&domain_b, "spiffe://b.example/bar");
+    assert!(federated.verify_client_cert(&valid, &[], now).is_ok());
+    let forged = leaf(&domain_b, "spiffe://a.example/bar");
+    assert!(federated.verify_client_cert(&forged, &[], now).is_err());
+    let unknown = leaf(&domain_b, "spiffe://c.example/bar");
+    assert!(federated.verify_client_cert(&unknown, &[], now).is_err());
+
+    // A federated CA may not issue identities outside of its trust domain
+    // alongside a valid SPIFFE ID.
+    let mixed = leaf_with_sans(
+        &domain_b,
+        vec![
+            SanType::URI("spiffe://b.example/bar".parse().unwrap()),
+            SanType::DnsName(
+                "foo.ns1.serviceaccount.identity.linkerd.cluster.local"
+                    .parse()
+                    .unwrap(),
+            ),
+        ],
+    );
+    assert!(federated.verify_client_cert(&mixed, &[], now).is_err());
+}
+
+#[tokio::test]
+async fn originates_web_pki_tls() {
+    use crate::web_pki::NewClient;
+    use linkerd_stack::{NewService, Service};
+    use linkerd_tls::{client::AlpnProtocols, OriginateTls};
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use std::{convert::TryFrom, sync::Arc};
+    use tokio_rustls::rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        ServerConfig,
+    };
+
+    let ca = || {
+        let mut params = CertificateParams::default();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params
+    };
+    let root_key = KeyPair::generate().unwrap();
+    let root = ca().self_signed(&root_key).unwrap();
+
+    let key = KeyPair::generate().unwrap();
+    let cert = CertificateParams::new(vec!["api.example.com".to_string()])
+        .unwrap()
+        .signed_by(&key, &root, &root_key)
+        .unwrap();
+    let mut server =
+        ServerConfig::builder_with_provider(Arc::new(crate::backend::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(cert.der().to_vec())],
+                PrivateKeyDer::try_from(key.serialize_der()).unwrap(),
+            )
+            .unwrap();
+    server.alpn_protocols = vec![b"h2".to_vec()];
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(server));
+
+    let client = NewClient::new(&root.pem(), None).expect("client must be valid");
+    let connect = |client: &NewClient, name: &str| {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let accept = tokio::spawn(acceptor.accept(server_io));
+        let tls = OriginateTls {
+            server_name: name.parse().unwrap(),
+            alpn: Some(AlpnProtocols(vec![b"h2".to_vec()])),
+        };
+        (client.new_service(tls).call(client_io), accept)
+    };
+
+    let (conn, accept) = connect(&client, "api.example.com");
+    let io = conn.await.expect("handshake must succeed");
+    assert_eq!(io.negotiated_protocol().map(|p| p.0), Some(&b"h2"[..]));
+    accept.await.unwrap().expect("server must accept");
+
+    let (conn, accept) = connect(&client, "other.example.com");
+    assert!(
+        conn.await.is_err(),
+        "certificate must be issued for the server name"
+    );
+    assert!(accept.await.unwrap().is_err());
+
+    assert!(NewClient::new("", None).is_err());
+    let other_root = ca().self_signed(&KeyPair::generate().unwrap()).unwrap();
+    let client = NewClient::new(&other_root.pem(), None).unwrap();
+    let (conn, _accept) = connect(&client, "api.example.com");
+    assert!(
+        conn.await.is_err(),
+        "server must be issued by a trusted root"
+    );
+}
@@ -0,0 +1,137 @@
+//! Originates TLS to servers outside of the mesh.
+//!
+//! Servers are authenticated by web PKI: they must present a certificate for
+//! the requested server name that chains to one of the configured roots.
+
+use crate::{backend, client::ClientIo, creds, ConnectFuture};
+use futures::prelude::*;
+use linkerd_error::Result;
+use linkerd_io as io;
+use linkerd_stack::{NewService, Service};
+use linkerd_tls::{client::AlpnProtocols, OriginateTls};
+use std::{convert::TryFrom, sync::Arc, task::Context};
+use tokio_rustls::rustls::{self, pki_types::ServerName, ClientConfig};
+use tracing::debug;
+
+/// A `NewService` that produces `Connect` services for servers outside of the
+/// mesh.
+#[derive(Clone)]
+pub struct NewClient {
+    config: Arc<ClientConfig>,
+}
+
+/// A `Service` that initiates client-side TLS connections to servers outside
+/// of the mesh.
+#[derive(Clone)]
+pub struct Connect {
+    server_name: ServerName<'static>,
+    config: Arc<ClientConfig>,
+}
+
+/// A PEM-encoded certificate chain and private key with which the client
+/// authenticates to servers that request a client certificate.
+#[derive(Clone)]
+pub struct ClientCert<'p> {
+    pub chain_pem: &'p str,
+    pub key_pem: &'p str,
+}
+
+// === impl NewClient ===
+
+impl NewClient {
+    /// Builds a client that trusts the roots in `roots_pem`.
+    pub fn new(roots_pem: &str, client_cert: Option<ClientCert<'_>>) -> Result<Self> {
+        let roots = creds::load_roots(roots_pem)?;
+
+        // Unlike the mesh's client configuration, servers outside of the mesh
+        // may only support TLS 1.2 or a more conservative set of cipher
+        // suites, so the backend's default provider is used as-is.
+        let builder =
+            rustls::ClientConfig::builder_with_provider(Arc::new(backend::default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_root_certificates(roots);
+        let config = match client_cert {
+            None => builder.with_no_client_auth(),
+            Some(ClientCert { chain_pem, key_pem }) => {
+                let chain = rustls_pemfile::certs(&mut std::io::Cursor::new(chain_pem))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if chain.is_empty() {
+                    return Err("no certificates in client certificate PEM".into());
+                }
+                let key = rustls_pemfile::private_key(&mut std::io::Cursor::new(key_pem))?
+                    .ok_or("no private key in client key PEM")?;
+                builder.with_client_auth_cert(chain, key)?
+            }
+        };
+
+        Ok(Self {
+            config: Arc::new(config),
+        })
+    }
+}
+
+impl NewService<OriginateTls> for NewClient {
+    type Service = Connect;
+
+    fn new_service(&self, target: OriginateTls) -> Self::Service {
+        Connect::new(target, self.config.clone())
+    }
+}
+
+impl std::fmt::Debug for NewClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewClient").finish()
+    }
+}
+
+// === impl Connect ===
+
+impl Connect {
+    fn new(tls: OriginateTls, config: Arc<ClientConfig>) -> Self {
+        let config = match tls.alpn {
+            None => config,
+            Some(AlpnProtocols(protocols)) => {
+                let mut c = (*config).clone();
+                c.alpn_protocols = protocols;
+                Arc::new(c)
+            }
+        };
+
+        let server_name = ServerName::try_from(tls.server_name.to_string())
+            .expect("server name must be a valid DNS name");
+
+        Self {
+            server_name,
+            config,
+        }
+    }
+}
+
+impl<I> Service<I> for Connect
+where
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
+{
+    type Response = ClientIo<I>;
+    type Error = io::Error;
+    type Future = ConnectFuture<I>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> io::Poll<()> {
+        io::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, io: I) -> Self::Future {
+        Box::pin(
+            tokio_rustls::TlsConnector::from(self.config.clone())
+                .connect(self.server_name.clone(), io)
+                .map_ok(|s| {
+                    let (_, conn) = s.get_ref();
+                    debug!(
+                        tls.version = ?conn.protocol_version(),
+                        tls.suite = ?conn.negotiated_cipher_suite().map(|s| s.suite()),
+                        "Established TLS connection"
+                    );
+                    ClientIo(s)
+                }),
+        )
+    }
+}
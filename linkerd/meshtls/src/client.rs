@@ -265,3 +265,18 @@ impl<I: io::PeerAddr> io::PeerAddr for ClientIo<I> {
         }
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ClientIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(io) => io.tcp_socket(),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(io) => io.tcp_socket(),
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(),
+        }
+    }
+}
@@ -3,7 +3,8 @@ use std::time::SystemTime;
 use crate::{NewClient, Server};
 use linkerd_dns_name as dns;
 use linkerd_error::Result;
-use linkerd_identity::{Credentials, DerX509, Id};
+use linkerd_identity::{Credentials, DerX509, Id, TrustBundles};
+use linkerd_meshtls_verifier::{KeyExchangeGroup, Revocation};
 
 #[cfg(feature = "boring")]
 pub use crate::boring;
@@ -34,6 +35,57 @@ pub enum Receiver {
 
 // === impl Store ===
 
+impl Store {
+    /// Replaces the trust roots used to validate peers.
+    ///
+    /// Established connections are not disrupted by the update.
+    pub fn set_roots(&mut self, roots_pem: &str) -> Result<()> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(store) => store.set_roots(roots_pem),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(store) => store.set_roots(roots_pem),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(roots_pem),
+        }
+    }
+
+    /// Configures revocation checking for peer certificates.
+    ///
+    /// Established connections are not disrupted by the update.
+    pub fn set_revocation(&mut self, revocation: Option<Revocation>) {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(store) => store.set_revocation(revocation),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(store) => store.set_revocation(revocation),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(revocation),
+        }
+    }
+
+    /// Sets the key exchange groups used by TLS clients and servers, in order
+    /// of preference.
+    ///
+    /// Fails if any of the groups is not supported by the TLS backend.
+    pub fn set_key_exchange_groups(&mut self, groups: &[KeyExchangeGroup]) -> Result<()> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(store) => store.set_key_exchange_groups(groups),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(store) => store.set_key_exchange_groups(groups),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(groups),
+        }
+    }
+}
+
 impl Credentials for Store {
     fn set_certificate(
         &mut self,
@@ -53,6 +105,19 @@ impl Credentials for Store {
             _ => crate::no_tls!(leaf, chain, key, exp),
         }
     }
+
+    fn set_trust_bundles(&mut self, bundles: TrustBundles) -> Result<()> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(store) => store.set_trust_bundles(bundles),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(store) => store.set_trust_bundles(bundles),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(bundles),
+        }
+    }
 }
 
 // === impl Receiver ===
@@ -15,6 +15,7 @@
 mod client;
 pub mod creds;
 mod server;
+pub mod web_pki;
 
 pub use self::{
     client::{ClientIo, Connect, ConnectFuture, NewClient},
@@ -23,6 +24,10 @@ pub use self::{
 use linkerd_dns_name as dns;
 use linkerd_error::{Error, Result};
 use linkerd_identity as id;
+pub use linkerd_meshtls_verifier::{
+    trust_anchors, InvalidKeyExchangeGroup, KeyExchangeGroup, PemCredentials, Revocation,
+    UnknownStatus,
+};
 use std::str::FromStr;
 
 #[cfg(feature = "boring")]
@@ -86,11 +91,13 @@ impl Mode {
         local_id: id::Id,
         server_name: dns::Name,
         roots_pem: &str,
+        metrics: id::HandshakeMetrics,
     ) -> Result<(creds::Store, creds::Receiver)> {
         match self {
             #[cfg(feature = "boring")]
             Self::Boring => {
-                let (store, receiver) = boring::creds::watch(local_id, server_name, roots_pem)?;
+                let (store, receiver) =
+                    boring::creds::watch(local_id, server_name, roots_pem, metrics)?;
                 Ok((
                     creds::Store::Boring(store),
                     creds::Receiver::Boring(receiver),
@@ -99,7 +106,8 @@ impl Mode {
 
             #[cfg(feature = "rustls")]
             Self::Rustls => {
-                let (store, receiver) = rustls::creds::watch(local_id, server_name, roots_pem)?;
+                let (store, receiver) =
+                    rustls::creds::watch(local_id, server_name, roots_pem, metrics)?;
                 Ok((
                     creds::Store::Rustls(store),
                     creds::Receiver::Rustls(receiver),
@@ -107,7 +115,7 @@ impl Mode {
             }
 
             #[cfg(not(feature = "__has_any_tls_impls"))]
-            _ => no_tls!(local_id, server_name, roots_pem),
+            _ => no_tls!(local_id, server_name, roots_pem, metrics),
         }
     }
 }
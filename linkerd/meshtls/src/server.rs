@@ -1,4 +1,5 @@
 use linkerd_error::Result;
+use linkerd_identity::PeerCert;
 use linkerd_io as io;
 use linkerd_stack::{Param, Service};
 use linkerd_tls::{ServerName, ServerTls};
@@ -158,6 +159,24 @@ where
 
 // === impl ServerIo ===
 
+impl<I> ServerIo<I> {
+    /// Describes the certificate presented by the client, if any.
+    ///
+    /// The certificate is parsed on each call, so callers should only do so
+    /// when the description is needed.
+    pub fn client_cert(&self) -> Option<PeerCert> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(io) => io.client_cert(),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(io) => io.client_cert(),
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(),
+        }
+    }
+}
+
 impl<I: io::AsyncRead + io::AsyncWrite + Unpin> io::AsyncRead for ServerIo<I> {
     #[inline]
     fn poll_read(
@@ -263,3 +282,18 @@ impl<I: io::PeerAddr> io::PeerAddr for ServerIo<I> {
         }
     }
 }
+
+impl<I: io::TcpInfo> io::TcpInfo for ServerIo<I> {
+    #[inline]
+    fn tcp_socket(&self) -> Option<std::os::fd::BorrowedFd<'_>> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(io) => io.tcp_socket(),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(io) => io.tcp_socket(),
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Originates TLS to servers outside of the mesh, authenticating them with web
+//! PKI rather than mesh identities.
+
+use crate::{ClientIo, ConnectFuture, Mode};
+use linkerd_error::Result;
+use linkerd_io as io;
+use linkerd_stack::{NewService, Service};
+use linkerd_tls::{NegotiatedProtocol, OriginateTls};
+use std::task::{Context, Poll};
+
+#[cfg(feature = "boring")]
+use crate::boring;
+
+#[cfg(feature = "rustls")]
+use crate::rustls;
+
+/// A PEM-encoded certificate chain and private key with which the client
+/// authenticates to servers that request a client certificate.
+#[derive(Clone)]
+pub struct ClientCert<'p> {
+    pub chain_pem: &'p str,
+    pub key_pem: &'p str,
+}
+
+#[derive(Clone, Debug)]
+pub enum NewClient {
+    #[cfg(feature = "boring")]
+    Boring(boring::web_pki::NewClient),
+
+    #[cfg(feature = "rustls")]
+    Rustls(rustls::web_pki::NewClient),
+
+    #[cfg(not(feature = "__has_any_tls_impls"))]
+    NoTls,
+}
+
+#[derive(Clone)]
+pub enum Connect {
+    #[cfg(feature = "boring")]
+    Boring(boring::web_pki::Connect),
+
+    #[cfg(feature = "rustls")]
+    Rustls(rustls::web_pki::Connect),
+
+    #[cfg(not(feature = "__has_any_tls_impls"))]
+    NoTls,
+}
+
+// === impl NewClient ===
+
+impl NewClient {
+    /// Builds a client that trusts the roots in `roots_pem`.
+    pub fn new(mode: Mode, roots_pem: &str, client_cert: Option<ClientCert<'_>>) -> Result<Self> {
+        match mode {
+            #[cfg(feature = "boring")]
+            Mode::Boring => {
+                let client_cert = client_cert.map(|ClientCert { chain_pem, key_pem }| {
+                    boring::web_pki::ClientCert { chain_pem, key_pem }
+                });
+                boring::web_pki::NewClient::new(roots_pem, client_cert).map(Self::Boring)
+            }
+
+            #[cfg(feature = "rustls")]
+            Mode::Rustls => {
+                let client_cert = client_cert.map(|ClientCert { chain_pem, key_pem }| {
+                    rustls::web_pki::ClientCert { chain_pem, key_pem }
+                });
+                rustls::web_pki::NewClient::new(roots_pem, client_cert).map(Self::Rustls)
+            }
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(roots_pem, client_cert),
+        }
+    }
+}
+
+impl NewService<OriginateTls> for NewClient {
+    type Service = Connect;
+
+    #[inline]
+    fn new_service(&self, target: OriginateTls) -> Self::Service {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(new_client) => Connect::Boring(new_client.new_service(target)),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(new_client) => Connect::Rustls(new_client.new_service(target)),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(target),
+        }
+    }
+}
+
+// === impl Connect ===
+
+impl<I> Service<I> for Connect
+where
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin + 'static,
+{
+    type Response = (ClientIo<I>, Option<NegotiatedProtocol>);
+    type Error = io::Error;
+    type Future = ConnectFuture<I>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(connect) => {
+                <boring::web_pki::Connect as Service<I>>::poll_ready(connect, cx)
+            }
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(connect) => {
+                <rustls::web_pki::Connect as Service<I>>::poll_ready(connect, cx)
+            }
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(cx),
+        }
+    }
+
+    #[inline]
+    fn call(&mut self, io: I) -> Self::Future {
+        match self {
+            #[cfg(feature = "boring")]
+            Self::Boring(connect) => ConnectFuture::Boring(connect.call(io)),
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls(connect) => ConnectFuture::Rustls(connect.call(io)),
+
+            #[cfg(not(feature = "__has_any_tls_impls"))]
+            _ => crate::no_tls!(io),
+        }
+    }
+}
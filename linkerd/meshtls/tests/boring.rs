@@ -11,6 +11,11 @@ fn fails_processing_cert_when_wrong_id_configured() {
     util::fails_processing_cert_when_wrong_id_configured(Mode::Boring);
 }
 
+#[test]
+fn fails_processing_cert_from_another_trust_domain() {
+    util::fails_processing_cert_from_another_trust_domain(Mode::Boring);
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn plaintext() {
     util::plaintext(Mode::Boring).await;
@@ -25,3 +30,8 @@ async fn proxy_to_proxy_tls_works() {
 async fn proxy_to_proxy_tls_pass_through_when_identity_does_not_match() {
     util::proxy_to_proxy_tls_pass_through_when_identity_does_not_match(Mode::Boring).await;
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn proxy_to_proxy_tls_works_across_trust_domains() {
+    util::proxy_to_proxy_tls_works_across_trust_domains(Mode::Boring).await;
+}
@@ -11,6 +11,11 @@ fn fails_processing_cert_when_wrong_id_configured() {
     util::fails_processing_cert_when_wrong_id_configured(Mode::Rustls);
 }
 
+#[test]
+fn fails_processing_cert_from_another_trust_domain() {
+    util::fails_processing_cert_from_another_trust_domain(Mode::Rustls);
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn plaintext() {
     util::plaintext(Mode::Rustls).await;
@@ -21,7 +26,17 @@ async fn proxy_to_proxy_tls_works() {
     util::proxy_to_proxy_tls_works(Mode::Rustls).await;
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn proxy_to_proxy_tls_resumes_sessions() {
+    util::proxy_to_proxy_tls_resumes_sessions(Mode::Rustls).await;
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn proxy_to_proxy_tls_pass_through_when_identity_does_not_match() {
     util::proxy_to_proxy_tls_pass_through_when_identity_does_not_match(Mode::Rustls).await;
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn proxy_to_proxy_tls_works_across_trust_domains() {
+    util::proxy_to_proxy_tls_works_across_trust_domains(Mode::Rustls).await;
+}
@@ -5,13 +5,14 @@ use futures::prelude::*;
 use linkerd_conditional::Conditional;
 use linkerd_dns_name::Name;
 use linkerd_error::Infallible;
-use linkerd_identity::{Credentials, DerX509, Id};
+use linkerd_identity::{Credentials, DerX509, HandshakeMetrics, Id, TrustBundles};
 use linkerd_io::{self as io, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use linkerd_meshtls as meshtls;
+use linkerd_metrics::prom;
 use linkerd_proxy_transport::{
     addrs::*,
     listen::{Addrs, Bind, BindTcp},
-    ConnectTcp, Keepalive, UserTimeout,
+    AcceptShards, ConnectTcp, Keepalive, UserTimeout,
 };
 use linkerd_stack::{
     layer::Layer, service_fn, ExtractParam, InsertParam, NewService, Param, ServiceExt,
@@ -58,7 +59,7 @@ pub fn fails_processing_cert_when_wrong_id_configured(mode: meshtls::Mode) {
     let (cert, key, roots) =
         generate_cert_with_name(vec![SanType::URI("spiffe://system/local".parse().unwrap())]);
     let (mut store, _) = mode
-        .watch(id, server_name.clone(), &roots)
+        .watch(id, server_name.clone(), &roots, Default::default())
         .expect("should construct");
 
     let err = store
@@ -71,9 +72,101 @@ pub fn fails_processing_cert_when_wrong_id_configured(mode: meshtls::Mode) {
     );
 }
 
+/// A SPIFFE trust domain with a self-signed root.
+struct TrustDomain {
+    name: &'static str,
+    root: rcgen::Certificate,
+    key: KeyPair,
+}
+
+impl TrustDomain {
+    fn new(name: &'static str) -> Self {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let root = params.self_signed(&key).expect("should generate root");
+        Self { name, root, key }
+    }
+
+    /// Issues a certificate for the given SPIFFE ID, returning the DER-encoded
+    /// certificate and key.
+    fn issue(&self, spiffe_id: &str) -> (Vec<u8>, Vec<u8>) {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.subject_alt_names = vec![SanType::URI(spiffe_id.parse().unwrap())];
+        let cert = params
+            .signed_by(&key, &self.root, &self.key)
+            .expect("should generate cert");
+        (cert.der().to_vec(), key.serialize_der())
+    }
+}
+
+/// Returns a PEM bundle that trusts both domains' roots and the per-domain
+/// trust bundles.
+fn federate(a: &TrustDomain, b: &TrustDomain) -> (String, TrustBundles) {
+    let roots = format!("{}{}", a.root.pem(), b.root.pem());
+    let bundles = [a, b]
+        .iter()
+        .map(|td| (td.name.to_string(), vec![DerX509(td.root.der().to_vec())]))
+        .collect();
+    (roots, bundles)
+}
+
+pub fn fails_processing_cert_from_another_trust_domain(mode: meshtls::Mode) {
+    let domain_a = TrustDomain::new("a.example");
+    let domain_b = TrustDomain::new("b.example");
+    let (roots, bundles) = federate(&domain_a, &domain_b);
+
+    let id = Id::parse_uri("spiffe://a.example/foo").unwrap();
+    let server_name = Name::from_str("foo.a.example").expect("should parse");
+    let (mut store, _) = mode
+        .watch(id.clone(), server_name.clone(), &roots, Default::default())
+        .expect("should construct");
+
+    // Without trust bundles, any trusted root may issue the certificate.
+    let (forged, forged_key) = domain_b.issue("spiffe://a.example/foo");
+    store
+        .set_certificate(
+            DerX509(forged.clone()),
+            vec![],
+            forged_key.clone(),
+            SystemTime::now(),
+        )
+        .expect("pooled roots must accept certificate");
+
+    // Once bundles are configured, the certificate must chain to its own
+    // trust domain's root.
+    store
+        .set_trust_bundles(bundles.clone())
+        .expect_err("certificate must not be valid for its trust domain");
+    let (mut store, _) = mode
+        .watch(id, server_name, &roots, Default::default())
+        .expect("should construct");
+    store
+        .set_trust_bundles(bundles)
+        .expect("bundles must be valid");
+    store
+        .set_certificate(DerX509(forged), vec![], forged_key, SystemTime::now())
+        .expect_err("certificate must not be valid for its trust domain");
+
+    let (cert, key) = domain_a.issue("spiffe://a.example/foo");
+    store
+        .set_certificate(DerX509(cert), vec![], key, SystemTime::now())
+        .expect("certificate must be valid");
+
+    // Bundles must include the local trust domain.
+    let bundles = TrustBundles::from([(
+        "b.example".to_string(),
+        vec![DerX509(domain_b.root.der().to_vec())],
+    )]);
+    store
+        .set_trust_bundles(bundles)
+        .expect_err("bundles must include the local trust domain");
+}
+
 pub async fn plaintext(mode: meshtls::Mode) {
-    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1);
-    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1);
+    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1, Default::default());
+    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1, Default::default());
     let (client_result, server_result) = run_test(
         client_tls,
         Conditional::None(tls::NoClientTls::NotProvidedByServiceDiscovery),
@@ -97,8 +190,10 @@ pub async fn plaintext(mode: meshtls::Mode) {
 }
 
 pub async fn proxy_to_proxy_tls_works(mode: meshtls::Mode) {
-    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1);
-    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1);
+    let mut registry = prom::Registry::default();
+    let metrics = HandshakeMetrics::register(&mut registry);
+    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1, metrics.clone());
+    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1, metrics);
     let server_id = tls::ServerId(test_util::FOO_NS1.id.parse().unwrap());
     let server_name = tls::ServerName(test_util::FOO_NS1.name.parse().unwrap());
     let (client_result, server_result) = run_test(
@@ -106,7 +201,17 @@ pub async fn proxy_to_proxy_tls_works(mode: meshtls::Mode) {
         Conditional::Some(tls::ClientTls::new(server_id.clone(), server_name.clone())),
         |conn| write_then_read(conn, PING),
         server_tls,
-        |(_, conn)| read_then_write(conn, PING.len(), PONG),
+        |(_, conn)| {
+            // The server reads the client's certificate from the connection.
+            let client_cert = match &conn {
+                io::EitherIo::Left(tls) => tls.client_cert(),
+                io::EitherIo::Right(_) => None,
+            };
+            async move {
+                let ping = read_then_write(conn, PING.len(), PONG).await?;
+                Ok((ping, client_cert))
+            }
+        },
     )
     .await;
     assert_eq!(
@@ -124,15 +229,101 @@ pub async fn proxy_to_proxy_tls_works(mode: meshtls::Mode) {
             negotiated_protocol: None,
         }))
     );
-    assert_eq!(&server_result.result.expect("ping")[..], PING);
+    let (ping, client_cert) = server_result.result.expect("ping");
+    let client_cert = client_cert.expect("server must describe the client's certificate");
+    assert_eq!(client_cert.dns_sans, vec![test_util::BAR_NS1.name]);
+    assert_eq!(client_cert.chain_depth, 1);
+    assert_eq!(client_cert.hash.len(), 64);
+    assert_eq!(&ping[..], PING);
+
+    // Both the client and server record the negotiated parameters.
+    let text = encode(&registry);
+    let handshakes = text
+        .lines()
+        .filter(|l| l.starts_with("handshakes_total{"))
+        .collect::<Vec<_>>();
+    assert_eq!(handshakes.len(), 2, "{}", text);
+    for direction in &["inbound", "outbound"] {
+        let line = handshakes
+            .iter()
+            .find(|l| l.contains(&format!("direction=\"{}\"", direction)))
+            .expect("handshake must be recorded");
+        assert!(line.ends_with(" 1"), "{}", text);
+        assert!(!line.contains("unknown"), "{}", text);
+    }
+    assert!(!text.contains("handshake_errors_total{"), "{}", text);
+}
+
+/// Session resumption is only supported by the rustls backend.
+#[allow(dead_code)]
+pub async fn proxy_to_proxy_tls_resumes_sessions(mode: meshtls::Mode) {
+    let mut registry = prom::Registry::default();
+    let metrics = HandshakeMetrics::register(&mut registry);
+    let (mut server_store, _, server_tls) = load(mode, &test_util::FOO_NS1, metrics.clone());
+    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1, metrics);
+    let server_id = tls::ServerId(test_util::FOO_NS1.id.parse().unwrap());
+    let server_name = tls::ServerName(test_util::FOO_NS1.name.parse().unwrap());
+
+    let connect = || async {
+        let (client_result, server_result) = run_test(
+            client_tls.clone(),
+            Conditional::Some(tls::ClientTls::new(server_id.clone(), server_name.clone())),
+            |conn| write_then_read(conn, PING),
+            server_tls.clone(),
+            |(_, conn)| read_then_write(conn, PING.len(), PONG),
+        )
+        .await;
+        assert_eq!(&client_result.result.expect("pong")[..], PONG);
+        assert_eq!(
+            server_result.tls,
+            Some(Conditional::Some(tls::ServerTls::Established {
+                client_id: Some(tls::ClientId(test_util::BAR_NS1.name.parse().unwrap())),
+                negotiated_protocol: None,
+            }))
+        );
+    };
+    let inbound = |resumed: bool| {
+        let text = encode(&registry);
+        text.lines()
+            .find(|l| {
+                l.starts_with("handshakes_total{direction=\"inbound\"")
+                    && l.contains(&format!("resumed=\"{}\"", resumed))
+            })
+            .and_then(|l| l.rsplit(' ').next())
+            .map(|v| v.parse::<u64>().unwrap())
+            .unwrap_or(0)
+    };
+
+    // The first connection performs a full handshake and the second resumes
+    // its session, authenticating the same client identity.
+    connect().await;
+    connect().await;
+    assert_eq!(inbound(false), 1);
+    assert_eq!(inbound(true), 1);
+
+    // Once the server's certificate is updated, sessions are no longer
+    // resumed.
+    server_store
+        .set_certificate(
+            DerX509(test_util::FOO_NS1.crt.to_vec()),
+            vec![],
+            test_util::FOO_NS1.key.to_vec(),
+            SystemTime::now() + Duration::from_secs(1000),
+        )
+        .expect("certificate must be valid");
+    connect().await;
+    assert_eq!(inbound(false), 2);
+    assert_eq!(inbound(true), 1);
 }
 
 pub async fn proxy_to_proxy_tls_pass_through_when_identity_does_not_match(mode: meshtls::Mode) {
-    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1);
+    let (_foo, _, server_tls) = load(mode, &test_util::FOO_NS1, Default::default());
 
     // Misuse the client's identity instead of the server's identity. Any
     // identity other than `server_tls.server_identity` would work.
-    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1);
+    let mut registry = prom::Registry::default();
+    let metrics = HandshakeMetrics::register(&mut registry);
+    let (_bar, client_tls, _) = load(mode, &test_util::BAR_NS1, metrics);
     let server_id = test_util::BAR_NS1.id.parse::<tls::ServerId>().unwrap();
     let server_name = test_util::BAR_NS1.name.parse::<tls::ServerName>().unwrap();
 
@@ -156,6 +347,74 @@ pub async fn proxy_to_proxy_tls_pass_through_when_identity_does_not_match(mode:
         }))
     );
     assert_eq!(&server_result.result.unwrap()[..], START_OF_TLS);
+
+    // The client fails its handshake, since the server responds with
+    // plaintext.
+    let text = encode(&registry);
+    assert!(
+        text.lines()
+            .any(|l| l == "handshake_errors_total{direction=\"outbound\",reason=\"not_tls\"} 1"),
+        "{}",
+        text
+    );
+}
+
+pub async fn proxy_to_proxy_tls_works_across_trust_domains(mode: meshtls::Mode) {
+    let domain_a = TrustDomain::new("a.example");
+    let domain_b = TrustDomain::new("b.example");
+    let (roots, bundles) = federate(&domain_a, &domain_b);
+
+    let load = |domain: &TrustDomain, id: &str, name: &str| {
+        let (mut store, rx) = mode
+            .watch(
+                id.parse().unwrap(),
+                name.parse().unwrap(),
+                &roots,
+                Default::default(),
+            )
+            .expect("credentials must be readable");
+        store
+            .set_trust_bundles(bundles.clone())
+            .expect("bundles must be valid");
+        let (cert, key) = domain.issue(id);
+        store
+            .set_certificate(
+                DerX509(cert),
+                vec![],
+                key,
+                SystemTime::now() + Duration::from_secs(1000),
+            )
+            .expect("certificate must be valid");
+        (store, rx.new_client(), rx.server())
+    };
+    let (_foo, _, server_tls) = load(&domain_a, "spiffe://a.example/foo", "foo.a.example");
+    let (_bar, client_tls, _) = load(&domain_b, "spiffe://b.example/bar", "bar.b.example");
+
+    let server_id = tls::ServerId("spiffe://a.example/foo".parse().unwrap());
+    let server_name = tls::ServerName("foo.a.example".parse().unwrap());
+    let (client_result, server_result) = run_test(
+        client_tls,
+        Conditional::Some(tls::ClientTls::new(server_id, server_name)),
+        |conn| write_then_read(conn, PING),
+        server_tls,
+        |(_, conn)| read_then_write(conn, PING.len(), PONG),
+    )
+    .await;
+    assert_eq!(&client_result.result.expect("pong")[..], PONG);
+    assert_eq!(
+        server_result.tls,
+        Some(Conditional::Some(tls::ServerTls::Established {
+            client_id: Some(tls::ClientId("spiffe://b.example/bar".parse().unwrap())),
+            negotiated_protocol: None,
+        }))
+    );
+    assert_eq!(&server_result.result.expect("ping")[..], PING);
+}
+
+fn encode(registry: &prom::Registry) -> String {
+    let mut text = String::new();
+    prom::encoding::text::encode(&mut text, registry).unwrap();
+    text
 }
 
 type ServerConn<T, I> = (
@@ -166,6 +425,7 @@ type ServerConn<T, I> = (
 fn load(
     mode: meshtls::Mode,
     ent: &test_util::Entity,
+    metrics: HandshakeMetrics,
 ) -> (meshtls::creds::Store, meshtls::NewClient, meshtls::Server) {
     let roots_pem = std::str::from_utf8(ent.trust_anchors).expect("valid PEM");
     let (mut store, rx) = mode
@@ -173,6 +433,7 @@ fn load(
             ent.name.parse().unwrap(),
             ent.name.parse().unwrap(),
             roots_pem,
+            metrics,
         )
         .expect("credentials must be readable");
 
@@ -406,6 +667,12 @@ impl Param<ListenAddr> for Server {
         ListenAddr(([127, 0, 0, 1], 0).into())
     }
 }
+impl Param<AcceptShards> for Server {
+    fn param(&self) -> AcceptShards {
+        AcceptShards::default()
+    }
+}
+
 impl Param<Keepalive> for Server {
     fn param(&self) -> Keepalive {
         Keepalive(None)
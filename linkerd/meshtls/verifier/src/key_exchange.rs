@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+/// A TLS key exchange group that may be offered and accepted by the proxy.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyExchangeGroup {
+    /// The hybrid post-quantum group combining X25519 with ML-KEM-768.
+    ///
+    /// This group is only supported by backends that implement ML-KEM (i.e.
+    /// rustls with aws-lc).
+    X25519MlKem768,
+    X25519,
+    Secp256r1,
+    Secp384r1,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("unknown TLS key exchange group: {0}")]
+pub struct InvalidKeyExchangeGroup(String);
+
+// === impl KeyExchangeGroup ===
+
+impl KeyExchangeGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::X25519MlKem768 => "X25519MLKEM768",
+            Self::X25519 => "X25519",
+            Self::Secp256r1 => "secp256r1",
+            Self::Secp384r1 => "secp384r1",
+        }
+    }
+}
+
+impl FromStr for KeyExchangeGroup {
+    type Err = InvalidKeyExchangeGroup;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let group = match s.to_ascii_lowercase().as_str() {
+            "x25519mlkem768" => Self::X25519MlKem768,
+            "x25519" => Self::X25519,
+            "secp256r1" | "p-256" => Self::Secp256r1,
+            "secp384r1" | "p-384" => Self::Secp384r1,
+            _ => return Err(InvalidKeyExchangeGroup(s.to_string())),
+        };
+        Ok(group)
+    }
+}
+
+impl std::fmt::Display for KeyExchangeGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -0,0 +1,476 @@
+use linkerd_error::Result;
+use linkerd_identity::RevocationMetrics;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use x509_parser::{pem::Pem, prelude::*};
+
+/// Checks peer certificates against a set of certificate revocation lists.
+///
+/// A CRL applies to a certificate only if it is signed by the key of the
+/// certificate's issuer, as determined from the verified certificate chain.
+/// CRLs that name the issuer but are not signed by its key are ignored.
+#[derive(Clone, Debug)]
+pub struct Revocation {
+    crls: Arc<[Crl]>,
+    unknown_status: UnknownStatus,
+    stale_status: UnknownStatus,
+    metrics: RevocationMetrics,
+}
+
+/// A certificate authority that may have issued a peer's certificate.
+///
+/// Like a `rustls` trust anchor, an issuer is described by the DER-encoded
+/// values (i.e. without their outer `SEQUENCE`) of its subject name and
+/// public key.
+#[derive(Copy, Clone, Debug)]
+pub struct Issuer<'a> {
+    pub subject: &'a [u8],
+    pub spki: &'a [u8],
+}
+
+/// Determines how certificates are handled when their revocation status
+/// cannot be determined, e.g. when no CRL is available for the certificate's
+/// issuer.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UnknownStatus {
+    /// Certificates with an unknown revocation status are rejected.
+    #[default]
+    Deny,
+
+    /// Certificates with an unknown revocation status are accepted (i.e.
+    /// revocation checking soft-fails).
+    Allow,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RevocationError {
+    #[error("certificate has been revoked")]
+    Revoked,
+
+    #[error("certificate revocation status is unknown")]
+    UnknownStatus,
+
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(#[source] X509Error),
+}
+
+#[derive(Debug)]
+struct Crl {
+    /// The value of the CRL's issuer name.
+    issuer: Vec<u8>,
+    der: Vec<u8>,
+    serials: HashSet<Vec<u8>>,
+    next_update: Option<SystemTime>,
+    /// Caches whether the CRL is signed by each issuer key it has been
+    /// checked against.
+    signed_by: Mutex<HashMap<Vec<u8>, bool>>,
+    warned_stale: AtomicBool,
+}
+
+// === impl Revocation ===
+
+impl Revocation {
+    /// Parses CRLs from a list of DER- or PEM-encoded files.
+    ///
+    /// `unknown_status` determines how certificates are handled when no CRL
+    /// is available for their issuer. `stale_status` determines how
+    /// certificates that are not listed by their issuer's CRLs are handled
+    /// when all of those CRLs are past their next update.
+    pub fn new(
+        files: &[Vec<u8>],
+        unknown_status: UnknownStatus,
+        stale_status: UnknownStatus,
+        metrics: RevocationMetrics,
+    ) -> Result<Self> {
+        let mut crls = Vec::new();
+        for file in files {
+            for der in decode(file)? {
+                let (_, crl) = CertificateRevocationList::from_der(&der)?;
+                let issuer = sequence_value(crl.issuer().as_raw())
+                    .ok_or("invalid CRL issuer")?
+                    .to_vec();
+                let serials = crl
+                    .iter_revoked_certificates()
+                    .map(|c| c.raw_serial().to_vec())
+                    .collect();
+                let next_update = crl.next_update().map(|t| to_system_time(t.timestamp()));
+                crls.push(Crl {
+                    issuer,
+                    serials,
+                    next_update,
+                    der,
+                    signed_by: Default::default(),
+                    warned_stale: AtomicBool::new(false),
+                });
+            }
+        }
+
+        if crls.is_empty() {
+            return Err("no certificate revocation lists loaded".into());
+        }
+
+        Ok(Self {
+            crls: crls.into(),
+            unknown_status,
+            stale_status,
+            metrics,
+        })
+    }
+
+    /// Ensures that the DER-encoded end-entity certificate has not been
+    /// revoked.
+    ///
+    /// `issuers` must include the certificate's issuer, i.e. the verified
+    /// chain's intermediates and trust anchors.
+    pub fn check<'i>(
+        &self,
+        cert: &[u8],
+        issuers: impl IntoIterator<Item = Issuer<'i>>,
+    ) -> Result<(), RevocationError> {
+        let (_, cert) = X509Certificate::from_der(cert).map_err(|e| {
+            RevocationError::InvalidCertificate(match e {
+                x509_parser::nom::Err::Error(e) | nom::Err::Failure(e) => e,
+                x509_parser::nom::Err::Incomplete(_) => X509Error::InvalidCertificate,
+            })
+        })?;
+        let issuer_name = sequence_value(cert.issuer().as_raw()).unwrap_or_default();
+
+        // Only the key that signed the certificate may revoke it.
+        let issuer = issuers.into_iter().find(|issuer| {
+            issuer.subject == issuer_name
+                && with_spki(issuer.spki, |spki| {
+                    cert.verify_signature(Some(spki)).is_ok()
+                })
+                .unwrap_or(false)
+        });
+        let crls = issuer
+            .map(|issuer| {
+                self.crls
+                    .iter()
+                    .filter(|crl| crl.issuer == issuer_name && crl.is_signed_by(issuer.spki))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if crls.is_empty() {
+            self.metrics.unknown();
+            tracing::debug!(issuer = %cert.issuer(), "No CRL for certificate issuer");
+            return match self.unknown_status {
+                UnknownStatus::Allow => Ok(()),
+                UnknownStatus::Deny => Err(RevocationError::UnknownStatus),
+            };
+        }
+
+        if crls
+            .iter()
+            .any(|crl| crl.serials.contains(cert.raw_serial()))
+        {
+            self.metrics.revoked();
+            tracing::debug!(serial = %cert.raw_serial_as_string(), "Certificate has been revoked");
+            return Err(RevocationError::Revoked);
+        }
+
+        // A stale CRL still revokes the certificates it lists, but it may not
+        // list certificates that have been revoked since it was issued.
+        let now = SystemTime::now();
+        if !crls.iter().any(|crl| crl.is_current(now)) {
+            for crl in &crls {
+                if !crl.warned_stale.swap(true, Ordering::Relaxed) {
+                    tracing::warn!(issuer = %cert.issuer(), "CRL is past its next update");
+                }
+            }
+            if self.stale_status == UnknownStatus::Deny {
+                self.metrics.unknown();
+                return Err(RevocationError::UnknownStatus);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// === impl Issuer ===
+
+impl<'a> Issuer<'a> {
+    /// Describes the issuer of a DER-encoded CA certificate.
+    pub fn from_certificate(der: &'a [u8]) -> Option<Self> {
+        let (_, cert) = X509Certificate::from_der(der).ok()?;
+        Some(Self {
+            subject: sequence_value(cert.tbs_certificate.subject.as_raw())?,
+            spki: sequence_value(cert.tbs_certificate.subject_pki.raw)?,
+        })
+    }
+}
+
+// === impl Crl ===
+
+impl Crl {
+    fn is_current(&self, now: SystemTime) -> bool {
+        self.next_update.is_none_or(|next| now < next)
+    }
+
+    /// Returns true if the CRL is signed by the given issuer key.
+    fn is_signed_by(&self, spki: &[u8]) -> bool {
+        let mut signed_by = self.signed_by.lock();
+        if let Some(signed) = signed_by.get(spki) {
+            return *signed;
+        }
+
+        let signed = CertificateRevocationList::from_der(&self.der)
+            .ok()
+            .and_then(|(_, crl)| with_spki(spki, |spki| crl.verify_signature(spki).is_ok()))
+            .unwrap_or(false);
+        if !signed {
+            tracing::warn!("CRL is not signed by its issuer's key");
+        }
+        signed_by.insert(spki.to_vec(), signed);
+        signed
+    }
+}
+
+fn decode(file: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if !file.starts_with(b"-----BEGIN") {
+        return Ok(vec![file.to_vec()]);
+    }
+
+    let mut ders = Vec::new();
+    for pem in Pem::iter_from_buffer(file) {
+        let pem = pem?;
+        if pem.label == "X509 CRL" {
+            ders.push(pem.contents);
+        }
+    }
+    Ok(ders)
+}
+
+/// Parses the value of a SubjectPublicKeyInfo.
+fn with_spki<T>(value: &[u8], f: impl FnOnce(&SubjectPublicKeyInfo<'_>) -> T) -> Option<T> {
+    let mut der = vec![0x30];
+    match value.len() {
+        len @ 0..=0x7f => der.push(len as u8),
+        len => {
+            let len = (len as u64).to_be_bytes();
+            let len = &len[len.iter().position(|b| *b != 0).unwrap_or(7)..];
+            der.push(0x80 | len.len() as u8);
+            der.extend_from_slice(len);
+        }
+    }
+    der.extend_from_slice(value);
+    let (_, spki) = SubjectPublicKeyInfo::from_der(&der).ok()?;
+    Some(f(&spki))
+}
+
+/// Returns the value of a DER-encoded `SEQUENCE`.
+fn sequence_value(der: &[u8]) -> Option<&[u8]> {
+    let (&tag, rest) = der.split_first()?;
+    if tag != 0x30 {
+        return None;
+    }
+    let (&len, rest) = rest.split_first()?;
+    if len < 0x80 {
+        return rest.get(..usize::from(len));
+    }
+    let (len, rest) = rest.split_at_checked(usize::from(len & 0x7f))?;
+    let len = len.iter().try_fold(0usize, |n, b| {
+        n.checked_mul(256)?.checked_add(usize::from(*b))
+    })?;
+    rest.get(..len)
+}
+
+fn to_system_time(ts: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(ts.try_into().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{
+        date_time_ymd, BasicConstraints, CertificateParams, CertificateRevocationListParams, IsCa,
+        KeyIdMethod, KeyPair, KeyUsagePurpose, RevokedCertParams, SerialNumber,
+    };
+
+    struct Ca {
+        cert: rcgen::Certificate,
+        key: KeyPair,
+    }
+
+    fn issuer(cn: &str) -> Ca {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, cn);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let cert = params.self_signed(&key).unwrap();
+        Ca { cert, key }
+    }
+
+    fn leaf(issuer: &Ca, serial: u8) -> Vec<u8> {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::default();
+        params.serial_number = Some(SerialNumber::from(vec![serial]));
+        params
+            .signed_by(&key, &issuer.cert, &issuer.key)
+            .unwrap()
+            .der()
+            .to_vec()
+    }
+
+    fn crl(issuer: &Ca, next_update_year: i32, revoked: &[u8]) -> Vec<u8> {
+        CertificateRevocationListParams {
+            this_update: date_time_ymd(2020, 1, 1),
+            next_update: date_time_ymd(next_update_year, 1, 1),
+            crl_number: SerialNumber::from(1u64),
+            issuing_distribution_point: None,
+            revoked_certs: revoked
+                .iter()
+                .map(|s| RevokedCertParams {
+                    serial_number: SerialNumber::from(vec![*s]),
+                    revocation_time: date_time_ymd(2020, 1, 1),
+                    reason_code: None,
+                    invalidity_date: None,
+                })
+                .collect(),
+            key_identifier_method: KeyIdMethod::Sha256,
+        }
+        .signed_by(&issuer.cert, &issuer.key)
+        .unwrap()
+        .pem()
+        .unwrap()
+        .into_bytes()
+    }
+
+    fn check(revocation: &Revocation, issuer: &Ca, leaf: &[u8]) -> Result<(), RevocationError> {
+        let ca = Issuer::from_certificate(issuer.cert.der()).unwrap();
+        revocation.check(leaf, [ca])
+    }
+
+    #[test]
+    fn rejects_revoked_certificates() {
+        let ca = issuer("ca");
+        let revocation = Revocation::new(
+            &[crl(&ca, 2100, &[2])],
+            UnknownStatus::Deny,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+
+        assert!(check(&revocation, &ca, &leaf(&ca, 1)).is_ok());
+        assert!(matches!(
+            check(&revocation, &ca, &leaf(&ca, 2)),
+            Err(RevocationError::Revoked)
+        ));
+    }
+
+    #[test]
+    fn ignores_crls_not_signed_by_issuer() {
+        let ca = issuer("ca");
+        let forged = issuer("ca");
+        let revocation = Revocation::new(
+            &[crl(&forged, 2100, &[1])],
+            UnknownStatus::Allow,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(check(&revocation, &ca, &leaf(&ca, 1)).is_ok());
+
+        let revocation = Revocation::new(
+            &[crl(&forged, 2100, &[1])],
+            UnknownStatus::Deny,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(matches!(
+            check(&revocation, &ca, &leaf(&ca, 1)),
+            Err(RevocationError::UnknownStatus)
+        ));
+
+        // The issuer must be the key that signed the certificate.
+        let leaf = leaf(&ca, 1);
+        let forged = Issuer::from_certificate(forged.cert.der()).unwrap();
+        assert!(matches!(
+            revocation.check(&leaf, [forged]),
+            Err(RevocationError::UnknownStatus)
+        ));
+    }
+
+    #[test]
+    fn unknown_status() {
+        let ca = issuer("ca");
+        let other = issuer("other");
+        let files = [crl(&ca, 2100, &[])];
+
+        let deny = Revocation::new(
+            &files,
+            UnknownStatus::Deny,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(matches!(
+            check(&deny, &other, &leaf(&other, 1)),
+            Err(RevocationError::UnknownStatus)
+        ));
+
+        let allow = Revocation::new(
+            &files,
+            UnknownStatus::Allow,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(check(&allow, &other, &leaf(&other, 1)).is_ok());
+    }
+
+    #[test]
+    fn stale_crls() {
+        let ca = issuer("ca");
+        let files = [crl(&ca, 2021, &[2])];
+
+        let allow = Revocation::new(
+            &files,
+            UnknownStatus::Deny,
+            UnknownStatus::Allow,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(check(&allow, &ca, &leaf(&ca, 1)).is_ok());
+        assert!(matches!(
+            check(&allow, &ca, &leaf(&ca, 2)),
+            Err(RevocationError::Revoked)
+        ));
+
+        let deny = Revocation::new(
+            &files,
+            UnknownStatus::Allow,
+            UnknownStatus::Deny,
+            RevocationMetrics::default(),
+        )
+        .unwrap();
+        assert!(matches!(
+            check(&deny, &ca, &leaf(&ca, 1)),
+            Err(RevocationError::UnknownStatus)
+        ));
+    }
+
+    #[test]
+    fn requires_crls() {
+        assert!(Revocation::new(
+            &[],
+            UnknownStatus::Allow,
+            UnknownStatus::Allow,
+            RevocationMetrics::default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,340 @@
+//! Guards against unbounded label cardinality.
+//!
+//! Some labels (e.g. hostnames, authorities, or client identities) are derived
+//! from traffic and may take an unbounded number of values. A
+//! [`CardinalityLimit`] caps the number of distinct label sets that a metric
+//! family may hold. Once the cap is reached, observations for new label sets
+//! are folded into a single series labeled `overflow="true"`.
+//!
+//! The overflow series holds the observations of every folded label set, so
+//! `overflow="true"` replaces all of the limited labels, including bounded ones
+//! such as `direction`. Labels that are written alongside the limited label set
+//! (e.g. a legacy response's `status_code` and `classification`, or a
+//! histogram's `le`) are still written on the overflow series.
+
+use crate::{prom, FmtLabels};
+use parking_lot::{MappedRwLockReadGuard, Mutex};
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeMetric, LabelSetEncoder, MetricEncoder},
+    metrics::{
+        family::{Family, MetricConstructor},
+        MetricType, TypedMetric,
+    },
+};
+use std::{fmt, hash::Hash, sync::Arc};
+
+/// Configures cardinality limits for a set of metric families and records
+/// how many observations were folded into each family's overflow series.
+#[derive(Clone, Debug, Default)]
+pub struct CardinalityLimits {
+    max_series: Option<usize>,
+    scope: String,
+    folded: Family<FamilyLabels, prom::Counter>,
+}
+
+/// The cardinality limit for a single metric family.
+///
+/// The default limit is unbounded.
+#[derive(Clone, Debug, Default)]
+pub struct CardinalityLimit {
+    max_series: Option<usize>,
+    folded: prom::Counter,
+}
+
+/// A label set that may have been folded into a family's overflow series.
+///
+/// The overflow series is labeled only `overflow="true"`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Overflow<L> {
+    Labels(L),
+    Overflow,
+}
+
+/// A [`Family`] that holds at most a limited number of distinct label sets.
+pub struct LimitedFamily<L, M, C = fn() -> M> {
+    family: Family<Overflow<L>, M, C>,
+    series: Arc<Mutex<usize>>,
+    limit: CardinalityLimit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct FamilyLabels {
+    family: String,
+}
+
+// === impl CardinalityLimits ===
+
+impl CardinalityLimits {
+    /// Limits each family to `max_series` distinct label sets. When
+    /// `max_series` is `None`, families are unbounded.
+    pub fn register(reg: &mut prom::Registry, max_series: Option<usize>) -> Self {
+        let folded = Family::default();
+        reg.register(
+            "observations_folded",
+            "The total number of observations for new label sets that were folded into a family's overflow series",
+            folded.clone(),
+        );
+        Self {
+            max_series,
+            scope: String::new(),
+            folded,
+        }
+    }
+
+    pub fn max_series(&self) -> Option<usize> {
+        self.max_series
+    }
+
+    /// Returns limits for families registered under the given prefix, so that
+    /// folds are attributed to the family's fully-qualified name.
+    pub fn scoped(&self, prefix: &str) -> Self {
+        Self {
+            max_series: self.max_series,
+            scope: self.qualify(prefix),
+            folded: self.folded.clone(),
+        }
+    }
+
+    /// Returns the limit for the named family.
+    pub fn limit(&self, family: &str) -> CardinalityLimit {
+        let family = self.qualify(family);
+        CardinalityLimit {
+            max_series: self.max_series,
+            folded: self.folded.get_or_create(&FamilyLabels { family }).clone(),
+        }
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        if self.scope.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}_{name}", self.scope)
+        }
+    }
+}
+
+// === impl CardinalityLimit ===
+
+impl CardinalityLimit {
+    pub fn is_limited(&self) -> bool {
+        self.max_series.is_some()
+    }
+
+    /// Returns true if a family that currently holds `series` label sets may
+    /// add another. Otherwise, the fold is recorded and false is returned.
+    pub fn admit(&self, series: usize) -> bool {
+        match self.max_series {
+            Some(max) if series >= max => {
+                self.folded.inc();
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// The number of observations that have been folded into the overflow
+    /// series.
+    pub fn folded(&self) -> u64 {
+        self.folded.get()
+    }
+}
+
+// === impl Overflow ===
+
+impl<L> Overflow<L> {
+    pub fn as_ref(&self) -> Overflow<&L> {
+        match self {
+            Self::Labels(l) => Overflow::Labels(l),
+            Self::Overflow => Overflow::Overflow,
+        }
+    }
+
+    pub fn map<T>(self, f: impl FnOnce(L) -> T) -> Overflow<T> {
+        match self {
+            Self::Labels(l) => Overflow::Labels(f(l)),
+            Self::Overflow => Overflow::Overflow,
+        }
+    }
+}
+
+impl<L: EncodeLabelSet> EncodeLabelSet for Overflow<L> {
+    fn encode(&self, encoder: LabelSetEncoder<'_>) -> fmt::Result {
+        match self {
+            Self::Labels(l) => l.encode(encoder),
+            Self::Overflow => [("overflow", "true")].encode(encoder),
+        }
+    }
+}
+
+impl<L: FmtLabels> FmtLabels for Overflow<L> {
+    fn fmt_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Labels(l) => l.fmt_labels(f),
+            Self::Overflow => f.pad("overflow=\"true\""),
+        }
+    }
+}
+
+// === impl LimitedFamily ===
+
+impl<L, M> LimitedFamily<L, M>
+where
+    L: Clone + Hash + Eq,
+    M: Default,
+{
+    pub fn new(limit: CardinalityLimit) -> Self {
+        Self::new_with_constructor(limit, M::default)
+    }
+}
+
+impl<L, M, C> LimitedFamily<L, M, C>
+where
+    L: Clone + Hash + Eq,
+    C: MetricConstructor<M>,
+{
+    pub fn new_with_constructor(limit: CardinalityLimit, constructor: C) -> Self {
+        Self {
+            family: Family::new_with_constructor(constructor),
+            series: Default::default(),
+            limit,
+        }
+    }
+
+    /// Returns the metric for the given labels, creating it if necessary.
+    ///
+    /// If the family is full, the overflow series is returned instead.
+    pub fn get_or_create(&self, labels: &L) -> MappedRwLockReadGuard<'_, M> {
+        if !self.limit.is_limited() {
+            return self.family.get_or_create(&Overflow::Labels(labels.clone()));
+        }
+
+        let labels = Overflow::Labels(labels.clone());
+        if let Some(metric) = self.family.get(&labels) {
+            return metric;
+        }
+
+        let mut series = self.series.lock();
+        if self.family.get(&labels).is_some() {
+            drop(series);
+            return self.family.get_or_create(&labels);
+        }
+        if self.limit.admit(*series) {
+            *series += 1;
+            drop(series);
+            return self.family.get_or_create(&labels);
+        }
+        drop(series);
+        self.family.get_or_create(&Overflow::Overflow)
+    }
+}
+
+impl<L, M: Default> Default for LimitedFamily<L, M>
+where
+    L: Clone + Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new(CardinalityLimit::default())
+    }
+}
+
+impl<L, M, C: Clone> Clone for LimitedFamily<L, M, C> {
+    fn clone(&self) -> Self {
+        Self {
+            family: self.family.clone(),
+            series: self.series.clone(),
+            limit: self.limit.clone(),
+        }
+    }
+}
+
+impl<L: fmt::Debug, M: fmt::Debug, C> fmt::Debug for LimitedFamily<L, M, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LimitedFamily")
+            .field("family", &self.family)
+            .field("series", &*self.series.lock())
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<L, M: TypedMetric, C> TypedMetric for LimitedFamily<L, M, C> {
+    const TYPE: MetricType = <M as TypedMetric>::TYPE;
+}
+
+impl<L, M, C> EncodeMetric for LimitedFamily<L, M, C>
+where
+    L: Clone + Hash + Eq + EncodeLabelSet,
+    M: EncodeMetric + TypedMetric,
+    C: MetricConstructor<M>,
+{
+    fn encode(&self, encoder: MetricEncoder<'_>) -> fmt::Result {
+        self.family.encode(encoder)
+    }
+
+    fn metric_type(&self) -> MetricType {
+        M::TYPE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+    struct Labels {
+        host: String,
+    }
+
+    fn labels(host: &str) -> Labels {
+        Labels {
+            host: host.to_string(),
+        }
+    }
+
+    #[test]
+    fn folds_new_label_sets() {
+        let mut reg = prom::Registry::default();
+        let limits = CardinalityLimits::register(reg.sub_registry_with_prefix("metrics"), Some(2));
+        let family =
+            LimitedFamily::<Labels, prom::Counter>::new(limits.scoped("http").limit("requests"));
+        reg.register("requests", "Requests", family.clone());
+
+        family.get_or_create(&labels("a")).inc();
+        family.get_or_create(&labels("b")).inc();
+        family.get_or_create(&labels("c")).inc();
+        family.get_or_create(&labels("d")).inc();
+        // Existing label sets are still recorded.
+        family.get_or_create(&labels("a")).inc();
+
+        let mut text = String::new();
+        prom::encoding::text::encode(&mut text, &reg).unwrap();
+        assert!(text.contains("requests_total{host=\"a\"} 2\n"), "{text}");
+        assert!(text.contains("requests_total{host=\"b\"} 1\n"), "{text}");
+        assert!(
+            text.contains("requests_total{overflow=\"true\"} 2\n"),
+            "{text}"
+        );
+        assert!(!text.contains("host=\"c\""), "{text}");
+        assert!(
+            text.contains("metrics_observations_folded_total{family=\"http_requests\"} 2\n"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn unlimited() {
+        let family = LimitedFamily::<Labels, prom::Counter>::default();
+        for i in 0..100 {
+            family.get_or_create(&labels(&i.to_string())).inc();
+        }
+        assert!(family.family.get(&Overflow::Overflow).is_none());
+        assert_eq!(
+            family
+                .family
+                .get(&Overflow::Labels(labels("99")))
+                .unwrap()
+                .get(),
+            1
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Metrics that are annotated with exemplars when they are encoded as
+//! OpenMetrics.
+//!
+//! `prometheus-client` always encodes exemplars, but the Prometheus text format
+//! does not support them, so registries are encoded through [`encode_text`],
+//! which omits them, unless they are served as OpenMetrics.
+
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeMetric, MetricEncoder},
+    metrics::{counter::Counter, exemplar, histogram::Histogram, MetricType, TypedMetric},
+    registry::Registry,
+};
+use std::fmt;
+
+/// Counts events, retaining the exemplar of the latest event that has one.
+#[derive(Debug)]
+pub struct CounterWithExemplar<S>(exemplar::CounterWithExemplar<S>);
+
+/// A histogram that retains the latest exemplar observed in each bucket.
+#[derive(Debug)]
+pub struct HistogramWithExemplars<S>(exemplar::HistogramWithExemplars<S>);
+
+/// Writes a registry's text encoding, omitting the `# EOF` terminator so that
+/// it may be followed by other metrics and, unless `exemplars` is set, the
+/// exemplars annotating each sample.
+struct Lines<'w, W> {
+    writer: &'w mut W,
+    line: String,
+    exemplars: bool,
+}
+
+/// Encodes the registry in the OpenMetrics text format, including exemplars.
+pub fn encode_openmetrics<W: fmt::Write>(writer: &mut W, registry: &Registry) -> fmt::Result {
+    prometheus_client::encoding::text::encode(writer, registry)
+}
+
+/// Encodes the registry's metric families in the Prometheus text format,
+/// without exemplars or a `# EOF` terminator.
+pub fn encode_text<W: fmt::Write>(writer: &mut W, registry: &Registry) -> fmt::Result {
+    Lines::encode(writer, registry, false)
+}
+
+/// Encodes the registry's metric families in the OpenMetrics text format,
+/// without a `# EOF` terminator, so that they may be followed by other
+/// families.
+pub(crate) fn encode_openmetrics_families<W: fmt::Write>(
+    writer: &mut W,
+    registry: &Registry,
+) -> fmt::Result {
+    Lines::encode(writer, registry, true)
+}
+
+// === impl Lines ===
+
+impl<'w, W: fmt::Write> Lines<'w, W> {
+    fn encode(writer: &'w mut W, registry: &Registry, exemplars: bool) -> fmt::Result {
+        let mut lines = Self {
+            writer,
+            line: String::new(),
+            exemplars,
+        };
+        prometheus_client::encoding::text::encode(&mut lines, registry)?;
+        lines.write_line()
+    }
+
+    fn write_line(&mut self) -> fmt::Result {
+        let line = self.line.as_str();
+        if line.is_empty() || line == "# EOF\n" {
+            self.line.clear();
+            return Ok(());
+        }
+        match Self::exemplar_start(line).filter(|_| !self.exemplars) {
+            Some(end) => {
+                self.writer.write_str(&line[..end])?;
+                self.writer.write_char('\n')?;
+            }
+            None => self.writer.write_str(line)?,
+        }
+        self.line.clear();
+        Ok(())
+    }
+
+    /// Returns the offset of the ` # ` that separates a sample from its
+    /// exemplar, ignoring any that occur in quoted label values.
+    fn exemplar_start(line: &str) -> Option<usize> {
+        if line.starts_with('#') {
+            return None;
+        }
+        let (mut quoted, mut escaped) = (false, false);
+        for (i, c) in line.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if quoted => escaped = true,
+                '"' => quoted = !quoted,
+                ' ' if !quoted && line[i..].starts_with(" # ") => return Some(i),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for Lines<'_, W> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while let Some(i) = s.find('\n') {
+            self.line.push_str(&s[..=i]);
+            self.write_line()?;
+            s = &s[i + 1..];
+        }
+        self.line.push_str(s);
+        Ok(())
+    }
+}
+
+// === impl CounterWithExemplar ===
+
+impl<S> Default for CounterWithExemplar<S> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<S> Clone for CounterWithExemplar<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> CounterWithExemplar<S> {
+    /// Increments the counter, replacing its exemplar if one is provided.
+    pub fn inc(&self, exemplar: Option<S>) {
+        self.0.inc_by(1, exemplar);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.get().0
+    }
+}
+
+impl<S> TypedMetric for CounterWithExemplar<S> {
+    const TYPE: MetricType = MetricType::Counter;
+}
+
+impl<S: EncodeLabelSet> EncodeMetric for CounterWithExemplar<S> {
+    fn encode(&self, encoder: MetricEncoder<'_>) -> fmt::Result {
+        self.0.encode(encoder)
+    }
+
+    fn metric_type(&self) -> MetricType {
+        Counter::<u64>::TYPE
+    }
+}
+
+// === impl HistogramWithExemplars ===
+
+impl<S> HistogramWithExemplars<S> {
+    pub fn new(buckets: impl IntoIterator<Item = f64>) -> Self {
+        Self(exemplar::HistogramWithExemplars::new(buckets.into_iter()))
+    }
+
+    /// Observes a value, replacing its bucket's exemplar if one is provided.
+    pub fn observe(&self, v: f64, exemplar: Option<S>) {
+        self.0.observe(v, exemplar);
+    }
+}
+
+impl<S> Clone for HistogramWithExemplars<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> TypedMetric for HistogramWithExemplars<S> {
+    const TYPE: MetricType = MetricType::Histogram;
+}
+
+impl<S: EncodeLabelSet> EncodeMetric for HistogramWithExemplars<S> {
+    fn encode(&self, encoder: MetricEncoder<'_>) -> fmt::Result {
+        self.0.encode(encoder)
+    }
+
+    fn metric_type(&self) -> MetricType {
+        Histogram::TYPE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_exemplars() {
+        let mut registry = Registry::default();
+        let counter = CounterWithExemplar::<Vec<(String, String)>>::default();
+        counter.inc(Some(vec![("trace_id".to_string(), "abc".to_string())]));
+        registry.register("requests", "Requests", counter);
+
+        let mut text = String::new();
+        encode_text(&mut text, &registry).unwrap();
+        assert_eq!(
+            text,
+            "# HELP requests Requests.\n# TYPE requests counter\nrequests_total 1\n"
+        );
+
+        let mut text = String::new();
+        encode_openmetrics_families(&mut text, &registry).unwrap();
+        assert!(
+            text.ends_with("requests_total 1 # {trace_id=\"abc\"} 1.0\n"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn ignores_quoted_exemplar_separators() {
+        type L = Lines<'static, String>;
+        assert_eq!(L::exemplar_start("a{b=\" # \\\" # \"} 1\n"), None);
+        assert_eq!(
+            L::exemplar_start("a{b=\"#\"} 1 # {c=\"d\"} 1.0\n"),
+            Some(10)
+        );
+        assert_eq!(L::exemplar_start("# HELP a b # c\n"), None);
+    }
+}
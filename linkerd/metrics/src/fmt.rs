@@ -5,6 +5,23 @@ use std::marker::PhantomData;
 pub trait FmtMetrics {
     fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Writes a block of metrics in the OpenMetrics text format, without a
+    /// `# EOF` terminator.
+    ///
+    /// By default, metrics are written as they are in the Prometheus text
+    /// format. Reports that include a `prometheus-client` registry write it with
+    /// its exemplars.
+    fn fmt_openmetrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_metrics(f)
+    }
+
+    /// Evicts metrics that have not been updated recently.
+    ///
+    /// Formatting metrics has no side effects, so that a report may be shared
+    /// by multiple consumers. Eviction is instead triggered periodically,
+    /// independently of the consumers that format the report.
+    fn evict_idle(&self) {}
+
     fn as_display(&self) -> DisplayMetrics<&Self>
     where
         Self: Sized,
@@ -12,6 +29,13 @@ pub trait FmtMetrics {
         DisplayMetrics(self)
     }
 
+    fn as_openmetrics(&self) -> DisplayOpenMetrics<&Self>
+    where
+        Self: Sized,
+    {
+        DisplayOpenMetrics(self)
+    }
+
     fn and_report<N>(self, next: N) -> AndThen<Self, N>
     where
         N: FmtMetrics,
@@ -24,6 +48,9 @@ pub trait FmtMetrics {
 /// Adapts `FmtMetrics` to `fmt::Display`.
 pub struct DisplayMetrics<F>(F);
 
+/// Adapts `FmtMetrics` to `fmt::Display` in the OpenMetrics text format.
+pub struct DisplayOpenMetrics<F>(F);
+
 #[derive(Clone, Debug)]
 pub struct AndThen<A, B>(A, B);
 
@@ -33,6 +60,12 @@ impl<F: FmtMetrics> fmt::Display for DisplayMetrics<F> {
     }
 }
 
+impl<F: FmtMetrics> fmt::Display for DisplayOpenMetrics<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_openmetrics(f)
+    }
+}
+
 /// Writes a series of key-quoted-val pairs for use as prometheus labels.
 pub trait FmtLabels {
     fn fmt_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
@@ -195,6 +228,16 @@ impl<'a, A: FmtMetrics + 'a> FmtMetrics for &'a A {
     fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         (*self).fmt_metrics(f)
     }
+
+    #[inline]
+    fn fmt_openmetrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (*self).fmt_openmetrics(f)
+    }
+
+    #[inline]
+    fn evict_idle(&self) {
+        (*self).evict_idle()
+    }
 }
 
 impl<M: FmtMetrics> FmtMetrics for Option<M> {
@@ -205,6 +248,21 @@ impl<M: FmtMetrics> FmtMetrics for Option<M> {
         }
         Ok(())
     }
+
+    #[inline]
+    fn fmt_openmetrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(m) = self.as_ref() {
+            m.fmt_openmetrics(f)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn evict_idle(&self) {
+        if let Some(m) = self.as_ref() {
+            m.evict_idle();
+        }
+    }
 }
 
 impl<A: FmtMetrics, B: FmtMetrics> FmtMetrics for AndThen<A, B> {
@@ -215,6 +273,20 @@ impl<A: FmtMetrics, B: FmtMetrics> FmtMetrics for AndThen<A, B> {
 
         Ok(())
     }
+
+    #[inline]
+    fn fmt_openmetrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_openmetrics(f)?;
+        self.1.fmt_openmetrics(f)?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn evict_idle(&self) {
+        self.0.evict_idle();
+        self.1.evict_idle();
+    }
 }
 
 impl FmtMetrics for () {
@@ -3,7 +3,9 @@
 
 //! Utilities for exposing metrics to Prometheus.
 
+mod cardinality;
 mod counter;
+pub mod exemplar;
 mod fmt;
 mod gauge;
 mod histogram;
@@ -19,10 +21,11 @@ pub use kubert_prometheus_process as process;
 #[cfg(feature = "stack")]
 pub use self::new_metrics::NewMetrics;
 pub use self::{
+    cardinality::{CardinalityLimit, CardinalityLimits, LimitedFamily, Overflow},
     counter::Counter,
     fmt::{FmtLabels, FmtMetric, FmtMetrics, Metric},
     gauge::Gauge,
-    histogram::Histogram,
+    histogram::{Bounds, Bucket, Histogram},
     serve::Serve,
     store::{LastUpdate, SharedStore, Store},
 };
@@ -54,7 +57,12 @@ pub mod prom {
     impl crate::FmtMetrics for Report {
         #[inline]
         fn fmt_metrics(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            encoding::text::encode(f, self)
+            crate::exemplar::encode_text(f, self)
+        }
+
+        #[inline]
+        fn fmt_openmetrics(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            crate::exemplar::encode_openmetrics_families(f, self)
         }
     }
 }
@@ -6,6 +6,8 @@ use tracing::trace;
 
 use super::FmtMetrics;
 
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
 /// Serve Prometheues metrics.
 #[derive(Debug, Clone)]
 pub struct Serve<M> {
@@ -19,6 +21,44 @@ impl<M> Serve<M> {
         Self { metrics }
     }
 
+    /// Returns true if the request prefers the OpenMetrics text format over
+    /// the Prometheus text format.
+    ///
+    /// Media ranges are weighted by their `q` parameter. OpenMetrics is only
+    /// served when it is strictly preferred, since the Prometheus text format
+    /// is the default.
+    fn is_openmetrics<B>(req: &http::Request<B>) -> bool {
+        let (mut openmetrics, mut text) = (0.0_f32, 0.0_f32);
+        let ranges = req
+            .headers()
+            .get_all(http::header::ACCEPT)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','));
+        for range in ranges {
+            let mut params = range.split(';').map(str::trim);
+            let media_type = params.next().unwrap_or_default().to_ascii_lowercase();
+            let (mut q, mut version) = (1.0, None);
+            for param in params {
+                match param.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                    Some((k, v)) if k.eq_ignore_ascii_case("q") => {
+                        q = v.parse::<f32>().unwrap_or(0.0).clamp(0.0, 1.0);
+                    }
+                    Some((k, v)) if k.eq_ignore_ascii_case("version") => version = Some(v),
+                    _ => {}
+                }
+            }
+            match media_type.as_str() {
+                "application/openmetrics-text" if matches!(version, None | Some("1.0.0")) => {
+                    openmetrics = openmetrics.max(q)
+                }
+                "text/plain" | "text/*" | "*/*" => text = text.max(q),
+                _ => {}
+            }
+        }
+        openmetrics > text
+    }
+
     fn is_gzip<B>(req: &http::Request<B>) -> bool {
         req.headers()
             .get_all(http::header::ACCEPT_ENCODING)
@@ -35,26 +75,135 @@ impl<M> Serve<M> {
 
 impl<M: FmtMetrics> Serve<M> {
     pub fn serve<B>(&self, req: http::Request<B>) -> std::io::Result<http::Response<BoxBody>> {
+        // Exemplars are only included when metrics are served as OpenMetrics,
+        // which must be terminated by a single `# EOF` line.
+        let (content_type, text) = if Self::is_openmetrics(&req) {
+            let text = format!("{}# EOF\n", self.metrics.as_openmetrics());
+            (OPENMETRICS_CONTENT_TYPE, text)
+        } else {
+            ("text/plain", self.metrics.as_display().to_string())
+        };
+
+        let rsp = http::Response::builder().header(http::header::CONTENT_TYPE, content_type);
         if Self::is_gzip(&req) {
             trace!("gzipping metrics");
             let mut writer = GzEncoder::new(Vec::<u8>::new(), CompressionOptions::fast());
-            write!(&mut writer, "{}", self.metrics.as_display())?;
-            Ok(http::Response::builder()
+            writer.write_all(text.as_bytes())?;
+            Ok(rsp
                 .header(http::header::CONTENT_ENCODING, "gzip")
-                .header(http::header::CONTENT_TYPE, "text/plain")
                 .body(BoxBody::new(http_body_util::Full::<Bytes>::from(
                     writer.finish().map(Bytes::from)?,
                 )))
                 .expect("Response must be valid"))
         } else {
-            let mut writer = Vec::<u8>::new();
-            write!(&mut writer, "{}", self.metrics.as_display())?;
-            Ok(http::Response::builder()
-                .header(http::header::CONTENT_TYPE, "text/plain")
+            Ok(rsp
                 .body(BoxBody::new(http_body_util::Full::<Bytes>::from(
-                    Bytes::from(writer),
+                    Bytes::from(text),
                 )))
                 .expect("Response must be valid"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exemplar::CounterWithExemplar, prom};
+    use std::sync::Arc;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+    struct Exemplar {
+        trace_id: String,
+    }
+
+    struct Legacy;
+
+    impl FmtMetrics for Legacy {
+        fn fmt_metrics(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("# TYPE legacy counter\nlegacy 1\n")
+        }
+    }
+
+    fn report() -> impl FmtMetrics {
+        let counter = CounterWithExemplar::<Exemplar>::default();
+        counter.inc(Some(Exemplar {
+            trace_id: "abc".to_string(),
+        }));
+        let mut registry = prom::Registry::default();
+        registry.register("requests", "Requests", counter);
+        Legacy.and_report(Arc::new(registry))
+    }
+
+    fn accept(value: &str) -> http::Request<()> {
+        http::Request::builder()
+            .header(http::header::ACCEPT, value)
+            .body(())
+            .unwrap()
+    }
+
+    async fn body(rsp: http::Response<BoxBody>) -> String {
+        let body = http_body_util::BodyExt::collect(rsp.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn negotiates_openmetrics() {
+        let openmetrics = [
+            "application/openmetrics-text;version=1.0.0,text/plain;q=0.5",
+            "application/openmetrics-text; version=1.0.0, application/openmetrics-text; version=0.0.1; q=0.75, text/plain; version=0.0.4; q=0.5, */*; q=0.1",
+            "text/plain;q=0.2, application/openmetrics-text;q=0.9",
+        ];
+        for value in openmetrics {
+            assert!(Serve::<()>::is_openmetrics(&accept(value)), "{value}");
+        }
+
+        let text = [
+            "text/plain",
+            "*/*",
+            "application/openmetrics-text;q=0.5,text/plain",
+            "application/openmetrics-text;q=0",
+            "application/openmetrics-text, text/plain",
+            "application/openmetrics-text;version=0.0.1",
+            "application/openmetrics-textual",
+        ];
+        for value in text {
+            assert!(!Serve::<()>::is_openmetrics(&accept(value)), "{value}");
+        }
+        assert!(!Serve::<()>::is_openmetrics(&http::Request::new(())));
+    }
+
+    #[tokio::test]
+    async fn serves_legacy_metrics_and_exemplars_as_openmetrics() {
+        let serve = Serve::new(report());
+
+        let rsp = serve
+            .serve(accept("application/openmetrics-text;version=1.0.0"))
+            .unwrap();
+        assert_eq!(
+            rsp.headers()[http::header::CONTENT_TYPE],
+            OPENMETRICS_CONTENT_TYPE
+        );
+        let text = body(rsp).await;
+        assert!(
+            text.starts_with("# TYPE legacy counter\nlegacy 1\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains("requests_total 1 # {trace_id=\"abc\"} 1.0"),
+            "{text}"
+        );
+        assert_eq!(text.matches("# EOF").count(), 1, "{text}");
+        assert!(text.ends_with("# EOF\n"), "{text}");
+
+        let rsp = serve.serve(http::Request::new(())).unwrap();
+        assert_eq!(rsp.headers()[http::header::CONTENT_TYPE], "text/plain");
+        let text = body(rsp).await;
+        assert!(text.contains("legacy 1\n"), "{text}");
+        assert!(text.contains("requests_total 1\n"), "{text}");
+        assert!(!text.contains("trace_id"), "{text}");
+        assert!(!text.contains("# EOF"), "{text}");
+    }
+}
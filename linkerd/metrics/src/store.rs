@@ -1,4 +1,4 @@
-use crate::{FmtLabels, FmtMetric, Metric};
+use crate::{CardinalityLimit, FmtLabels, FmtMetric, Metric, Overflow};
 use parking_lot::Mutex;
 use std::{
     borrow::Borrow,
@@ -21,6 +21,8 @@ where
     K: Hash + Eq,
 {
     inner: HashMap<K, Arc<V>>,
+    limit: CardinalityLimit,
+    overflow: Option<Arc<V>>,
 }
 
 impl<K, V> Store<K, V>
@@ -31,8 +33,18 @@ where
         Self::default()
     }
 
+    /// Returns a store that holds at most `limit` keys. Values for additional
+    /// keys are folded into a single overflow value.
+    pub fn with_limit(limit: CardinalityLimit) -> Self {
+        Self {
+            inner: HashMap::new(),
+            limit,
+            overflow: None,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.inner.is_empty() && self.overflow.is_none()
     }
 
     pub fn len(&self) -> usize {
@@ -55,9 +67,26 @@ where
     where
         V: Default,
     {
+        if !self.inner.contains_key(&k) && !self.limit.admit(self.inner.len()) {
+            return self.overflow.get_or_insert_with(Default::default);
+        }
         self.inner.entry(k).or_default()
     }
 
+    /// Returns the value into which values for keys beyond the store's limit
+    /// have been folded.
+    pub fn overflow(&self) -> Option<&Arc<V>> {
+        self.overflow.as_ref()
+    }
+
+    /// Iterates over all values, including the overflow value.
+    pub fn iter_with_overflow(&self) -> impl Iterator<Item = (Overflow<&K>, &Arc<V>)> {
+        self.inner
+            .iter()
+            .map(|(k, v)| (Overflow::Labels(k), v))
+            .chain(self.overflow.iter().map(|v| (Overflow::Overflow, v)))
+    }
+
     pub fn iter(&self) -> hash_map::Iter<'_, K, Arc<V>> {
         self.inner.iter()
     }
@@ -66,8 +95,12 @@ where
     where
         V: LastUpdate,
     {
-        self.inner
-            .retain(|_, metric| Arc::strong_count(metric) > 1 || metric.last_update() >= epoch)
+        let retain =
+            |metric: &Arc<V>| Arc::strong_count(metric) > 1 || metric.last_update() >= epoch;
+        self.inner.retain(|_, metric| retain(metric));
+        if self.overflow.as_ref().is_some_and(|m| !retain(m)) {
+            self.overflow = None;
+        }
     }
 
     /// Formats a metric across all instances of `Metrics` in the registry.
@@ -82,7 +115,7 @@ where
         N: fmt::Display,
         M: FmtMetric,
     {
-        for (key, m) in self.iter() {
+        for (key, m) in self.iter_with_overflow() {
             get_metric(m).fmt_metric_labeled(f, &metric.name, key)?;
         }
 
@@ -106,7 +139,7 @@ where
         N: fmt::Display,
         M: FmtMetric,
     {
-        for (key, m) in self.iter() {
+        for (key, m) in self.iter_with_overflow() {
             let m = m.lock();
             get_metric(&*m).fmt_metric_labeled(f, &metric.name, key)?;
         }
@@ -120,9 +153,7 @@ where
     K: Hash + Eq,
 {
     fn default() -> Self {
-        Self {
-            inner: HashMap::new(),
-        }
+        Self::with_limit(CardinalityLimit::default())
     }
 }
 
@@ -139,3 +170,47 @@ impl<M: LastUpdate> LastUpdate for Arc<M> {
         std::ops::Deref::deref(self).last_update()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prom, CardinalityLimits, Counter, FmtMetrics};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Key(&'static str);
+
+    impl FmtLabels for Key {
+        fn fmt_labels(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "key=\"{}\"", self.0)
+        }
+    }
+
+    struct Report(Store<Key, Counter>);
+
+    impl FmtMetrics for Report {
+        fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0
+                .fmt_by(f, Metric::<_, Counter>::new("requests_total", ""), |c| c)
+        }
+    }
+
+    #[test]
+    fn folds_keys_beyond_limit() {
+        let limits = CardinalityLimits::register(&mut prom::Registry::default(), Some(1));
+        let limit = limits.limit("requests");
+        let mut store = Store::<Key, Counter>::with_limit(limit.clone());
+        store.get_or_default(Key("a")).incr();
+        store.get_or_default(Key("b")).incr();
+        store.get_or_default(Key("c")).incr();
+        store.get_or_default(Key("a")).incr();
+
+        assert_eq!(store.len(), 1);
+        assert_eq!(limit.folded(), 2);
+
+        let text = Report(store).as_display().to_string();
+        assert_eq!(
+            text,
+            "requests_total{key=\"a\"} 2\nrequests_total{overflow=\"true\"} 2\n"
+        );
+    }
+}
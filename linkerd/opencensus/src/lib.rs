@@ -236,7 +236,12 @@ fn convert_span(span: ExportSpan) -> Result<Span, Error> {
         trace_id: span.trace_id.into_bytes::<16>()?.to_vec(),
         span_id: span.span_id.into_bytes::<8>()?.to_vec(),
         tracestate: None,
-        parent_span_id: span.parent_id.into_bytes::<8>()?.to_vec(),
+        // Root spans have no parent.
+        parent_span_id: if span.parent_id.as_ref().is_empty() {
+            Vec::new()
+        } else {
+            span.parent_id.into_bytes::<8>()?.to_vec()
+        },
         name: Some(truncatable(span.span_name)),
         kind: kind as i32,
         start_time: Some(span.start.into()),
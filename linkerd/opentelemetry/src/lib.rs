@@ -2,8 +2,10 @@
 #![forbid(unsafe_code)]
 
 pub mod metrics;
+pub mod metrics_export;
 
 use self::metrics::Registry;
+pub use self::metrics_export::export_metrics;
 use futures::stream::{Stream, StreamExt};
 use http_body::Body;
 use linkerd_error::Error;
@@ -241,8 +243,14 @@ fn convert_span(span: ExportSpan) -> Result<SpanData, Error> {
         attributes.push(KeyValue::new(*k, v.clone()));
     }
     let is_remote = kind != trace_context::export::SpanKind::Client;
+    // Root spans have no parent.
+    let parent_span_id = if span.parent_id.as_ref().is_empty() {
+        SpanId::INVALID
+    } else {
+        SpanId::from_bytes(span.parent_id.into_bytes()?)
+    };
     Ok(SpanData {
-        parent_span_id: SpanId::from_bytes(span.parent_id.into_bytes()?),
+        parent_span_id,
         span_kind: match kind {
             trace_context::export::SpanKind::Server => SpanKind::Server,
             trace_context::export::SpanKind::Client => SpanKind::Client,
@@ -0,0 +1,286 @@
+use futures::StreamExt;
+use http_body::Body;
+use linkerd_error::Error;
+use linkerd_exp_backoff::ExponentialBackoff;
+use linkerd_metrics::{prom, FmtMetrics};
+use opentelemetry_proto::{
+    proto::{
+        collector::metrics::v1::{
+            metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        },
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        metrics::v1::{metric, ResourceMetrics, ScopeMetrics},
+        resource::v1::Resource,
+    },
+    transform::common::ResourceAttributesWithSchema,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time;
+use tonic::{self as grpc, body::BoxBody, client::GrpcService};
+use tracing::{debug, info, trace};
+
+mod openmetrics;
+
+/// Metrics describing the metrics exporter itself.
+#[derive(Clone, Debug)]
+pub struct ExportMetrics {
+    ok: prom::Counter,
+    errors: prom::Counter,
+    retries: prom::Counter,
+    data_points: prom::Counter,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct ResultLabels {
+    result: ExportResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelValue)]
+#[allow(non_camel_case_types)]
+enum ExportResult {
+    ok,
+    error,
+}
+
+/// Periodically exports the metrics in `report` to the given MetricsService
+/// gRPC service.
+///
+/// Each export is retried with a backoff when the collector is unavailable.
+/// If an export still fails, its data is dropped; the next export includes
+/// the latest (cumulative) values.
+pub async fn export_metrics<T, R>(
+    client: T,
+    report: R,
+    resource: ResourceAttributesWithSchema,
+    interval: time::Duration,
+    metrics: ExportMetrics,
+) where
+    T: GrpcService<BoxBody> + Clone,
+    T::Error: Into<Error>,
+    T::ResponseBody: Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <T::ResponseBody as Body>::Error: Into<Error> + Send,
+    R: FmtMetrics,
+{
+    debug!("Metrics exporter running");
+    MetricsExporter {
+        client: MetricsServiceClient::new(client),
+        report,
+        resource,
+        metrics,
+        start_time_unix_nano: unix_nanos(SystemTime::now()),
+    }
+    .run(interval)
+    .await
+}
+
+/// MetricsExporter collects metrics and sends them to a MetricsService.
+struct MetricsExporter<T, R> {
+    client: MetricsServiceClient<T>,
+    report: R,
+    resource: ResourceAttributesWithSchema,
+    metrics: ExportMetrics,
+    start_time_unix_nano: u64,
+}
+
+/// Timestamps applied to all converted data points.
+#[derive(Copy, Clone, Debug)]
+struct Timestamps {
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+}
+
+// === impl ExportMetrics ===
+
+impl ExportMetrics {
+    pub fn register(registry: &mut prom::Registry) -> Self {
+        let requests = prom::Family::<ResultLabels, prom::Counter>::default();
+        registry.register(
+            "requests",
+            "The total number of metrics export requests, by result",
+            requests.clone(),
+        );
+
+        let retries = prom::Counter::default();
+        registry.register(
+            "retries",
+            "The total number of metrics export requests that were retried",
+            retries.clone(),
+        );
+
+        let data_points = prom::Counter::default();
+        registry.register(
+            "data_points",
+            "The total number of data points exported",
+            data_points.clone(),
+        );
+
+        let ok = requests
+            .get_or_create(&ResultLabels {
+                result: ExportResult::ok,
+            })
+            .clone();
+        let errors = requests
+            .get_or_create(&ResultLabels {
+                result: ExportResult::error,
+            })
+            .clone();
+
+        Self {
+            ok,
+            errors,
+            retries,
+            data_points,
+        }
+    }
+}
+
+// === impl MetricsExporter ===
+
+impl<T, R> MetricsExporter<T, R>
+where
+    T: GrpcService<BoxBody> + Clone,
+    T::Error: Into<Error>,
+    T::ResponseBody: Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <T::ResponseBody as Body>::Error: Into<Error> + Send,
+    R: FmtMetrics,
+{
+    const MAX_ATTEMPTS: usize = 3;
+    const BACKOFF: ExponentialBackoff = ExponentialBackoff::new_unchecked(
+        time::Duration::from_millis(500),
+        time::Duration::from_secs(5),
+        0.1,
+    );
+    const SCOPE_NAME: &'static str = "linkerd-proxy";
+
+    async fn run(mut self, interval: time::Duration) {
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        // The first tick completes immediately. Skip it so that the first
+        // export includes some traffic.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let req = self.collect();
+            self.export(req).await;
+        }
+    }
+
+    /// Collects the metrics report as an export request.
+    fn collect(&self) -> ExportMetricsServiceRequest {
+        let ts = Timestamps {
+            start_time_unix_nano: self.start_time_unix_nano,
+            time_unix_nano: unix_nanos(SystemTime::now()),
+        };
+        let text = self.report.as_display().to_string();
+        let metrics = openmetrics::convert(&text, ts);
+        trace!(metrics = metrics.len(), "Collected metrics");
+
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: self.resource.attributes.0.clone(),
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: Self::SCOPE_NAME.to_string(),
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: self.resource.schema_url.clone().unwrap_or_default(),
+            }],
+        }
+    }
+
+    /// Sends an export request, retrying transient failures.
+    async fn export(&mut self, req: ExportMetricsServiceRequest) {
+        let data_points = data_points(&req);
+        let mut backoff = Self::BACKOFF.stream();
+        let mut attempt = 1;
+        loop {
+            match self.client.export(grpc::Request::new(req.clone())).await {
+                Ok(rsp) => {
+                    self.metrics.ok.inc();
+                    self.metrics.data_points.inc_by(data_points);
+                    if let Some(partial_success) = rsp.into_inner().partial_success {
+                        if partial_success.rejected_data_points > 0
+                            || !partial_success.error_message.is_empty()
+                        {
+                            debug!(
+                                %partial_success.error_message,
+                                rejected_data_points = partial_success.rejected_data_points,
+                                "Response partially successful",
+                            );
+                        }
+                    }
+                    return;
+                }
+                Err(status) => {
+                    self.metrics.errors.inc();
+                    if attempt == Self::MAX_ATTEMPTS || !is_retryable(status.code()) {
+                        info!(%status, data_points, "Failed to export metrics");
+                        return;
+                    }
+                    debug!(%status, attempt, "Export failed; retrying");
+                    self.metrics.retries.inc();
+                    attempt += 1;
+                    backoff.next().await;
+                }
+            }
+        }
+    }
+}
+
+/// Returns true for the gRPC status codes that OTLP considers retryable.
+///
+/// Transport errors, including failure to connect to the collector, are
+/// surfaced by the client as `Unknown`, so they are retried as well.
+fn is_retryable(code: grpc::Code) -> bool {
+    matches!(
+        code,
+        grpc::Code::Unknown
+            | grpc::Code::Cancelled
+            | grpc::Code::DeadlineExceeded
+            | grpc::Code::ResourceExhausted
+            | grpc::Code::Aborted
+            | grpc::Code::OutOfRange
+            | grpc::Code::Unavailable
+            | grpc::Code::DataLoss
+    )
+}
+
+fn data_points(req: &ExportMetricsServiceRequest) -> u64 {
+    req.resource_metrics
+        .iter()
+        .flat_map(|rm| &rm.scope_metrics)
+        .flat_map(|sm| &sm.metrics)
+        .map(|m| match &m.data {
+            Some(metric::Data::Gauge(d)) => d.data_points.len(),
+            Some(metric::Data::Sum(d)) => d.data_points.len(),
+            Some(metric::Data::Histogram(d)) => d.data_points.len(),
+            Some(metric::Data::ExponentialHistogram(d)) => d.data_points.len(),
+            Some(metric::Data::Summary(d)) => d.data_points.len(),
+            None => 0,
+        } as u64)
+        .sum()
+}
+
+fn attributes<'l>(labels: impl IntoIterator<Item = (&'l str, &'l str)>) -> Vec<KeyValue> {
+    labels
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        })
+        .collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
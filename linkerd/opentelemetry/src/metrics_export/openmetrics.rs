@@ -0,0 +1,530 @@
+//! Converts a metrics report into OTLP metrics.
+//!
+//! Legacy metrics only expose their values by formatting them, and
+//! `prometheus-client` only exposes a registry's values through its encoders,
+//! so the report is formatted once, without exemplars, and each family is
+//! converted from that text. Label values are borrowed from the text unless
+//! they must be unescaped.
+
+use super::{attributes, Timestamps};
+use opentelemetry_proto::proto::{
+    common::v1::KeyValue,
+    metrics::v1::{
+        metric, number_data_point, summary_data_point::ValueAtQuantile, AggregationTemporality,
+        Gauge, Histogram, HistogramDataPoint, Metric, NumberDataPoint, Sum, Summary,
+        SummaryDataPoint,
+    },
+};
+use std::borrow::Cow;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+    Summary,
+    Info,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Default)]
+struct Family<'t> {
+    name: &'t str,
+    help: String,
+    unit: &'t str,
+    kind: Kind,
+    samples: Vec<Sample<'t>>,
+}
+
+#[derive(Debug)]
+struct Sample<'t> {
+    suffix: &'t str,
+    labels: Vec<(&'t str, Cow<'t, str>)>,
+    value: f64,
+}
+
+/// Converts an OpenMetrics exposition into OTLP metrics.
+///
+/// Counters are exported as cumulative, monotonic sums; histograms and
+/// summaries keep their Prometheus semantics; all other families are exported
+/// as gauges. Exemplars and `_created` samples are ignored.
+pub(super) fn convert(text: &str, ts: Timestamps) -> Vec<Metric> {
+    parse(text)
+        .into_iter()
+        .filter_map(|family| family.into_metric(ts))
+        .collect()
+}
+
+fn parse(text: &str) -> Vec<Family<'_>> {
+    let mut families = Vec::<Family<'_>>::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, ' ');
+            let (Some(keyword @ ("HELP" | "TYPE" | "UNIT")), Some(name)) =
+                (parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let rest = parts.next().unwrap_or_default().trim();
+            let idx = family_index(&mut families, name);
+            let family = &mut families[idx];
+            match keyword {
+                "HELP" => family.help = unescape(rest),
+                "TYPE" => family.kind = Kind::parse(rest),
+                _ => family.unit = rest,
+            }
+            continue;
+        }
+
+        let Some((name, labels, value)) = parse_sample(line) else {
+            tracing::trace!(line, "Skipping malformed sample");
+            continue;
+        };
+        // Samples belong to the most recently described family if their names
+        // match. Otherwise, they are treated as an untyped family.
+        let idx = match families.last() {
+            Some(family) if family.suffix_of(name).is_some() => families.len() - 1,
+            _ => family_index(&mut families, name),
+        };
+        let family = &mut families[idx];
+        let suffix = &name[family.name.len()..];
+        family.samples.push(Sample {
+            suffix,
+            labels,
+            value,
+        });
+    }
+    families
+}
+
+fn family_index<'t>(families: &mut Vec<Family<'t>>, name: &'t str) -> usize {
+    if let Some(idx) = families.iter().rposition(|f| f.name == name) {
+        return idx;
+    }
+    families.push(Family {
+        name,
+        ..Default::default()
+    });
+    families.len() - 1
+}
+
+/// Parses a sample line into its name, labels, and value. Timestamps and
+/// exemplars are discarded.
+type Labels<'t> = Vec<(&'t str, Cow<'t, str>)>;
+
+fn parse_sample(line: &str) -> Option<(&str, Labels<'_>, f64)> {
+    let name_end = line.find(|c: char| c == '{' || c.is_ascii_whitespace())?;
+    let (name, mut rest) = line.split_at(name_end);
+    let mut labels = Vec::new();
+    if let Some(label_set) = rest.strip_prefix('{') {
+        rest = parse_labels(label_set, &mut labels)?;
+    }
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some((name, labels, value))
+}
+
+/// Parses labels up to the closing brace, returning the remainder of the line.
+fn parse_labels<'t>(s: &'t str, labels: &mut Labels<'t>) -> Option<&'t str> {
+    let mut rest = s.trim_start();
+    loop {
+        if let Some(rest) = rest.strip_prefix('}') {
+            return Some(rest);
+        }
+
+        let (key, value) = rest.split_once('=')?;
+        let value = value.trim_start().strip_prefix('"')?;
+        let mut unescaped = None::<String>;
+        let mut chars = value.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (i, '\\') => {
+                    let s = unescaped.get_or_insert_with(|| value[..i].to_string());
+                    match chars.next()?.1 {
+                        'n' => s.push('\n'),
+                        c => s.push(c),
+                    }
+                }
+                (_, c) => {
+                    if let Some(s) = unescaped.as_mut() {
+                        s.push(c);
+                    }
+                }
+            }
+        };
+        let unescaped = match unescaped {
+            Some(s) => Cow::Owned(s),
+            None => Cow::Borrowed(&value[..end]),
+        };
+        labels.push((key.trim(), unescaped));
+
+        rest = value[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+// === impl Kind ===
+
+impl Kind {
+    fn parse(s: &str) -> Self {
+        match s {
+            "counter" => Self::Counter,
+            "gauge" => Self::Gauge,
+            "histogram" => Self::Histogram,
+            "summary" => Self::Summary,
+            "info" => Self::Info,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+// === impl Family ===
+
+impl<'t> Family<'t> {
+    /// Returns the suffix of a sample's name if the sample belongs to this
+    /// family.
+    fn suffix_of<'n>(&self, name: &'n str) -> Option<&'n str> {
+        let suffix = name.strip_prefix(self.name)?;
+        let valid: &[&str] = match self.kind {
+            Kind::Counter => &["", "_total", "_created"],
+            Kind::Histogram => &["_bucket", "_sum", "_count", "_created"],
+            Kind::Summary => &["", "_sum", "_count", "_created"],
+            Kind::Info => &["_info"],
+            Kind::Gauge | Kind::Unknown => &[""],
+        };
+        valid.contains(&suffix).then_some(suffix)
+    }
+
+    fn into_metric(self, ts: Timestamps) -> Option<Metric> {
+        let data = match self.kind {
+            Kind::Counter => metric::Data::Sum(Sum {
+                data_points: self.number_points(ts),
+                aggregation_temporality: AggregationTemporality::Cumulative.into(),
+                is_monotonic: true,
+            }),
+            Kind::Histogram => metric::Data::Histogram(Histogram {
+                data_points: self.histogram_points(ts),
+                aggregation_temporality: AggregationTemporality::Cumulative.into(),
+            }),
+            Kind::Summary => metric::Data::Summary(Summary {
+                data_points: self.summary_points(ts),
+            }),
+            Kind::Gauge | Kind::Info | Kind::Unknown => metric::Data::Gauge(Gauge {
+                data_points: self.number_points(ts),
+            }),
+        };
+
+        let empty = match &data {
+            metric::Data::Sum(sum) => sum.data_points.is_empty(),
+            metric::Data::Gauge(gauge) => gauge.data_points.is_empty(),
+            metric::Data::Histogram(histogram) => histogram.data_points.is_empty(),
+            metric::Data::Summary(summary) => summary.data_points.is_empty(),
+            metric::Data::ExponentialHistogram(histogram) => histogram.data_points.is_empty(),
+        };
+        if empty {
+            return None;
+        }
+
+        Some(Metric {
+            name: self.name.to_string(),
+            description: self.help,
+            unit: self.unit.to_string(),
+            metadata: Vec::new(),
+            data: Some(data),
+        })
+    }
+
+    fn number_points(&self, ts: Timestamps) -> Vec<NumberDataPoint> {
+        self.samples
+            .iter()
+            .filter(|s| s.suffix != "_created")
+            .map(|s| NumberDataPoint {
+                attributes: attributes(s.labels.iter().map(|(k, v)| (*k, v.as_ref()))),
+                start_time_unix_nano: ts.start_time_unix_nano,
+                time_unix_nano: ts.time_unix_nano,
+                value: Some(number_data_point::Value::AsDouble(s.value)),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn histogram_points(&self, ts: Timestamps) -> Vec<HistogramDataPoint> {
+        #[derive(Default)]
+        struct Acc {
+            buckets: Vec<(f64, f64)>,
+            sum: Option<f64>,
+            count: Option<f64>,
+        }
+
+        self.grouped("le", |acc: &mut Acc, sample, le| {
+            match (sample.suffix, le) {
+                ("_bucket", Some(le)) => acc.buckets.push((le, sample.value)),
+                ("_sum", _) => acc.sum = Some(sample.value),
+                ("_count", _) => acc.count = Some(sample.value),
+                _ => {}
+            }
+        })
+        .into_iter()
+        .map(|(attributes, mut acc)| {
+            acc.buckets.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            // Prometheus buckets are cumulative, whereas OTLP buckets count
+            // only the values that fall within each bucket's bounds.
+            let mut explicit_bounds = Vec::with_capacity(acc.buckets.len());
+            let mut bucket_counts = Vec::with_capacity(acc.buckets.len() + 1);
+            let mut prior = 0.0;
+            for (le, cumulative) in &acc.buckets {
+                if le.is_finite() {
+                    explicit_bounds.push(*le);
+                }
+                bucket_counts.push((cumulative - prior).max(0.0) as u64);
+                prior = *cumulative;
+            }
+            let count = acc.count.unwrap_or(prior);
+            if bucket_counts.len() == explicit_bounds.len() {
+                // There's no +Inf bucket, so the overflow is derived from the
+                // total count.
+                bucket_counts.push((count - prior).max(0.0) as u64);
+            }
+
+            HistogramDataPoint {
+                attributes,
+                start_time_unix_nano: ts.start_time_unix_nano,
+                time_unix_nano: ts.time_unix_nano,
+                count: count as u64,
+                sum: acc.sum,
+                bucket_counts,
+                explicit_bounds,
+                ..Default::default()
+            }
+        })
+        .collect()
+    }
+
+    fn summary_points(&self, ts: Timestamps) -> Vec<SummaryDataPoint> {
+        self.grouped(
+            "quantile",
+            |point: &mut SummaryDataPoint, sample, quantile| match (sample.suffix, quantile) {
+                ("", Some(quantile)) => point.quantile_values.push(ValueAtQuantile {
+                    quantile,
+                    value: sample.value,
+                }),
+                ("_sum", _) => point.sum = sample.value,
+                ("_count", _) => point.count = sample.value as u64,
+                _ => {}
+            },
+        )
+        .into_iter()
+        .map(|(attributes, point)| SummaryDataPoint {
+            attributes,
+            start_time_unix_nano: ts.start_time_unix_nano,
+            time_unix_nano: ts.time_unix_nano,
+            ..point
+        })
+        .collect()
+    }
+
+    /// Groups samples by their labels, excluding `label`, whose value is
+    /// passed to `update` along with each sample in the group.
+    fn grouped<A: Default>(
+        &self,
+        label: &str,
+        mut update: impl FnMut(&mut A, &Sample<'t>, Option<f64>),
+    ) -> Vec<(Vec<KeyValue>, A)> {
+        let mut groups = Vec::<(Vec<(&str, &str)>, A)>::new();
+        for sample in &self.samples {
+            let mut value = None;
+            let mut key = Vec::with_capacity(sample.labels.len());
+            for (k, v) in &sample.labels {
+                if *k == label {
+                    value = v.parse().ok();
+                } else {
+                    key.push((*k, v.as_ref()));
+                }
+            }
+
+            let idx = match groups.iter().position(|(k, _)| *k == key) {
+                Some(idx) => idx,
+                None => {
+                    groups.push((key, A::default()));
+                    groups.len() - 1
+                }
+            };
+            update(&mut groups[idx].1, sample, value);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, acc)| (attributes(key), acc))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::proto::common::v1::any_value;
+
+    const TS: Timestamps = Timestamps {
+        start_time_unix_nano: 1,
+        time_unix_nano: 2,
+    };
+
+    fn labels(attributes: &[KeyValue]) -> Vec<(&str, &str)> {
+        attributes
+            .iter()
+            .map(
+                |kv| match kv.value.as_ref().and_then(|v| v.value.as_ref()) {
+                    Some(any_value::Value::StringValue(v)) => (kv.key.as_str(), v.as_str()),
+                    v => panic!("unexpected value: {v:?}"),
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn converts_openmetrics_families() {
+        let text = r#"# HELP outbound_http_route_request_duration_seconds The time between request initialization and response completion.
+# TYPE outbound_http_route_request_duration_seconds histogram
+# UNIT outbound_http_route_request_duration_seconds seconds
+outbound_http_route_request_duration_seconds_sum{route="a"} 0.5
+outbound_http_route_request_duration_seconds_count{route="a"} 2
+outbound_http_route_request_duration_seconds_bucket{le="0.1",route="a"} 1 # {trace_id="abc",span_id="def"} 0.05
+outbound_http_route_request_duration_seconds_bucket{le="1.0",route="a"} 2
+outbound_http_route_request_duration_seconds_bucket{le="+Inf",route="a"} 2
+# HELP control_identity_refreshes The total number of times this proxy's mTLS identity certificate has been refreshed.
+# TYPE control_identity_refreshes counter
+control_identity_refreshes_total{result="ok"} 1 # {trace_id="abc"} 1.0
+control_identity_refreshes_created{result="ok"} 1700000000.0
+# HELP proxy_build_info Proxy build info.
+# TYPE proxy_build_info info
+proxy_build_info_info{version="1.0"} 1
+# HELP latency A summary.
+# TYPE latency summary
+latency{quantile="0.5"} 3
+latency{quantile="0.99"} 9
+latency_sum 20
+latency_count 4
+# EOF
+"#;
+        let metrics = convert(text, TS);
+        assert_eq!(metrics.len(), 4);
+
+        let duration = &metrics[0];
+        assert_eq!(duration.unit, "seconds");
+        let Some(metric::Data::Histogram(histogram)) = &duration.data else {
+            panic!("expected a histogram: {duration:?}");
+        };
+        let point = &histogram.data_points[0];
+        assert_eq!(labels(&point.attributes), [("route", "a")]);
+        assert_eq!(point.explicit_bounds, [0.1, 1.0]);
+        assert_eq!(point.bucket_counts, [1, 1, 0]);
+
+        let Some(metric::Data::Sum(sum)) = &metrics[1].data else {
+            panic!("expected a sum: {:?}", metrics[1]);
+        };
+        assert_eq!(sum.data_points.len(), 1, "_created samples are ignored");
+
+        assert!(matches!(metrics[2].data, Some(metric::Data::Gauge(_))));
+
+        let Some(metric::Data::Summary(summary)) = &metrics[3].data else {
+            panic!("expected a summary: {:?}", metrics[3]);
+        };
+        let point = &summary.data_points[0];
+        assert_eq!(point.count, 4);
+        assert_eq!(point.sum, 20.0);
+        assert_eq!(
+            point.quantile_values,
+            [
+                ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 3.0
+                },
+                ValueAtQuantile {
+                    quantile: 0.99,
+                    value: 9.0
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_legacy_families() {
+        let text = r#"# HELP request_total Total count of HTTP requests.
+# TYPE request_total counter
+request_total{direction="inbound",authority="foo.ns.svc:8080"} 3
+request_total{direction="outbound",authority="a\"b\\c"} 1
+# HELP tcp_open_connections Number of currently-open connections.
+# TYPE tcp_open_connections gauge
+tcp_open_connections 2
+# HELP response_latency_ms Elapsed times between a request's headers being received
+# TYPE response_latency_ms histogram
+response_latency_ms_bucket{direction="inbound",le="1"} 1
+response_latency_ms_bucket{direction="inbound",le="10"} 3
+response_latency_ms_bucket{direction="inbound",le="+Inf"} 4
+response_latency_ms_count{direction="inbound"} 4
+response_latency_ms_sum{direction="inbound"} 120
+# HELP route_retryable_total Total count of retryable HTTP responses.
+# TYPE route_retryable_total counter
+"#;
+        let metrics = convert(text, TS);
+        assert_eq!(metrics.len(), 3, "empty families are not exported");
+
+        let requests = &metrics[0];
+        assert_eq!(requests.name, "request_total");
+        assert_eq!(requests.description, "Total count of HTTP requests.");
+        let Some(metric::Data::Sum(sum)) = &requests.data else {
+            panic!("expected a sum: {requests:?}");
+        };
+        assert!(sum.is_monotonic);
+        assert_eq!(sum.data_points.len(), 2);
+        assert_eq!(
+            labels(&sum.data_points[1].attributes),
+            [("direction", "outbound"), ("authority", "a\"b\\c")]
+        );
+        assert_eq!(
+            sum.data_points[1].value,
+            Some(number_data_point::Value::AsDouble(1.0))
+        );
+
+        let Some(metric::Data::Gauge(gauge)) = &metrics[1].data else {
+            panic!("expected a gauge: {:?}", metrics[1]);
+        };
+        assert!(labels(&gauge.data_points[0].attributes).is_empty());
+
+        let Some(metric::Data::Histogram(histogram)) = &metrics[2].data else {
+            panic!("expected a histogram: {:?}", metrics[2]);
+        };
+        let point = &histogram.data_points[0];
+        assert_eq!(labels(&point.attributes), [("direction", "inbound")]);
+        assert_eq!(point.explicit_bounds, [1.0, 10.0]);
+        assert_eq!(point.bucket_counts, [1, 2, 1]);
+        assert_eq!(point.count, 4);
+        assert_eq!(point.sum, Some(120.0));
+    }
+}
@@ -0,0 +1,195 @@
+use super::Header;
+use futures::prelude::*;
+use linkerd_addr::IpMatch;
+use linkerd_duplex::Duplex;
+use linkerd_error::{Error, Result};
+use linkerd_io::{self as io, PeerAddr};
+use linkerd_stack::{layer, Service};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::{debug, trace};
+
+/// Forwards a server-side connection to a client connection, first writing a
+/// v2 PROXY protocol header that describes the server-side connection's peer
+/// when the client connection targets one of the configured networks.
+#[derive(Clone, Debug)]
+pub struct Forward<C> {
+    connect: C,
+    networks: IpMatch,
+}
+
+impl<C> Forward<C> {
+    fn new(networks: IpMatch, connect: C) -> Self {
+        Self { connect, networks }
+    }
+
+    pub fn layer(networks: IpMatch) -> impl layer::Layer<C, Service = Self> + Clone {
+        layer::mk(move |connect| Self::new(networks.clone(), connect))
+    }
+}
+
+impl<C, I> Service<I> for Forward<C>
+where
+    I: io::AsyncRead + io::AsyncWrite + PeerAddr + Send + Unpin + 'static,
+    C: Service<()> + Send + 'static,
+    C::Error: Into<Error>,
+    C::Future: Send + 'static,
+    C::Response: io::AsyncRead + io::AsyncWrite + PeerAddr + Send + Unpin + 'static,
+{
+    type Response = ();
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.connect.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, src_io: I) -> Self::Future {
+        let networks = self.networks.clone();
+        let connect = self.connect.call(()).err_into::<Error>();
+        Box::pin(async move {
+            let mut dst_io = connect.await?;
+
+            if !networks.is_empty() {
+                let server = dst_io.peer_addr()?;
+                if networks.matches(server.ip()) {
+                    let header = Header::Proxied {
+                        client: src_io.peer_addr()?,
+                        server,
+                    };
+                    debug!(?header, "Writing PROXY protocol header");
+                    header.write(&mut dst_io).await?;
+                } else {
+                    trace!(%server, "Not writing PROXY protocol header");
+                }
+            }
+
+            Duplex::new(src_io, dst_io).err_into::<Error>().await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_io::{AsyncReadExt, AsyncWriteExt};
+    use linkerd_stack::{service_fn, ServiceExt};
+    use std::net::SocketAddr;
+
+    /// A stream with a fixed peer address.
+    struct Peer(tokio::io::DuplexStream, SocketAddr);
+
+    /// Forwards a connection from `client` to `server` and returns the bytes
+    /// that the server receives before the client's payload.
+    async fn forward(networks: IpMatch, client: &str, server: &str) -> Vec<u8> {
+        const MSG: &[u8] = b"hello";
+
+        let (mut client_io, src_io) = tokio::io::duplex(1024);
+        let (dst_io, mut server_io) = tokio::io::duplex(1024);
+        let mut dst_io = Some(Peer(dst_io, server.parse().unwrap()));
+        let connect = service_fn(move |()| {
+            let io = dst_io.take().expect("must only connect once");
+            future::ok::<_, Error>(io)
+        });
+        tokio::spawn(
+            Forward::new(networks, connect).oneshot(Peer(src_io, client.parse().unwrap())),
+        );
+
+        client_io.write_all(MSG).await.unwrap();
+        client_io.shutdown().await.unwrap();
+        let mut buf = Vec::new();
+        server_io.read_to_end(&mut buf).await.unwrap();
+        assert!(buf.ends_with(MSG), "payload must follow the header");
+        buf.truncate(buf.len() - MSG.len());
+        buf
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn writes_v2_tcp4() {
+        let networks = IpMatch::new(Some("198.51.100.0/24".parse().unwrap()));
+        let header = forward(networks, "192.0.2.1:56324", "198.51.100.2:443").await;
+        assert_eq!(
+            header,
+            [
+                &b"\r\n\r\n\0\r\nQUIT\n"[..],
+                // Version 2, PROXY command, TCP over IPv4, 12 address bytes.
+                &[0x21, 0x11, 0x00, 0x0c],
+                &[192, 0, 2, 1],
+                &[198, 51, 100, 2],
+                &56324u16.to_be_bytes(),
+                &443u16.to_be_bytes(),
+            ]
+            .concat()
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn writes_v2_tcp6() {
+        let networks = IpMatch::new(Some("2001:db8::/32".parse().unwrap()));
+        let header = forward(networks, "[2001:db8::1]:56324", "[2001:db8::2]:443").await;
+        let client = "2001:db8::1".parse::<std::net::Ipv6Addr>().unwrap();
+        let server = "2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap();
+        assert_eq!(
+            header,
+            [
+                &b"\r\n\r\n\0\r\nQUIT\n"[..],
+                // Version 2, PROXY command, TCP over IPv6, 36 address bytes.
+                &[0x21, 0x21, 0x00, 0x24],
+                &client.octets(),
+                &server.octets(),
+                &56324u16.to_be_bytes(),
+                &443u16.to_be_bytes(),
+            ]
+            .concat()
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn skips_unmatched_servers() {
+        let networks = IpMatch::new(Some("203.0.113.0/24".parse().unwrap()));
+        let header = forward(networks, "192.0.2.1:56324", "198.51.100.2:443").await;
+        assert!(header.is_empty(), "must not write a header");
+
+        let header = forward(IpMatch::new(None), "192.0.2.1:56324", "198.51.100.2:443").await;
+        assert!(header.is_empty(), "must not write a header");
+    }
+
+    // === impl Peer ===
+
+    impl io::AsyncRead for Peer {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut io::ReadBuf<'_>,
+        ) -> io::Poll<()> {
+            Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    impl io::AsyncWrite for Peer {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> io::Poll<usize> {
+            Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> io::Poll<()> {
+            Pin::new(&mut self.0).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> io::Poll<()> {
+            Pin::new(&mut self.0).poll_shutdown(cx)
+        }
+    }
+
+    impl PeerAddr for Peer {
+        fn peer_addr(&self) -> io::Result<SocketAddr> {
+            Ok(self.1)
+        }
+    }
+}
@@ -0,0 +1,464 @@
+//! Support for the HAProxy [PROXY protocol][spec].
+//!
+//! Load balancers may prefix a connection with a PROXY protocol header that
+//! describes the connection's original client and server addresses. Both the
+//! human-readable (v1) and binary (v2) forms of the header may be decoded; only
+//! the binary form is encoded.
+//!
+//! [spec]: https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt
+
+#![deny(rust_2018_idioms, clippy::disallowed_methods, clippy::disallowed_types)]
+#![forbid(unsafe_code)]
+
+mod client;
+mod server;
+
+pub use self::{
+    client::Forward,
+    server::{DetectHeader, Io, NewDetectHeader, Timeout},
+};
+use bytes::{Buf, BufMut, BytesMut};
+use linkerd_io::{self as io, AsyncReadExt, AsyncWriteExt};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use tracing::trace;
+
+/// A decoded PROXY protocol header.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Header {
+    /// The connection was relayed on behalf of a client.
+    Proxied {
+        client: SocketAddr,
+        server: SocketAddr,
+    },
+
+    /// The connection was initiated by the sender itself (e.g. for a health
+    /// check), or it described an address family that is not supported.
+    Local,
+}
+
+/// The signature that begins every v2 header.
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The length of the fixed portion of a v2 header, including the signature.
+const V2_PREFIX_LEN: usize = V2_SIGNATURE.len() + 4;
+
+/// The prefix that begins every v1 header.
+const V1_PREFIX: &[u8] = b"PROXY ";
+
+/// The maximum length of a v1 header, including the trailing CRLF.
+const V1_MAX_LEN: usize = 107;
+
+const V2_VERSION: u8 = 0x20;
+const V2_CMD_LOCAL: u8 = 0x00;
+const V2_CMD_PROXY: u8 = 0x01;
+
+const V2_FAMILY_UNSPEC: u8 = 0x00;
+const V2_FAMILY_TCP4: u8 = 0x11;
+const V2_FAMILY_UDP4: u8 = 0x12;
+const V2_FAMILY_TCP6: u8 = 0x21;
+const V2_FAMILY_UDP6: u8 = 0x22;
+
+const V2_ADDRS_IPV4_LEN: usize = 12;
+const V2_ADDRS_IPV6_LEN: usize = 36;
+
+/// The outcome of attempting to decode a header from a buffer.
+#[derive(Debug, PartialEq, Eq)]
+enum Decoded {
+    /// A header was decoded from the first `usize` bytes of the buffer.
+    Header(Header, usize),
+
+    /// The buffer does not begin with a PROXY protocol header.
+    NotPresent,
+
+    /// More data is needed to determine whether a header is present.
+    Incomplete,
+}
+
+// === impl Header ===
+
+impl Header {
+    /// Returns the original client address, if one was conveyed.
+    pub fn client_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Self::Proxied { client, .. } => Some(*client),
+            Self::Local => None,
+        }
+    }
+
+    /// Returns the original server address, if one was conveyed.
+    pub fn server_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Self::Proxied { server, .. } => Some(*server),
+            Self::Local => None,
+        }
+    }
+
+    /// Writes a v2 header to the provided I/O stream.
+    pub async fn write(&self, io: &mut (impl io::AsyncWrite + Unpin)) -> io::Result<usize> {
+        let mut buf = BytesMut::with_capacity(V2_PREFIX_LEN + V2_ADDRS_IPV6_LEN);
+        self.encode_v2(&mut buf);
+        let sz = buf.len();
+        io.write_all(&buf).await?;
+        trace!(written = sz, "Wrote PROXY protocol header");
+        Ok(sz)
+    }
+
+    /// Encodes a v2 header to the provided buffer.
+    ///
+    /// If the client and server addresses are of different families, IPv4
+    /// addresses are encoded as IPv4-mapped IPv6 addresses.
+    pub fn encode_v2(&self, buf: &mut BytesMut) {
+        buf.reserve(V2_PREFIX_LEN + V2_ADDRS_IPV6_LEN);
+        buf.put_slice(V2_SIGNATURE);
+        let (client, server) = match self {
+            Self::Local => {
+                buf.put_u8(V2_VERSION | V2_CMD_LOCAL);
+                buf.put_u8(V2_FAMILY_UNSPEC);
+                buf.put_u16(0);
+                return;
+            }
+            Self::Proxied { client, server } => (*client, *server),
+        };
+
+        buf.put_u8(V2_VERSION | V2_CMD_PROXY);
+        match (client.ip(), server.ip()) {
+            (IpAddr::V4(c), IpAddr::V4(s)) => {
+                buf.put_u8(V2_FAMILY_TCP4);
+                buf.put_u16(V2_ADDRS_IPV4_LEN as u16);
+                buf.put_slice(&c.octets());
+                buf.put_slice(&s.octets());
+            }
+            (c, s) => {
+                buf.put_u8(V2_FAMILY_TCP6);
+                buf.put_u16(V2_ADDRS_IPV6_LEN as u16);
+                buf.put_slice(&to_ipv6(c).octets());
+                buf.put_slice(&to_ipv6(s).octets());
+            }
+        }
+        buf.put_u16(client.port());
+        buf.put_u16(server.port());
+    }
+
+    /// Attempts to decode a header from an I/O stream.
+    ///
+    /// Bytes are read into the caller-provided buffer until it can be
+    /// determined whether a header is present. If a header is decoded, its
+    /// bytes are removed from the buffer; any remaining bytes must be replayed
+    /// to the inner stream. If no header is present, all bytes that were read
+    /// are left in the buffer.
+    ///
+    /// An I/O error is returned if the header is invalid.
+    async fn read(
+        io: &mut (impl io::AsyncRead + Unpin),
+        buf: &mut BytesMut,
+    ) -> io::Result<Option<Self>> {
+        loop {
+            match Self::decode(buf.as_ref())? {
+                Decoded::Header(header, len) => {
+                    buf.advance(len);
+                    return Ok(Some(header));
+                }
+                Decoded::NotPresent => return Ok(None),
+                Decoded::Incomplete => {
+                    if io.read_buf(buf).await? == 0 {
+                        // The stream closed before a complete header was read,
+                        // so there's nothing more to decode.
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode(buf: &[u8]) -> io::Result<Decoded> {
+        if buf.starts_with(V2_SIGNATURE) {
+            return Self::decode_v2(buf);
+        }
+        if buf.starts_with(V1_PREFIX) {
+            return Self::decode_v1(buf);
+        }
+
+        // If the buffer is a prefix of either signature, more data is needed.
+        if V2_SIGNATURE.starts_with(buf) || V1_PREFIX.starts_with(buf) {
+            return Ok(Decoded::Incomplete);
+        }
+
+        Ok(Decoded::NotPresent)
+    }
+
+    fn decode_v1(buf: &[u8]) -> io::Result<Decoded> {
+        let Some(end) = buf.windows(2).position(|w| w == b"\r\n") else {
+            if buf.len() >= V1_MAX_LEN {
+                return Err(invalid("PROXY protocol v1 header is too long"));
+            }
+            return Ok(Decoded::Incomplete);
+        };
+        if end + 2 > V1_MAX_LEN {
+            return Err(invalid("PROXY protocol v1 header is too long"));
+        }
+
+        let line = std::str::from_utf8(&buf[V1_PREFIX.len()..end])
+            .map_err(|_| invalid("PROXY protocol v1 header is not valid ASCII"))?;
+        let mut parts = line.split(' ');
+        let header = match parts.next() {
+            Some("TCP4") | Some("TCP6") => {
+                let mut next = || {
+                    parts
+                        .next()
+                        .ok_or_else(|| invalid("PROXY protocol v1 header is truncated"))
+                };
+                let client_ip = next()?.parse::<IpAddr>();
+                let server_ip = next()?.parse::<IpAddr>();
+                let client_port = next()?.parse::<u16>();
+                let server_port = next()?.parse::<u16>();
+                match (client_ip, server_ip, client_port, server_port) {
+                    (Ok(cip), Ok(sip), Ok(cport), Ok(sport)) => Header::Proxied {
+                        client: SocketAddr::new(cip, cport),
+                        server: SocketAddr::new(sip, sport),
+                    },
+                    _ => return Err(invalid("PROXY protocol v1 header has invalid addresses")),
+                }
+            }
+            // The remainder of the line must be ignored for unknown protocols.
+            Some("UNKNOWN") => return Ok(Decoded::Header(Header::Local, end + 2)),
+            _ => return Err(invalid("PROXY protocol v1 header has an invalid protocol")),
+        };
+        if parts.next().is_some() {
+            return Err(invalid("PROXY protocol v1 header has trailing fields"));
+        }
+
+        Ok(Decoded::Header(header, end + 2))
+    }
+
+    fn decode_v2(buf: &[u8]) -> io::Result<Decoded> {
+        if buf.len() < V2_PREFIX_LEN {
+            return Ok(Decoded::Incomplete);
+        }
+
+        let ver_cmd = buf[V2_SIGNATURE.len()];
+        if ver_cmd & 0xF0 != V2_VERSION {
+            return Err(invalid("PROXY protocol v2 header has an invalid version"));
+        }
+        let family = buf[V2_SIGNATURE.len() + 1];
+        let len = u16::from_be_bytes([buf[V2_SIGNATURE.len() + 2], buf[V2_SIGNATURE.len() + 3]]);
+        let total = V2_PREFIX_LEN + len as usize;
+        if buf.len() < total {
+            return Ok(Decoded::Incomplete);
+        }
+
+        // Any type-length-value extensions following the addresses are
+        // ignored.
+        let mut addrs = &buf[V2_PREFIX_LEN..total];
+        let header = match ver_cmd & 0x0F {
+            V2_CMD_LOCAL => Header::Local,
+            V2_CMD_PROXY => match family {
+                V2_FAMILY_TCP4 | V2_FAMILY_UDP4 => {
+                    if addrs.len() < V2_ADDRS_IPV4_LEN {
+                        return Err(invalid("PROXY protocol v2 header is truncated"));
+                    }
+                    let client = Ipv4Addr::from(addrs.get_u32());
+                    let server = Ipv4Addr::from(addrs.get_u32());
+                    Header::Proxied {
+                        client: SocketAddr::new(client.into(), addrs.get_u16()),
+                        server: SocketAddr::new(server.into(), addrs.get_u16()),
+                    }
+                }
+                V2_FAMILY_TCP6 | V2_FAMILY_UDP6 => {
+                    if addrs.len() < V2_ADDRS_IPV6_LEN {
+                        return Err(invalid("PROXY protocol v2 header is truncated"));
+                    }
+                    let client = Ipv6Addr::from(addrs.get_u128());
+                    let server = Ipv6Addr::from(addrs.get_u128());
+                    Header::Proxied {
+                        client: SocketAddr::new(client.into(), addrs.get_u16()),
+                        server: SocketAddr::new(server.into(), addrs.get_u16()),
+                    }
+                }
+                // Unix socket and unspecified addresses can't be used to
+                // describe the client, so they are treated as local.
+                _ => Header::Local,
+            },
+            _ => return Err(invalid("PROXY protocol v2 header has an invalid command")),
+        };
+
+        Ok(Decoded::Header(header, total))
+    }
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxied(client: &str, server: &str) -> Header {
+        Header::Proxied {
+            client: client.parse().unwrap(),
+            server: server.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn decode_v1_tcp4() {
+        let buf = b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 443\r\nGET /";
+        assert_eq!(
+            Header::decode(buf).unwrap(),
+            Decoded::Header(proxied("192.0.2.1:56324", "198.51.100.2:443"), 45),
+        );
+    }
+
+    #[test]
+    fn decode_v1_tcp6() {
+        let buf = b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n";
+        assert_eq!(
+            Header::decode(buf).unwrap(),
+            Decoded::Header(
+                proxied("[2001:db8::1]:56324", "[2001:db8::2]:443"),
+                buf.len()
+            ),
+        );
+    }
+
+    #[test]
+    fn decode_v1_unknown() {
+        let buf = b"PROXY UNKNOWN ffff::1 ffff::2 1 2\r\n";
+        assert_eq!(
+            Header::decode(buf).unwrap(),
+            Decoded::Header(Header::Local, buf.len()),
+        );
+    }
+
+    #[test]
+    fn decode_v1_invalid() {
+        assert!(Header::decode(b"PROXY TCP4 192.0.2.1 56324 443\r\n").is_err());
+        assert!(Header::decode(b"PROXY UDP4 192.0.2.1 198.51.100.2 1 2\r\n").is_err());
+        assert!(Header::decode(&[V1_PREFIX, &[b'1'; 120][..]].concat()).is_err());
+    }
+
+    #[test]
+    fn decode_incomplete() {
+        assert_eq!(Header::decode(b"").unwrap(), Decoded::Incomplete);
+        assert_eq!(Header::decode(b"PRO").unwrap(), Decoded::Incomplete);
+        assert_eq!(
+            Header::decode(b"PROXY TCP4 192.0.2.1").unwrap(),
+            Decoded::Incomplete
+        );
+        assert_eq!(Header::decode(b"\r\n\r\n\0").unwrap(), Decoded::Incomplete);
+    }
+
+    #[test]
+    fn decode_not_present() {
+        assert_eq!(
+            Header::decode(b"GET / HTTP/1.1\r\n").unwrap(),
+            Decoded::NotPresent
+        );
+        assert_eq!(
+            Header::decode(b"\x16\x03\x01").unwrap(),
+            Decoded::NotPresent
+        );
+        // A request that looks like it starts with a v2 signature.
+        assert_eq!(Header::decode(b"\r\n\r\nfoo").unwrap(), Decoded::NotPresent);
+    }
+
+    #[test]
+    fn roundtrip_v2() {
+        for header in [
+            proxied("192.0.2.1:56324", "198.51.100.2:443"),
+            proxied("[2001:db8::1]:56324", "[2001:db8::2]:443"),
+            Header::Local,
+        ] {
+            let mut buf = BytesMut::new();
+            header.encode_v2(&mut buf);
+            let len = buf.len();
+            buf.put_slice(b"12345");
+            assert_eq!(
+                Header::decode(buf.as_ref()).unwrap(),
+                Decoded::Header(header, len)
+            );
+        }
+    }
+
+    #[test]
+    fn encode_v2_mixed_families() {
+        let mut buf = BytesMut::new();
+        proxied("192.0.2.1:56324", "[2001:db8::2]:443").encode_v2(&mut buf);
+        assert_eq!(
+            Header::decode(buf.as_ref()).unwrap(),
+            Decoded::Header(
+                proxied("[::ffff:192.0.2.1]:56324", "[2001:db8::2]:443"),
+                buf.len()
+            ),
+        );
+    }
+
+    #[test]
+    fn decode_v2_ignores_tlvs() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(V2_SIGNATURE);
+        buf.put_u8(V2_VERSION | V2_CMD_PROXY);
+        buf.put_u8(V2_FAMILY_TCP4);
+        buf.put_u16(V2_ADDRS_IPV4_LEN as u16 + 4);
+        buf.put_slice(&[192, 0, 2, 1, 198, 51, 100, 2]);
+        buf.put_u16(56324);
+        buf.put_u16(443);
+        // A NOOP TLV.
+        buf.put_slice(&[0x04, 0x00, 0x01, 0x00]);
+        assert_eq!(
+            Header::decode(buf.as_ref()).unwrap(),
+            Decoded::Header(proxied("192.0.2.1:56324", "198.51.100.2:443"), buf.len()),
+        );
+    }
+
+    #[test]
+    fn decode_v2_invalid() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(V2_SIGNATURE);
+        buf.put_u8(0x11);
+        buf.put_u8(V2_FAMILY_TCP4);
+        buf.put_u16(0);
+        assert!(Header::decode(buf.as_ref()).is_err());
+
+        let mut buf = BytesMut::new();
+        buf.put_slice(V2_SIGNATURE);
+        buf.put_u8(V2_VERSION | V2_CMD_PROXY);
+        buf.put_u8(V2_FAMILY_TCP6);
+        buf.put_u16(V2_ADDRS_IPV4_LEN as u16);
+        buf.put_slice(&[0; V2_ADDRS_IPV4_LEN]);
+        assert!(Header::decode(buf.as_ref()).is_err());
+    }
+
+    #[tokio::test]
+    async fn read_many() {
+        let (mut rx, _tx) = tokio_test::io::Builder::new()
+            .read(b"PROXY TCP4 ")
+            .read(b"192.0.2.1 198.51.100.2 ")
+            .read(b"56324 443\r")
+            .read(b"\nGET / HTTP/1.1\r\n")
+            .build_with_handle();
+        let mut buf = BytesMut::new();
+        let header = Header::read(&mut rx, &mut buf).await.expect("must decode");
+        assert_eq!(header, Some(proxied("192.0.2.1:56324", "198.51.100.2:443")));
+        assert_eq!(buf.as_ref(), b"GET / HTTP/1.1\r\n");
+    }
+
+    #[tokio::test]
+    async fn read_not_present() {
+        const MSG: &[u8] = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n";
+        let (mut rx, _tx) = tokio_test::io::Builder::new().read(MSG).build_with_handle();
+        let mut buf = BytesMut::new();
+        let header = Header::read(&mut rx, &mut buf)
+            .await
+            .expect("must not fail");
+        assert!(header.is_none());
+        assert_eq!(buf.as_ref(), MSG);
+    }
+}
@@ -0,0 +1,178 @@
+use super::Header;
+use bytes::BytesMut;
+use linkerd_error::{Error, Result};
+use linkerd_io::{self as io, EitherIo, PrefixedIo};
+use linkerd_stack::{layer, ExtractParam, NewService, Service, ServiceExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::time;
+use tracing::{debug, trace};
+
+/// A param type indicating that connections may be prefixed by a PROXY protocol
+/// header and how long the server should wait to determine whether one is
+/// present.
+///
+/// When no timeout is configured for a target, headers are not detected.
+#[derive(Copy, Clone, Debug)]
+pub struct Timeout(pub time::Duration);
+
+pub type Io<I> = EitherIo<I, PrefixedIo<I>>;
+
+/// Builds services that decode a PROXY protocol header from the start of a
+/// connection, when one is present.
+#[derive(Clone, Debug)]
+pub struct NewDetectHeader<X, N> {
+    inner: N,
+    params: X,
+}
+
+#[derive(Clone, Debug)]
+pub struct DetectHeader<T, N> {
+    target: T,
+    timeout: Option<Timeout>,
+    inner: N,
+}
+
+// A v1 header is at most 107 bytes and a v2 header carrying IPv6 addresses is
+// 52 bytes, so this is sufficient for all headers without extensions.
+const BUFFER_CAPACITY: usize = 256;
+
+impl<X, N> NewDetectHeader<X, N> {
+    pub fn new(params: X, inner: N) -> Self {
+        Self { inner, params }
+    }
+
+    pub fn layer(params: X) -> impl layer::Layer<N, Service = Self> + Clone
+    where
+        X: Clone,
+    {
+        layer::mk(move |inner| Self::new(params.clone(), inner))
+    }
+}
+
+impl<T, X, N> NewService<T> for NewDetectHeader<X, N>
+where
+    X: ExtractParam<Option<Timeout>, T>,
+    N: Clone,
+{
+    type Service = DetectHeader<T, N>;
+
+    fn new_service(&self, target: T) -> Self::Service {
+        DetectHeader {
+            timeout: self.params.extract_param(&target),
+            target,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, I, N, S> Service<I> for DetectHeader<T, N>
+where
+    T: Clone + Send + 'static,
+    I: io::AsyncRead + Send + Unpin + 'static,
+    N: NewService<(Option<Header>, T), Service = S> + Clone + Send + 'static,
+    S: Service<Io<I>> + Send,
+    S::Error: Into<Error>,
+    S::Future: Send,
+{
+    type Response = S::Response;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response>> + Send + 'static>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut io: I) -> Self::Future {
+        let target = self.target.clone();
+        let inner = self.inner.clone();
+        let timeout = self.timeout;
+        Box::pin(async move {
+            let Some(Timeout(timeout)) = timeout else {
+                trace!("PROXY protocol detection disabled");
+                let svc = inner.new_service((None, target));
+                return svc.oneshot(EitherIo::Left(io)).await.map_err(Into::into);
+            };
+
+            trace!("Reading PROXY protocol header");
+            let mut buf = BytesMut::with_capacity(BUFFER_CAPACITY);
+            let header = match time::timeout(timeout, Header::read(&mut io, &mut buf)).await {
+                Ok(res) => res?,
+                Err(_) => {
+                    // Clients that wait for the server to speak first never
+                    // send enough data to determine whether a header is
+                    // present, so the connection is processed as-is.
+                    debug!("PROXY protocol header detection timed out");
+                    None
+                }
+            };
+            debug!(?header, "Read PROXY protocol header");
+
+            let svc = inner.new_service((header, target));
+            svc.oneshot(EitherIo::Right(PrefixedIo::new(buf.freeze(), io)))
+                .await
+                .map_err(Into::into)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_io::AsyncReadExt;
+    use linkerd_stack::service_fn;
+
+    type Detected = (Option<Header>, Vec<u8>);
+
+    async fn detect(timeout: Option<Timeout>, io: tokio_test::io::Mock) -> Detected {
+        let new_inner = |(header, ()): (Option<Header>, ())| {
+            service_fn(move |mut io: Io<tokio_test::io::Mock>| async move {
+                let mut buf = Vec::new();
+                io.read_to_end(&mut buf).await?;
+                Ok::<_, Error>((header, buf))
+            })
+        };
+        NewDetectHeader::new(move |_: &()| timeout, new_inner)
+            .new_service(())
+            .oneshot(io)
+            .await
+            .expect("must succeed")
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn detects_header() {
+        let io = tokio_test::io::Builder::new()
+            .read(b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 443\r\nhello")
+            .build();
+        let (header, buf) = detect(Some(Timeout(time::Duration::from_secs(1))), io).await;
+        assert_eq!(
+            header,
+            Some(Header::Proxied {
+                client: ([192, 0, 2, 1], 56324).into(),
+                server: ([198, 51, 100, 2], 443).into(),
+            })
+        );
+        assert_eq!(buf, b"hello");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn passes_through_without_header() {
+        let io = tokio_test::io::Builder::new().read(b"hello").build();
+        let (header, buf) = detect(Some(Timeout(time::Duration::from_secs(1))), io).await;
+        assert_eq!(header, None);
+        assert_eq!(buf, b"hello");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn disabled() {
+        const MSG: &[u8] = b"PROXY TCP4 192.0.2.1 198.51.100.2 56324 443\r\n";
+        let io = tokio_test::io::Builder::new().read(MSG).build();
+        let (header, buf) = detect(None, io).await;
+        assert_eq!(header, None);
+        assert_eq!(buf, MSG);
+    }
+}
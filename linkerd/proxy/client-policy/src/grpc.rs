@@ -186,7 +186,7 @@ pub mod proto {
 
     impl Grpc {
         pub fn try_from(
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
             proto: outbound::proxy_protocol::Grpc,
         ) -> Result<Self, InvalidGrpcRoute> {
             let routes = proto
@@ -210,7 +210,7 @@ pub mod proto {
     }
 
     fn try_route(
-        overrides: ClientPolicyOverrides,
+        overrides: &ClientPolicyOverrides,
         proto: outbound::GrpcRoute,
     ) -> Result<Route, InvalidGrpcRoute> {
         let outbound::GrpcRoute {
@@ -238,7 +238,7 @@ pub mod proto {
 
     fn try_rule(
         meta: &Arc<Meta>,
-        overrides: ClientPolicyOverrides,
+        overrides: &ClientPolicyOverrides,
         proto: outbound::grpc_route::Rule,
     ) -> Result<Rule, InvalidGrpcRoute> {
         #[allow(deprecated)]
@@ -262,9 +262,10 @@ pub mod proto {
             .map(Filter::try_from)
             .collect::<Result<Arc<[_]>, _>>()?;
 
-        let distribution = backends
-            .ok_or(InvalidGrpcRoute::Missing("distribution"))?
-            .try_into()?;
+        let distribution = RouteDistribution::try_from(
+            backends.ok_or(InvalidGrpcRoute::Missing("distribution"))?,
+        )?
+        .originate_tls(overrides);
 
         let mut params =
             RouteParams::try_from_proto(timeouts, retry, allow_l5d_request_headers, overrides)?;
@@ -287,7 +288,7 @@ pub mod proto {
             timeouts: Option<linkerd2_proxy_api::http_route::Timeouts>,
             retry: Option<grpc_route::Retry>,
             allow_l5d_request_headers: bool,
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
         ) -> Result<Self, InvalidGrpcRoute> {
             Ok(Self {
                 retry: retry.map(Retry::try_from).transpose()?,
@@ -1,7 +1,8 @@
 use crate::FailureAccrual;
 use linkerd_exp_backoff::ExponentialBackoff;
 use linkerd_http_route::http;
-use std::{ops::RangeInclusive, sync::Arc, time};
+use std::{hash::Hash, ops::RangeInclusive, sync::Arc, time};
+use tokio::sync::watch;
 
 pub use linkerd_http_route::http::{filter, find, r#match, RouteMatch};
 
@@ -41,9 +42,31 @@ pub enum Filter {
     Redirect(filter::RedirectRequest),
     RequestHeaders(filter::ModifyHeader),
     ResponseHeaders(filter::ModifyHeader),
+    InjectJwtSvid(InjectJwtSvid),
     InternalError(&'static str),
 }
 
+/// Sets each request's `Authorization` header to a bearer JWT-SVID issued for
+/// an audience.
+///
+/// Unlike other filters, this is configured locally rather than by the policy
+/// controller. See [`crate::ClientPolicyOverrides`].
+#[derive(Clone, Debug)]
+pub struct InjectJwtSvid {
+    pub audience: Arc<str>,
+
+    /// Holds the `Authorization` header value for the audience's current
+    /// JWT-SVID, if one is available.
+    pub authorization: watch::Receiver<Option<::http::HeaderValue>>,
+}
+
+/// Identifies an HTTP route resource.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RouteName {
+    pub namespace: String,
+    pub name: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Retry {
     pub max_retries: u16,
@@ -100,6 +123,22 @@ impl Default for Http2 {
     }
 }
 
+// === impl InjectJwtSvid ===
+
+impl PartialEq for InjectJwtSvid {
+    fn eq(&self, other: &Self) -> bool {
+        self.audience == other.audience && self.authorization.same_channel(&other.authorization)
+    }
+}
+
+impl Eq for InjectJwtSvid {}
+
+impl Hash for InjectJwtSvid {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.audience.hash(state);
+    }
+}
+
 // === impl StatusRanges ===
 
 impl StatusRanges {
@@ -219,7 +258,7 @@ pub mod proto {
 
     impl Http1 {
         pub fn try_from(
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
             proto: outbound::proxy_protocol::Http1,
         ) -> Result<Self, InvalidHttpRoute> {
             let routes = proto
@@ -236,7 +275,7 @@ pub mod proto {
 
     impl Http2 {
         pub fn try_from(
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
             proto: outbound::proxy_protocol::Http2,
         ) -> Result<Self, InvalidHttpRoute> {
             let routes = proto
@@ -252,7 +291,7 @@ pub mod proto {
     }
 
     fn try_route(
-        overrides: ClientPolicyOverrides,
+        overrides: &ClientPolicyOverrides,
         proto: outbound::HttpRoute,
     ) -> Result<Route, InvalidHttpRoute> {
         let outbound::HttpRoute {
@@ -280,7 +319,7 @@ pub mod proto {
 
     fn try_rule(
         meta: &Arc<Meta>,
-        overrides: ClientPolicyOverrides,
+        overrides: &ClientPolicyOverrides,
         proto: outbound::http_route::Rule,
     ) -> Result<Rule, InvalidHttpRoute> {
         #[allow(deprecated)]
@@ -299,14 +338,25 @@ pub mod proto {
             .map(r#match::MatchRequest::try_from)
             .collect::<Result<Vec<_>, InvalidRouteMatch>>()?;
 
+        let jwt_svid = match &**meta {
+            Meta::Resource {
+                namespace, name, ..
+            } => overrides.jwt_svid_routes.get(&RouteName {
+                namespace: namespace.clone(),
+                name: name.clone(),
+            }),
+            Meta::Default { .. } => None,
+        };
         let filters = filters
             .into_iter()
             .map(Filter::try_from)
+            .chain(jwt_svid.cloned().map(|f| Ok(Filter::InjectJwtSvid(f))))
             .collect::<Result<Arc<[_]>, _>>()?;
 
-        let distribution = backends
-            .ok_or(InvalidHttpRoute::Missing("distribution"))?
-            .try_into()?;
+        let distribution = RouteDistribution::try_from(
+            backends.ok_or(InvalidHttpRoute::Missing("distribution"))?,
+        )?
+        .originate_tls(overrides);
 
         let mut params =
             RouteParams::try_from_proto(timeouts, retry, allow_l5d_request_headers, overrides)?;
@@ -329,7 +379,7 @@ pub mod proto {
             timeouts: Option<linkerd2_proxy_api::http_route::Timeouts>,
             retry: Option<http_route::Retry>,
             allow_l5d_request_headers: bool,
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
         ) -> Result<Self, InvalidHttpRoute> {
             Ok(Self {
                 retry: retry.map(Retry::try_from).transpose()?,
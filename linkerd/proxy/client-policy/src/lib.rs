@@ -2,7 +2,10 @@
 #![forbid(unsafe_code)]
 
 use once_cell::sync::Lazy;
-use std::{borrow::Cow, fmt, hash::Hash, net::SocketAddr, num::NonZeroU16, sync::Arc, time};
+use std::{
+    borrow::Cow, collections::HashMap, fmt, hash::Hash, net::SocketAddr, num::NonZeroU16,
+    sync::Arc, time,
+};
 
 pub mod grpc;
 pub mod http;
@@ -19,9 +22,22 @@ pub struct ClientPolicy {
     pub backends: Arc<[Backend]>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// Proxy-local configuration applied to the policies discovered from the
+/// policy controller.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ClientPolicyOverrides {
     pub export_hostname_labels: bool,
+
+    /// JWT-SVID injection filters, appended to each rule of the named HTTP
+    /// routes.
+    pub jwt_svid_routes: Arc<HashMap<http::RouteName, http::InjectJwtSvid>>,
+
+    /// TLS origination, configured on the named backends of HTTP and gRPC
+    /// routes.
+    ///
+    /// The policy API cannot yet describe backends outside of the mesh, so
+    /// origination is configured locally until it can.
+    pub tls_origination_backends: Arc<HashMap<BackendName, TlsOrigination>>,
 }
 
 // TODO additional server configs (e.g. concurrency limits, window sizes, etc)
@@ -90,6 +106,27 @@ pub struct Backend {
     pub meta: Arc<Meta>,
     pub queue: Queue,
     pub dispatcher: BackendDispatcher,
+
+    /// Only honored by HTTP backends.
+    pub tls_origination: Option<TlsOrigination>,
+}
+
+/// Identifies a backend resource.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BackendName {
+    pub namespace: String,
+    pub name: String,
+}
+
+/// Configures the proxy to originate TLS to a backend outside of the mesh.
+///
+/// Applications send plaintext to these backends and the proxy upgrades
+/// connections to TLS, authenticating servers with web PKI rather than mesh
+/// identity.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TlsOrigination {
+    /// The name for which the backend's servers must present a certificate.
+    pub server_name: linkerd_dns_name::Name,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -437,7 +474,7 @@ pub mod proto {
 
     impl ClientPolicy {
         pub fn try_from(
-            overrides: ClientPolicyOverrides,
+            overrides: &ClientPolicyOverrides,
             policy: outbound::OutboundPolicy,
         ) -> Result<Self, InvalidPolicy> {
             use outbound::proxy_protocol;
@@ -609,6 +646,45 @@ pub mod proto {
         }
     }
 
+    impl<T: Clone> RouteDistribution<T> {
+        /// Configures TLS origination on the distribution's backends, as
+        /// overridden by the proxy's configuration.
+        pub(crate) fn originate_tls(self, overrides: &ClientPolicyOverrides) -> Self {
+            let backends = &overrides.tls_origination_backends;
+            if backends.is_empty() {
+                return self;
+            }
+
+            let originate = |mut rb: RouteBackend<T>| {
+                rb.backend.tls_origination = match &*rb.backend.meta {
+                    Meta::Resource {
+                        namespace, name, ..
+                    } => backends
+                        .get(&BackendName {
+                            namespace: namespace.clone(),
+                            name: name.clone(),
+                        })
+                        .cloned(),
+                    Meta::Default { .. } => None,
+                };
+                rb
+            };
+            match self {
+                Self::Empty => Self::Empty,
+                Self::FirstAvailable(backends) => {
+                    Self::FirstAvailable(backends.iter().cloned().map(originate).collect())
+                }
+                Self::RandomAvailable(backends) => Self::RandomAvailable(
+                    backends
+                        .iter()
+                        .cloned()
+                        .map(|(rb, weight)| (originate(rb), weight))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
     // === impl RouteBackend ===
 
     impl<T> RouteBackend<T> {
@@ -700,6 +776,7 @@ pub mod proto {
                 queue,
                 dispatcher,
                 meta,
+                tls_origination: None,
             };
 
             Ok(backend)
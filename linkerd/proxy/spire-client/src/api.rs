@@ -2,7 +2,7 @@ use futures::prelude::*;
 use linkerd_error::{Error, Recover, Result};
 use linkerd_exp_backoff::{ExponentialBackoff, ExponentialBackoffStream};
 use linkerd_identity::DerX509;
-use linkerd_identity::{Credentials, Id};
+use linkerd_identity::{Credentials, Id, TrustBundles};
 use linkerd_proxy_http as http;
 use linkerd_tonic_watch::StreamWatch;
 use spiffe_proto::client::{
@@ -33,9 +33,15 @@ pub struct SvidUpdate {
     svids: HashMap<Id, Svid>,
 }
 
+/// Trust bundles for the local and federated trust domains.
+#[derive(Clone, Debug, Default)]
+pub struct BundlesUpdate {
+    bundles: TrustBundles,
+}
+
 #[derive(Clone, Debug)]
 pub struct Api<S> {
-    client: Client<S>,
+    pub(super) client: Client<S>,
 }
 
 #[derive(Clone)]
@@ -85,19 +91,11 @@ impl TryFrom<api::X509svid> for Svid {
             return Err("empty private key".into());
         }
 
-        let cert_der_blocks = asn1::from_der(&proto.x509_svid)?;
-        let (leaf, intermediates) = match cert_der_blocks.split_first() {
-            None => return Err("empty cert chain".into()),
-            Some((leaf_block, intermediates_block)) => {
-                let leaf = DerX509(asn1::to_der(leaf_block)?);
-                let mut intermediates = vec![];
-                for block in intermediates_block.iter() {
-                    let cert_der = asn1::to_der(block)?;
-                    intermediates.push(DerX509(cert_der));
-                }
-                (leaf, intermediates)
-            }
+        let mut certs = split_certs(&proto.x509_svid)?.into_iter();
+        let Some(leaf) = certs.next() else {
+            return Err("empty cert chain".into());
         };
+        let intermediates = certs.collect::<Vec<_>>();
 
         let spiffe_id = Id::parse_uri(&proto.spiffe_id)?;
 
@@ -105,11 +103,69 @@ impl TryFrom<api::X509svid> for Svid {
             spiffe_id,
             leaf,
             private_key: proto.x509_svid_key,
-            intermediates: intermediates.to_vec(),
+            intermediates,
         })
     }
 }
 
+// === impl BundlesUpdate ===
+
+impl BundlesUpdate {
+    #[cfg(test)]
+    pub(super) fn new(bundles: TrustBundles) -> Self {
+        Self { bundles }
+    }
+
+    /// Parses the trust bundles in a Workload API response.
+    ///
+    /// Bundles that cannot be parsed are skipped.
+    fn from_proto(proto: api::X509BundlesResponse) -> Self {
+        let bundles = proto
+            .bundles
+            .into_iter()
+            .filter_map(|(id, der)| {
+                parse_bundle(&id, &der)
+                    .map_err(|err| error!("could not parse trust bundle for {}: {}", id, err))
+                    .ok()
+            })
+            .collect();
+        Self { bundles }
+    }
+}
+
+/// Parses a trust bundle, returning its trust domain name and roots.
+///
+/// Bundles are keyed by the trust domain's SPIFFE ID, e.g.
+/// `spiffe://example.org`.
+fn parse_bundle(id: &str, der: &[u8]) -> Result<(String, Vec<DerX509>)> {
+    let domain = trust_domain(&Id::parse_uri(id)?)?;
+
+    let roots = split_certs(der)?;
+    if roots.is_empty() {
+        return Err("empty trust bundle".into());
+    }
+    Ok((domain, roots))
+}
+
+/// Returns the trust domain name of a SPIFFE ID.
+pub(super) fn trust_domain(id: &Id) -> Result<String> {
+    match id {
+        Id::Uri(uri) if uri.scheme() == "spiffe" => Ok(uri
+            .host_str()
+            .ok_or("SPIFFE ID has no trust domain")?
+            .to_string()),
+        _ => Err("not a SPIFFE ID".into()),
+    }
+}
+
+/// Splits concatenated ASN.1 DER-encoded certificates.
+fn split_certs(der: &[u8]) -> Result<Vec<DerX509>> {
+    asn1::from_der(der)?
+        .iter()
+        .map(|block| Ok(DerX509(asn1::to_der(block)?)))
+        .collect()
+}
+
 // === impl Api ===
 
 impl<S> Api<S>
@@ -119,9 +175,14 @@ where
     S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
     <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
 {
+    pub fn new(client: S) -> Self {
+        Self {
+            client: Client::new(client),
+        }
+    }
+
     pub fn watch(client: S, backoff: ExponentialBackoff) -> Watch<S> {
-        let client = Client::new(client);
-        StreamWatch::new(GrpcRecover(backoff), Self { client })
+        StreamWatch::new(GrpcRecover(backoff), Self::new(client))
     }
 }
 
@@ -146,16 +207,9 @@ where
     }
 
     fn call(&mut self, _: ()) -> Self::Future {
-        let req = api::X509svidRequest {};
         let mut client = self.client.clone();
         Box::pin(async move {
-            let parsed_header = SPIFFE_HEADER_VALUE
-                .parse()
-                .map_err(|e| tonic::Status::internal(format!("Failed to parse header: {}", e)))?;
-
-            let mut req = tonic::Request::new(req);
-            req.metadata_mut().insert(SPIFFE_HEADER_KEY, parsed_header);
-
+            let req = request(api::X509svidRequest {})?;
             let rsp = client.fetch_x509svid(req).await?;
             Ok(rsp.map(|svids| {
                 svids
@@ -179,6 +233,47 @@ where
     }
 }
 
+impl<S> Service<api::X509BundlesRequest> for Api<S>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone,
+    S: Clone + Send + Sync + 'static,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    type Response =
+        tonic::Response<futures::stream::BoxStream<'static, Result<BundlesUpdate, tonic::Status>>>;
+    type Error = tonic::Status;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, tonic::Status>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: api::X509BundlesRequest) -> Self::Future {
+        let mut client = self.client.clone();
+        Box::pin(async move {
+            let req = request(req)?;
+            let rsp = client.fetch_x509_bundles(req).await?;
+            Ok(rsp.map(|bundles| bundles.map_ok(BundlesUpdate::from_proto).boxed()))
+        })
+    }
+}
+
+/// Builds a Workload API request, which must include the SPIFFE header.
+pub(super) fn request<T>(msg: T) -> Result<tonic::Request<T>, tonic::Status> {
+    let parsed_header = SPIFFE_HEADER_VALUE
+        .parse()
+        .map_err(|e| tonic::Status::internal(format!("Failed to parse header: {}", e)))?;
+
+    let mut req = tonic::Request::new(msg);
+    req.metadata_mut().insert(SPIFFE_HEADER_KEY, parsed_header);
+    Ok(req)
+}
+
 // === impl GrpcRecover ===
 
 impl Recover<tonic::Status> for GrpcRecover {
@@ -217,9 +312,16 @@ where
     Err(NoMatchingSVIDFound(()).into())
 }
 
+pub(super) fn process_bundles<C>(credentials: &mut C, update: BundlesUpdate) -> Result<()>
+where
+    C: Credentials,
+{
+    credentials.set_trust_bundles(update.bundles)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::Svid;
+    use crate::api::{BundlesUpdate, Svid};
     use rcgen::{CertificateParams, KeyPair, SanType};
     use spiffe_proto::client as api;
 
@@ -266,4 +368,32 @@ mod tests {
         svid_pb.x509_svid_key = Vec::default();
         assert!(Svid::try_from(svid_pb).is_err());
     }
+
+    #[test]
+    fn can_parse_bundles() {
+        let root = || {
+            let key = KeyPair::generate().expect("should generate key");
+            let cert = CertificateParams::default()
+                .self_signed(&key)
+                .expect("should generate cert");
+            cert.der().to_vec()
+        };
+        let (old, new) = (root(), root());
+        let rsp = api::X509BundlesResponse {
+            crl: Vec::default(),
+            bundles: [
+                ("spiffe://some-domain".to_string(), [old, new].concat()),
+                ("spiffe://other-domain".to_string(), root()),
+                ("spiffe://empty-domain".to_string(), Vec::default()),
+                ("other-domain".to_string(), root()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let BundlesUpdate { bundles } = BundlesUpdate::from_proto(rsp);
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles["some-domain"].len(), 2);
+        assert_eq!(bundles["other-domain"].len(), 1);
+    }
 }
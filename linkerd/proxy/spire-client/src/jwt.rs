@@ -0,0 +1,675 @@
+//! JWT-SVIDs and JWT bundles served by the Workload API.
+//!
+//! See <https://github.com/spiffe/spiffe/blob/main/standards/JWT-SVID.md>.
+
+use crate::api::{self, Api};
+use futures::{
+    future::{self, Either},
+    prelude::*,
+};
+use linkerd_error::{Error, Result};
+use linkerd_exp_backoff::ExponentialBackoff;
+use linkerd_identity::Id;
+use linkerd_proxy_http as http;
+use ring::signature;
+use serde::{de::DeserializeOwned, Deserialize};
+use spiffe_proto::client as proto;
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{sync::watch, time};
+use tower::Service;
+use tracing::{debug, warn, Instrument};
+
+/// Refreshes are not attempted more frequently than this, even as a token
+/// nears its expiry.
+const MIN_REFRESH: Duration = Duration::from_secs(1);
+
+/// The JWK `use` of keys that sign JWT-SVIDs.
+const JWT_SVID_USE: &str = "jwt-svid";
+
+/// A JWT-SVID issued to this workload.
+#[derive(Clone)]
+pub struct JwtSvid {
+    spiffe_id: Id,
+    token: Arc<str>,
+    expiry: SystemTime,
+}
+
+/// The keys that sign JWT-SVIDs, by trust domain and key ID.
+#[derive(Clone, Debug, Default)]
+pub struct JwtBundles {
+    keys: HashMap<String, HashMap<String, PublicKey>>,
+}
+
+/// Validates the JWT-SVIDs presented by peers.
+#[derive(Clone, Debug)]
+pub struct Validator {
+    audience: Arc<str>,
+    bundles: watch::Receiver<JwtBundles>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidJwtSvid {
+    #[error("malformed token")]
+    Malformed,
+
+    #[error("unsupported signing algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("subject is not a SPIFFE ID")]
+    Subject,
+
+    #[error("no key {kid} in the JWT bundle for {domain}")]
+    UnknownKey { domain: String, kid: String },
+
+    #[error("invalid signature")]
+    Signature,
+
+    #[error("token has expired")]
+    Expired,
+
+    #[error("token was not issued for audience {0}")]
+    Audience(Arc<str>),
+}
+
+#[derive(Clone, Debug)]
+enum PublicKey {
+    /// An uncompressed elliptic curve point.
+    Ec(Curve, Vec<u8>),
+    Rsa {
+        n: Vec<u8>,
+        e: Vec<u8>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Curve {
+    P256,
+    P384,
+}
+
+/// A decoded, but not yet validated, token.
+struct Token<'t> {
+    header: Header,
+    claims: Claims,
+    signed: &'t str,
+    signature: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    aud: Audience,
+    exp: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    #[serde(default)]
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    #[serde(rename = "use")]
+    usage: Option<String>,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+/// Fetches JWT-SVIDs for an audience on a background task, publishing the
+/// `Authorization` header value that bears the current token.
+///
+/// Tokens are refreshed once half of their remaining lifetime has elapsed. If
+/// a token cannot be refreshed before it expires, it is withdrawn. The task
+/// completes when all receivers are dropped.
+pub fn spawn_authorization_watch<S>(
+    client: S,
+    spiffe_id: &Id,
+    audience: Arc<str>,
+    backoff: ExponentialBackoff,
+) -> watch::Receiver<Option<http::HeaderValue>>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone + Send + Sync + 'static,
+    S::Error: Into<Error>,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    let (tx, rx) = watch::channel(None);
+    let req = proto::JwtsvidRequest {
+        audience: vec![audience.to_string()],
+        spiffe_id: spiffe_id.to_string(),
+    };
+    let refresh = async move {
+        let mut api = Api::new(client);
+        let mut expiry = None;
+        let mut retries = backoff.stream();
+        loop {
+            let fetched = api.call(req.clone()).await;
+            let wait = match fetched.and_then(|svid| Ok((svid.authorization()?, svid))) {
+                Ok((authz, svid)) => {
+                    debug!(expiry = ?svid.expiry, "Refreshed JWT-SVID");
+                    tx.send_replace(Some(authz));
+                    expiry = Some(svid.expiry);
+                    retries = backoff.stream();
+                    Either::Left(time::sleep(refresh_delay(svid.expiry)))
+                }
+                Err(error) => {
+                    warn!(%error, "Failed to fetch JWT-SVID");
+                    if expiry.is_some_and(|exp| exp <= SystemTime::now()) {
+                        warn!("JWT-SVID has expired");
+                        tx.send_replace(None);
+                        expiry = None;
+                    }
+                    Either::Right(retries.next().map(|_| ()))
+                }
+            };
+            if let Either::Left(_) = future::select(pin!(tx.closed()), pin!(wait)).await {
+                debug!("JWT-SVID watch dropped; terminating");
+                return;
+            }
+        }
+    };
+    tokio::spawn(refresh.instrument(tracing::debug_span!("jwt", %audience).or_current()));
+    rx
+}
+
+/// Watches the Workload API for JWT bundle updates.
+///
+/// The watch is updated on a background task and is empty until the first
+/// bundles are received.
+pub fn watch_bundles<S>(client: S, backoff: ExponentialBackoff) -> watch::Receiver<JwtBundles>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone + Send + Sync + 'static,
+    S::Error: Into<Error>,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    Api::watch(client, backoff).spawn_with_init(proto::JwtBundlesRequest {}, JwtBundles::default())
+}
+
+/// Returns the time to wait before refreshing a token that expires at the
+/// given time.
+fn refresh_delay(expiry: SystemTime) -> Duration {
+    let remaining = expiry.duration_since(SystemTime::now()).unwrap_or_default();
+    (remaining / 2).max(MIN_REFRESH)
+}
+
+// === impl JwtSvid ===
+
+impl JwtSvid {
+    pub fn spiffe_id(&self) -> &Id {
+        &self.spiffe_id
+    }
+
+    pub fn expiry(&self) -> SystemTime {
+        self.expiry
+    }
+
+    /// Returns an `Authorization` header value bearing the token.
+    pub fn authorization(&self) -> Result<http::HeaderValue> {
+        let mut value = http::HeaderValue::try_from(format!("Bearer {}", self.token))?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+impl TryFrom<proto::Jwtsvid> for JwtSvid {
+    type Error = Error;
+
+    fn try_from(proto: proto::Jwtsvid) -> Result<Self> {
+        // Tokens are issued by the local agent, so their signatures need not
+        // be checked.
+        let Token { claims, .. } = Token::decode(&proto.svid)?;
+        let spiffe_id = claims.spiffe_id()?;
+        if spiffe_id != Id::parse_uri(&proto.spiffe_id)? {
+            return Err("JWT-SVID subject does not match its SPIFFE ID".into());
+        }
+        let expiry = claims.expiry();
+        if expiry <= SystemTime::now() {
+            return Err(InvalidJwtSvid::Expired.into());
+        }
+        Ok(Self {
+            spiffe_id,
+            token: proto.svid.into(),
+            expiry,
+        })
+    }
+}
+
+impl fmt::Debug for JwtSvid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The token is a credential, so it is not included.
+        f.debug_struct("JwtSvid")
+            .field("spiffe_id", &self.spiffe_id)
+            .field("expiry", &self.expiry)
+            .finish_non_exhaustive()
+    }
+}
+
+// === impl JwtBundles ===
+
+impl JwtBundles {
+    /// Parses the JWKS documents in a Workload API response.
+    ///
+    /// Bundles and keys that cannot be parsed are skipped.
+    fn from_proto(proto: proto::JwtBundlesResponse) -> Self {
+        let keys = proto
+            .bundles
+            .into_iter()
+            .filter_map(|(id, jwks)| {
+                parse_jwks(&id, &jwks)
+                    .map_err(|error| warn!(%error, "Could not parse JWT bundle for {}", id))
+                    .ok()
+            })
+            .collect();
+        Self { keys }
+    }
+}
+
+/// Parses a JWT bundle, returning its trust domain name and keys.
+fn parse_jwks(id: &str, jwks: &[u8]) -> Result<(String, HashMap<String, PublicKey>)> {
+    let domain = api::trust_domain(&Id::parse_uri(id)?)?;
+    let Jwks { keys } = serde_json::from_slice(jwks)?;
+    let keys = keys
+        .into_iter()
+        .filter(|jwk| jwk.usage.as_deref().is_none_or(|u| u == JWT_SVID_USE))
+        .filter_map(|jwk| {
+            let kid = jwk.kid.clone()?;
+            PublicKey::try_from(jwk)
+                .map_err(|error| warn!(%error, %kid, "Skipping JWT bundle key"))
+                .ok()
+                .map(|key| (kid, key))
+        })
+        .collect::<HashMap<_, _>>();
+    Ok((domain, keys))
+}
+
+// === impl Validator ===
+
+impl Validator {
+    pub fn new(audience: Arc<str>, bundles: watch::Receiver<JwtBundles>) -> Self {
+        Self { audience, bundles }
+    }
+
+    /// Validates a JWT-SVID, returning its SPIFFE ID.
+    ///
+    /// The token must be signed by a key in its trust domain's JWT bundle,
+    /// must not have expired, and must have been issued for this validator's
+    /// audience.
+    pub fn validate(&self, token: &str) -> Result<Id, InvalidJwtSvid> {
+        let Token {
+            header,
+            claims,
+            signed,
+            signature,
+        } = Token::decode(token)?;
+        let spiffe_id = claims.spiffe_id()?;
+        let domain = api::trust_domain(&spiffe_id).map_err(|_| InvalidJwtSvid::Subject)?;
+        let kid = header.kid.ok_or(InvalidJwtSvid::Malformed)?;
+
+        {
+            let bundles = self.bundles.borrow();
+            let Some(key) = bundles.keys.get(&domain).and_then(|keys| keys.get(&kid)) else {
+                return Err(InvalidJwtSvid::UnknownKey { domain, kid });
+            };
+            key.verify(&header.alg, signed.as_bytes(), &signature)?;
+        }
+
+        if claims.expiry() <= SystemTime::now() {
+            return Err(InvalidJwtSvid::Expired);
+        }
+        if !claims.aud.contains(&self.audience) {
+            return Err(InvalidJwtSvid::Audience(self.audience.clone()));
+        }
+
+        Ok(spiffe_id)
+    }
+}
+
+// === impl Token ===
+
+impl<'t> Token<'t> {
+    fn decode(token: &'t str) -> Result<Self, InvalidJwtSvid> {
+        let (signed, signature) = token.rsplit_once('.').ok_or(InvalidJwtSvid::Malformed)?;
+        let (header, claims) = signed.split_once('.').ok_or(InvalidJwtSvid::Malformed)?;
+        Ok(Self {
+            header: decode_json(header)?,
+            claims: decode_json(claims)?,
+            signed,
+            signature: decode_b64(signature).ok_or(InvalidJwtSvid::Malformed)?,
+        })
+    }
+}
+
+fn decode_b64(part: &str) -> Option<Vec<u8>> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).ok()
+}
+
+fn decode_json<T: DeserializeOwned>(part: &str) -> Result<T, InvalidJwtSvid> {
+    let json = decode_b64(part).ok_or(InvalidJwtSvid::Malformed)?;
+    serde_json::from_slice(&json).map_err(|_| InvalidJwtSvid::Malformed)
+}
+
+// === impl Claims ===
+
+impl Claims {
+    fn spiffe_id(&self) -> Result<Id, InvalidJwtSvid> {
+        let id = Id::parse_uri(&self.sub).map_err(|_| InvalidJwtSvid::Subject)?;
+        api::trust_domain(&id).map_err(|_| InvalidJwtSvid::Subject)?;
+        Ok(id)
+    }
+
+    fn expiry(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.exp)
+    }
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Self::One(aud) => aud == audience,
+            Self::Many(auds) => auds.iter().any(|aud| aud == audience),
+        }
+    }
+}
+
+// === impl PublicKey ===
+
+impl PublicKey {
+    fn verify(&self, alg: &str, msg: &[u8], sig: &[u8]) -> Result<(), InvalidJwtSvid> {
+        let verified = match (self, alg) {
+            (Self::Ec(Curve::P256, point), "ES256") => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point)
+                    .verify(msg, sig)
+            }
+            (Self::Ec(Curve::P384, point), "ES384") => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P384_SHA384_FIXED, point)
+                    .verify(msg, sig)
+            }
+            (Self::Rsa { n, e }, _) => {
+                let params = match alg {
+                    "RS256" => &signature::RSA_PKCS1_2048_8192_SHA256,
+                    "RS384" => &signature::RSA_PKCS1_2048_8192_SHA384,
+                    "RS512" => &signature::RSA_PKCS1_2048_8192_SHA512,
+                    "PS256" => &signature::RSA_PSS_2048_8192_SHA256,
+                    "PS384" => &signature::RSA_PSS_2048_8192_SHA384,
+                    "PS512" => &signature::RSA_PSS_2048_8192_SHA512,
+                    _ => return Err(InvalidJwtSvid::UnsupportedAlgorithm(alg.to_string())),
+                };
+                signature::RsaPublicKeyComponents { n, e }.verify(params, msg, sig)
+            }
+            _ => return Err(InvalidJwtSvid::UnsupportedAlgorithm(alg.to_string())),
+        };
+        verified.map_err(|_| InvalidJwtSvid::Signature)
+    }
+}
+
+impl TryFrom<Jwk> for PublicKey {
+    type Error = Error;
+
+    fn try_from(jwk: Jwk) -> Result<Self> {
+        fn param(value: Option<String>, name: &str) -> Result<Vec<u8>> {
+            let value = value.ok_or_else(|| format!("missing {name} parameter"))?;
+            decode_b64(&value).ok_or_else(|| format!("invalid {name} parameter").into())
+        }
+
+        match jwk.kty.as_str() {
+            "EC" => {
+                let (curve, len) = match jwk.crv.as_deref() {
+                    Some("P-256") => (Curve::P256, 32),
+                    Some("P-384") => (Curve::P384, 48),
+                    crv => return Err(format!("unsupported curve: {crv:?}").into()),
+                };
+                let (x, y) = (param(jwk.x, "x")?, param(jwk.y, "y")?);
+                if x.len() != len || y.len() != len {
+                    return Err("invalid curve point".into());
+                }
+                let mut point = Vec::with_capacity(1 + 2 * len);
+                point.push(0x04);
+                point.extend(x);
+                point.extend(y);
+                Ok(Self::Ec(curve, point))
+            }
+            "RSA" => Ok(Self::Rsa {
+                n: param(jwk.n, "n")?,
+                e: param(jwk.e, "e")?,
+            }),
+            kty => Err(format!("unsupported key type: {kty}").into()),
+        }
+    }
+}
+
+// === impl Api ===
+
+impl<S> Service<proto::JwtsvidRequest> for Api<S>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone,
+    S: Clone + Send + Sync + 'static,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    type Response = JwtSvid;
+    type Error = Error;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: proto::JwtsvidRequest) -> Self::Future {
+        let mut client = self.client.clone();
+        Box::pin(async move {
+            let req = api::request(req)?;
+            let rsp = client.fetch_jwtsvid(req).await?.into_inner();
+            let svid = rsp.svids.into_iter().next().ok_or("no JWT-SVID issued")?;
+            svid.try_into()
+        })
+    }
+}
+
+impl<S> Service<proto::JwtBundlesRequest> for Api<S>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone,
+    S: Clone + Send + Sync + 'static,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    type Response =
+        tonic::Response<futures::stream::BoxStream<'static, Result<JwtBundles, tonic::Status>>>;
+    type Error = tonic::Status;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, tonic::Status>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: proto::JwtBundlesRequest) -> Self::Future {
+        let mut client = self.client.clone();
+        Box::pin(async move {
+            let req = api::request(req)?;
+            let rsp = client.fetch_jwt_bundles(req).await?;
+            Ok(rsp.map(|bundles| bundles.map_ok(JwtBundles::from_proto).boxed()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workload_api::WorkloadApi;
+
+    fn validator(bundles: proto::JwtBundlesResponse) -> Validator {
+        let (_, rx) = watch::channel(JwtBundles::from_proto(bundles));
+        Validator::new("some-audience".into(), rx)
+    }
+
+    fn audience(aud: &str) -> Vec<String> {
+        vec![aud.to_string()]
+    }
+
+    #[test]
+    fn validates_jwt_svids() {
+        let stand_in = WorkloadApi::default();
+        let lifetime = Duration::from_secs(60);
+        let authority = stand_in.jwt_authority("some-domain", "some-key", lifetime, true);
+        let validator = validator(authority.bundle());
+
+        let spiffe_id = "spiffe://some-domain/some-workload";
+        let token = authority.issue(spiffe_id, &audience("some-audience"));
+        let id = validator.validate(&token).expect("token must be valid");
+        assert_eq!(id, Id::parse_uri(spiffe_id).unwrap());
+
+        assert!(matches!(
+            validator.validate(&authority.issue(spiffe_id, &audience("other-audience"))),
+            Err(InvalidJwtSvid::Audience(_))
+        ));
+
+        let expired = authority.sign(&serde_json::json!({
+            "sub": spiffe_id,
+            "aud": "some-audience",
+            "exp": 1_000_000_000,
+        }));
+        assert!(matches!(
+            validator.validate(&expired),
+            Err(InvalidJwtSvid::Expired)
+        ));
+
+        // A signature from one token does not validate another's claims.
+        let other = authority.issue(
+            "spiffe://some-domain/other-workload",
+            &audience("some-audience"),
+        );
+        let (signed, _) = other.rsplit_once('.').unwrap();
+        let (_, sig) = token.rsplit_once('.').unwrap();
+        let tampered = format!("{signed}.{sig}");
+        assert!(matches!(
+            validator.validate(&tampered),
+            Err(InvalidJwtSvid::Signature)
+        ));
+
+        assert!(matches!(
+            validator.validate("some-token"),
+            Err(InvalidJwtSvid::Malformed)
+        ));
+    }
+
+    #[test]
+    fn rejects_untrusted_keys() {
+        let stand_in = WorkloadApi::default();
+        let lifetime = Duration::from_secs(60);
+        let authority = stand_in.jwt_authority("some-domain", "some-key", lifetime, true);
+        let validator = validator(authority.bundle());
+
+        // A key that reuses a trusted key ID.
+        let rogue = WorkloadApi::default().jwt_authority("some-domain", "some-key", lifetime, true);
+        let token = rogue.issue(
+            "spiffe://some-domain/some-workload",
+            &audience("some-audience"),
+        );
+        assert!(matches!(
+            validator.validate(&token),
+            Err(InvalidJwtSvid::Signature)
+        ));
+
+        // A key from another trust domain.
+        let other = stand_in.jwt_authority("other-domain", "other-key", lifetime, false);
+        let token = other.issue(
+            "spiffe://other-domain/some-workload",
+            &audience("some-audience"),
+        );
+        assert!(matches!(
+            validator.validate(&token),
+            Err(InvalidJwtSvid::UnknownKey { .. })
+        ));
+
+        // A key from one trust domain may not sign for another.
+        let token = authority.issue(
+            "spiffe://other-domain/some-workload",
+            &audience("some-audience"),
+        );
+        assert!(matches!(
+            validator.validate(&token),
+            Err(InvalidJwtSvid::UnknownKey { .. })
+        ));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn refreshes_jwt_svids() {
+        let stand_in = WorkloadApi::default();
+        let lifetime = Duration::from_secs(2);
+        let authority = stand_in.jwt_authority("some-domain", "some-key", lifetime, true);
+        let addr = stand_in.spawn().await;
+
+        let chan = tonic::transport::Endpoint::from_shared(format!("http://{addr}"))
+            .expect("must be a valid endpoint")
+            .connect()
+            .await
+            .expect("must connect");
+        let backoff =
+            ExponentialBackoff::try_new(Duration::from_millis(10), Duration::from_millis(100), 0.0)
+                .expect("must be a valid backoff");
+        let spiffe_id = Id::parse_uri("spiffe://some-domain/some-workload").unwrap();
+
+        let mut bundles = watch_bundles(chan.clone(), backoff);
+        let mut authz =
+            spawn_authorization_watch(chan, &spiffe_id, "some-audience".into(), backoff);
+        bundles.changed().await.unwrap();
+        let validator = Validator::new("some-audience".into(), bundles);
+
+        let token = |authz: &watch::Receiver<Option<http::HeaderValue>>| {
+            let value = authz.borrow().clone().expect("must have a token");
+            assert!(value.is_sensitive());
+            value
+                .to_str()
+                .unwrap()
+                .strip_prefix("Bearer ")
+                .expect("must be a bearer token")
+                .to_string()
+        };
+
+        authz.changed().await.unwrap();
+        let first = token(&authz);
+        assert_eq!(validator.validate(&first).unwrap(), spiffe_id);
+
+        // The token is refreshed before it expires.
+        authz.changed().await.unwrap();
+        let second = token(&authz);
+        assert_ne!(first, second);
+        assert_eq!(validator.validate(&second).unwrap(), spiffe_id);
+        assert_eq!(authority.issued(), 2);
+    }
+}
@@ -2,12 +2,22 @@
 #![forbid(unsafe_code)]
 
 mod api;
+pub mod jwt;
+#[cfg(test)]
+mod workload_api;
 
-pub use api::{Api, SvidUpdate};
+pub use api::{Api, BundlesUpdate, SvidUpdate};
+use futures::future::{self, Either};
 use linkerd_error::Error;
+use linkerd_exp_backoff::ExponentialBackoff;
 use linkerd_identity::Credentials;
 use linkerd_identity::Id;
-use std::fmt::{Debug, Display};
+use linkerd_proxy_http as http;
+use spiffe_proto::client::X509BundlesRequest;
+use std::{
+    fmt::{Debug, Display},
+    pin::pin,
+};
 use tokio::sync::watch;
 use tower::{util::ServiceExt, Service};
 
@@ -15,6 +25,29 @@ pub struct Spire {
     id: Id,
 }
 
+/// Watches of the X.509 SVIDs and trust bundles served by the Workload API.
+pub struct Updates {
+    pub svids: watch::Receiver<SvidUpdate>,
+    pub bundles: watch::Receiver<BundlesUpdate>,
+}
+
+/// Watches the Workload API for X.509 SVID and trust bundle updates.
+///
+/// Each watch is updated on a background task.
+pub async fn watch<S>(client: S, backoff: ExponentialBackoff) -> Result<Updates, tonic::Status>
+where
+    S: tonic::client::GrpcService<tonic::body::BoxBody> + Clone + Send + Sync + 'static,
+    S::Error: Into<Error>,
+    S::ResponseBody: Default + http::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <S::ResponseBody as http::Body>::Error: Into<Error> + Send,
+    S::Future: Send + 'static,
+{
+    let api = Api::watch(client, backoff);
+    let svids = api.clone().spawn_watch(()).await?.into_inner();
+    let bundles = api.spawn_watch(X509BundlesRequest {}).await?.into_inner();
+    Ok(Updates { svids, bundles })
+}
+
 // === impl Spire ===
 
 impl Spire {
@@ -25,34 +58,55 @@ impl Spire {
     pub async fn run<C, S>(self, credentials: C, mut client: S)
     where
         C: Credentials,
-        S: Service<(), Response = tonic::Response<watch::Receiver<SvidUpdate>>>,
+        S: Service<(), Response = Updates>,
         S::Error: Into<Error> + Display + Debug,
     {
         let client = client.ready().await.expect("should be ready");
-        let rsp = client
+        let updates = client
             .call(())
             .await
             .expect("spire client must gracefully handle errors");
-        consume_updates(&self.id, rsp.into_inner(), credentials).await
+        consume_updates(&self.id, updates, credentials).await
     }
 }
 
-async fn consume_updates<C>(
-    id: &Id,
-    mut updates: watch::Receiver<api::SvidUpdate>,
-    mut credentials: C,
-) where
+async fn consume_updates<C>(id: &Id, updates: Updates, mut credentials: C)
+where
     C: Credentials,
 {
+    let Updates {
+        mut svids,
+        mut bundles,
+    } = updates;
+    let (mut svids_changed, mut bundles_changed) = (true, true);
     loop {
-        let svid_update = updates.borrow_and_update().clone();
-        if let Err(error) = api::process_svid(&mut credentials, svid_update, id) {
-            tracing::error!(%error, "Error processing SVID update");
+        // Bundles are applied first so that a new SVID may be validated
+        // against its trust domain's new bundle.
+        if bundles_changed {
+            let update = bundles.borrow_and_update().clone();
+            if let Err(error) = api::process_bundles(&mut credentials, update) {
+                tracing::error!(%error, "Error processing trust bundle update");
+            }
         }
-        if updates.changed().await.is_err() {
-            tracing::debug!("SVID watch closed; terminating");
-            return;
+        if svids_changed {
+            let update = svids.borrow_and_update().clone();
+            if let Err(error) = api::process_svid(&mut credentials, update, id) {
+                tracing::error!(%error, "Error processing SVID update");
+            }
         }
+
+        // `changed` marks the new value as seen, so note which watch was
+        // updated before checking the other.
+        let changed = match future::select(pin!(svids.changed()), pin!(bundles.changed())).await {
+            Either::Left((res, _)) => res.map(|()| true),
+            Either::Right((res, _)) => res.map(|()| false),
+        };
+        let Ok(svid_updated) = changed else {
+            tracing::debug!("Workload API watch closed; terminating");
+            return;
+        };
+        svids_changed = svid_updated || svids.has_changed().unwrap_or(false);
+        bundles_changed = !svid_updated || bundles.has_changed().unwrap_or(false);
     }
 }
 
@@ -61,7 +115,7 @@ mod tests {
     use super::*;
     use crate::api::Svid;
     use linkerd_error::Result;
-    use linkerd_identity::DerX509;
+    use linkerd_identity::{DerX509, TrustBundles};
     use rcgen::{CertificateParams, KeyPair, SanType, SerialNumber};
     use std::time::SystemTime;
 
@@ -86,18 +140,27 @@ mod tests {
     }
 
     struct MockClient {
-        rx: watch::Receiver<SvidUpdate>,
+        svids: watch::Receiver<SvidUpdate>,
+        bundles: watch::Receiver<BundlesUpdate>,
     }
 
     impl MockClient {
-        fn new(init: SvidUpdate) -> (Self, watch::Sender<SvidUpdate>) {
-            let (tx, rx) = watch::channel(init);
-            (Self { rx }, tx)
+        fn new(
+            svids: SvidUpdate,
+            bundles: BundlesUpdate,
+        ) -> (
+            Self,
+            watch::Sender<SvidUpdate>,
+            watch::Sender<BundlesUpdate>,
+        ) {
+            let (svids_tx, svids) = watch::channel(svids);
+            let (bundles_tx, bundles) = watch::channel(bundles);
+            (Self { svids, bundles }, svids_tx, bundles_tx)
         }
     }
 
     impl tower::Service<()> for MockClient {
-        type Response = tonic::Response<watch::Receiver<SvidUpdate>>;
+        type Response = Updates;
         type Error = Error;
         // type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
         type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
@@ -110,19 +173,28 @@ mod tests {
         }
 
         fn call(&mut self, _req: ()) -> Self::Future {
-            let rsp = tonic::Response::new(self.rx.clone());
+            let rsp = Updates {
+                svids: self.svids.clone(),
+                bundles: self.bundles.clone(),
+            };
             Box::pin(futures::future::ready(Ok(rsp)))
         }
     }
 
     struct MockCredentials {
         tx: watch::Sender<Option<SerialNumber>>,
+        bundles_tx: watch::Sender<Vec<String>>,
     }
 
     impl MockCredentials {
-        fn new() -> (Self, watch::Receiver<Option<SerialNumber>>) {
+        fn new() -> (
+            Self,
+            watch::Receiver<Option<SerialNumber>>,
+            watch::Receiver<Vec<String>>,
+        ) {
             let (tx, rx) = watch::channel(None);
-            (Self { tx }, rx)
+            let (bundles_tx, bundles_rx) = watch::channel(Vec::new());
+            (Self { tx, bundles_tx }, rx, bundles_rx)
         }
     }
 
@@ -139,6 +211,13 @@ mod tests {
             self.tx.send(Some(serial)).unwrap();
             Ok(())
         }
+
+        fn set_trust_bundles(&mut self, bundles: TrustBundles) -> Result<()> {
+            let mut domains = bundles.into_keys().collect::<Vec<_>>();
+            domains.sort();
+            self.bundles_tx.send_replace(domains);
+            Ok(())
+        }
     }
 
     #[tokio::test(flavor = "current_thread")]
@@ -146,7 +225,7 @@ mod tests {
         let spiffe_san = "spiffe://some-domain/some-workload";
         let spiffe_id = Id::parse_uri("spiffe://some-domain/some-workload").expect("should parse");
 
-        let (creds, mut creds_rx) = MockCredentials::new();
+        let (creds, mut creds_rx, _) = MockCredentials::new();
 
         let spire = Spire::new(spiffe_id.clone());
 
@@ -157,7 +236,7 @@ mod tests {
             serial_1.clone(),
         )]);
 
-        let (client, svid_tx) = MockClient::new(update_1);
+        let (client, svid_tx, _bundles_tx) = MockClient::new(update_1, BundlesUpdate::default());
         tokio::spawn(spire.run(creds, client));
 
         creds_rx.changed().await.unwrap();
@@ -181,7 +260,7 @@ mod tests {
         let spiffe_san = "spiffe://some-domain/some-workload";
         let spiffe_id = Id::parse_uri("spiffe://some-domain/some-workload").expect("should parse");
 
-        let (creds, mut creds_rx) = MockCredentials::new();
+        let (creds, mut creds_rx, _) = MockCredentials::new();
 
         let spire = Spire::new(spiffe_id.clone());
 
@@ -192,7 +271,7 @@ mod tests {
             serial_1.clone(),
         )]);
 
-        let (client, svid_tx) = MockClient::new(update_1);
+        let (client, svid_tx, _bundles_tx) = MockClient::new(update_1, BundlesUpdate::default());
         tokio::spawn(spire.run(creds, client));
 
         creds_rx.changed().await.unwrap();
@@ -223,7 +302,7 @@ mod tests {
         let spiffe_id = Id::parse_uri("spiffe://some-domain/some-workload").expect("should parse");
         let spiffe_id_wrong = Id::parse_uri("spiffe://some-domain/wrong").expect("should parse");
 
-        let (creds, mut creds_rx) = MockCredentials::new();
+        let (creds, mut creds_rx, _) = MockCredentials::new();
 
         let spire = Spire::new(spiffe_id.clone());
 
@@ -234,7 +313,7 @@ mod tests {
             serial_1.clone(),
         )]);
 
-        let (client, svid_tx) = MockClient::new(update_1);
+        let (client, svid_tx, _bundles_tx) = MockClient::new(update_1, BundlesUpdate::default());
         tokio::spawn(spire.run(creds, client));
 
         creds_rx.changed().await.unwrap();
@@ -255,4 +334,89 @@ mod tests {
         assert!(!creds_rx.has_changed().unwrap());
         assert!(*creds_rx.borrow_and_update() == Some(serial_1));
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn trust_bundle_updates() {
+        let spiffe_san = "spiffe://some-domain/some-workload";
+        let spiffe_id = Id::parse_uri(spiffe_san).expect("should parse");
+        let root = DerX509(vec![1, 2, 3]);
+
+        let (creds, _creds_rx, mut bundles_rx) = MockCredentials::new();
+        let spire = Spire::new(spiffe_id.clone());
+
+        let svids = SvidUpdate::new(vec![gen_svid(
+            spiffe_id,
+            vec![SanType::URI(spiffe_san.parse().unwrap())],
+            SerialNumber::from_slice("some-serial-1".as_bytes()),
+        )]);
+        let bundles = BundlesUpdate::new(TrustBundles::from([
+            ("some-domain".to_string(), vec![root.clone()]),
+            ("other-domain".to_string(), vec![root.clone()]),
+        ]));
+        let (client, _svid_tx, bundles_tx) = MockClient::new(svids, bundles);
+        tokio::spawn(spire.run(creds, client));
+
+        bundles_rx.changed().await.unwrap();
+        assert_eq!(
+            *bundles_rx.borrow_and_update(),
+            vec!["other-domain".to_string(), "some-domain".to_string()]
+        );
+
+        bundles_tx
+            .send(BundlesUpdate::new(TrustBundles::from([(
+                "some-domain".to_string(),
+                vec![root],
+            )])))
+            .expect("should send");
+        bundles_rx.changed().await.unwrap();
+        assert_eq!(
+            *bundles_rx.borrow_and_update(),
+            vec!["some-domain".to_string()]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn workload_api() {
+        let spiffe_san = "spiffe://some-domain/some-workload";
+        let spiffe_id = Id::parse_uri(spiffe_san).expect("should parse");
+
+        let stand_in = workload_api::WorkloadApi::default();
+        let some_domain = stand_in.trust_domain("some-domain");
+        let other_domain = stand_in.trust_domain("other-domain");
+        let serial = SerialNumber::from_slice("some-serial-1".as_bytes());
+        stand_in.issue(&some_domain, spiffe_san, serial.clone());
+        let addr = stand_in.spawn().await;
+
+        let (creds, mut creds_rx, mut bundles_rx) = MockCredentials::new();
+        let client = tower::service_fn(move |()| async move {
+            let chan = tonic::transport::Endpoint::from_shared(format!("http://{addr}"))
+                .expect("must be a valid endpoint")
+                .connect()
+                .await
+                .map_err(Error::from)?;
+            let backoff = ExponentialBackoff::try_new(
+                std::time::Duration::from_millis(10),
+                std::time::Duration::from_millis(100),
+                0.0,
+            )
+            .expect("must be a valid backoff");
+            watch(chan, backoff).await.map_err(Error::from)
+        });
+        tokio::spawn(Spire::new(spiffe_id).run(creds, client));
+
+        creds_rx.changed().await.unwrap();
+        assert!(*creds_rx.borrow_and_update() == Some(serial));
+        assert_eq!(
+            *bundles_rx.borrow_and_update(),
+            vec!["other-domain".to_string(), "some-domain".to_string()]
+        );
+
+        // Federation with a trust domain ends when its bundle is removed.
+        stand_in.remove_trust_domain(&other_domain);
+        bundles_rx.changed().await.unwrap();
+        assert_eq!(
+            *bundles_rx.borrow_and_update(),
+            vec!["some-domain".to_string()]
+        );
+    }
 }
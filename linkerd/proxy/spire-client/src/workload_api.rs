@@ -0,0 +1,270 @@
+//! A local stand-in for a SPIRE agent's Workload API, used to drive tests.
+
+use futures::prelude::*;
+use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair, SanType, SerialNumber};
+use ring::{rand::SystemRandom, signature};
+use spiffe_proto::client::{
+    self as api,
+    spiffe_workload_api_server::{SpiffeWorkloadApi, SpiffeWorkloadApiServer},
+};
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::watch;
+use tokio_stream::wrappers::{TcpListenerStream, WatchStream};
+
+/// Serves the SVIDs and trust bundles published to it.
+#[derive(Clone, Default)]
+pub(crate) struct WorkloadApi {
+    svids: Arc<watch::Sender<api::X509svidResponse>>,
+    bundles: Arc<watch::Sender<api::X509BundlesResponse>>,
+    jwt_issuer: Arc<watch::Sender<Option<Arc<JwtAuthority>>>>,
+    jwt_bundles: Arc<watch::Sender<api::JwtBundlesResponse>>,
+}
+
+/// Signs JWT-SVIDs for a trust domain.
+pub(crate) struct JwtAuthority {
+    id: String,
+    kid: String,
+    key: signature::EcdsaKeyPair,
+    lifetime: Duration,
+    issued: AtomicU64,
+}
+
+/// A trust domain's signing CA.
+pub(crate) struct TrustDomain {
+    id: String,
+    root: rcgen::Certificate,
+    key: KeyPair,
+}
+
+// === impl WorkloadApi ===
+
+impl WorkloadApi {
+    /// Creates a trust domain and serves its bundle.
+    pub(crate) fn trust_domain(&self, name: &str) -> TrustDomain {
+        let key = KeyPair::generate().expect("should generate key");
+        let mut params = CertificateParams::default();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let root = params.self_signed(&key).expect("should generate root");
+
+        let id = format!("spiffe://{name}");
+        self.bundles.send_modify(|rsp| {
+            rsp.bundles.insert(id.clone(), root.der().to_vec());
+        });
+        TrustDomain { id, root, key }
+    }
+
+    /// Stops serving a trust domain's bundle.
+    pub(crate) fn remove_trust_domain(&self, domain: &TrustDomain) {
+        self.bundles.send_modify(|rsp| {
+            rsp.bundles.remove(&domain.id);
+        });
+    }
+
+    /// Issues an SVID for the given SPIFFE ID, replacing any served SVIDs.
+    pub(crate) fn issue(&self, domain: &TrustDomain, spiffe_id: &str, serial: SerialNumber) {
+        let key = KeyPair::generate().expect("should generate key");
+        let mut params = CertificateParams::default();
+        params.subject_alt_names = vec![SanType::URI(spiffe_id.parse().unwrap())];
+        params.serial_number = Some(serial);
+        let cert = params
+            .signed_by(&key, &domain.root, &domain.key)
+            .expect("should generate cert");
+
+        self.svids.send_replace(api::X509svidResponse {
+            svids: vec![api::X509svid {
+                spiffe_id: spiffe_id.to_string(),
+                x509_svid: cert.der().to_vec(),
+                x509_svid_key: key.serialize_der(),
+                bundle: domain.root.der().to_vec(),
+            }],
+            ..Default::default()
+        });
+    }
+
+    /// Creates a JWT signing authority for a trust domain and serves its JWT
+    /// bundle. Unless `issuer` is false, it issues the JWT-SVIDs served by the
+    /// Workload API.
+    pub(crate) fn jwt_authority(
+        &self,
+        name: &str,
+        kid: &str,
+        lifetime: Duration,
+        issuer: bool,
+    ) -> Arc<JwtAuthority> {
+        let rng = SystemRandom::new();
+        let alg = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+        let pkcs8 =
+            signature::EcdsaKeyPair::generate_pkcs8(alg, &rng).expect("should generate key");
+        let key = signature::EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref(), &rng)
+            .expect("should parse key");
+        let authority = Arc::new(JwtAuthority {
+            id: format!("spiffe://{name}"),
+            kid: kid.to_string(),
+            key,
+            lifetime,
+            issued: AtomicU64::new(0),
+        });
+
+        self.jwt_bundles.send_modify(|rsp| {
+            rsp.bundles.insert(authority.id.clone(), authority.jwks());
+        });
+        if issuer {
+            self.jwt_issuer.send_replace(Some(authority.clone()));
+        }
+        authority
+    }
+
+    /// Serves the Workload API on a local port.
+    pub(crate) async fn spawn(&self) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("must bind");
+        let addr = listener.local_addr().expect("must have an address");
+        let server = tonic::transport::Server::builder()
+            .add_service(SpiffeWorkloadApiServer::new(self.clone()))
+            .serve_with_incoming(TcpListenerStream::new(listener));
+        tokio::spawn(server);
+        addr
+    }
+}
+
+// === impl JwtAuthority ===
+
+impl JwtAuthority {
+    /// Returns the number of JWT-SVIDs issued.
+    pub(crate) fn issued(&self) -> u64 {
+        self.issued.load(Ordering::SeqCst)
+    }
+
+    /// Issues a JWT-SVID for a SPIFFE ID and audience.
+    pub(crate) fn issue(&self, spiffe_id: &str, audience: &[String]) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let jti = self.issued.fetch_add(1, Ordering::SeqCst);
+        self.sign(&serde_json::json!({
+            "sub": spiffe_id,
+            "aud": audience,
+            "iat": now.as_secs(),
+            "exp": (now + self.lifetime).as_secs(),
+            "jti": jti.to_string(),
+        }))
+    }
+
+    /// Signs a token with the given claims.
+    pub(crate) fn sign(&self, claims: &serde_json::Value) -> String {
+        let header = serde_json::json!({"alg": "ES256", "kid": self.kid, "typ": "JWT"});
+        let signed = format!("{}.{}", b64(header.to_string()), b64(claims.to_string()));
+        let sig = self
+            .key
+            .sign(&SystemRandom::new(), signed.as_bytes())
+            .expect("should sign");
+        format!("{signed}.{}", b64(sig))
+    }
+
+    /// Returns the trust domain's JWT bundle.
+    pub(crate) fn bundle(&self) -> api::JwtBundlesResponse {
+        api::JwtBundlesResponse {
+            bundles: [(self.id.clone(), self.jwks())].into_iter().collect(),
+        }
+    }
+
+    fn jwks(&self) -> Vec<u8> {
+        use signature::KeyPair;
+        let point = self.key.public_key().as_ref();
+        let (x, y) = point[1..].split_at(32);
+        serde_json::json!({
+            "keys": [{
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "jwt-svid",
+                "kid": self.kid,
+                "x": b64(x),
+                "y": b64(y),
+            }],
+        })
+        .to_string()
+        .into_bytes()
+    }
+}
+
+fn b64(data: impl AsRef<[u8]>) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+/// Rejects requests without the SPIFFE header, as SPIRE does.
+fn check_header<T>(req: &tonic::Request<T>) -> Result<(), tonic::Status> {
+    match req.metadata().get("workload.spiffe.io") {
+        Some(v) if v == "true" => Ok(()),
+        _ => Err(tonic::Status::invalid_argument("missing security header")),
+    }
+}
+
+#[tonic::async_trait]
+impl SpiffeWorkloadApi for WorkloadApi {
+    type FetchX509SVIDStream =
+        stream::BoxStream<'static, Result<api::X509svidResponse, tonic::Status>>;
+    type FetchX509BundlesStream =
+        stream::BoxStream<'static, Result<api::X509BundlesResponse, tonic::Status>>;
+    type FetchJWTBundlesStream =
+        stream::BoxStream<'static, Result<api::JwtBundlesResponse, tonic::Status>>;
+
+    async fn fetch_x509svid(
+        &self,
+        req: tonic::Request<api::X509svidRequest>,
+    ) -> Result<tonic::Response<Self::FetchX509SVIDStream>, tonic::Status> {
+        check_header(&req)?;
+        let updates = WatchStream::new(self.svids.subscribe()).map(Ok);
+        Ok(tonic::Response::new(updates.boxed()))
+    }
+
+    async fn fetch_x509_bundles(
+        &self,
+        req: tonic::Request<api::X509BundlesRequest>,
+    ) -> Result<tonic::Response<Self::FetchX509BundlesStream>, tonic::Status> {
+        check_header(&req)?;
+        let updates = WatchStream::new(self.bundles.subscribe()).map(Ok);
+        Ok(tonic::Response::new(updates.boxed()))
+    }
+
+    async fn fetch_jwtsvid(
+        &self,
+        req: tonic::Request<api::JwtsvidRequest>,
+    ) -> Result<tonic::Response<api::JwtsvidResponse>, tonic::Status> {
+        check_header(&req)?;
+        let api::JwtsvidRequest {
+            audience,
+            spiffe_id,
+        } = req.into_inner();
+        if audience.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "audience must be specified",
+            ));
+        }
+        let Some(issuer) = self.jwt_issuer.borrow().clone() else {
+            return Err(tonic::Status::unavailable("no JWT issuer"));
+        };
+        let svid = issuer.issue(&spiffe_id, &audience);
+        Ok(tonic::Response::new(api::JwtsvidResponse {
+            svids: vec![api::Jwtsvid {
+                spiffe_id,
+                svid,
+                hint: String::new(),
+            }],
+        }))
+    }
+
+    async fn fetch_jwt_bundles(
+        &self,
+        req: tonic::Request<api::JwtBundlesRequest>,
+    ) -> Result<tonic::Response<Self::FetchJWTBundlesStream>, tonic::Status> {
+        check_header(&req)?;
+        let updates = WatchStream::new(self.jwt_bundles.subscribe()).map(Ok);
+        Ok(tonic::Response::new(updates.boxed()))
+    }
+}
@@ -3,10 +3,12 @@ use linkerd_io as io;
 use linkerd_stack::{Param, Service};
 use std::{
     future::Future,
+    path::Path,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UnixStream};
 use tracing::debug;
 
 #[derive(Copy, Clone, Debug)]
@@ -15,6 +17,15 @@ pub struct ConnectTcp {
     user_timeout: UserTimeout,
 }
 
+/// The path of a Unix domain socket.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnixPath(pub Arc<Path>);
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConnectUnix(());
+
+// === impl ConnectTcp ===
+
 impl ConnectTcp {
     pub fn new(keepalive: Keepalive, user_timeout: UserTimeout) -> Self {
         Self {
@@ -53,3 +64,25 @@ impl<T: Param<Remote<ServerAddr>>> Service<T> for ConnectTcp {
         })
     }
 }
+
+// === impl ConnectUnix ===
+
+impl<T: Param<UnixPath>> Service<T> for ConnectUnix {
+    type Response = (io::ScopedIo<UnixStream>, UnixPath);
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send + Sync + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, t: T) -> Self::Future {
+        let UnixPath(path) = t.param();
+        debug!(server.path = %path.display(), "Connecting");
+        Box::pin(async move {
+            let io = UnixStream::connect(&path).await?;
+            debug!("Connected");
+            Ok((io::ScopedIo::client(io), UnixPath(path)))
+        })
+    }
+}
@@ -1,6 +1,7 @@
 //! Utilities for use TCP servers & clients.
 //!
-//! Uses unsafe code to interact with socket options for SO_ORIGINAL_DST.
+//! Uses unsafe code to interact with socket options for SO_ORIGINAL_DST and
+//! TCP_INFO.
 
 #![deny(
     rust_2018_idioms,
@@ -13,13 +14,14 @@ pub mod addrs;
 mod connect;
 pub mod listen;
 pub mod orig_dst;
+pub mod tcp_info;
 
 pub use self::{
     addrs::{
         AddrPair, ClientAddr, DualListenAddr, ListenAddr, Local, OrigDstAddr, Remote, ServerAddr,
     },
-    connect::ConnectTcp,
-    listen::{Bind, BindTcp},
+    connect::{ConnectTcp, ConnectUnix, UnixPath},
+    listen::{AcceptMetrics, Bind, BindTcp},
     orig_dst::BindWithOrigDst,
 };
 use linkerd_io as io;
@@ -45,6 +47,17 @@ impl From<UserTimeout> for Option<Duration> {
     }
 }
 
+/// The number of `SO_REUSEPORT` sockets a listener binds, each of which accepts
+/// connections on its own task.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AcceptShards(pub usize);
+
+impl Default for AcceptShards {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 // Misc.
 
 fn set_nodelay_or_warn(socket: &TcpStream) {
@@ -1,15 +1,18 @@
 mod dual_bind;
+mod metrics;
 
-use crate::{addrs::*, Keepalive, UserTimeout};
+pub use self::metrics::AcceptMetrics;
+use crate::{addrs::*, AcceptShards, Keepalive, UserTimeout};
 use dual_bind::DualBind;
 use futures::prelude::*;
 use linkerd_error::Result;
 use linkerd_io as io;
 use linkerd_stack::Param;
-use std::{fmt, net::SocketAddr, pin::Pin};
+use std::{fmt, net::SocketAddr, pin::Pin, time::Duration};
 use thiserror::Error;
-use tokio::net::TcpStream;
-use tokio_stream::wrappers::TcpListenerStream;
+use tokio::{net::TcpStream, sync::mpsc};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tracing::{debug_span, Instrument};
 
 /// Binds a listener, producing a stream of incoming connections.
 ///
@@ -20,6 +23,7 @@ pub trait Bind<T> {
         + io::AsyncWrite
         + io::Peek
         + io::PeerAddr
+        + io::TcpInfo
         + fmt::Debug
         + Unpin
         + Send
@@ -32,8 +36,12 @@ pub trait Bind<T> {
     fn bind(self, params: &T) -> Result<(Self::BoundAddrs, Self::Incoming)>;
 }
 
-#[derive(Copy, Clone, Debug, Default)]
-pub struct BindTcp(());
+type Incoming = Pin<Box<dyn Stream<Item = Result<(Addrs, TcpStream)>> + Send + Sync>>;
+
+#[derive(Clone, Debug, Default)]
+pub struct BindTcp {
+    metrics: AcceptMetrics,
+}
 
 #[derive(Clone, Debug)]
 pub struct Addrs {
@@ -57,63 +65,162 @@ struct UserTimeoutError(#[source] io::Error);
 #[error("failed to obtain peer address: {0}")]
 struct PeerAddrError(#[source] io::Error);
 
+/// The backlog for each sharded listener socket.
+const SHARD_BACKLOG: i32 = 1024;
+
 // === impl BindTcp ===
 
 impl BindTcp {
-    pub fn with_orig_dst() -> super::BindWithOrigDst<Self> {
-        super::BindWithOrigDst::from(Self::default())
+    pub fn new(metrics: AcceptMetrics) -> Self {
+        Self { metrics }
+    }
+
+    pub fn with_orig_dst(self) -> super::BindWithOrigDst<Self> {
+        super::BindWithOrigDst::from(self)
+    }
+
+    pub fn dual_with_orig_dst(self) -> DualBind<super::BindWithOrigDst<Self>> {
+        DualBind::from(super::BindWithOrigDst::from(self))
     }
 
-    pub fn dual_with_orig_dst() -> DualBind<super::BindWithOrigDst<Self>> {
-        DualBind::from(super::BindWithOrigDst::default())
+    /// Binds `shards` listeners on the same address with `SO_REUSEPORT` so
+    /// that the kernel distributes incoming connections across them. Each
+    /// listener accepts connections on its own task so that accepts (and socket
+    /// configuration) proceed in parallel across runtime workers.
+    fn bind_sharded(
+        self,
+        addr: SocketAddr,
+        shards: usize,
+        keepalive: Option<Duration>,
+        user_timeout: Option<Duration>,
+    ) -> Result<(Local<ServerAddr>, Incoming)> {
+        let mut listeners = Vec::with_capacity(shards);
+        let mut addr = addr;
+        for _ in 0..shards {
+            let l = bind_reuseport(addr)?;
+            // When an ephemeral port is requested, the remaining shards must
+            // bind the port assigned to the first.
+            addr = l.local_addr()?;
+            listeners.push(l);
+        }
+        let server = Local(ServerAddr(addr));
+
+        let (tx, rx) = mpsc::channel(shards);
+        for (shard, listen) in listeners.into_iter().enumerate() {
+            let accepts = self.metrics.shard(server, shard);
+            let tx = tx.clone();
+            tokio::spawn(
+                async move {
+                    loop {
+                        let res = tokio::select! {
+                            res = listen.accept() => res,
+                            // Stop accepting once the incoming stream is dropped.
+                            () = tx.closed() => return,
+                        };
+                        let conn = res.map_err(|e| AcceptError(e).into()).and_then(|(tcp, _)| {
+                            accepts.inc();
+                            accepted(tcp, server, keepalive, user_timeout)
+                        });
+                        if tx.send(conn).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                .instrument(debug_span!("accept", shard)),
+            );
+        }
+
+        Ok((server, Box::pin(ReceiverStream::new(rx))))
     }
 }
 
 impl<T> Bind<T> for BindTcp
 where
-    T: Param<ListenAddr> + Param<Keepalive> + Param<UserTimeout>,
+    T: Param<ListenAddr> + Param<AcceptShards> + Param<Keepalive> + Param<UserTimeout>,
 {
     type Addrs = Addrs;
     type BoundAddrs = Local<ServerAddr>;
-    type Incoming = Pin<Box<dyn Stream<Item = Result<(Self::Addrs, Self::Io)>> + Send + Sync>>;
+    type Incoming = Incoming;
     type Io = TcpStream;
 
     fn bind(self, params: &T) -> Result<(Self::BoundAddrs, Self::Incoming)> {
+        let ListenAddr(addr) = params.param();
+        let AcceptShards(shards) = params.param();
+        let Keepalive(keepalive) = params.param();
+        let UserTimeout(user_timeout) = params.param();
+        if shards > 1 {
+            return self.bind_sharded(addr, shards, keepalive, user_timeout);
+        }
+
         let listen = {
-            let ListenAddr(addr) = params.param();
             let l = std::net::TcpListener::bind(addr)?;
             // Ensure that O_NONBLOCK is set on the socket before using it with Tokio.
             l.set_nonblocking(true)?;
             tokio::net::TcpListener::from_std(l).expect("listener must be valid")
         };
         let server = Local(ServerAddr(listen.local_addr()?));
-        let Keepalive(keepalive) = params.param();
-        let UserTimeout(user_timeout) = params.param();
+        let accepts = self.metrics.shard(server, 0);
         let accept = TcpListenerStream::new(listen).map(move |res| {
             let tcp = res.map_err(AcceptError)?;
-            super::set_nodelay_or_warn(&tcp);
-            let tcp = super::set_keepalive_or_warn(tcp, keepalive).map_err(KeepaliveError)?;
-            let tcp =
-                super::set_user_timeout_or_warn(tcp, user_timeout).map_err(UserTimeoutError)?;
-
-            fn ipv4_mapped(orig: SocketAddr) -> SocketAddr {
-                if let SocketAddr::V6(v6) = orig {
-                    if let Some(ip) = v6.ip().to_ipv4_mapped() {
-                        return (ip, orig.port()).into();
-                    }
-                }
-                orig
-            }
-
-            let client_addr = tcp.peer_addr().map_err(PeerAddrError)?;
-            let client = Remote(ClientAddr(ipv4_mapped(client_addr)));
-            Ok((Addrs { server, client }, tcp))
+            accepts.inc();
+            accepted(tcp, server, keepalive, user_timeout)
         });
 
         Ok((server, Box::pin(accept)))
     }
 }
 
+/// Configures an accepted socket and determines its addresses.
+fn accepted(
+    tcp: TcpStream,
+    server: Local<ServerAddr>,
+    keepalive: Option<Duration>,
+    user_timeout: Option<Duration>,
+) -> Result<(Addrs, TcpStream)> {
+    super::set_nodelay_or_warn(&tcp);
+    let tcp = super::set_keepalive_or_warn(tcp, keepalive).map_err(KeepaliveError)?;
+    let tcp = super::set_user_timeout_or_warn(tcp, user_timeout).map_err(UserTimeoutError)?;
+
+    fn ipv4_mapped(orig: SocketAddr) -> SocketAddr {
+        if let SocketAddr::V6(v6) = orig {
+            if let Some(ip) = v6.ip().to_ipv4_mapped() {
+                return (ip, orig.port()).into();
+            }
+        }
+        orig
+    }
+
+    let client_addr = tcp.peer_addr().map_err(PeerAddrError)?;
+    let client = Remote(ClientAddr(ipv4_mapped(client_addr)));
+    Ok((Addrs { server, client }, tcp))
+}
+
+fn bind_reuseport(addr: SocketAddr) -> io::Result<tokio::net::TcpListener> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_reuse_address(true)?;
+    set_reuse_port(&socket)?;
+    socket.bind(&addr.into())?;
+    socket.listen(SHARD_BACKLOG)?;
+    // Ensure that O_NONBLOCK is set on the socket before using it with Tokio.
+    socket.set_nonblocking(true)?;
+    tokio::net::TcpListener::from_std(socket.into())
+}
+
+#[cfg(unix)]
+fn set_reuse_port(socket: &socket2::Socket) -> io::Result<()> {
+    socket.set_reuse_port(true)
+}
+
+#[cfg(not(unix))]
+fn set_reuse_port(_: &socket2::Socket) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "SO_REUSEPORT is not supported on this platform",
+    ))
+}
+
 // === impl Addrs ===
 
 impl Param<Remote<ClientAddr>> for Addrs {
@@ -138,3 +245,58 @@ impl Param<AddrPair> for Addrs {
         AddrPair(client, server)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct Server(usize);
+
+    impl Param<ListenAddr> for Server {
+        fn param(&self) -> ListenAddr {
+            ListenAddr(([127, 0, 0, 1], 0).into())
+        }
+    }
+
+    impl Param<AcceptShards> for Server {
+        fn param(&self) -> AcceptShards {
+            AcceptShards(self.0)
+        }
+    }
+
+    impl Param<Keepalive> for Server {
+        fn param(&self) -> Keepalive {
+            Keepalive(None)
+        }
+    }
+
+    impl Param<UserTimeout> for Server {
+        fn param(&self) -> UserTimeout {
+            UserTimeout(None)
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn sharded_listeners_share_port() {
+        let (Local(ServerAddr(addr)), mut incoming) =
+            BindTcp::default().bind(&Server(4)).expect("must bind");
+        assert_ne!(addr.port(), 0, "shards must share the assigned port");
+
+        let mut clients = Vec::new();
+        for _ in 0..16 {
+            clients.push(TcpStream::connect(addr).await.expect("must connect"));
+        }
+        for _ in 0..clients.len() {
+            let (addrs, _) = incoming
+                .next()
+                .await
+                .expect("listener must not close")
+                .expect("must accept");
+            assert_eq!(addrs.server, Local(ServerAddr(addr)));
+            assert!(clients
+                .iter()
+                .any(|c| Remote(ClientAddr(c.local_addr().unwrap())) == addrs.client));
+        }
+    }
+}
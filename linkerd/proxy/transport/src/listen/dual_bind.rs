@@ -1,4 +1,6 @@
-use crate::{addrs::DualListenAddr, listen::Bind, Keepalive, ListenAddr, UserTimeout};
+use crate::{
+    addrs::DualListenAddr, listen::Bind, AcceptShards, Keepalive, ListenAddr, UserTimeout,
+};
 use futures::Stream;
 use linkerd_error::Result;
 use linkerd_stack::Param;
@@ -56,6 +58,12 @@ where
 
 // === impl Listen ===
 
+impl<T: Param<AcceptShards>> Param<AcceptShards> for Listen<T> {
+    fn param(&self) -> AcceptShards {
+        self.parent.param()
+    }
+}
+
 impl<T: Param<Keepalive>> Param<Keepalive> for Listen<T> {
     fn param(&self) -> Keepalive {
         self.parent.param()
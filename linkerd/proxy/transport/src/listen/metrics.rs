@@ -0,0 +1,61 @@
+use crate::addrs::{Local, ServerAddr};
+use linkerd_metrics::prom;
+
+/// Counts the connections accepted by each listener shard.
+#[derive(Clone, Debug, Default)]
+pub struct AcceptMetrics {
+    accepts: prom::Family<AcceptLabels, prom::Counter>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, prom::encoding::EncodeLabelSet)]
+struct AcceptLabels {
+    listen_addr: String,
+    shard: u64,
+}
+
+// === impl AcceptMetrics ===
+
+impl AcceptMetrics {
+    pub fn register(registry: &mut prom::Registry) -> Self {
+        let accepts = prom::Family::default();
+        registry.register(
+            "accepts",
+            "The total number of connections accepted by each listener shard",
+            accepts.clone(),
+        );
+        Self { accepts }
+    }
+
+    pub(super) fn shard(
+        &self,
+        Local(ServerAddr(addr)): Local<ServerAddr>,
+        shard: usize,
+    ) -> prom::Counter {
+        self.accepts
+            .get_or_create(&AcceptLabels {
+                listen_addr: addr.to_string(),
+                shard: shard as u64,
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_per_shard() {
+        let mut registry = prom::Registry::default();
+        let metrics = AcceptMetrics::register(&mut registry);
+        let addr = Local(ServerAddr(([127, 0, 0, 1], 4143).into()));
+        metrics.shard(addr, 0).inc();
+        metrics.shard(addr, 1).inc();
+        metrics.shard(addr, 1).inc();
+
+        let mut out = String::new();
+        prom::encoding::text::encode(&mut out, &registry).unwrap();
+        assert!(out.contains(r#"accepts_total{listen_addr="127.0.0.1:4143",shard="0"} 1"#));
+        assert!(out.contains(r#"accepts_total{listen_addr="127.0.0.1:4143",shard="1"} 2"#));
+    }
+}
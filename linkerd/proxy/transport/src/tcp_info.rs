@@ -0,0 +1,162 @@
+//! Samples the kernel statistics of TCP sockets via `TCP_INFO`.
+
+use linkerd_io as io;
+use std::{os::fd::BorrowedFd, time::Duration};
+
+/// A snapshot of a TCP socket's kernel statistics, as reported by `TCP_INFO`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpStats {
+    /// The smoothed round-trip time.
+    pub rtt: Duration,
+
+    /// The total number of segments retransmitted over the connection's
+    /// lifetime.
+    pub retransmits: u32,
+
+    /// The sender's congestion window, in segments.
+    pub congestion_window: u32,
+
+    /// The most recent delivery rate estimate, in bytes per second, if it is
+    /// reported by the kernel.
+    pub delivery_rate: Option<u64>,
+}
+
+/// Samples the statistics of the given TCP socket.
+///
+/// An `Unsupported` error is returned when the platform does not support
+/// `TCP_INFO`.
+#[cfg(target_os = "linux")]
+#[allow(unsafe_code)]
+pub fn sample(sock: BorrowedFd<'_>) -> io::Result<TcpStats> {
+    use std::{
+        mem::{offset_of, size_of},
+        os::fd::AsRawFd,
+    };
+
+    let mut info = linux::RawTcpInfo::default();
+    let mut len = size_of::<linux::RawTcpInfo>() as libc::socklen_t;
+    // Safety: the descriptor is borrowed for the duration of the call, and
+    // `info` is a valid, writable buffer of `len` bytes; the kernel writes at
+    // most `len` bytes to it.
+    let ret = unsafe {
+        libc::getsockopt(
+            sock.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_INFO,
+            &mut info as *mut linux::RawTcpInfo as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Older kernels return a truncated structure that does not include the
+    // delivery rate.
+    let delivery_rate = (len as usize
+        >= offset_of!(linux::RawTcpInfo, delivery_rate) + size_of::<u64>())
+    .then_some(info.delivery_rate);
+
+    Ok(TcpStats {
+        rtt: Duration::from_micros(info.rtt.into()),
+        retransmits: info.total_retrans,
+        congestion_window: info.snd_cwnd,
+        delivery_rate,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn sample(_: BorrowedFd<'_>) -> io::Result<TcpStats> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "TCP_INFO is not available",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    /// Mirrors the prefix of Linux's `struct tcp_info` through
+    /// `tcpi_delivery_rate`, which is not yet exposed by `libc`.
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    #[allow(dead_code)]
+    pub(super) struct RawTcpInfo {
+        pub state: u8,
+        pub ca_state: u8,
+        pub retransmits: u8,
+        pub probes: u8,
+        pub backoff: u8,
+        pub options: u8,
+        pub wscale: u8,
+        pub flags: u8,
+        pub rto: u32,
+        pub ato: u32,
+        pub snd_mss: u32,
+        pub rcv_mss: u32,
+        pub unacked: u32,
+        pub sacked: u32,
+        pub lost: u32,
+        pub retrans: u32,
+        pub fackets: u32,
+        pub last_data_sent: u32,
+        pub last_ack_sent: u32,
+        pub last_data_recv: u32,
+        pub last_ack_recv: u32,
+        pub pmtu: u32,
+        pub rcv_ssthresh: u32,
+        pub rtt: u32,
+        pub rttvar: u32,
+        pub snd_ssthresh: u32,
+        pub snd_cwnd: u32,
+        pub advmss: u32,
+        pub reordering: u32,
+        pub rcv_rtt: u32,
+        pub rcv_space: u32,
+        pub total_retrans: u32,
+        pub pacing_rate: u64,
+        pub max_pacing_rate: u64,
+        pub bytes_acked: u64,
+        pub bytes_received: u64,
+        pub segs_out: u32,
+        pub segs_in: u32,
+        pub notsent_bytes: u32,
+        pub min_rtt: u32,
+        pub data_segs_in: u32,
+        pub data_segs_out: u32,
+        pub delivery_rate: u64,
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use io::TcpInfo;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn samples_tcp_stream() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (client, server) = tokio::join!(TcpStream::connect(addr), listener.accept());
+        let mut client = client.unwrap();
+        let (mut server, _) = server.unwrap();
+
+        client.write_all(b"hello").await.unwrap();
+        let mut buf = [0u8; 5];
+        server.read_exact(&mut buf).await.unwrap();
+
+        let sock = client.tcp_socket().expect("must be a TCP socket");
+        let stats = sample(sock).expect("must sample");
+        assert!(stats.congestion_window > 0, "{stats:?}");
+        assert_eq!(stats.retransmits, 0, "{stats:?}");
+    }
+
+    #[test]
+    fn duplex_has_no_socket() {
+        let (io, _) = tokio::io::duplex(1);
+        assert!(io.tcp_socket().is_none());
+    }
+}
@@ -2,10 +2,12 @@
 #![forbid(unsafe_code)]
 
 pub mod client;
+pub mod originate;
 pub mod server;
 
 pub use self::{
     client::{Client, ClientTls, ConditionalClientTls, ConnectMeta, NoClientTls, ServerId},
+    originate::{Originate, OriginateTls},
     server::{
         ClientId, ConditionalServerTls, NewDetectRequiredSni, NewDetectTls, NoServerTls,
         NoSniFoundError, ServerTls, SniDetectionTimeoutError,
@@ -0,0 +1,100 @@
+use crate::{client::AlpnProtocols, NegotiatedProtocol, ServerName};
+use futures::prelude::*;
+use linkerd_error::Error;
+use linkerd_io as io;
+use linkerd_stack::{layer, ExtractParam, MakeConnection, NewService, Service, ServiceExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::debug;
+
+/// A stack parameter that configures a connection to be upgraded to TLS for a
+/// server outside of the mesh.
+///
+/// Unlike [`ClientTls`](crate::ClientTls), the server is not authenticated by
+/// a mesh identity: it must present a certificate for `server_name` that is
+/// issued by one of the client's configured web PKI roots.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OriginateTls {
+    pub server_name: ServerName,
+    pub alpn: Option<AlpnProtocols>,
+}
+
+/// Establishes connections to targets, upgrading them to TLS when an
+/// [`OriginateTls`] parameter is extracted from the target.
+///
+/// The protocol negotiated via ALPN, if any, is returned alongside the inner
+/// connection's metadata.
+#[derive(Clone, Debug)]
+pub struct Originate<X, L, C> {
+    extract: X,
+    tls: L,
+    inner: C,
+}
+
+pub type OriginateFuture<I, M> =
+    Pin<Box<dyn Future<Output = Result<(I, M), Error>> + Send + 'static>>;
+
+// === impl Originate ===
+
+impl<X: Clone, L: Clone, C> Originate<X, L, C> {
+    pub fn layer_via(extract: X, tls: L) -> impl layer::Layer<C, Service = Self> + Clone {
+        layer::mk(move |inner| Self {
+            inner,
+            extract: extract.clone(),
+            tls: tls.clone(),
+        })
+    }
+}
+
+impl<T, X, L, H, I, C> Service<T> for Originate<X, L, C>
+where
+    X: ExtractParam<Option<OriginateTls>, T>,
+    L: NewService<OriginateTls, Service = H>,
+    C: MakeConnection<T>,
+    C::Connection: Send + Unpin + 'static,
+    C::Metadata: Send + 'static,
+    C::Future: Send + 'static,
+    H: Service<C::Connection, Response = (I, Option<NegotiatedProtocol>), Error = io::Error>
+        + Send
+        + 'static,
+    H::Future: Send + 'static,
+    I: io::AsyncRead + io::AsyncWrite + Send + Unpin,
+{
+    type Response = (
+        io::EitherIo<C::Connection, I>,
+        (C::Metadata, Option<NegotiatedProtocol>),
+    );
+    type Error = Error;
+    type Future =
+        OriginateFuture<io::EitherIo<C::Connection, I>, (C::Metadata, Option<NegotiatedProtocol>)>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        let handshake = self.extract.extract_param(&target).map(|tls| {
+            debug!(server.name = %tls.server_name, "Originating TLS");
+            self.tls.new_service(tls)
+        });
+
+        let connect = self.inner.connect(target);
+        Box::pin(async move {
+            let (io, meta) = connect.await.map_err(Into::into)?;
+            let Some(handshake) = handshake else {
+                return Ok((io::EitherIo::Left(io), (meta, None)));
+            };
+            let (io, alpn) = handshake.oneshot(io).await?;
+            debug!(
+                alpn = alpn
+                    .as_ref()
+                    .and_then(|NegotiatedProtocol(ref p)| std::str::from_utf8(p).ok())
+                    .map(tracing::field::display)
+            );
+            Ok((io::EitherIo::Right(io), (meta, alpn)))
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc};
+
+/// A handle for adding attributes to the span of the request being
+/// instrumented.
+///
+/// The [`TraceContext`](crate::TraceContext) service inserts a handle into the
+/// extensions of each request it records, so that inner services may describe
+/// how the request was handled (e.g. the route and backend it was dispatched
+/// to). Attributes are recorded on the span when the response is received.
+#[derive(Clone, Debug, Default)]
+pub struct SpanAttributes(Arc<Mutex<HashMap<&'static str, String>>>);
+
+// === impl SpanAttributes ===
+
+impl SpanAttributes {
+    /// Returns the handle for the span of the given request, if it is being
+    /// recorded.
+    pub fn get<B>(req: &http::Request<B>) -> Option<&Self> {
+        req.extensions().get::<Self>()
+    }
+
+    /// Sets an attribute on the span, replacing any prior value.
+    pub fn insert(&self, key: &'static str, value: impl ToString) {
+        self.0.lock().insert(key, value.to_string());
+    }
+
+    pub(crate) fn take(&self) -> HashMap<&'static str, String> {
+        std::mem::take(&mut *self.0.lock())
+    }
+}
@@ -1,11 +1,18 @@
 #![deny(rust_2018_idioms, clippy::disallowed_methods, clippy::disallowed_types)]
 #![forbid(unsafe_code)]
 
+mod attributes;
 pub mod export;
 mod propagation;
+mod sampler;
 mod service;
 
-pub use self::service::TraceContext;
+pub use self::{
+    attributes::SpanAttributes,
+    propagation::{Format, Formats, UnknownFormat},
+    sampler::Sampler,
+    service::TraceContext,
+};
 use bytes::Bytes;
 use linkerd_error::Error;
 use rand::Rng;
@@ -15,10 +22,36 @@ use std::time::SystemTime;
 use thiserror::Error;
 
 const SPAN_ID_LEN: usize = 8;
+const TRACE_ID_LEN: usize = 16;
+
+/// Returns the trace ID and span ID carried by the request in the first of the
+/// configured formats, if any.
+///
+/// Trace IDs shorter than 16 bytes (e.g. 64-bit B3 trace IDs) are padded so
+/// that they may be rendered as W3C trace IDs.
+pub fn trace_ids<B>(request: &http::Request<B>, formats: &Formats) -> Option<(Id, Id)> {
+    let context = propagation::unpack_trace_context(request, formats)?;
+    let mut trace_id = context.trace_id.0;
+    if trace_id.len() < TRACE_ID_LEN {
+        trace_id.splice(
+            0..0,
+            std::iter::repeat(0).take(TRACE_ID_LEN - trace_id.len()),
+        );
+    }
+    Some((Id(trace_id), context.parent_id))
+}
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Id(Vec<u8>);
 
+/// A request extension identifying the span that the proxy records for a
+/// sampled request.
+#[derive(Clone, Debug)]
+pub struct SampledSpan {
+    pub trace_id: Id,
+    pub span_id: Id,
+}
+
 #[derive(Debug, Error)]
 #[error("ID '{:?} should have {} bytes, but it has {}", self.id, self.expected_size, self.actual_size)]
 pub struct IdLengthError {
@@ -84,6 +117,12 @@ impl Id {
         rng.fill(bytes.as_mut_slice());
         Self(bytes)
     }
+
+    fn new_trace_id<R: Rng>(rng: &mut R) -> Self {
+        let mut bytes = vec![0; TRACE_ID_LEN];
+        rng.fill(bytes.as_mut_slice());
+        Self(bytes)
+    }
 }
 
 impl From<Id> for Vec<u8> {
@@ -116,8 +155,10 @@ impl From<Bytes> for Id {
 // === impl Flags ===
 
 impl Flags {
+    const SAMPLED: u8 = 1;
+
     pub fn is_sampled(&self) -> bool {
-        self.0 & 1 == 1
+        self.0 & Self::SAMPLED == Self::SAMPLED
     }
 }
 
@@ -134,3 +175,16 @@ impl TryFrom<Bytes> for Flags {
         buf.first().map(|b| Flags(*b)).ok_or(InsufficientBytes)
     }
 }
+
+#[cfg(test)]
+#[test]
+fn trace_ids_padded() {
+    let req = http::Request::builder()
+        .header("x-b3-traceid", "0102030405060708")
+        .header("x-b3-spanid", "0a0b0c0d0e0f0001")
+        .body(())
+        .unwrap();
+    let (trace_id, span_id) = trace_ids(&req, &Formats::default()).expect("must have trace ids");
+    assert_eq!(trace_id.to_string(), "00000000000000000102030405060708");
+    assert_eq!(span_id.to_string(), "0a0b0c0d0e0f0001");
+}
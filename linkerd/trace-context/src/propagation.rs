@@ -1,17 +1,47 @@
 use crate::{Flags, Id, InsufficientBytes};
 use bytes::Bytes;
-
+use rand::Rng;
+use std::{str::FromStr, sync::Arc, time::SystemTime};
 use thiserror::Error;
-use tracing::debug;
+use tracing::{debug, trace};
 
 mod b3;
+mod datadog;
 mod w3c;
+mod xray;
+
+/// A format in which trace contexts are propagated in request headers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// W3C `traceparent` headers.
+    W3C,
+    /// B3 `x-b3-*` headers, or an OpenCensus `grpc-trace-bin` header.
+    B3,
+    /// Datadog `x-datadog-*` headers.
+    Datadog,
+    /// AWS X-Ray `X-Amzn-Trace-Id` headers.
+    XRay,
+}
+
+/// Configures the formats from which trace contexts are extracted and into
+/// which they are injected.
+#[derive(Clone, Debug)]
+pub struct Formats {
+    extract: Arc<[Format]>,
+    inject: Arc<[Format]>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("unknown trace propagation format: {0}")]
+pub struct UnknownFormat(String);
 
 #[derive(Debug)]
 pub enum Propagation {
     B3Http,
     B3Grpc,
     W3CHttp,
+    Datadog,
+    XRay,
 }
 
 #[derive(Debug)]
@@ -26,34 +56,170 @@ pub struct TraceContext {
 #[error("unknown field ID {0}")]
 struct UnknownFieldId(u8);
 
+// === impl Format ===
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("w3c") || s.eq_ignore_ascii_case("tracecontext") {
+            Ok(Self::W3C)
+        } else if s.eq_ignore_ascii_case("b3") {
+            Ok(Self::B3)
+        } else if s.eq_ignore_ascii_case("datadog") {
+            Ok(Self::Datadog)
+        } else if s.eq_ignore_ascii_case("xray") {
+            Ok(Self::XRay)
+        } else {
+            Err(UnknownFormat(s.to_string()))
+        }
+    }
+}
+
+// === impl Formats ===
+
+impl Formats {
+    /// Extracts trace contexts from the first matching format in `extract`.
+    ///
+    /// A context is always propagated in the format from which it was
+    /// extracted. It is additionally injected in each of the `inject` formats.
+    pub fn new(
+        extract: impl IntoIterator<Item = Format>,
+        inject: impl IntoIterator<Item = Format>,
+    ) -> Self {
+        Self {
+            extract: extract.into_iter().collect(),
+            inject: inject.into_iter().collect(),
+        }
+    }
+
+    /// The formats from which trace contexts are extracted, in order.
+    pub fn extract(&self) -> &[Format] {
+        &self.extract
+    }
+
+    /// The formats in which trace contexts are additionally injected.
+    pub fn inject(&self) -> &[Format] {
+        &self.inject
+    }
+
+    /// The format in which new traces are propagated.
+    fn root(&self) -> Propagation {
+        match self.inject.first().or(self.extract.first()) {
+            Some(Format::B3) => Propagation::B3Http,
+            Some(Format::Datadog) => Propagation::Datadog,
+            Some(Format::XRay) => Propagation::XRay,
+            Some(Format::W3C) | None => Propagation::W3CHttp,
+        }
+    }
+}
+
+impl Default for Formats {
+    fn default() -> Self {
+        Self::new([Format::W3C, Format::B3], None)
+    }
+}
+
+// === impl Propagation ===
+
+impl Propagation {
+    fn is(&self, format: Format) -> bool {
+        matches!(
+            (self, format),
+            (Self::W3CHttp, Format::W3C)
+                | (Self::B3Http | Self::B3Grpc, Format::B3)
+                | (Self::Datadog, Format::Datadog)
+                | (Self::XRay, Format::XRay)
+        )
+    }
+}
+
 // === impl TraceContext ===
 
 impl TraceContext {
     pub fn is_sampled(&self) -> bool {
         self.flags.is_sampled()
     }
+
+    /// Returns true if the context starts a new trace, i.e. it has no parent
+    /// span.
+    fn is_root(&self) -> bool {
+        self.parent_id.as_ref().is_empty()
+    }
 }
 
 /// Given an http request, attempt to unpack a distributed tracing context from
-/// the headers. Only w3c and b3 context propagation formats are supported. The
-/// former is tried first, and if no headers are present, function will attempt
-/// to unpack as b3.
-pub fn unpack_trace_context<B>(request: &http::Request<B>) -> Option<TraceContext> {
-    // Attempt to parse as w3c first since it's the newest interface in
-    // distributed tracing ecosystem
-    w3c::unpack_w3c_trace_context(request)
-        .or_else(|| b3::unpack_grpc_trace_context(request))
-        .or_else(|| b3::unpack_http_trace_context(request))
+/// the headers. Each of the configured formats is tried in order.
+pub fn unpack_trace_context<B>(
+    request: &http::Request<B>,
+    formats: &Formats,
+) -> Option<TraceContext> {
+    formats.extract.iter().find_map(|format| match format {
+        Format::W3C => w3c::unpack_w3c_trace_context(request),
+        Format::B3 => b3::unpack_grpc_trace_context(request)
+            .or_else(|| b3::unpack_http_trace_context(request)),
+        Format::Datadog => datadog::unpack_http_trace_context(request),
+        Format::XRay => xray::unpack_http_trace_context(request),
+    })
+}
+
+/// Creates a sampled context for a new trace.
+///
+/// The context has no parent span, so the proxy's span is the trace's root.
+/// It is propagated in the first of the configured inject formats, or the
+/// first extract format if none are configured. If the trace is propagated as
+/// X-Ray, its ID begins with the current time, as X-Ray requires.
+pub fn new_root_context<R: Rng>(rng: &mut R, formats: &Formats) -> TraceContext {
+    let propagation = formats.root();
+    let mut trace_id = Id::new_trace_id(rng);
+    if propagation.is(Format::XRay) || formats.inject.contains(&Format::XRay) {
+        xray::set_epoch(&mut trace_id, SystemTime::now());
+    }
+    TraceContext {
+        propagation,
+        trace_id,
+        parent_id: Id::default(),
+        flags: Flags(Flags::SAMPLED),
+    }
 }
 
 // Generates a new span id, writes it to the request in the appropriate
-// propagation format and returns the generated span id.
-pub fn increment_span_id<B>(request: &mut http::Request<B>, context: &TraceContext) -> Id {
+// propagation format (and in each additional inject format) and returns the
+// generated span id. A new trace has no headers to update, so all of its
+// headers are written.
+pub fn increment_span_id<B>(
+    request: &mut http::Request<B>,
+    context: &TraceContext,
+    formats: &Formats,
+) -> Id {
+    let span_id = Id::new_span_id(&mut rand::thread_rng());
+    trace!(%span_id, "Incremented span id");
+
     match context.propagation {
-        Propagation::B3Grpc => b3::increment_grpc_span_id(request, context),
-        Propagation::B3Http => b3::increment_http_span_id(request),
-        Propagation::W3CHttp => w3c::increment_http_span_id(request, context),
+        Propagation::B3Grpc => b3::set_grpc_span_id(request, context, &span_id),
+        Propagation::B3Http if context.is_root() => b3::inject_http(request, context, &span_id),
+        Propagation::B3Http => b3::set_http_span_id(request, &span_id),
+        Propagation::W3CHttp => w3c::set_http_span_id(request, context, &span_id),
+        Propagation::Datadog if context.is_root() => {
+            datadog::inject_http(request, context, &span_id)
+        }
+        Propagation::Datadog => datadog::set_parent_id(request, &span_id),
+        Propagation::XRay => xray::set_parent_id(request, context, &span_id),
     }
+
+    for format in formats.inject.iter().copied() {
+        if context.propagation.is(format) {
+            continue;
+        }
+        match format {
+            Format::W3C => w3c::set_http_span_id(request, context, &span_id),
+            Format::B3 => b3::inject_http(request, context, &span_id),
+            Format::Datadog => datadog::inject_http(request, context, &span_id),
+            Format::XRay => xray::inject_http(request, context, &span_id),
+        }
+    }
+
+    span_id
 }
 
 // === Header parse utils ===
@@ -92,3 +258,116 @@ fn try_split_to(buf: &mut Bytes, n: usize) -> Result<Bytes, InsufficientBytes> {
         Err(InsufficientBytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_in_configured_order() {
+        let req = http::Request::builder()
+            .header("x-datadog-trace-id", "1234")
+            .header("x-datadog-parent-id", "5678")
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .body(())
+            .unwrap();
+
+        let ctx = unpack_trace_context(&req, &Formats::default()).unwrap();
+        assert!(matches!(ctx.propagation, Propagation::W3CHttp));
+
+        let formats = Formats::new([Format::Datadog, Format::W3C], None);
+        let ctx = unpack_trace_context(&req, &formats).unwrap();
+        assert!(matches!(ctx.propagation, Propagation::Datadog));
+
+        let formats = Formats::new([Format::XRay], None);
+        assert!(unpack_trace_context(&req, &formats).is_none());
+    }
+
+    #[test]
+    fn injects_additional_formats() {
+        let mut req = http::Request::builder()
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .body(())
+            .unwrap();
+        let formats = Formats::new([Format::W3C], [Format::W3C, Format::XRay]);
+        let ctx = unpack_trace_context(&req, &formats).unwrap();
+        let span_id = increment_span_id(&mut req, &ctx, &formats);
+
+        let w3c = w3c::unpack_w3c_trace_context(&req).unwrap();
+        assert_eq!(w3c.parent_id.0, span_id.0);
+        let xray = xray::unpack_http_trace_context(&req).unwrap();
+        assert_eq!(xray.trace_id.0, ctx.trace_id.0);
+        assert_eq!(xray.parent_id.0, span_id.0);
+        assert!(xray.is_sampled());
+        assert!(!req.headers().contains_key("x-b3-traceid"));
+    }
+
+    #[test]
+    fn injects_sampled_root_contexts() {
+        let mut rng = rand::thread_rng();
+
+        let formats = Formats::new([Format::B3], None);
+        let ctx = new_root_context(&mut rng, &formats);
+        let mut req = http::Request::new(());
+        let span_id = increment_span_id(&mut req, &ctx, &formats);
+        let headers = req.headers();
+        assert_eq!(headers["x-b3-traceid"], hex::encode(&ctx.trace_id.0));
+        assert_eq!(headers["x-b3-spanid"], hex::encode(&span_id.0));
+        assert_eq!(headers["x-b3-sampled"], "1");
+        let b3 = b3::unpack_http_trace_context(&req).unwrap();
+        assert_eq!(b3.trace_id.0, ctx.trace_id.0);
+        assert!(b3.is_sampled());
+
+        let formats = Formats::new([Format::Datadog], None);
+        let ctx = new_root_context(&mut rng, &formats);
+        let mut req = http::Request::new(());
+        let span_id = increment_span_id(&mut req, &ctx, &formats);
+        let headers = req.headers();
+        let low = u64::from_be_bytes(ctx.trace_id.0[8..].try_into().unwrap());
+        assert_eq!(headers["x-datadog-trace-id"], low.to_string());
+        let parent = u64::from_be_bytes(span_id.0[..].try_into().unwrap());
+        assert_eq!(headers["x-datadog-parent-id"], parent.to_string());
+        assert_eq!(headers["x-datadog-sampling-priority"], "1");
+        let datadog = datadog::unpack_http_trace_context(&req).unwrap();
+        assert_eq!(datadog.trace_id.0, ctx.trace_id.0);
+        assert!(datadog.is_sampled());
+    }
+
+    #[test]
+    fn xray_root_contexts_begin_with_the_current_time() {
+        let before = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let formats = Formats::new([Format::XRay], None);
+        let ctx = new_root_context(&mut rand::thread_rng(), &formats);
+        let mut req = http::Request::new(());
+        increment_span_id(&mut req, &ctx, &formats);
+        let after = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let header = req.headers()["x-amzn-trace-id"].to_str().unwrap();
+        let epoch = header
+            .strip_prefix("Root=1-")
+            .and_then(|root| root.get(..8))
+            .unwrap();
+        let epoch = u64::from_str_radix(epoch, 16).unwrap();
+        assert!((before..=after).contains(&epoch), "{header}");
+        assert!(header.ends_with(";Sampled=1"), "{header}");
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("W3C".parse::<Format>().unwrap(), Format::W3C);
+        assert_eq!("datadog".parse::<Format>().unwrap(), Format::Datadog);
+        assert!("jaeger".parse::<Format>().is_err());
+    }
+}
@@ -3,7 +3,6 @@ use crate::{Flags, Id};
 use bytes::Bytes;
 use http::header::{HeaderName, HeaderValue};
 use linkerd_error::Error;
-use rand::thread_rng;
 
 use tracing::{debug, trace};
 
@@ -21,11 +20,7 @@ const GRPC_TRACE_FIELD_TRACE_OPTIONS: u8 = 2;
 // This code looks significantly weirder if some of the elements are added using
 // the `vec![]` macro, despite clippy's suggestions otherwise...
 #[allow(clippy::vec_init_then_push)]
-pub fn increment_grpc_span_id<B>(request: &mut http::Request<B>, context: &TraceContext) -> Id {
-    let span_id = Id::new_span_id(&mut thread_rng());
-
-    trace!(%span_id, "Incremented span id");
-
+pub fn set_grpc_span_id<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
     let mut bytes = Vec::<u8>::new();
 
     // version
@@ -50,22 +45,30 @@ pub fn increment_grpc_span_id<B>(request: &mut http::Request<B>, context: &Trace
     } else {
         debug!(header = %GRPC_TRACE_HEADER, header_value = %bytes_b64, "Invalid non-ASCII or control character in header value");
     }
-    span_id
 }
 
-pub fn increment_http_span_id<B>(request: &mut http::Request<B>) -> Id {
-    let span_id = Id::new_span_id(&mut thread_rng());
-
-    trace!(%span_id, "Incremented span id");
+pub fn set_http_span_id<B>(request: &mut http::Request<B>, span_id: &Id) {
+    insert_hex(request, &HTTP_SPAN_ID_HEADER, span_id);
+}
 
-    let span_str = hex::encode(span_id.as_ref());
+/// Writes all B3 headers for the given context, e.g. when the context was
+/// extracted from another format.
+pub fn inject_http<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
+    insert_hex(request, &HTTP_TRACE_ID_HEADER, &context.trace_id);
+    insert_hex(request, &HTTP_SPAN_ID_HEADER, span_id);
+    let sampled = if context.is_sampled() { "1" } else { "0" };
+    request
+        .headers_mut()
+        .insert(&HTTP_SAMPLED_HEADER, HeaderValue::from_static(sampled));
+}
 
-    if let Ok(hv) = HeaderValue::from_str(&span_str) {
-        request.headers_mut().insert(&HTTP_SPAN_ID_HEADER, hv);
+fn insert_hex<B>(request: &mut http::Request<B>, header: &HeaderName, id: &Id) {
+    let value = hex::encode(id.as_ref());
+    if let Ok(hv) = HeaderValue::from_str(&value) {
+        request.headers_mut().insert(header, hv);
     } else {
-        debug!(header = %HTTP_SPAN_ID_HEADER, header_value = %span_str, "Invalid non-ASCII or control character in header value");
+        debug!(%header, header_value = %value, "Invalid non-ASCII or control character in header value");
     }
-    span_id
 }
 
 pub fn unpack_grpc_trace_context<B>(request: &http::Request<B>) -> Option<TraceContext> {
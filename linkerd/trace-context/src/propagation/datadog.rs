@@ -0,0 +1,178 @@
+use http::header::{HeaderName, HeaderValue};
+use tracing::debug;
+
+use super::{get_header_str, Propagation, TraceContext};
+use crate::{Flags, Id};
+
+static HTTP_TRACE_ID_HEADER: HeaderName = HeaderName::from_static("x-datadog-trace-id");
+static HTTP_PARENT_ID_HEADER: HeaderName = HeaderName::from_static("x-datadog-parent-id");
+static HTTP_SAMPLING_PRIORITY_HEADER: HeaderName =
+    HeaderName::from_static("x-datadog-sampling-priority");
+static HTTP_TAGS_HEADER: HeaderName = HeaderName::from_static("x-datadog-tags");
+
+/// Carries the upper 64 bits of a 128-bit trace ID, hex-encoded.
+const TRACE_ID_HIGH_TAG: &str = "_dd.p.tid";
+
+/// Unpacks a trace context from Datadog headers.
+///
+/// Datadog encodes trace and span IDs as unsigned 64-bit decimal integers. The
+/// upper 64 bits of a 128-bit trace ID may be carried in the `_dd.p.tid` tag.
+pub fn unpack_http_trace_context<B>(request: &http::Request<B>) -> Option<TraceContext> {
+    let trace_id_low = parse_header_u64(request, &HTTP_TRACE_ID_HEADER)?;
+    let parent_id = parse_header_u64(request, &HTTP_PARENT_ID_HEADER)?;
+    let trace_id_high = get_header_str(request, &HTTP_TAGS_HEADER)
+        .and_then(parse_trace_id_high)
+        .unwrap_or(0);
+
+    // A positive sampling priority indicates that the trace should be kept.
+    let sampled = get_header_str(request, &HTTP_SAMPLING_PRIORITY_HEADER)
+        .and_then(|p| p.trim().parse::<i32>().ok())
+        .is_some_and(|p| p > 0);
+
+    let mut trace_id = trace_id_high.to_be_bytes().to_vec();
+    trace_id.extend_from_slice(&trace_id_low.to_be_bytes());
+    Some(TraceContext {
+        propagation: Propagation::Datadog,
+        trace_id: Id(trace_id),
+        parent_id: Id(parent_id.to_be_bytes().to_vec()),
+        flags: Flags(sampled as u8),
+    })
+}
+
+pub fn set_parent_id<B>(request: &mut http::Request<B>, span_id: &Id) {
+    let Some(span_id) = to_u64(span_id.as_ref()) else {
+        debug!(header = %HTTP_PARENT_ID_HEADER, "Span ID must have 8 bytes");
+        return;
+    };
+    request
+        .headers_mut()
+        .insert(&HTTP_PARENT_ID_HEADER, HeaderValue::from(span_id));
+}
+
+/// Writes all Datadog headers for the given context, e.g. when the context was
+/// extracted from another format.
+pub fn inject_http<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
+    let trace_id = context.trace_id.as_ref();
+    let Some((high, low)) = trace_id
+        .split_at_checked(trace_id.len().saturating_sub(8))
+        .and_then(|(high, low)| Some((to_u64(high).unwrap_or(0), to_u64(low)?)))
+    else {
+        debug!(header = %HTTP_TRACE_ID_HEADER, "Trace ID must have at least 8 bytes");
+        return;
+    };
+
+    let headers = request.headers_mut();
+    headers.insert(&HTTP_TRACE_ID_HEADER, HeaderValue::from(low));
+    let priority = if context.is_sampled() { "1" } else { "0" };
+    headers.insert(
+        &HTTP_SAMPLING_PRIORITY_HEADER,
+        HeaderValue::from_static(priority),
+    );
+    if high != 0 && !headers.contains_key(&HTTP_TAGS_HEADER) {
+        if let Ok(hv) = HeaderValue::from_str(&format!("{TRACE_ID_HIGH_TAG}={high:016x}")) {
+            headers.insert(&HTTP_TAGS_HEADER, hv);
+        }
+    }
+    set_parent_id(request, span_id);
+}
+
+fn parse_header_u64<B>(request: &http::Request<B>, header: &HeaderName) -> Option<u64> {
+    let header_value = get_header_str(request, header)?;
+    match header_value.trim().parse::<u64>() {
+        Ok(0) => {
+            debug!(%header, "Id in header value must not be zero");
+            None
+        }
+        Ok(id) => Some(id),
+        Err(error) => {
+            debug!(%header, %header_value, %error, "Id in header value is not a decimal integer");
+            None
+        }
+    }
+}
+
+fn parse_trace_id_high(tags: &str) -> Option<u64> {
+    tags.split(',').find_map(|tag| {
+        let (key, value) = tag.split_once('=')?;
+        if key.trim() != TRACE_ID_HIGH_TAG {
+            return None;
+        }
+        u64::from_str_radix(value.trim(), 16).ok()
+    })
+}
+
+fn to_u64(bytes: &[u8]) -> Option<u64> {
+    Some(u64::from_be_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datadog_context_parsed_successfully() {
+        let req = http::Request::builder()
+            .header("x-datadog-trace-id", "1234")
+            .header("x-datadog-parent-id", "5678")
+            .header("x-datadog-sampling-priority", "2")
+            .header("x-datadog-tags", "_dd.p.dm=-4,_dd.p.tid=640cfd8d00000000")
+            .body(())
+            .unwrap();
+        let ctx = unpack_http_trace_context(&req).expect("must parse");
+        assert_eq!(
+            hex::encode(ctx.trace_id.as_ref()),
+            "640cfd8d0000000000000000000004d2"
+        );
+        assert_eq!(hex::encode(ctx.parent_id.as_ref()), "000000000000162e");
+        assert!(ctx.is_sampled());
+    }
+
+    #[test]
+    fn datadog_context_unsampled() {
+        for priority in [None, Some("0"), Some("-1")] {
+            let mut req = http::Request::builder()
+                .header("x-datadog-trace-id", "1234")
+                .header("x-datadog-parent-id", "5678");
+            if let Some(p) = priority {
+                req = req.header("x-datadog-sampling-priority", p);
+            }
+            let ctx = unpack_http_trace_context(&req.body(()).unwrap()).unwrap();
+            assert!(!ctx.is_sampled(), "{priority:?}");
+        }
+    }
+
+    #[test]
+    fn datadog_context_invalid() {
+        for (trace_id, parent_id) in [("0", "5678"), ("1234", "abc"), ("-1", "5678")] {
+            let req = http::Request::builder()
+                .header("x-datadog-trace-id", trace_id)
+                .header("x-datadog-parent-id", parent_id)
+                .body(())
+                .unwrap();
+            assert!(unpack_http_trace_context(&req).is_none());
+        }
+    }
+
+    #[test]
+    fn datadog_context_injected() {
+        let ctx = TraceContext {
+            propagation: Propagation::W3CHttp,
+            trace_id: Id(hex::decode("640cfd8d0000000000000000000004d2").unwrap()),
+            parent_id: Id(vec![0; 8]),
+            flags: Flags(1),
+        };
+        let mut req = http::Request::new(());
+        inject_http(&mut req, &ctx, &Id(5678u64.to_be_bytes().to_vec()));
+
+        assert_eq!(req.headers()["x-datadog-trace-id"], "1234");
+        assert_eq!(req.headers()["x-datadog-parent-id"], "5678");
+        assert_eq!(req.headers()["x-datadog-sampling-priority"], "1");
+        assert_eq!(
+            req.headers()["x-datadog-tags"],
+            "_dd.p.tid=640cfd8d00000000"
+        );
+
+        let unpacked = unpack_http_trace_context(&req).unwrap();
+        assert_eq!(unpacked.trace_id.0, ctx.trace_id.0);
+    }
+}
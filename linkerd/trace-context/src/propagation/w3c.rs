@@ -1,5 +1,5 @@
 use http::header::HeaderName;
-use tracing::{debug, trace};
+use tracing::debug;
 
 use super::{decode_id_with_padding, get_header_str, Propagation, TraceContext};
 use crate::{Flags, Id};
@@ -11,14 +11,13 @@ pub fn unpack_w3c_trace_context<B>(request: &http::Request<B>) -> Option<TraceCo
     get_header_str(request, &HTTP_TRACEPARENT).and_then(parse_context)
 }
 
-/// Given an http request and a w3c trace context, create a new Span ID and
-/// assign it to the tracecontext header value, in order to propagate
-/// the trace context downstream.
-pub fn increment_http_span_id<B>(request: &mut http::Request<B>, context: &TraceContext) -> Id {
-    let span_id = Id::new_span_id(&mut rand::thread_rng());
-
-    trace!(%span_id, "Incremented span id");
-
+/// Given an http request and a trace context, assign the span ID to the
+/// traceparent header value, in order to propagate the trace context
+/// downstream.
+///
+/// The `tracestate` and `baggage` headers are opaque to the proxy and are
+/// forwarded unmodified.
+pub fn set_http_span_id<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
     let new_header = {
         let mut buf = String::with_capacity(60);
         buf.push_str(VERSION_00);
@@ -36,8 +35,6 @@ pub fn increment_http_span_id<B>(request: &mut http::Request<B>, context: &Trace
     } else {
         debug!(header = %HTTP_TRACEPARENT, header_value = %new_header, "Invalid non-ASCII or control character in header value");
     }
-
-    span_id
 }
 
 /// Parse a given header value as a w3c TraceContext value.
@@ -97,7 +94,8 @@ fn parse_header_value(next_header_value: &str, pad_to: usize) -> Option<(Id, &st
 
 #[cfg(test)]
 mod tests {
-    use super::parse_context;
+    use super::{parse_context, set_http_span_id, unpack_w3c_trace_context};
+    use crate::Id;
 
     #[test]
     fn w3c_context_parsed_successfully() {
@@ -143,4 +141,40 @@ mod tests {
         let input = "00-94d7f6ec6b95f3e916179cb6cfd013901-55ccfce77972614-01";
         assert!(parse_context(input).is_none());
     }
+
+    #[test]
+    fn w3c_root_context_injected() {
+        let root = super::super::new_root_context(&mut rand::thread_rng(), &Default::default());
+        let mut req = http::Request::new(());
+        let span_id = Id::new_span_id(&mut rand::thread_rng());
+        set_http_span_id(&mut req, &root, &span_id);
+
+        let ctx = unpack_w3c_trace_context(&req).expect("traceparent must be set");
+        assert_eq!(ctx.trace_id.0, root.trace_id.0);
+        assert_eq!(ctx.parent_id.0, span_id.0);
+        assert!(ctx.is_sampled());
+    }
+
+    #[test]
+    fn w3c_tracestate_and_baggage_forwarded() {
+        let mut req = http::Request::builder()
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .header("tracestate", "vendor=opaque")
+            .header("baggage", "userId=alice")
+            .body(())
+            .unwrap();
+        let ctx = unpack_w3c_trace_context(&req).unwrap();
+        let span_id = Id::new_span_id(&mut rand::thread_rng());
+        set_http_span_id(&mut req, &ctx, &span_id);
+
+        assert_eq!(
+            req.headers()["traceparent"],
+            format!("00-94d7f6ec6b95f3e916179cb6cfd01390-{span_id}-01")
+        );
+        assert_eq!(req.headers()["tracestate"], "vendor=opaque");
+        assert_eq!(req.headers()["baggage"], "userId=alice");
+    }
 }
@@ -0,0 +1,223 @@
+use http::header::{HeaderName, HeaderValue};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use super::{decode_id_with_padding, get_header_str, Propagation, TraceContext};
+use crate::{Flags, Id, SPAN_ID_LEN};
+
+static HTTP_TRACE_ID_HEADER: HeaderName = HeaderName::from_static("x-amzn-trace-id");
+const ROOT_VERSION: &str = "1";
+
+/// Unpacks a trace context from an `X-Amzn-Trace-Id` header, e.g.
+/// `Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1`.
+///
+/// A header without a `Parent` field (e.g. as set by a load balancer) starts a
+/// new trace.
+pub fn unpack_http_trace_context<B>(request: &http::Request<B>) -> Option<TraceContext> {
+    let header_value = get_header_str(request, &HTTP_TRACE_ID_HEADER)?;
+
+    let mut trace_id = None;
+    let mut parent_id = None;
+    let mut flags = Flags(0);
+    for (key, value) in fields(header_value) {
+        match key {
+            "Root" => trace_id = Some(parse_root(value)?),
+            "Parent" => {
+                parent_id = Some(
+                    decode_id_with_padding(value, SPAN_ID_LEN)
+                        .map_err(|error| debug!(header = %HTTP_TRACE_ID_HEADER, %error, %value, "Parent in header value contains invalid hex"))
+                        .ok()?,
+                )
+            }
+            "Sampled" => flags = Flags((value == "1") as u8),
+            _ => {}
+        }
+    }
+
+    let Some(trace_id) = trace_id else {
+        debug!(header = %HTTP_TRACE_ID_HEADER, %header_value, "Header value does not contain a root trace ID");
+        return None;
+    };
+    Some(TraceContext {
+        propagation: Propagation::XRay,
+        trace_id,
+        parent_id: parent_id.unwrap_or_default(),
+        flags,
+    })
+}
+
+/// Assigns the span ID to the `Parent` field, preserving the header's other
+/// fields.
+pub fn set_parent_id<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
+    let Some(header_value) = get_header_str(request, &HTTP_TRACE_ID_HEADER) else {
+        return inject_http(request, context, span_id);
+    };
+
+    let parent = span_id.to_string();
+    let mut has_parent = false;
+    let mut new_header = fields(header_value)
+        .map(|(key, value)| {
+            if key == "Parent" {
+                has_parent = true;
+                format!("{key}={parent}")
+            } else {
+                format!("{key}={value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    if !has_parent {
+        new_header.push_str(";Parent=");
+        new_header.push_str(&parent);
+    }
+    insert(request, new_header);
+}
+
+/// Writes an `X-Amzn-Trace-Id` header for the given context, e.g. when the
+/// context was extracted from another format.
+pub fn inject_http<B>(request: &mut http::Request<B>, context: &TraceContext, span_id: &Id) {
+    let trace_id = hex::encode(context.trace_id.as_ref());
+    if trace_id.len() != 32 {
+        debug!(header = %HTTP_TRACE_ID_HEADER, "Trace ID must have 16 bytes");
+        return;
+    }
+    let (epoch, unique) = trace_id.split_at(8);
+    let sampled = if context.is_sampled() { 1 } else { 0 };
+    insert(
+        request,
+        format!("Root={ROOT_VERSION}-{epoch}-{unique};Parent={span_id};Sampled={sampled}"),
+    );
+}
+
+/// Replaces the first 4 bytes of a new trace ID with the given time, in Unix
+/// epoch seconds, since X-Ray rejects trace IDs that were not started recently.
+pub fn set_epoch(trace_id: &mut Id, now: SystemTime) {
+    let secs = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as u32)
+        .unwrap_or_default();
+    if let Some(epoch) = trace_id.0.get_mut(..4) {
+        epoch.copy_from_slice(&secs.to_be_bytes());
+    }
+}
+
+fn insert<B>(request: &mut http::Request<B>, header_value: String) {
+    if let Ok(hv) = HeaderValue::from_str(&header_value) {
+        request.headers_mut().insert(&HTTP_TRACE_ID_HEADER, hv);
+    } else {
+        debug!(header = %HTTP_TRACE_ID_HEADER, %header_value, "Invalid non-ASCII or control character in header value");
+    }
+}
+
+fn fields(header_value: &str) -> impl Iterator<Item = (&str, &str)> {
+    header_value.split(';').filter_map(|field| {
+        let (key, value) = field.split_once('=')?;
+        Some((key.trim(), value.trim()))
+    })
+}
+
+/// Parses a root trace ID, which consists of a version, an 8-character epoch,
+/// and a 24-character unique identifier, e.g. `1-5759e988-bd862e3fe1be46a994272793`.
+fn parse_root(value: &str) -> Option<Id> {
+    let mut parts = value.split('-');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(ROOT_VERSION), Some(epoch), Some(unique), None)
+            if epoch.len() == 8 && unique.len() == 24 =>
+        {
+            hex::decode(format!("{epoch}{unique}"))
+                .map(Id)
+                .map_err(|error| debug!(header = %HTTP_TRACE_ID_HEADER, %error, %value, "Root in header value contains invalid hex"))
+                .ok()
+        }
+        _ => {
+            debug!(header = %HTTP_TRACE_ID_HEADER, %value, "Invalid root trace ID");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(header: &str) -> http::Request<()> {
+        http::Request::builder()
+            .header("x-amzn-trace-id", header)
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn xray_context_parsed_successfully() {
+        let req =
+            request("Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1");
+        let ctx = unpack_http_trace_context(&req).expect("must parse");
+        assert_eq!(
+            hex::encode(ctx.trace_id.as_ref()),
+            "5759e988bd862e3fe1be46a994272793"
+        );
+        assert_eq!(hex::encode(ctx.parent_id.as_ref()), "53995c3f42cd8ad8");
+        assert!(ctx.is_sampled());
+    }
+
+    #[test]
+    fn xray_context_without_parent() {
+        let req = request("Root=1-5759e988-bd862e3fe1be46a994272793");
+        let ctx = unpack_http_trace_context(&req).expect("must parse");
+        assert!(ctx.parent_id.0.is_empty(), "the trace has no parent span");
+        assert!(!ctx.is_sampled());
+    }
+
+    #[test]
+    fn xray_context_invalid() {
+        for header in [
+            "Parent=53995c3f42cd8ad8;Sampled=1",
+            "Root=2-5759e988-bd862e3fe1be46a994272793",
+            "Root=1-5759e98-bd862e3fe1be46a994272793",
+            "Root=1-5759e988-bd862e3fe1be46a99427279z",
+            "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=xyz",
+        ] {
+            assert!(
+                unpack_http_trace_context(&request(header)).is_none(),
+                "{header}"
+            );
+        }
+    }
+
+    #[test]
+    fn xray_parent_replaced() {
+        let mut req = request(
+            "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1;Lineage=a87bd80c:1",
+        );
+        let ctx = unpack_http_trace_context(&req).unwrap();
+        set_parent_id(
+            &mut req,
+            &ctx,
+            &Id(hex::decode("0102030405060708").unwrap()),
+        );
+        assert_eq!(
+            req.headers()["x-amzn-trace-id"],
+            "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=0102030405060708;Sampled=1;Lineage=a87bd80c:1"
+        );
+    }
+
+    #[test]
+    fn xray_context_injected() {
+        let ctx = TraceContext {
+            propagation: Propagation::W3CHttp,
+            trace_id: Id(hex::decode("5759e988bd862e3fe1be46a994272793").unwrap()),
+            parent_id: Id(vec![0; 8]),
+            flags: Flags(1),
+        };
+        let mut req = http::Request::new(());
+        inject_http(
+            &mut req,
+            &ctx,
+            &Id(hex::decode("53995c3f42cd8ad8").unwrap()),
+        );
+        assert_eq!(
+            req.headers()["x-amzn-trace-id"],
+            "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1"
+        );
+    }
+}
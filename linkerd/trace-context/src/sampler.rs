@@ -0,0 +1,121 @@
+use parking_lot::Mutex;
+use rand::Rng;
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+/// Decides whether the proxy should start a new trace for a request that does
+/// not carry a trace context.
+///
+/// A sampler may sample requests with a fixed probability, limit the number
+/// of traces started each second, or both. By default, no traces are started.
+#[derive(Clone, Debug, Default)]
+pub struct Sampler {
+    ratio: Option<f64>,
+    limit: Option<Arc<RateLimit>>,
+}
+
+#[derive(Debug)]
+struct RateLimit {
+    max_per_second: u32,
+    window: Mutex<Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+    start: Instant,
+    sampled: u32,
+}
+
+// === impl Sampler ===
+
+impl Sampler {
+    /// Returns a sampler that samples the given ratio of requests (between 0.0
+    /// and 1.0) and starts at most `max_per_second` traces each second.
+    ///
+    /// If only `max_per_second` is set, every request is sampled until the
+    /// limit is reached.
+    pub fn new(ratio: Option<f64>, max_per_second: Option<u32>) -> Self {
+        Self {
+            ratio: ratio.map(|r| r.clamp(0.0, 1.0)),
+            limit: max_per_second.map(|max_per_second| {
+                Arc::new(RateLimit {
+                    max_per_second,
+                    window: Mutex::new(Window {
+                        start: Instant::now(),
+                        sampled: 0,
+                    }),
+                })
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.ratio.is_some() || self.limit.is_some()
+    }
+
+    /// Returns true if a new trace should be started.
+    pub fn sample(&self) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        if let Some(ratio) = self.ratio {
+            if !rand::thread_rng().gen_bool(ratio) {
+                return false;
+            }
+        }
+
+        match self.limit {
+            Some(ref limit) => limit.acquire(Instant::now()),
+            None => true,
+        }
+    }
+}
+
+// === impl RateLimit ===
+
+impl RateLimit {
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    fn acquire(&self, now: Instant) -> bool {
+        let mut window = self.window.lock();
+        if now.saturating_duration_since(window.start) >= Self::WINDOW {
+            window.start = now;
+            window.sampled = 0;
+        }
+        if window.sampled >= self.max_per_second {
+            return false;
+        }
+        window.sampled += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_by_default() {
+        let sampler = Sampler::default();
+        assert!(!sampler.is_enabled());
+        assert!(!sampler.sample());
+    }
+
+    #[test]
+    fn ratio() {
+        assert!((0..100).all(|_| Sampler::new(Some(1.0), None).sample()));
+        assert!((0..100).all(|_| !Sampler::new(Some(0.0), None).sample()));
+    }
+
+    #[test]
+    fn rate_limited() {
+        let sampler = Sampler::new(None, Some(2));
+        let limit = sampler.limit.as_ref().unwrap();
+        let start = limit.window.lock().start;
+        assert!(limit.acquire(start));
+        assert!(limit.acquire(start + Duration::from_millis(500)));
+        assert!(!limit.acquire(start + Duration::from_millis(999)));
+        assert!(limit.acquire(start + Duration::from_secs(1)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{propagation, Span, SpanSink};
+use crate::{propagation, Formats, SampledSpan, Sampler, Span, SpanAttributes, SpanSink};
 use futures::{future::Either, prelude::*};
 use http::Uri;
 use linkerd_stack::layer;
@@ -12,26 +12,47 @@ use tracing::{debug, info, trace};
 
 /// A layer that adds distributed tracing instrumentation.
 ///
-/// This layer reads the `traceparent` HTTP header from the request. If this
-/// header is absent, the request is fowarded unmodified.  If the header is
-/// present, a new span will be started in the current trace by creating a new
-/// random span id setting it into the `traceparent` header before forwarding
-/// the request. If the sampled bit of the header was set, we emit metadata
-/// about the span to the given SpanSink when the span is complete, i.e. when
-/// we receive the response.
+/// This layer reads trace context headers (e.g. `traceparent`) from the
+/// request in each of the configured [`Formats`]. If a trace context is
+/// present, a new span is started in its trace: a new random span ID is
+/// written into the request's headers before the request is forwarded. If the
+/// context is sampled, the span is emitted to the [`SpanSink`] when it
+/// completes, i.e. when the response is received.
+///
+/// If the request carries no trace context and the layer's [`Sampler`] selects
+/// it, a new trace is started with the proxy's span as its root. Otherwise, the
+/// request is forwarded unmodified.
+///
+/// Inner services may describe how a recorded request was handled by adding
+/// [`SpanAttributes`] to it. The recorded span is identified by a
+/// [`SampledSpan`] request extension.
 #[derive(Clone, Debug)]
 pub struct TraceContext<K, S> {
     inner: S,
     sink: K,
+    formats: Formats,
+    sampler: Sampler,
 }
 
 // === impl TraceContext ===
 
 impl<K: Clone, S> TraceContext<K, S> {
     pub fn layer(sink: K) -> impl layer::Layer<S, Service = TraceContext<K, S>> + Clone {
+        Self::layer_with(sink, Formats::default(), Sampler::default())
+    }
+
+    /// Returns a layer that propagates trace contexts in the given formats and
+    /// may start new traces for requests that do not carry a trace context.
+    pub fn layer_with(
+        sink: K,
+        formats: Formats,
+        sampler: Sampler,
+    ) -> impl layer::Layer<S, Service = TraceContext<K, S>> + Clone {
         layer::mk(move |inner| TraceContext {
             inner,
             sink: sink.clone(),
+            formats: formats.clone(),
+            sampler: sampler.clone(),
         })
     }
 
@@ -108,20 +129,38 @@ where
 
     fn call(&mut self, mut req: http::Request<ReqB>) -> Self::Future {
         if self.sink.is_enabled() {
-            if let Some(context) = propagation::unpack_trace_context(&req) {
+            let context = propagation::unpack_trace_context(&req, &self.formats).or_else(|| {
+                if !self.sampler.sample() {
+                    return None;
+                }
+                trace!("Starting a new trace");
+                Some(propagation::new_root_context(
+                    &mut rand::thread_rng(),
+                    &self.formats,
+                ))
+            });
+            if let Some(context) = context {
                 // Update the trace ID if the request set one and the proxy is configured to emit
                 // spans.
-                let span_id = propagation::increment_span_id(&mut req, &context);
+                let span_id = propagation::increment_span_id(&mut req, &context, &self.formats);
                 debug!(?span_id, sampled = context.is_sampled());
 
                 if context.is_sampled() {
                     // If the request has been marked for sampling, record its metadata.
                     let start = SystemTime::now();
                     let req_labels = Self::request_labels(&req);
+                    let attributes = SpanAttributes::default();
+                    req.extensions_mut().insert(attributes.clone());
+                    req.extensions_mut().insert(SampledSpan {
+                        trace_id: context.trace_id.clone(),
+                        span_id: span_id.clone(),
+                    });
                     let mut sink = self.sink.clone();
                     let span_name = req.uri().path().to_owned();
                     return Either::Right(Box::pin(self.inner.call(req).map_ok(move |rsp| {
                         // Emit the completed span with the response metadata.
+                        let mut labels = Self::add_response_labels(req_labels, &rsp);
+                        labels.extend(attributes.take());
                         let span = Span {
                             span_id,
                             trace_id: context.trace_id,
@@ -129,7 +168,7 @@ where
                             span_name,
                             start,
                             end: SystemTime::now(),
-                            labels: Self::add_response_labels(req_labels, &rsp),
+                            labels,
                         };
                         trace!(?span);
                         if let Err(error) = sink.try_send(span) {
@@ -145,3 +184,74 @@ where
         Either::Left(self.inner.call(req))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linkerd_error::Error;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use tower::{Layer, ServiceExt};
+
+    #[derive(Clone, Default)]
+    struct Spans(Arc<Mutex<Vec<Span>>>);
+
+    impl SpanSink for Spans {
+        fn is_enabled(&self) -> bool {
+            true
+        }
+
+        fn try_send(&mut self, span: Span) -> Result<(), Error> {
+            self.0.lock().push(span);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn records_span_attributes() {
+        let spans = Spans::default();
+        let svc = TraceContext::layer(spans.clone()).layer(tower::service_fn(
+            |req: http::Request<()>| async move {
+                let attrs = SpanAttributes::get(&req).expect("request must be recorded");
+                attrs.insert("linkerd.route.name", "default");
+                Ok::<_, Error>(http::Response::new(()))
+            },
+        ));
+
+        let req = http::Request::builder()
+            .header(
+                "traceparent",
+                "00-94d7f6ec6b95f3e916179cb6cfd01390-55ccfce77f972614-01",
+            )
+            .body(())
+            .unwrap();
+        svc.oneshot(req).await.unwrap();
+
+        let spans = spans.0.lock();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].labels["linkerd.route.name"], "default");
+        assert_eq!(spans[0].labels["http.response.status_code"], "200");
+    }
+
+    #[tokio::test]
+    async fn starts_root_spans_without_parent() {
+        let spans = Spans::default();
+        let svc = TraceContext::layer_with(
+            spans.clone(),
+            Formats::default(),
+            Sampler::new(Some(1.0), None),
+        )
+        .layer(tower::service_fn(|_: http::Request<()>| async move {
+            Ok::<_, Error>(http::Response::new(()))
+        }));
+
+        svc.oneshot(http::Request::new(())).await.unwrap();
+
+        let spans = spans.0.lock();
+        assert_eq!(spans.len(), 1);
+        assert!(
+            spans[0].parent_id.as_ref().is_empty(),
+            "root spans must not have a parent"
+        );
+    }
+}
@@ -1,4 +1,10 @@
-use std::fmt;
+mod sink;
+
+pub use self::sink::SinkMetrics;
+pub(crate) use self::sink::{Config as SinkConfig, Destination};
+
+use self::sink::Sink;
+use std::{collections::HashMap, fmt, sync::Arc};
 use tracing::{field, span, Id, Level, Metadata, Subscriber};
 use tracing_subscriber::{
     field::RecordFields,
@@ -13,9 +19,17 @@ pub const TRACE_TARGET: &str = "_access_log";
 pub(super) type AccessLogLayer<S> =
     Filtered<Box<dyn Layer<S> + Send + Sync + 'static>, FilterFn, S>;
 
-#[derive(Default)]
 pub(super) struct Writer<F = ApacheCommon> {
     formatter: F,
+    sampler: Sampler,
+    sink: Sink,
+}
+
+/// Writes access log lines by rendering a user-supplied template.
+struct TemplateWriter {
+    template: Template,
+    sampler: Sampler,
+    sink: Sink,
 }
 
 #[derive(Default)]
@@ -23,37 +37,115 @@ pub(super) struct ApacheCommon {
     _p: (),
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub(super) enum Format {
     Apache,
     Json,
+    Template(Template),
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Config {
+    pub(super) format: Format,
+    pub(super) sampler: Sampler,
+    pub(super) sink: Option<SinkConfig>,
+}
+
+/// Determines which access log lines are written.
+///
+/// Requests that fail or that receive an error response are always logged.
+/// Other requests are logged at the configured rate. Client errors (4xx) are
+/// treated as errors unless they are configured to be sampled like
+/// successes.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct Sampler {
+    success_rate: f64,
+    sample_client_errors: bool,
+}
+
+/// A parsed access log template, e.g. `{method} {uri} {status} {total_ns}`.
+///
+/// Each `{field}` placeholder is replaced with the value of the named field,
+/// or `-` when the field was not recorded. `{{` and `}}` escape braces.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Template(Arc<[Segment]>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
 }
 
+/// The fields recorded on a span, as formatted for a template.
+#[derive(Default)]
+struct Values(HashMap<&'static str, String>);
+
+/// The response status recorded on a span, if any.
+#[derive(Copy, Clone)]
+struct Status(u16);
+
+struct StatusVisitor(Option<Status>);
+
 struct ApacheCommonVisitor<'writer> {
     res: fmt::Result,
     writer: format::Writer<'writer>,
 }
 
-pub(super) fn build<S>(format: Format) -> AccessLogLayer<S>
+/// Builds an access log layer.
+///
+/// If a sink is configured, metrics describing the access log lines that the
+/// sink fails to deliver are also returned.
+pub(super) fn build<S>(
+    Config {
+        format,
+        sampler,
+        sink,
+    }: Config,
+) -> (AccessLogLayer<S>, Option<SinkMetrics>)
 where
     S: Subscriber + for<'span> LookupSpan<'span>,
 {
+    let (sink, metrics) = match sink.map(Sink::spawn) {
+        None => (Sink::Stderr, None),
+        Some(Ok((sink, metrics))) => (sink, Some(metrics)),
+        Some(Err(error)) => {
+            eprintln!("Failed to start access log sink, logging to stderr: {error}");
+            (Sink::Stderr, None)
+        }
+    };
+
     let writer: Box<dyn Layer<S> + Send + Sync + 'static> = match format {
-        Format::Apache => Box::<Writer<ApacheCommon>>::default(),
-        Format::Json => Box::<Writer<format::JsonFields>>::default(),
+        Format::Apache => Box::new(Writer::<ApacheCommon>::new(sampler, sink)),
+        Format::Json => Box::new(Writer::<format::JsonFields>::new(sampler, sink)),
+        Format::Template(template) => Box::new(TemplateWriter {
+            template,
+            sampler,
+            sink,
+        }),
     };
 
-    writer.with_filter(
+    let layer = writer.with_filter(
         FilterFn::new(
             (|meta| meta.level() == &Level::INFO && meta.target().starts_with(TRACE_TARGET))
                 as fn(&Metadata<'_>) -> bool,
         )
         .with_max_level_hint(Level::INFO),
-    )
+    );
+    (layer, metrics)
 }
 
 // === impl Writer ===
 
+impl<F: Default> Writer<F> {
+    fn new(sampler: Sampler, sink: Sink) -> Self {
+        Self {
+            formatter: F::default(),
+            sampler,
+            sink,
+        }
+    }
+}
+
 impl<S, F> Layer<S> for Writer<F>
 where
     S: Subscriber + for<'span> LookupSpan<'span>,
@@ -78,6 +170,9 @@ where
     fn on_record(&self, id: &Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
         let span = ctx.span(id).expect("Span not found, this is a bug");
         let mut extensions = span.extensions_mut();
+        if let Some(status) = StatusVisitor::status(values) {
+            extensions.replace(status);
+        }
         if let Some(fields) = extensions.get_mut::<FormattedFields<F>>() {
             let _ = self.formatter.add_fields(fields, values);
             return;
@@ -95,8 +190,49 @@ where
 
     fn on_close(&self, id: Id, ctx: Context<'_, S>) {
         if let Some(span) = ctx.span(&id) {
-            if let Some(fields) = span.extensions().get::<FormattedFields<F>>() {
-                eprintln!("{}", fields.fields);
+            let extensions = span.extensions();
+            if !self.sampler.sample(extensions.get::<Status>().copied()) {
+                return;
+            }
+            if let Some(fields) = extensions.get::<FormattedFields<F>>() {
+                self.sink.write(fields.fields.clone());
+            }
+        }
+    }
+}
+
+// === impl TemplateWriter ===
+
+impl<S> Layer<S> for TemplateWriter
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut values = Values::default();
+        attrs.record(&mut values);
+        span.extensions_mut().insert(values);
+    }
+
+    fn on_record(&self, id: &Id, record: &span::Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if let Some(status) = StatusVisitor::status(record) {
+            extensions.replace(status);
+        }
+        if let Some(values) = extensions.get_mut::<Values>() {
+            record.record(values);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            let extensions = span.extensions();
+            if !self.sampler.sample(extensions.get::<Status>().copied()) {
+                return;
+            }
+            if let Some(values) = extensions.get::<Values>() {
+                self.sink.write(self.template.render(values));
             }
         }
     }
@@ -111,6 +247,11 @@ impl ApacheCommon {
         "response_bytes",
         "user_agent",
         "host",
+        "parent",
+        "route",
+        "backend",
+        "endpoint.addr",
+        "retries",
     ];
 }
 
@@ -152,6 +293,135 @@ impl field::Visit for ApacheCommonVisitor<'_> {
     }
 }
 
+// === impl Sampler ===
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self {
+            success_rate: 1.0,
+            sample_client_errors: false,
+        }
+    }
+}
+
+impl Sampler {
+    /// Returns a sampler that logs the given fraction of successful requests.
+    ///
+    /// If `sample_client_errors` is set, client error (4xx) responses are
+    /// logged at the same rate as successes.
+    pub(super) fn new(success_rate: f64, sample_client_errors: bool) -> Self {
+        Self {
+            success_rate: success_rate.clamp(0.0, 1.0),
+            sample_client_errors,
+        }
+    }
+
+    fn sample(&self, status: Option<Status>) -> bool {
+        match status {
+            Some(Status(status)) if status < 400 || (status < 500 && self.sample_client_errors) => {
+                self.success_rate >= 1.0 || rand::random::<f64>() < self.success_rate
+            }
+            // Errors are always logged.
+            _ => true,
+        }
+    }
+}
+
+// === impl StatusVisitor ===
+
+impl StatusVisitor {
+    fn status(record: &span::Record<'_>) -> Option<Status> {
+        let mut visitor = Self(None);
+        record.record(&mut visitor);
+        visitor.0
+    }
+}
+
+impl field::Visit for StatusVisitor {
+    fn record_u64(&mut self, field: &field::Field, val: u64) {
+        if field.name() == "status" {
+            self.0 = u16::try_from(val).ok().map(Status);
+        }
+    }
+
+    fn record_debug(&mut self, _: &field::Field, _: &dyn fmt::Debug) {}
+}
+
+// === impl Values ===
+
+impl field::Visit for Values {
+    fn record_str(&mut self, field: &field::Field, val: &str) {
+        self.0.insert(field.name(), val.to_string());
+    }
+
+    fn record_debug(&mut self, field: &field::Field, val: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{:?}", val));
+    }
+}
+
+// === impl Template ===
+
+impl Template {
+    fn render(&self, Values(values): &Values) -> String {
+        let mut out = String::new();
+        for segment in self.0.iter() {
+            match segment {
+                Segment::Literal(s) => out.push_str(s),
+                Segment::Field(name) => match values.get(name.as_str()) {
+                    Some(v) if !v.is_empty() => out.push_str(v),
+                    _ => out.push('-'),
+                },
+            }
+        }
+        out
+    }
+}
+
+impl std::str::FromStr for Template {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err("unterminated field in template"),
+                        }
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err("empty field in template");
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(name.to_string()));
+                }
+                '}' => return Err("unmatched '}' in template"),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self(segments.into()))
+    }
+}
+
 // === impl Format ===
 
 impl std::str::FromStr for Format {
@@ -164,3 +434,55 @@ impl std::str::FromStr for Format {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(fields: &[(&'static str, &str)]) -> Values {
+        Values(fields.iter().map(|(k, v)| (*k, v.to_string())).collect())
+    }
+
+    #[test]
+    fn renders_template() {
+        let template = "{method} {uri} -> {status} ({route}) {{ok}}"
+            .parse::<Template>()
+            .unwrap();
+        let rendered = template.render(&values(&[
+            ("method", "GET"),
+            ("uri", "http://example.com/"),
+            ("status", "200"),
+        ]));
+        assert_eq!(rendered, "GET http://example.com/ -> 200 (-) {ok}");
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert!("{method".parse::<Template>().is_err());
+        assert!("{}".parse::<Template>().is_err());
+        assert!("method}".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn samples_successes() {
+        let never = Sampler::new(0.0, false);
+        assert!(!never.sample(Some(Status(200))));
+        assert!(
+            never.sample(Some(Status(404))),
+            "client errors must be logged"
+        );
+        assert!(never.sample(Some(Status(503))));
+        assert!(never.sample(None), "failed requests must be logged");
+
+        let always = Sampler::default();
+        assert!(always.sample(Some(Status(200))));
+    }
+
+    #[test]
+    fn samples_client_errors() {
+        let never = Sampler::new(0.0, true);
+        assert!(!never.sample(Some(Status(200))));
+        assert!(!never.sample(Some(Status(404))));
+        assert!(never.sample(Some(Status(503))));
+    }
+}
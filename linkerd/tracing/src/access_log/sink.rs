@@ -0,0 +1,405 @@
+//! Delivers access log lines to a dedicated destination.
+//!
+//! By default, access logs are written to stderr as they are produced. When a
+//! sink is configured, lines are instead enqueued on a bounded channel and
+//! written by a dedicated thread, so that a slow or unavailable destination
+//! never blocks the data path. Lines that cannot be enqueued are dropped, and
+//! lines that cannot be written are discarded; each is counted separately.
+
+use prometheus_client::{metrics::counter::Counter, registry::Registry};
+#[cfg(unix)]
+use std::os::unix::net::{UnixDatagram, UnixStream};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
+
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+    pub(crate) destination: Destination,
+    pub(crate) capacity: usize,
+}
+
+/// Where access log lines are written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Destination {
+    Stderr,
+    File {
+        path: Arc<Path>,
+        max_bytes: u64,
+        max_files: usize,
+    },
+    #[cfg(unix)]
+    UnixStream(Arc<Path>),
+    #[cfg(unix)]
+    UnixDatagram(Arc<Path>),
+}
+
+/// Counts the access log lines that the sink fails to deliver.
+#[derive(Clone, Debug, Default)]
+pub struct SinkMetrics {
+    /// Lines dropped because the sink's queue was full.
+    dropped: Counter,
+    /// Lines discarded because they could not be written to the destination.
+    write_errors: Counter,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) enum Sink {
+    #[default]
+    Stderr,
+    Channel {
+        tx: mpsc::SyncSender<String>,
+        dropped: Counter,
+    },
+}
+
+type WriteLine = Box<dyn FnMut(String) -> io::Result<()> + Send>;
+
+/// A file that is rotated once it reaches a maximum size.
+///
+/// When rotated, `path` is renamed to `path.1`, `path.1` to `path.2`, and so
+/// on, retaining at most `max_files` rotated files.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<fs::File>,
+    size: u64,
+}
+
+/// A Unix stream socket that is reconnected after failures.
+#[cfg(unix)]
+struct Reconnect {
+    path: PathBuf,
+    stream: Option<UnixStream>,
+}
+
+// === impl Sink ===
+
+impl Sink {
+    /// Spawns a thread that writes lines to the configured destination,
+    /// returning a sink that enqueues lines for it along with metrics
+    /// describing the lines that were not delivered.
+    pub(super) fn spawn(
+        Config {
+            destination,
+            capacity,
+        }: Config,
+    ) -> io::Result<(Self, SinkMetrics)> {
+        let (tx, rx) = mpsc::sync_channel(capacity);
+        let metrics = SinkMetrics::default();
+        let mut write = Self::writer(destination)?;
+        std::thread::Builder::new()
+            .name("access-log".into())
+            .spawn({
+                let write_errors = metrics.write_errors.clone();
+                move || {
+                    while let Ok(line) = rx.recv() {
+                        if write(line).is_err() {
+                            write_errors.inc();
+                        }
+                    }
+                }
+            })?;
+
+        let sink = Self::Channel {
+            tx,
+            dropped: metrics.dropped.clone(),
+        };
+        Ok((sink, metrics))
+    }
+
+    fn writer(destination: Destination) -> io::Result<WriteLine> {
+        Ok(match destination {
+            Destination::Stderr => Box::new(|line| writeln!(io::stderr().lock(), "{line}")),
+            Destination::File {
+                path,
+                max_bytes,
+                max_files,
+            } => {
+                let mut file = RotatingFile::open(path.to_path_buf(), max_bytes, max_files)?;
+                Box::new(move |line| file.write_line(&line))
+            }
+            #[cfg(unix)]
+            Destination::UnixStream(path) => {
+                let mut socket = Reconnect {
+                    path: path.to_path_buf(),
+                    stream: None,
+                };
+                Box::new(move |line| socket.write_line(&line))
+            }
+            #[cfg(unix)]
+            Destination::UnixDatagram(path) => {
+                let socket = UnixDatagram::unbound()?;
+                Box::new(move |line| socket.send_to(line.as_bytes(), &path).map(|_| ()))
+            }
+        })
+    }
+
+    pub(super) fn write(&self, line: String) {
+        match self {
+            Self::Stderr => eprintln!("{line}"),
+            Self::Channel { tx, dropped } => {
+                if tx.try_send(line).is_err() {
+                    dropped.inc();
+                }
+            }
+        }
+    }
+}
+
+// === impl SinkMetrics ===
+
+impl SinkMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "dropped",
+            "The total number of access log lines dropped because the access log sink was full",
+            self.dropped.clone(),
+        );
+        registry.register(
+            "write_errors",
+            "The total number of access log lines that could not be written by the access log sink",
+            self.write_errors.clone(),
+        );
+    }
+}
+
+// === impl RotatingFile ===
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let mut file = Self {
+            path,
+            max_bytes,
+            max_files,
+            file: None,
+            size: 0,
+        };
+        file.reopen()?;
+        Ok(file)
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        if self.file.is_none() {
+            self.reopen()?;
+        }
+
+        let file = self.file.as_mut().expect("file must be open");
+        writeln!(file, "{line}")?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        for n in (1..self.max_files).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(from, self.rotated(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        path.into()
+    }
+}
+
+// === impl Reconnect ===
+
+#[cfg(unix)]
+impl Reconnect {
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => self.stream.insert(UnixStream::connect(&self.path)?),
+        };
+
+        let res = stream
+            .write_all(line.as_bytes())
+            .and_then(|()| stream.write_all(b"\n"));
+        if res.is_err() {
+            // Reconnect on the next write.
+            self.stream = None;
+        }
+        res
+    }
+}
+
+// === impl Destination ===
+
+impl Destination {
+    /// Parses a destination of the form `stderr`, `file:<path>`,
+    /// `unix:<path>`, or `unixgram:<path>`.
+    pub(crate) fn parse(s: &str, max_bytes: u64, max_files: usize) -> Result<Self, &'static str> {
+        if s.eq_ignore_ascii_case("stderr") {
+            return Ok(Self::Stderr);
+        }
+
+        let (scheme, path) = s
+            .split_once(':')
+            .ok_or("expected 'stderr', 'file:<path>', 'unix:<path>', or 'unixgram:<path>'")?;
+        let path = Path::new(path);
+        if !path.is_absolute() {
+            return Err("access log sink paths must be absolute");
+        }
+
+        match scheme {
+            "file" => Ok(Self::File {
+                path: path.into(),
+                max_bytes,
+                max_files,
+            }),
+            #[cfg(unix)]
+            "unix" => Ok(Self::UnixStream(path.into())),
+            #[cfg(unix)]
+            "unixgram" => Ok(Self::UnixDatagram(path.into())),
+            #[cfg(not(unix))]
+            "unix" | "unixgram" => Err("unix sockets are not supported on this platform"),
+            _ => Err("expected 'stderr', 'file:<path>', 'unix:<path>', or 'unixgram:<path>'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmp(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "linkerd-access-log-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_destinations() {
+        assert_eq!(Destination::parse("stderr", 1, 1), Ok(Destination::Stderr));
+        assert_eq!(
+            Destination::parse("file:/var/log/access.log", 10, 2),
+            Ok(Destination::File {
+                path: Path::new("/var/log/access.log").into(),
+                max_bytes: 10,
+                max_files: 2,
+            })
+        );
+        assert_eq!(
+            Destination::parse("unix:/run/log.sock", 1, 1),
+            Ok(Destination::UnixStream(Path::new("/run/log.sock").into()))
+        );
+        assert_eq!(
+            Destination::parse("unixgram:/run/log.sock", 1, 1),
+            Ok(Destination::UnixDatagram(Path::new("/run/log.sock").into()))
+        );
+        assert!(Destination::parse("file:access.log", 1, 1).is_err());
+        assert!(Destination::parse("tcp:/foo", 1, 1).is_err());
+        assert!(Destination::parse("/foo", 1, 1).is_err());
+    }
+
+    #[test]
+    fn rotates_files() {
+        let dir = tmp("rotate");
+        let path = dir.join("access.log");
+        let mut file = RotatingFile::open(path.clone(), 8, 2).unwrap();
+        for line in ["aaaaaa", "bbbbbb", "cccccc", "dddddd"] {
+            file.write_line(line).unwrap();
+        }
+
+        let read = |p: PathBuf| fs::read_to_string(p).unwrap();
+        assert_eq!(read(path.clone()), "dddddd\n");
+        assert_eq!(read(dir.join("access.log.1")), "cccccc\n");
+        assert_eq!(read(dir.join("access.log.2")), "bbbbbb\n");
+        assert!(!dir.join("access.log.3").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn counts_dropped_lines() {
+        let (tx, _rx) = mpsc::sync_channel(1);
+        let dropped = Counter::default();
+        let sink = Sink::Channel {
+            tx,
+            dropped: dropped.clone(),
+        };
+        for _ in 0..3 {
+            sink.write("line".to_string());
+        }
+        assert_eq!(dropped.get(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_datagrams() {
+        let dir = tmp("unixgram");
+        let path = dir.join("log.sock");
+        let server = UnixDatagram::bind(&path).unwrap();
+        let (sink, metrics) = Sink::spawn(Config {
+            destination: Destination::UnixDatagram(path.as_path().into()),
+            capacity: 8,
+        })
+        .unwrap();
+
+        sink.write("hello".to_string());
+        let mut buf = [0; 16];
+        let n = server.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"hello");
+        assert_eq!(metrics.dropped.get(), 0);
+        assert_eq!(metrics.write_errors.get(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn counts_write_errors() {
+        let dir = tmp("write-errors");
+        let (sink, metrics) = Sink::spawn(Config {
+            destination: Destination::UnixStream(dir.join("missing.sock").as_path().into()),
+            capacity: 8,
+        })
+        .unwrap();
+
+        sink.write("hello".to_string());
+        // Dropping the sink closes the channel so that the writer exits once
+        // it has processed every line.
+        drop(sink);
+        for _ in 0..100 {
+            if metrics.write_errors.get() == 1 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(metrics.write_errors.get(), 1);
+        assert_eq!(metrics.dropped.get(), 0, "write errors are not drops");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
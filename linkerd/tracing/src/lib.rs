@@ -25,9 +25,19 @@ pub use tracing_subscriber::{registry, EnvFilter};
 const ENV_LOG_LEVEL: &str = "LINKERD2_PROXY_LOG";
 const ENV_LOG_FORMAT: &str = "LINKERD2_PROXY_LOG_FORMAT";
 const ENV_ACCESS_LOG: &str = "LINKERD2_PROXY_ACCESS_LOG";
+const ENV_ACCESS_LOG_TEMPLATE: &str = "LINKERD2_PROXY_ACCESS_LOG_TEMPLATE";
+const ENV_ACCESS_LOG_SAMPLE_RATE: &str = "LINKERD2_PROXY_ACCESS_LOG_SAMPLE_RATE";
+const ENV_ACCESS_LOG_SAMPLE_CLIENT_ERRORS: &str = "LINKERD2_PROXY_ACCESS_LOG_SAMPLE_CLIENT_ERRORS";
+const ENV_ACCESS_LOG_SINK: &str = "LINKERD2_PROXY_ACCESS_LOG_SINK";
+const ENV_ACCESS_LOG_SINK_CAPACITY: &str = "LINKERD2_PROXY_ACCESS_LOG_SINK_CAPACITY";
+const ENV_ACCESS_LOG_FILE_MAX_BYTES: &str = "LINKERD2_PROXY_ACCESS_LOG_FILE_MAX_BYTES";
+const ENV_ACCESS_LOG_FILE_MAX_FILES: &str = "LINKERD2_PROXY_ACCESS_LOG_FILE_MAX_FILES";
 
 const DEFAULT_LOG_LEVEL: &str = "warn,linkerd=info,hickory=error";
 const DEFAULT_LOG_FORMAT: &str = "PLAIN";
+const DEFAULT_ACCESS_LOG_SINK_CAPACITY: usize = 10_000;
+const DEFAULT_ACCESS_LOG_FILE_MAX_BYTES: u64 = 100 * 1024 * 1024;
+const DEFAULT_ACCESS_LOG_FILE_MAX_FILES: usize = 5;
 
 #[derive(Debug, Default)]
 #[must_use]
@@ -35,13 +45,14 @@ pub struct Settings {
     filter: String,
     format: String,
     start_time: Option<time::Instant>,
-    access_log: Option<access_log::Format>,
+    access_log: Option<access_log::Config>,
     is_test: bool,
 }
 
 #[derive(Clone)]
 pub struct Handle {
     level: Option<level::Handle>,
+    access_log_metrics: Option<access_log::SinkMetrics>,
     #[cfg(feature = "stream")]
     stream: stream::StreamHandle<LogStack>,
 }
@@ -71,7 +82,7 @@ impl Settings {
             format: std::env::var(ENV_LOG_FORMAT)
                 .ok()
                 .unwrap_or_else(|| DEFAULT_LOG_FORMAT.to_string()),
-            access_log: Self::access_log_format(),
+            access_log: Self::access_log_config(),
             start_time: Some(now),
             is_test: false,
         }
@@ -82,20 +93,108 @@ impl Settings {
             filter,
             format,
             start_time: None,
-            access_log: Self::access_log_format(),
+            access_log: Self::access_log_config(),
             is_test: true,
         }
     }
 
-    fn access_log_format() -> Option<access_log::Format> {
-        let env = std::env::var(ENV_ACCESS_LOG).ok()?;
-        match env.parse() {
-            Ok(format) => Some(format),
-            Err(err) => {
-                eprintln!("Invalid {}={:?}: {}", ENV_ACCESS_LOG, env, err);
-                None
+    /// Access logging is enabled when either a format or a template is
+    /// configured. A template takes precedence over a format.
+    fn access_log_config() -> Option<access_log::Config> {
+        let format = match std::env::var(ENV_ACCESS_LOG_TEMPLATE).ok() {
+            Some(env) => match env.parse() {
+                Ok(template) => access_log::Format::Template(template),
+                Err(err) => {
+                    eprintln!("Invalid {}={:?}: {}", ENV_ACCESS_LOG_TEMPLATE, env, err);
+                    return None;
+                }
+            },
+            None => {
+                let env = std::env::var(ENV_ACCESS_LOG).ok()?;
+                match env.parse() {
+                    Ok(format) => format,
+                    Err(err) => {
+                        eprintln!("Invalid {}={:?}: {}", ENV_ACCESS_LOG, env, err);
+                        return None;
+                    }
+                }
+            }
+        };
+
+        // By default, client errors (4xx) are logged like server errors.
+        let sample_client_errors = match std::env::var(ENV_ACCESS_LOG_SAMPLE_CLIENT_ERRORS).ok() {
+            None => false,
+            Some(env) => env.parse::<bool>().unwrap_or_else(|_| {
+                eprintln!(
+                    "Invalid {}={:?}: expected true or false",
+                    ENV_ACCESS_LOG_SAMPLE_CLIENT_ERRORS, env
+                );
+                false
+            }),
+        };
+        let sampler = match std::env::var(ENV_ACCESS_LOG_SAMPLE_RATE).ok() {
+            None => access_log::Sampler::new(1.0, sample_client_errors),
+            Some(env) => match env.parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => {
+                    access_log::Sampler::new(rate, sample_client_errors)
+                }
+                _ => {
+                    eprintln!(
+                        "Invalid {}={:?}: expected a number between 0.0 and 1.0",
+                        ENV_ACCESS_LOG_SAMPLE_RATE, env
+                    );
+                    access_log::Sampler::new(1.0, sample_client_errors)
+                }
+            },
+        };
+
+        Some(access_log::Config {
+            format,
+            sampler,
+            sink: Self::access_log_sink(),
+        })
+    }
+
+    /// Access logs are written to stderr unless a dedicated sink is
+    /// configured.
+    fn access_log_sink() -> Option<access_log::SinkConfig> {
+        fn parse_env<T: str::FromStr>(name: &str, default: T) -> T {
+            match std::env::var(name).ok() {
+                None => default,
+                Some(env) => env.parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid {}={:?}: expected a number", name, env);
+                    default
+                }),
             }
         }
+
+        let env = std::env::var(ENV_ACCESS_LOG_SINK).ok()?;
+        let destination = match access_log::Destination::parse(
+            &env,
+            parse_env(
+                ENV_ACCESS_LOG_FILE_MAX_BYTES,
+                DEFAULT_ACCESS_LOG_FILE_MAX_BYTES,
+            ),
+            parse_env(
+                ENV_ACCESS_LOG_FILE_MAX_FILES,
+                DEFAULT_ACCESS_LOG_FILE_MAX_FILES,
+            ),
+        ) {
+            Ok(destination) => destination,
+            Err(err) => {
+                eprintln!("Invalid {}={:?}: {}", ENV_ACCESS_LOG_SINK, env, err);
+                return None;
+            }
+        };
+
+        Some(access_log::SinkConfig {
+            destination,
+            capacity: parse_env(
+                ENV_ACCESS_LOG_SINK_CAPACITY,
+                DEFAULT_ACCESS_LOG_SINK_CAPACITY,
+            )
+            .max(1),
+        })
     }
 
     fn timer(&self) -> Uptime {
@@ -157,6 +256,7 @@ impl Settings {
         if self.filter.trim().eq_ignore_ascii_case("off") {
             return Ok(Handle {
                 level: None,
+                access_log_metrics: None,
 
                 // logging is disabled, but log streaming might still be enabled later
                 #[cfg(feature = "stream")]
@@ -186,7 +286,7 @@ impl Settings {
     /// The log dispatcher handles:
     ///
     /// - process diagnostic logging to stdout;
-    /// - optional access logging to stderr;
+    /// - optional access logging to stderr or a dedicated sink;
     /// - if the `stream` feature is enabled, on-demand log streaming via the
     ///   returned `Handle`
     pub fn build(self) -> (Dispatch, Handle) {
@@ -222,11 +322,16 @@ impl Settings {
         };
 
         // Access logging is optionally enabled process-wide.
-        let registry = registry.with(self.access_log.map(access_log::build));
+        let (access_log, access_log_metrics) = match self.access_log.map(access_log::build) {
+            Some((layer, metrics)) => (Some(layer), metrics),
+            None => (None, None),
+        };
+        let registry = registry.with(access_log);
 
         // The handle controls the logging system at runtime.
         let handle = Handle {
             level: Some(level::Handle::new(level)),
+            access_log_metrics,
             #[cfg(feature = "stream")]
             stream,
         };
@@ -246,6 +351,7 @@ impl Handle {
     pub fn disabled() -> Self {
         Self {
             level: None,
+            access_log_metrics: None,
             #[cfg(feature = "stream")]
             stream: stream::StreamHandle::new().0,
         }
@@ -255,6 +361,12 @@ impl Handle {
         self.level.as_ref()
     }
 
+    /// Returns metrics describing the access log lines that the access log
+    /// sink failed to deliver, if a sink is configured.
+    pub fn access_log_metrics(&self) -> Option<&access_log::SinkMetrics> {
+        self.access_log_metrics.as_ref()
+    }
+
     #[cfg(feature = "stream")]
     pub fn into_stream(self) -> stream::StreamHandle<LogStack> {
         self.stream
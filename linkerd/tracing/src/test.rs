@@ -1,5 +1,8 @@
 use super::*;
-use std::env;
+use parking_lot::Mutex;
+use std::{collections::HashMap, env, fmt, sync::Arc};
+use tracing::{field, span, Id};
+use tracing_subscriber::layer::Context;
 
 /// By default, disable logging in modules that are expected to error in tests.
 pub const DEFAULT_LOG: &str = "warn,\
@@ -28,3 +31,79 @@ pub fn with_default_filter(
 pub fn trace_init() -> (tracing::dispatcher::DefaultGuard, crate::Handle) {
     with_default_filter(DEFAULT_LOG)
 }
+
+/// Records the fields of each closed span with a given target.
+#[derive(Clone, Debug, Default)]
+pub struct CapturedSpans {
+    target: &'static str,
+    closed: Arc<Mutex<Vec<SpanFields>>>,
+}
+
+pub type SpanFields = HashMap<&'static str, String>;
+
+struct Visitor<'a>(&'a mut SpanFields);
+
+// === impl CapturedSpans ===
+
+impl CapturedSpans {
+    /// Sets a default subscriber that captures spans with the given target
+    /// until the returned guard is dropped.
+    pub fn init(target: &'static str) -> (Self, tracing::subscriber::DefaultGuard) {
+        let spans = Self {
+            target,
+            ..Default::default()
+        };
+        let guard = tracing_subscriber::registry()
+            .with(spans.clone())
+            .set_default();
+        (spans, guard)
+    }
+
+    /// Returns the fields of all spans that have been closed.
+    pub fn closed(&self) -> Vec<SpanFields> {
+        self.closed.lock().clone()
+    }
+}
+
+impl<S> Layer<S> for CapturedSpans
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().target() != self.target {
+            return;
+        }
+        let span = ctx.span(id).expect("span must exist");
+        let mut fields = SpanFields::default();
+        attrs.record(&mut Visitor(&mut fields));
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("span must exist");
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut Visitor(fields));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = ctx.span(&id).expect("span must exist");
+        let fields = span.extensions_mut().remove::<SpanFields>();
+        if let Some(fields) = fields {
+            self.closed.lock().push(fields);
+        }
+    }
+}
+
+// === impl Visitor ===
+
+impl field::Visit for Visitor<'_> {
+    fn record_str(&mut self, field: &field::Field, value: &str) {
+        self.0.insert(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{value:?}"));
+    }
+}
@@ -11,6 +11,10 @@ pub struct Header {
     /// connection is handled opaquely.
     #[prost(message, optional, tag = "3")]
     pub session_protocol: ::core::option::Option<SessionProtocol>,
+    /// The client that originated the connection, if the sender is forwarding a
+    /// connection on behalf of another client. Intended for gateway forwarding.
+    #[prost(message, optional, tag = "4")]
+    pub original_client: ::core::option::Option<OriginalClient>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct SessionProtocol {
@@ -31,3 +35,16 @@ pub mod session_protocol {
         Http2(Http2),
     }
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OriginalClient {
+    /// The client's IP address, encoded as 4 (IPv4) or 16 (IPv6) bytes. Empty if
+    /// the address is not known.
+    #[prost(bytes = "vec", tag = "1")]
+    pub ip: ::prost::alloc::vec::Vec<u8>,
+    /// The client's port.
+    #[prost(uint32, tag = "2")]
+    pub port: u32,
+    /// The client's mTLS identity. Empty if the client was not authenticated.
+    #[prost(string, tag = "3")]
+    pub identity: ::prost::alloc::string::String,
+}
@@ -10,9 +10,13 @@ use bytes::{
 };
 use linkerd_dns_name::Name;
 use linkerd_error::Error;
+use linkerd_identity::Id;
 use linkerd_io::{self as io, AsyncReadExt, AsyncWriteExt};
 use prost::Message;
-use std::str::FromStr;
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
 use tracing::trace;
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -30,6 +34,27 @@ pub struct TransportHeader {
 
     /// Indicates whether a protocol is known for the connection.
     pub protocol: Option<SessionProtocol>,
+
+    /// The client that originated the connection, if the sender is forwarding
+    /// the connection on behalf of another client.
+    ///
+    /// This is asserted by the sender and must only be trusted when the
+    /// sender's mTLS identity is trusted to forward connections.
+    pub original_client: Option<OriginalClient>,
+}
+
+/// Describes the client that originated a forwarded connection.
+///
+/// At least one of the address or identity is set. Gateways only describe the
+/// client's identity, since their connections are not specific to a single
+/// client address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OriginalClient {
+    /// The original client's address, if known.
+    pub addr: Option<SocketAddr>,
+
+    /// The original client's mTLS identity, if it was authenticated.
+    pub identity: Option<Id>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -104,6 +129,22 @@ impl TransportHeader {
                     )),
                 },
             }),
+            original_client: self
+                .original_client
+                .as_ref()
+                .map(|c| proto::OriginalClient {
+                    ip: match c.addr.map(|a| a.ip()) {
+                        Some(IpAddr::V4(ip)) => ip.octets().to_vec(),
+                        Some(IpAddr::V6(ip)) => ip.octets().to_vec(),
+                        None => Vec::new(),
+                    },
+                    port: c.addr.map(|a| a.port().into()).unwrap_or_default(),
+                    identity: c
+                        .identity
+                        .as_ref()
+                        .map(|id| id.to_str().into_owned())
+                        .unwrap_or_default(),
+                }),
         }
     }
 
@@ -186,14 +227,55 @@ impl TransportHeader {
             })
         });
 
+        let original_client = h
+            .original_client
+            .map(OriginalClient::decode)
+            .transpose()?
+            .flatten();
+
         Ok(Some(Self {
             port: h.port as u16,
             name,
             protocol,
+            original_client,
         }))
     }
 }
 
+// === impl OriginalClient ===
+
+impl OriginalClient {
+    fn decode(c: proto::OriginalClient) -> io::Result<Option<Self>> {
+        let ip = match c.ip.len() {
+            0 => None,
+            4 => Some(IpAddr::from(<[u8; 4]>::try_from(&c.ip[..]).unwrap())),
+            16 => Some(IpAddr::from(<[u8; 16]>::try_from(&c.ip[..]).unwrap())),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid original client IP",
+                ))
+            }
+        };
+        let port = u16::try_from(c.port)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid port value"))?;
+        let addr = ip.map(|ip| SocketAddr::new(ip, port));
+
+        let identity = if c.identity.is_empty() {
+            None
+        } else {
+            let id = Id::from_str(&c.identity)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Some(id)
+        };
+
+        if addr.is_none() && identity.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self { addr, identity }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +286,7 @@ mod tests {
             port: 4040,
             name: Some(Name::from_str("foo.bar.example.com").unwrap()),
             protocol: Some(SessionProtocol::Http2),
+            original_client: None,
         };
         let mut rx = {
             let mut buf = BytesMut::new();
@@ -220,6 +303,58 @@ mod tests {
         assert_eq!(buf.as_ref(), b"12345");
     }
 
+    #[tokio::test]
+    async fn roundtrip_original_client() {
+        for original_client in [
+            OriginalClient {
+                addr: Some(([192, 0, 2, 10], 40000).into()),
+                identity: Some(
+                    Id::from_str("web.ns.serviceaccount.identity.linkerd.cluster.local").unwrap(),
+                ),
+            },
+            OriginalClient {
+                addr: Some((std::net::Ipv6Addr::LOCALHOST, 40000).into()),
+                identity: None,
+            },
+            OriginalClient {
+                addr: None,
+                identity: Some(Id::from_str("spiffe://cluster.local/ns/default/sa/web").unwrap()),
+            },
+        ] {
+            let header = TransportHeader {
+                port: 4040,
+                name: Some(Name::from_str("foo.bar.example.com").unwrap()),
+                protocol: None,
+                original_client: Some(original_client),
+            };
+            let mut rx = std::io::Cursor::new(header.encode_prefaced_buf().expect("must encode"));
+            let h = TransportHeader::read_prefaced(&mut rx, &mut BytesMut::with_capacity(1024))
+                .await
+                .expect("decodes")
+                .expect("decodes");
+            assert_eq!(header, h);
+        }
+    }
+
+    #[test]
+    fn invalid_original_client_ip() {
+        let mut header = TransportHeader {
+            port: 4040,
+            name: None,
+            protocol: None,
+            original_client: None,
+        }
+        .to_proto();
+        header.original_client = Some(proto::OriginalClient {
+            ip: vec![10, 0, 0],
+            port: 40000,
+            identity: String::new(),
+        });
+        let err = TransportHeader::decode(header.encode_to_vec().as_slice())
+            .expect_err("must not decode");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
     #[tokio::test]
     async fn no_header() {
         const MSG: &[u8] = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n";
@@ -238,6 +373,7 @@ mod tests {
             port: 4040,
             name: Some(Name::from_str("foo.bar.example.com").unwrap()),
             protocol: None,
+            original_client: None,
         };
         let mut rx = {
             let msg = {
@@ -297,6 +433,7 @@ pub mod fuzz_logic {
                 port: transport_header.port,
                 name: Name::from_str(fuzz_name).ok(),
                 protocol: Some(fuzz_proto),
+                original_client: None,
             };
             let mut rx = {
                 let mut buf = BytesMut::new();
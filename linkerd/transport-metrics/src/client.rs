@@ -1,5 +1,6 @@
 use super::{Metrics, Sensor, SensorIo};
 use futures::{ready, TryFuture};
+use linkerd_io as io;
 use linkerd_stack::{layer, ExtractParam, MakeConnection, Service};
 use pin_project::pin_project;
 use std::{
@@ -38,6 +39,7 @@ impl<T, P, S> Service<T> for Client<P, S>
 where
     P: ExtractParam<Arc<Metrics>, T>,
     S: MakeConnection<T>,
+    S::Connection: io::TcpInfo,
 {
     type Response = (SensorIo<S::Connection>, S::Metadata);
     type Error = S::Error;
@@ -60,7 +62,11 @@ where
 
 // === impl ConnectFuture ===
 
-impl<I, M, F: TryFuture<Ok = (I, M)>> Future for ConnectFuture<F> {
+impl<I, M, F> Future for ConnectFuture<F>
+where
+    I: io::TcpInfo,
+    F: TryFuture<Ok = (I, M)>,
+{
     type Output = Result<(SensorIo<I>, M), F::Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -5,8 +5,10 @@ mod client;
 mod report;
 mod sensor;
 mod server;
+mod tcp_info;
 pub mod zone;
 
+use self::tcp_info::TcpInfoMetrics;
 pub use self::{
     client::Client,
     report::Report,
@@ -14,7 +16,7 @@ pub use self::{
     server::NewServer,
 };
 use linkerd_errno::Errno;
-use linkerd_metrics::{metrics, Counter, FmtLabels, Gauge, LastUpdate, Store};
+use linkerd_metrics::{latency, metrics, Counter, FmtLabels, Gauge, Histogram, LastUpdate, Store};
 use parking_lot::Mutex;
 use std::{collections::HashMap, fmt, hash::Hash, sync::Arc};
 use tokio::time::{Duration, Instant};
@@ -25,7 +27,12 @@ metrics! {
     tcp_read_bytes_total: Counter { "Total count of bytes read from peers" },
     tcp_write_bytes_total: Counter { "Total count of bytes written to peers" },
 
-    tcp_close_total: Counter { "Total count of closed connections" }
+    tcp_close_total: Counter { "Total count of closed connections" },
+
+    tcp_rtt_us: Histogram<latency::Us> { "Smoothed round-trip time of connections, sampled periodically, in microseconds" },
+    tcp_retransmits: Histogram<u64> { "Total segments retransmitted by closed connections" },
+    tcp_congestion_window_segments: Histogram<u64> { "Congestion window of connections, sampled periodically, in segments" },
+    tcp_delivery_rate_bytes_per_second: Histogram<u64> { "Delivery rate of connections, sampled periodically, in bytes per second" }
 }
 
 pub fn new<K: Eq + Hash + FmtLabels>(retain_idle: Duration) -> (Registry<K>, Report<K>) {
@@ -46,6 +53,7 @@ pub struct Metrics {
     open_connections: Gauge,
     write_bytes_total: Counter,
     read_bytes_total: Counter,
+    tcp_info: TcpInfoMetrics,
 
     by_eos: Arc<Mutex<ByEos>>,
 }
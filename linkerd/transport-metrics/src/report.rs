@@ -1,5 +1,6 @@
 use super::{
-    tcp_close_total, tcp_open_connections, tcp_open_total, tcp_read_bytes_total,
+    tcp_close_total, tcp_congestion_window_segments, tcp_delivery_rate_bytes_per_second,
+    tcp_open_connections, tcp_open_total, tcp_read_bytes_total, tcp_retransmits, tcp_rtt_us,
     tcp_write_bytes_total, EosMetrics, Inner,
 };
 use linkerd_metrics::{FmtLabels, FmtMetric, FmtMetrics, Metric};
@@ -50,7 +51,7 @@ impl<K: Eq + Hash + FmtLabels + 'static> Report<K> {
 
 impl<K: Eq + Hash + FmtLabels + 'static> FmtMetrics for Report<K> {
     fn fmt_metrics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut metrics = self.metrics.lock();
+        let metrics = self.metrics.lock();
         if metrics.is_empty() {
             return Ok(());
         }
@@ -70,8 +71,28 @@ impl<K: Eq + Hash + FmtLabels + 'static> FmtMetrics for Report<K> {
         tcp_close_total.fmt_help(f)?;
         Self::fmt_eos_by(&*metrics, f, tcp_close_total, |e| &e.close_total)?;
 
-        metrics.retain_since(Instant::now() - self.retain_idle);
+        tcp_rtt_us.fmt_help(f)?;
+        metrics.fmt_by(f, tcp_rtt_us, |m| &m.tcp_info.rtt)?;
+
+        tcp_retransmits.fmt_help(f)?;
+        metrics.fmt_by(f, tcp_retransmits, |m| &m.tcp_info.retransmits)?;
+
+        tcp_congestion_window_segments.fmt_help(f)?;
+        metrics.fmt_by(f, tcp_congestion_window_segments, |m| {
+            &m.tcp_info.congestion_window
+        })?;
+
+        tcp_delivery_rate_bytes_per_second.fmt_help(f)?;
+        metrics.fmt_by(f, tcp_delivery_rate_bytes_per_second, |m| {
+            &m.tcp_info.delivery_rate
+        })?;
 
         Ok(())
     }
+
+    fn evict_idle(&self) {
+        self.metrics
+            .lock()
+            .retain_since(Instant::now() - self.retain_idle);
+    }
 }
@@ -1,13 +1,28 @@
 use super::{Eos, EosMetrics, Metrics};
 use linkerd_errno::Errno;
 use linkerd_io as io;
+use linkerd_proxy_transport::tcp_info::{self, TcpStats};
 use std::{sync::Arc, task::Poll};
-use tokio::time::Instant;
+use tokio::time::{Duration, Instant};
+use tracing::trace;
+
+/// The minimum interval between samples of a connection's `TCP_INFO`.
+const TCP_INFO_INTERVAL: Duration = Duration::from_secs(10);
 
 /// Tracks the state of a single instance of `Io` throughout its lifetime.
 #[derive(Debug)]
 pub struct Sensor {
     metrics: Option<Arc<Metrics>>,
+
+    /// Tracks `TCP_INFO` sampling. This is unset once the transport is found
+    /// not to be backed by a TCP socket (or if sampling fails).
+    tcp_info: Option<TcpInfoState>,
+}
+
+#[derive(Debug)]
+struct TcpInfoState {
+    next_sample: Instant,
+    last: Option<TcpStats>,
 }
 
 pub type SensorIo<T> = io::SensorIo<T, Sensor>;
@@ -18,9 +33,16 @@ impl Sensor {
     pub(crate) fn open(metrics: Arc<Metrics>) -> Self {
         metrics.open_total.incr();
         metrics.open_connections.incr();
-        metrics.by_eos.lock().last_update = Instant::now();
+        let now = Instant::now();
+        metrics.by_eos.lock().last_update = now;
         Self {
             metrics: Some(metrics),
+            // The first sample is taken as soon as the transport is used, so
+            // that short-lived connections are described.
+            tcp_info: Some(TcpInfoState {
+                next_sample: now,
+                last: None,
+            }),
         }
     }
 }
@@ -47,6 +69,10 @@ impl io::Sensor for Sensor {
         if let Some(m) = self.metrics.take() {
             m.open_connections.decr();
 
+            if let Some(last) = self.tcp_info.take().and_then(|s| s.last) {
+                m.tcp_info.record_close(&last);
+            }
+
             let mut by_eos = m.by_eos.lock();
             let class = by_eos
                 .metrics
@@ -75,6 +101,35 @@ impl io::Sensor for Sensor {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    fn record_tcp_info<I: io::TcpInfo + ?Sized>(&mut self, io: &I, closing: bool) {
+        let (Some(m), Some(state)) = (self.metrics.as_ref(), self.tcp_info.as_mut()) else {
+            return;
+        };
+        let now = Instant::now();
+        if !closing && now < state.next_sample {
+            return;
+        }
+
+        let Some(sock) = io.tcp_socket() else {
+            self.tcp_info = None;
+            return;
+        };
+        match tcp_info::sample(sock) {
+            Ok(stats) => {
+                m.tcp_info.record_sample(&stats);
+                state.last = Some(stats);
+                state.next_sample = now + TCP_INFO_INTERVAL;
+            }
+            Err(error) => {
+                trace!(%error, "Failed to sample TCP_INFO");
+                if let Some(last) = state.last.take() {
+                    m.tcp_info.record_close(&last);
+                }
+                self.tcp_info = None;
+            }
+        }
+    }
 }
 
 impl Drop for Sensor {
@@ -82,3 +137,30 @@ impl Drop for Sensor {
         io::Sensor::record_close(self, None)
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::AsyncWriteExt,
+        net::{TcpListener, TcpStream},
+    };
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn samples_tcp_info() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (client, server) = tokio::join!(TcpStream::connect(addr), listener.accept());
+        let _server = server.unwrap();
+
+        let metrics = Arc::new(Metrics::default());
+        let mut io = SensorIo::new(client.unwrap(), Sensor::open(metrics.clone()));
+        io.write_all(b"hello").await.unwrap();
+        metrics.tcp_info.retransmits.assert_bucket_exactly(1.0, 0.0);
+
+        // Retransmissions are recorded once, from the final sample.
+        io.shutdown().await.unwrap();
+        drop(io);
+        metrics.tcp_info.retransmits.assert_bucket_exactly(1.0, 1.0);
+    }
+}
@@ -1,4 +1,5 @@
 use super::{Metrics, Sensor, SensorIo};
+use linkerd_io as io;
 use linkerd_stack::{layer, ExtractParam, NewService, Service};
 use std::{
     sync::Arc,
@@ -46,6 +47,7 @@ where
 
 impl<I, A> Service<I> for Server<A>
 where
+    I: io::TcpInfo,
     A: Service<SensorIo<I>, Response = ()>,
 {
     type Response = ();
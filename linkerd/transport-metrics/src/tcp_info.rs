@@ -0,0 +1,119 @@
+use linkerd_metrics::{latency, Bounds, Bucket, Histogram};
+use linkerd_proxy_transport::tcp_info::TcpStats;
+
+/// Smoothed round-trip times, in microseconds.
+const RTT_BOUNDS: &Bounds = &Bounds(&[
+    Bucket::Le(100.0),
+    Bucket::Le(250.0),
+    Bucket::Le(500.0),
+    Bucket::Le(1_000.0),
+    Bucket::Le(2_500.0),
+    Bucket::Le(5_000.0),
+    Bucket::Le(10_000.0),
+    Bucket::Le(25_000.0),
+    Bucket::Le(50_000.0),
+    Bucket::Le(100_000.0),
+    Bucket::Le(250_000.0),
+    Bucket::Le(500_000.0),
+    Bucket::Le(1_000_000.0),
+    Bucket::Inf,
+]);
+
+const RETRANSMIT_BOUNDS: &Bounds = &Bounds(&[
+    Bucket::Le(1.0),
+    Bucket::Le(2.0),
+    Bucket::Le(5.0),
+    Bucket::Le(10.0),
+    Bucket::Le(25.0),
+    Bucket::Le(50.0),
+    Bucket::Le(100.0),
+    Bucket::Inf,
+]);
+
+/// Congestion window sizes, in segments.
+const CWND_BOUNDS: &Bounds = &Bounds(&[
+    Bucket::Le(10.0),
+    Bucket::Le(20.0),
+    Bucket::Le(50.0),
+    Bucket::Le(100.0),
+    Bucket::Le(200.0),
+    Bucket::Le(500.0),
+    Bucket::Le(1_000.0),
+    Bucket::Inf,
+]);
+
+/// Delivery rates, in bytes per second.
+const DELIVERY_RATE_BOUNDS: &Bounds = &Bounds(&[
+    Bucket::Le(10_000.0),
+    Bucket::Le(100_000.0),
+    Bucket::Le(1_000_000.0),
+    Bucket::Le(10_000_000.0),
+    Bucket::Le(100_000_000.0),
+    Bucket::Le(1_000_000_000.0),
+    Bucket::Le(10_000_000_000.0),
+    Bucket::Inf,
+]);
+
+/// Histograms of `TCP_INFO` statistics.
+///
+/// Round-trip times, congestion windows, and delivery rates are recorded each
+/// time a connection is sampled. Retransmissions are cumulative, so they are
+/// only recorded once per connection, from its last sample.
+#[derive(Debug)]
+pub(crate) struct TcpInfoMetrics {
+    pub(crate) rtt: Histogram<latency::Us>,
+    pub(crate) retransmits: Histogram<u64>,
+    pub(crate) congestion_window: Histogram<u64>,
+    pub(crate) delivery_rate: Histogram<u64>,
+}
+
+// === impl TcpInfoMetrics ===
+
+impl TcpInfoMetrics {
+    pub(crate) fn record_sample(&self, stats: &TcpStats) {
+        self.rtt.add(stats.rtt);
+        self.congestion_window.add(stats.congestion_window);
+        if let Some(rate) = stats.delivery_rate {
+            self.delivery_rate.add(rate);
+        }
+    }
+
+    pub(crate) fn record_close(&self, last: &TcpStats) {
+        self.retransmits.add(last.retransmits);
+    }
+}
+
+impl Default for TcpInfoMetrics {
+    fn default() -> Self {
+        Self {
+            rtt: Histogram::new(RTT_BOUNDS),
+            retransmits: Histogram::new(RETRANSMIT_BOUNDS),
+            congestion_window: Histogram::new(CWND_BOUNDS),
+            delivery_rate: Histogram::new(DELIVERY_RATE_BOUNDS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn records_stats() {
+        let metrics = TcpInfoMetrics::default();
+        let stats = TcpStats {
+            rtt: Duration::from_micros(300),
+            retransmits: 3,
+            congestion_window: 10,
+            delivery_rate: None,
+        };
+        metrics.record_sample(&stats);
+        metrics.record_sample(&stats);
+        metrics.record_close(&stats);
+        metrics.rtt.assert_bucket_exactly(500.0, 2.0);
+        metrics.retransmits.assert_bucket_exactly(5.0, 1.0);
+        metrics.congestion_window.assert_bucket_exactly(10.0, 2.0);
+        metrics.delivery_rate.assert_bucket_exactly(10_000.0, 0.0);
+    }
+}
@@ -16,7 +16,7 @@ compile_error!(
     "at least one of the following TLS implementations must be enabled: 'meshtls-boring', 'meshtls-rustls'"
 );
 
-use linkerd_app::{trace, BindTcp, Config, BUILD_INFO};
+use linkerd_app::{trace, AcceptMetrics, BindTcp, Config, BUILD_INFO};
 use linkerd_signal as signal;
 use tokio::{sync::mpsc, time};
 use tracing::{debug, info, warn};
@@ -68,17 +68,13 @@ fn main() {
         let (shutdown_tx, mut shutdown_rx) = mpsc::unbounded_channel();
         let shutdown_grace_period = config.shutdown_grace_period;
 
-        let bind_in = BindTcp::with_orig_dst();
-        let bind_out = BindTcp::dual_with_orig_dst();
+        let bind = BindTcp::new(AcceptMetrics::register(
+            metrics.sub_registry_with_prefix("listen"),
+        ));
+        let bind_in = bind.clone().with_orig_dst();
+        let bind_out = bind.clone().dual_with_orig_dst();
         let app = match config
-            .build(
-                bind_in,
-                bind_out,
-                BindTcp::default(),
-                shutdown_tx,
-                trace,
-                metrics,
-            )
+            .build(bind_in, bind_out, bind, shutdown_tx, trace, metrics)
             .await
         {
             Ok(app) => app,
@@ -1,4 +1,10 @@
 pub mod collector {
+    pub mod metrics {
+        pub mod v1 {
+            include!("gen/opentelemetry.proto.collector.metrics.v1.rs");
+        }
+    }
+
     pub mod trace {
         pub mod v1 {
             include!("gen/opentelemetry.proto.collector.trace.v1.rs");
@@ -12,6 +18,12 @@ pub mod common {
     }
 }
 
+pub mod metrics {
+    pub mod v1 {
+        include!("gen/opentelemetry.proto.metrics.v1.rs");
+    }
+}
+
 pub mod trace {
     pub mod v1 {
         include!("gen/opentelemetry.proto.trace.v1.rs");
@@ -22,8 +22,10 @@ fn bootstrap() {
 /// Generates protobuf bindings into the given directory
 fn generate(out_dir: &std::path::Path) {
     let iface_files = &[
+        "opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
         "opentelemetry/proto/collector/trace/v1/trace_service.proto",
         "opentelemetry/proto/common/v1/common.proto",
+        "opentelemetry/proto/metrics/v1/metrics.proto",
         "opentelemetry/proto/resource/v1/resource.proto",
         "opentelemetry/proto/trace/v1/trace.proto",
     ];
@@ -24,7 +24,8 @@ fn generate(out_dir: &std::path::Path) {
     let iface_files = &["spiffe/proto/workload.proto"];
     if let Err(error) = tonic_build::configure()
         .build_client(true)
-        .build_server(false)
+        .build_server(true)
+        .server_mod_attribute(".", "#[cfg(feature = \"server\")]")
         .emit_rerun_if_changed(false)
         .disable_package_emission()
         .out_dir(out_dir)